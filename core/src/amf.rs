@@ -0,0 +1,250 @@
+//! Action Message Format (AMF) serialization
+//!
+//! AMF is the binary format Flash uses to persist and transmit ActionScript
+//! values, such as the contents of a `SharedObject`. AVM1 content uses AMF0,
+//! while AVM2 content uses AMF3.
+
+pub mod amf0;
+pub mod amf3;
+pub mod sol;
+
+use thiserror::Error;
+
+/// A VM-agnostic ActionScript value, as stored in AMF.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+
+    /// A date, in milliseconds since the Unix epoch.
+    Date(f64),
+
+    /// An object. Objects of registered classes carry their class name.
+    Object {
+        class_name: Option<String>,
+        properties: Vec<(String, Value)>,
+    },
+
+    /// An array, split into its dense elements and any other named
+    /// properties.
+    ///
+    /// The `length` of a sparse array may exceed its dense elements, with
+    /// any elements past them stored as named properties.
+    Array {
+        length: usize,
+        dense: Vec<Value>,
+        associative: Vec<(String, Value)>,
+    },
+
+    /// A reference to an object or array that appeared earlier in the same
+    /// message.
+    ///
+    /// Objects and arrays are numbered in the order they start, so a value
+    /// may refer to any of its parents to form a cycle.
+    Reference(usize),
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum Error {
+    #[error("Unexpected end of AMF data")]
+    UnexpectedEof,
+
+    #[error("Unknown AMF type marker {0:#x}")]
+    UnknownMarker(u8),
+
+    #[error("Unsupported AMF type marker {0:#x}")]
+    UnsupportedMarker(u8),
+
+    #[error("Invalid AMF reference {0}")]
+    InvalidReference(usize),
+
+    #[error("AMF reference {0} is too large to encode")]
+    ReferenceOutOfRange(usize),
+
+    #[error("Invalid UTF-8 in AMF string")]
+    InvalidUtf8,
+
+    #[error("Invalid SharedObject file")]
+    InvalidSol,
+}
+
+/// A cursor over AMF data.
+struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.data.len() < len {
+            return Err(Error::UnexpectedEof);
+        }
+
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_u16(&mut self) -> Result<u16, Error> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_f64(&mut self) -> Result<f64, Error> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.read_bytes(8)?);
+        Ok(f64::from_be_bytes(bytes))
+    }
+
+    fn read_utf8(&mut self, len: usize) -> Result<String, Error> {
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| Error::InvalidUtf8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `{ name: "ruffle", scores: [1, 2.5, true], nested: { self: <ref>, when: <date> } }`,
+    /// where the nested object refers back to its parent.
+    fn nested_value() -> Value {
+        Value::Object {
+            class_name: None,
+            properties: vec![
+                ("name".to_string(), Value::String("ruffle".to_string())),
+                (
+                    "scores".to_string(),
+                    Value::Array {
+                        length: 3,
+                        dense: vec![Value::Number(1.0), Value::Number(2.5), Value::Bool(true)],
+                        associative: vec![("best".to_string(), Value::Number(2.5))],
+                    },
+                ),
+                (
+                    "nested".to_string(),
+                    Value::Object {
+                        class_name: Some("flash.geom.Point".to_string()),
+                        properties: vec![
+                            ("self".to_string(), Value::Reference(0)),
+                            ("when".to_string(), Value::Date(1_600_000_000_000.0)),
+                            ("missing".to_string(), Value::Undefined),
+                            ("empty".to_string(), Value::Null),
+                            ("unicode".to_string(), Value::String("ラッフル".to_string())),
+                        ],
+                    },
+                ),
+                ("negative".to_string(), Value::Number(-300.0)),
+                ("large".to_string(), Value::Number(1e300)),
+            ],
+        }
+    }
+
+    #[test]
+    fn amf0_round_trip() {
+        let value = nested_value();
+        let data = amf0::serialize(&value).unwrap();
+        assert_eq!(amf0::deserialize(&data), Ok(value));
+    }
+
+    #[test]
+    fn amf3_round_trip() {
+        let value = nested_value();
+        let data = amf3::serialize(&value).unwrap();
+        assert_eq!(amf3::deserialize(&data), Ok(value));
+    }
+
+    #[test]
+    fn amf0_sparse_array_keeps_length() {
+        let value = Value::Array {
+            length: 100,
+            dense: vec![Value::Number(1.0)],
+            associative: vec![("99".to_string(), Value::Number(2.0))],
+        };
+        let data = amf0::serialize(&value).unwrap();
+        assert_eq!(amf0::deserialize(&data), Ok(value));
+    }
+
+    #[test]
+    fn amf0_long_names_are_truncated_on_char_boundary() {
+        // Two bytes per character, so the 65535 byte limit falls within a
+        // character.
+        let name = "é".repeat(40000);
+        let value = Value::Object {
+            class_name: None,
+            properties: vec![(name, Value::Null)],
+        };
+        let data = amf0::serialize(&value).unwrap();
+
+        match amf0::deserialize(&data) {
+            Ok(Value::Object { properties, .. }) => {
+                assert_eq!(properties[0].0, "é".repeat(32767));
+            }
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn amf0_long_strings_are_preserved() {
+        let value = Value::String("ラ".repeat(30000));
+        let data = amf0::serialize(&value).unwrap();
+        assert_eq!(amf0::deserialize(&data), Ok(value));
+    }
+
+    #[test]
+    fn unencodable_reference_is_rejected() {
+        let value = Value::Reference(0x10000);
+        assert_eq!(
+            amf0::serialize(&value),
+            Err(Error::ReferenceOutOfRange(0x10000))
+        );
+        assert_eq!(
+            amf3::serialize(&value),
+            Err(Error::InvalidReference(0x10000))
+        );
+    }
+
+    #[test]
+    fn invalid_reference_is_rejected() {
+        // `{ later: <reference 1> }`, where only the object itself exists.
+        let amf0_data = [
+            0x03, 0x00, 0x05, b'l', b'a', b't', b'e', b'r', 0x07, 0x00, 0x01, 0x00, 0x00, 0x09,
+        ];
+        assert_eq!(
+            amf0::deserialize(&amf0_data),
+            Err(Error::InvalidReference(1))
+        );
+
+        let amf3_data = [
+            0x0A, 0x0B, 0x01, 0x0B, b'l', b'a', b't', b'e', b'r', 0x0A, 0x02, 0x01,
+        ];
+        assert_eq!(
+            amf3::deserialize(&amf3_data),
+            Err(Error::InvalidReference(1))
+        );
+    }
+
+    #[test]
+    fn truncated_data_is_rejected() {
+        let data = amf0::serialize(&nested_value()).unwrap();
+        assert_eq!(
+            amf0::deserialize(&data[..data.len() - 1]),
+            Err(Error::UnexpectedEof)
+        );
+    }
+}
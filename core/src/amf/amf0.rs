@@ -0,0 +1,280 @@
+//! AMF0, as used by AVM1
+
+use super::{Error, Reader, Value};
+use std::convert::TryFrom;
+
+const NUMBER: u8 = 0x00;
+const BOOLEAN: u8 = 0x01;
+const STRING: u8 = 0x02;
+const OBJECT: u8 = 0x03;
+const MOVIE_CLIP: u8 = 0x04;
+const NULL: u8 = 0x05;
+const UNDEFINED: u8 = 0x06;
+const REFERENCE: u8 = 0x07;
+const ECMA_ARRAY: u8 = 0x08;
+const OBJECT_END: u8 = 0x09;
+const STRICT_ARRAY: u8 = 0x0A;
+const DATE: u8 = 0x0B;
+const LONG_STRING: u8 = 0x0C;
+const UNSUPPORTED: u8 = 0x0D;
+const RECORD_SET: u8 = 0x0E;
+const XML_DOCUMENT: u8 = 0x0F;
+const TYPED_OBJECT: u8 = 0x10;
+const AVMPLUS_OBJECT: u8 = 0x11;
+
+/// Encode a value as AMF0.
+pub fn serialize(value: &Value) -> Result<Vec<u8>, Error> {
+    let mut serializer = Serializer::new(0);
+    serializer.write_value(value)?;
+    Ok(serializer.output)
+}
+
+/// Decode a single AMF0 value.
+pub fn deserialize(data: &[u8]) -> Result<Value, Error> {
+    Deserializer::new(data, 0).read_value()
+}
+
+pub(super) struct Serializer {
+    pub(super) output: Vec<u8>,
+
+    /// The number of objects and arrays that precede this message, which
+    /// AMF0 can't refer to.
+    skipped: usize,
+}
+
+impl Serializer {
+    pub(super) fn new(skipped: usize) -> Self {
+        Self {
+            output: Vec::new(),
+            skipped,
+        }
+    }
+
+    pub(super) fn write_value(&mut self, value: &Value) -> Result<(), Error> {
+        match value {
+            Value::Undefined => self.output.push(UNDEFINED),
+            Value::Null => self.output.push(NULL),
+            Value::Bool(value) => self.output.extend_from_slice(&[BOOLEAN, u8::from(*value)]),
+            Value::Number(value) => {
+                self.output.push(NUMBER);
+                self.output.extend_from_slice(&value.to_be_bytes());
+            }
+            Value::String(value) if value.len() > usize::from(u16::MAX) => {
+                self.output.push(LONG_STRING);
+                self.output
+                    .extend_from_slice(&(value.len() as u32).to_be_bytes());
+                self.output.extend_from_slice(value.as_bytes());
+            }
+            Value::String(value) => {
+                self.output.push(STRING);
+                self.write_utf8(value);
+            }
+            Value::Date(time) => {
+                self.output.push(DATE);
+                self.output.extend_from_slice(&time.to_be_bytes());
+                // The time zone is reserved, and always zero.
+                self.output.extend_from_slice(&[0, 0]);
+            }
+            Value::Object {
+                class_name: Some(class_name),
+                properties,
+            } => {
+                self.output.push(TYPED_OBJECT);
+                self.write_utf8(class_name);
+                self.write_properties(properties)?;
+            }
+            Value::Object {
+                class_name: None,
+                properties,
+            } => {
+                self.output.push(OBJECT);
+                self.write_properties(properties)?;
+            }
+            Value::Array {
+                length,
+                dense,
+                associative,
+            } => {
+                let length = u32::try_from(*length).unwrap_or(u32::MAX);
+                self.output.push(ECMA_ARRAY);
+                self.output.extend_from_slice(&length.to_be_bytes());
+                for (index, element) in dense.iter().enumerate() {
+                    self.write_utf8(&index.to_string());
+                    self.write_value(element)?;
+                }
+                self.write_properties(associative)?;
+            }
+            Value::Reference(index) => {
+                let reference = index
+                    .checked_sub(self.skipped)
+                    .ok_or(Error::InvalidReference(*index))?;
+                let reference =
+                    u16::try_from(reference).map_err(|_| Error::ReferenceOutOfRange(*index))?;
+                self.output.push(REFERENCE);
+                self.output.extend_from_slice(&reference.to_be_bytes());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_properties(&mut self, properties: &[(String, Value)]) -> Result<(), Error> {
+        for (name, value) in properties {
+            self.write_utf8(name);
+            self.write_value(value)?;
+        }
+
+        self.write_utf8("");
+        self.output.push(OBJECT_END);
+        Ok(())
+    }
+
+    /// Write a string with a 16-bit length.
+    ///
+    /// Longer strings are truncated to the last whole character that fits.
+    pub(super) fn write_utf8(&mut self, value: &str) {
+        let mut length = value.len().min(usize::from(u16::MAX));
+        while !value.is_char_boundary(length) {
+            length -= 1;
+        }
+
+        self.output
+            .extend_from_slice(&(length as u16).to_be_bytes());
+        self.output.extend_from_slice(&value.as_bytes()[..length]);
+    }
+}
+
+pub(super) struct Deserializer<'a> {
+    pub(super) reader: Reader<'a>,
+
+    /// The number of objects and arrays that precede this message, which
+    /// AMF0 can't refer to.
+    skipped: usize,
+
+    /// The number of objects and arrays started so far, including skipped
+    /// ones, which may be the target of a reference.
+    complex_count: usize,
+}
+
+impl<'a> Deserializer<'a> {
+    pub(super) fn new(data: &'a [u8], skipped: usize) -> Self {
+        Self {
+            reader: Reader::new(data),
+            skipped,
+            complex_count: skipped,
+        }
+    }
+
+    pub(super) fn read_value(&mut self) -> Result<Value, Error> {
+        let marker = self.reader.read_u8()?;
+        self.read_value_with_marker(marker)
+    }
+
+    fn read_value_with_marker(&mut self, marker: u8) -> Result<Value, Error> {
+        Ok(match marker {
+            NUMBER => Value::Number(self.reader.read_f64()?),
+            BOOLEAN => Value::Bool(self.reader.read_u8()? != 0),
+            STRING => Value::String(self.read_utf8()?),
+            OBJECT => {
+                self.complex_count += 1;
+                Value::Object {
+                    class_name: None,
+                    properties: self.read_properties()?,
+                }
+            }
+            NULL => Value::Null,
+            UNDEFINED | UNSUPPORTED => Value::Undefined,
+            REFERENCE => {
+                let index = usize::from(self.reader.read_u16()?) + self.skipped;
+                if index >= self.complex_count {
+                    return Err(Error::InvalidReference(index));
+                }
+                Value::Reference(index)
+            }
+            ECMA_ARRAY => {
+                self.complex_count += 1;
+                let length = self.reader.read_u32()? as usize;
+                self.read_ecma_array(length)?
+            }
+            STRICT_ARRAY => {
+                self.complex_count += 1;
+                let length = self.reader.read_u32()?;
+                let mut dense = Vec::new();
+                for _ in 0..length {
+                    dense.push(self.read_value()?);
+                }
+                Value::Array {
+                    length: dense.len(),
+                    dense,
+                    associative: Vec::new(),
+                }
+            }
+            DATE => {
+                let time = self.reader.read_f64()?;
+                // Skip the reserved time zone.
+                self.reader.read_u16()?;
+                Value::Date(time)
+            }
+            LONG_STRING | XML_DOCUMENT => {
+                let length = self.reader.read_u32()? as usize;
+                Value::String(self.reader.read_utf8(length)?)
+            }
+            TYPED_OBJECT => {
+                self.complex_count += 1;
+                let class_name = self.read_utf8()?;
+                Value::Object {
+                    class_name: Some(class_name),
+                    properties: self.read_properties()?,
+                }
+            }
+            MOVIE_CLIP | RECORD_SET | AVMPLUS_OBJECT => {
+                return Err(Error::UnsupportedMarker(marker))
+            }
+            _ => return Err(Error::UnknownMarker(marker)),
+        })
+    }
+
+    /// Read an ECMA array, splitting its index properties into the dense
+    /// part.
+    fn read_ecma_array(&mut self, length: usize) -> Result<Value, Error> {
+        let properties = self.read_properties()?;
+
+        // Only the leading run of elements is stored densely, so that the
+        // length of a sparse (or corrupt) array can't exhaust memory. The
+        // rest are kept by name.
+        let mut dense = Vec::new();
+        let mut associative = Vec::new();
+        for (name, value) in properties {
+            match name.parse::<usize>() {
+                Ok(index) if index == dense.len() && index < length => dense.push(value),
+                _ => associative.push((name, value)),
+            }
+        }
+
+        Ok(Value::Array {
+            length,
+            dense,
+            associative,
+        })
+    }
+
+    fn read_properties(&mut self) -> Result<Vec<(String, Value)>, Error> {
+        let mut properties = Vec::new();
+
+        loop {
+            let name = self.read_utf8()?;
+            let marker = self.reader.read_u8()?;
+            if name.is_empty() && marker == OBJECT_END {
+                return Ok(properties);
+            }
+
+            let value = self.read_value_with_marker(marker)?;
+            properties.push((name, value));
+        }
+    }
+
+    pub(super) fn read_utf8(&mut self) -> Result<String, Error> {
+        let length = usize::from(self.reader.read_u16()?);
+        self.reader.read_utf8(length)
+    }
+}
@@ -0,0 +1,414 @@
+//! AMF3, as used by AVM2
+
+use super::{Error, Reader, Value};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+const UNDEFINED: u8 = 0x00;
+const NULL: u8 = 0x01;
+const FALSE: u8 = 0x02;
+const TRUE: u8 = 0x03;
+const INTEGER: u8 = 0x04;
+const DOUBLE: u8 = 0x05;
+const STRING: u8 = 0x06;
+const XML_DOCUMENT: u8 = 0x07;
+const DATE: u8 = 0x08;
+const ARRAY: u8 = 0x09;
+const OBJECT: u8 = 0x0A;
+const XML: u8 = 0x0B;
+const BYTE_ARRAY: u8 = 0x0C;
+const VECTOR_INT: u8 = 0x0D;
+const VECTOR_UINT: u8 = 0x0E;
+const VECTOR_DOUBLE: u8 = 0x0F;
+const VECTOR_OBJECT: u8 = 0x10;
+const DICTIONARY: u8 = 0x11;
+
+/// The range of numbers that fit in the 29-bit `integer` type.
+const MIN_INTEGER: f64 = -(1 << 28) as f64;
+const MAX_INTEGER: f64 = ((1 << 28) - 1) as f64;
+
+/// Encode a value as AMF3.
+pub fn serialize(value: &Value) -> Result<Vec<u8>, Error> {
+    let mut serializer = Serializer::new(0);
+    serializer.write_value(value)?;
+    Ok(serializer.output)
+}
+
+/// Decode a single AMF3 value.
+pub fn deserialize(data: &[u8]) -> Result<Value, Error> {
+    Deserializer::new(data, 0).read_value()
+}
+
+/// The class description shared by objects of the same type.
+#[derive(Clone)]
+struct Traits {
+    class_name: Option<String>,
+    dynamic: bool,
+    sealed: Vec<String>,
+}
+
+/// An entry in the AMF3 object reference table.
+///
+/// Unlike our `Value` model, AMF3 also allows references to dates.
+#[derive(Clone, Copy)]
+enum TableEntry {
+    /// An object or array, by its index in our `Value` model.
+    Complex(usize),
+    Date(f64),
+}
+
+#[derive(Default)]
+pub(super) struct Serializer {
+    pub(super) output: Vec<u8>,
+    strings: HashMap<String, usize>,
+    traits: HashMap<Option<String>, usize>,
+
+    /// The marker and AMF3 table index of each object or array written so
+    /// far, by their index in our `Value` model.
+    ///
+    /// Objects that precede this message, which AMF3 can't refer to, have
+    /// no entry.
+    complex: Vec<Option<(u8, usize)>>,
+
+    /// The number of entries in the AMF3 object table.
+    object_count: usize,
+}
+
+impl Serializer {
+    pub(super) fn new(skipped: usize) -> Self {
+        Self {
+            complex: vec![None; skipped],
+            ..Default::default()
+        }
+    }
+
+    pub(super) fn write_value(&mut self, value: &Value) -> Result<(), Error> {
+        match value {
+            Value::Undefined => self.output.push(UNDEFINED),
+            Value::Null => self.output.push(NULL),
+            Value::Bool(false) => self.output.push(FALSE),
+            Value::Bool(true) => self.output.push(TRUE),
+            Value::Number(value)
+                if value.fract() == 0.0
+                    && (MIN_INTEGER..=MAX_INTEGER).contains(value)
+                    && !(*value == 0.0 && value.is_sign_negative()) =>
+            {
+                self.output.push(INTEGER);
+                self.write_u29(*value as i32 as u32 & 0x1FFF_FFFF);
+            }
+            Value::Number(value) => {
+                self.output.push(DOUBLE);
+                self.output.extend_from_slice(&value.to_be_bytes());
+            }
+            Value::String(value) => {
+                self.output.push(STRING);
+                self.write_string(value)?;
+            }
+            Value::Date(time) => {
+                self.output.push(DATE);
+                self.object_count += 1;
+                self.write_u29(1);
+                self.output.extend_from_slice(&time.to_be_bytes());
+            }
+            Value::Object {
+                class_name,
+                properties,
+            } => {
+                self.output.push(OBJECT);
+                self.start_complex(OBJECT);
+
+                if let Some(index) = self.traits.get(class_name) {
+                    let header = reference_header(*index, 2)?;
+                    self.write_u29(header | 0b01);
+                } else {
+                    self.traits.insert(class_name.clone(), self.traits.len());
+                    // Inline traits for a dynamic object with no sealed
+                    // members.
+                    self.write_u29(0b1011);
+                    self.write_string(class_name.as_deref().unwrap_or(""))?;
+                }
+
+                self.write_dynamic_properties(properties)?;
+            }
+            Value::Array {
+                dense, associative, ..
+            } => {
+                // The length of a sparse array is implied by its named
+                // elements.
+                self.output.push(ARRAY);
+                self.start_complex(ARRAY);
+                self.write_u29(((dense.len() as u32) << 1) | 1);
+                self.write_dynamic_properties(associative)?;
+                for element in dense {
+                    self.write_value(element)?;
+                }
+            }
+            Value::Reference(index) => match self.complex.get(*index) {
+                Some(&Some((marker, table_index))) => {
+                    let header = reference_header(table_index, 1)?;
+                    self.output.push(marker);
+                    self.write_u29(header);
+                }
+                _ => return Err(Error::InvalidReference(*index)),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn start_complex(&mut self, marker: u8) {
+        self.complex.push(Some((marker, self.object_count)));
+        self.object_count += 1;
+    }
+
+    /// Write name/value pairs, terminated by the empty string.
+    ///
+    /// Properties with an empty name can't be represented, and are skipped.
+    fn write_dynamic_properties(&mut self, properties: &[(String, Value)]) -> Result<(), Error> {
+        for (name, value) in properties {
+            if !name.is_empty() {
+                self.write_string(name)?;
+                self.write_value(value)?;
+            }
+        }
+
+        self.write_string("")
+    }
+
+    pub(super) fn write_string(&mut self, value: &str) -> Result<(), Error> {
+        if value.is_empty() {
+            self.write_u29(1);
+        } else if let Some(index) = self.strings.get(value) {
+            let header = reference_header(*index, 1)?;
+            self.write_u29(header);
+        } else {
+            self.strings.insert(value.to_string(), self.strings.len());
+            self.write_u29(((value.len() as u32) << 1) | 1);
+            self.output.extend_from_slice(value.as_bytes());
+        }
+
+        Ok(())
+    }
+
+    /// Write a variable-length 29-bit unsigned integer.
+    fn write_u29(&mut self, value: u32) {
+        let value = value & 0x1FFF_FFFF;
+        if value < 0x80 {
+            self.output.push(value as u8);
+        } else if value < 0x4000 {
+            self.output
+                .extend_from_slice(&[(value >> 7) as u8 | 0x80, value as u8 & 0x7F]);
+        } else if value < 0x20_0000 {
+            self.output.extend_from_slice(&[
+                (value >> 14) as u8 | 0x80,
+                (value >> 7) as u8 | 0x80,
+                value as u8 & 0x7F,
+            ]);
+        } else {
+            self.output.extend_from_slice(&[
+                (value >> 22) as u8 | 0x80,
+                (value >> 15) as u8 | 0x80,
+                (value >> 8) as u8 | 0x80,
+                value as u8,
+            ]);
+        }
+    }
+}
+
+/// The header of a reference to entry `index` of a table, leaving room for
+/// `flag_bits` flags.
+fn reference_header(index: usize, flag_bits: u32) -> Result<u32, Error> {
+    u32::try_from(index)
+        .ok()
+        .filter(|index| *index < 1 << (29 - flag_bits))
+        .map(|index| index << flag_bits)
+        .ok_or(Error::ReferenceOutOfRange(index))
+}
+
+pub(super) struct Deserializer<'a> {
+    pub(super) reader: Reader<'a>,
+    strings: Vec<String>,
+    traits: Vec<Traits>,
+    objects: Vec<TableEntry>,
+
+    /// The number of objects and arrays started so far, including any that
+    /// precede this message.
+    complex_count: usize,
+}
+
+impl<'a> Deserializer<'a> {
+    pub(super) fn new(data: &'a [u8], skipped: usize) -> Self {
+        Self {
+            reader: Reader::new(data),
+            strings: Vec::new(),
+            traits: Vec::new(),
+            objects: Vec::new(),
+            complex_count: skipped,
+        }
+    }
+
+    pub(super) fn read_value(&mut self) -> Result<Value, Error> {
+        let marker = self.reader.read_u8()?;
+        Ok(match marker {
+            UNDEFINED => Value::Undefined,
+            NULL => Value::Null,
+            FALSE => Value::Bool(false),
+            TRUE => Value::Bool(true),
+            INTEGER => {
+                let value = self.read_u29()?;
+                // Sign-extend from 29 bits.
+                Value::Number(f64::from(((value << 3) as i32) >> 3))
+            }
+            DOUBLE => Value::Number(self.reader.read_f64()?),
+            STRING => Value::String(self.read_string()?),
+            DATE => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.read_reference(header);
+                }
+
+                let time = self.reader.read_f64()?;
+                self.objects.push(TableEntry::Date(time));
+                Value::Date(time)
+            }
+            ARRAY => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.read_reference(header);
+                }
+
+                self.start_complex();
+                let associative = self.read_dynamic_properties()?;
+                let mut dense = Vec::new();
+                for _ in 0..header >> 1 {
+                    dense.push(self.read_value()?);
+                }
+                Value::Array {
+                    length: dense.len(),
+                    dense,
+                    associative,
+                }
+            }
+            OBJECT => {
+                let header = self.read_u29()?;
+                if header & 1 == 0 {
+                    return self.read_reference(header);
+                }
+
+                let traits = self.read_traits(header)?;
+                self.start_complex();
+
+                let mut properties = Vec::new();
+                for name in traits.sealed {
+                    let value = self.read_value()?;
+                    properties.push((name, value));
+                }
+                if traits.dynamic {
+                    properties.extend(self.read_dynamic_properties()?);
+                }
+
+                Value::Object {
+                    class_name: traits.class_name,
+                    properties,
+                }
+            }
+            XML_DOCUMENT | XML | BYTE_ARRAY | VECTOR_INT | VECTOR_UINT | VECTOR_DOUBLE
+            | VECTOR_OBJECT | DICTIONARY => return Err(Error::UnsupportedMarker(marker)),
+            _ => return Err(Error::UnknownMarker(marker)),
+        })
+    }
+
+    fn start_complex(&mut self) {
+        self.objects.push(TableEntry::Complex(self.complex_count));
+        self.complex_count += 1;
+    }
+
+    /// Resolve a reference to an earlier entry in the object table.
+    fn read_reference(&self, header: u32) -> Result<Value, Error> {
+        let index = (header >> 1) as usize;
+        match self.objects.get(index) {
+            Some(TableEntry::Complex(index)) => Ok(Value::Reference(*index)),
+            Some(TableEntry::Date(time)) => Ok(Value::Date(*time)),
+            None => Err(Error::InvalidReference(index)),
+        }
+    }
+
+    fn read_traits(&mut self, header: u32) -> Result<Traits, Error> {
+        if header & 0b10 == 0 {
+            let index = (header >> 2) as usize;
+            return self
+                .traits
+                .get(index)
+                .cloned()
+                .ok_or(Error::InvalidReference(index));
+        }
+
+        if header & 0b100 != 0 {
+            // Externalizable objects have a class-specific encoding.
+            return Err(Error::UnsupportedMarker(OBJECT));
+        }
+
+        let class_name = self.read_string()?;
+        let mut sealed = Vec::new();
+        for _ in 0..header >> 4 {
+            sealed.push(self.read_string()?);
+        }
+
+        let traits = Traits {
+            class_name: Some(class_name).filter(|name| !name.is_empty()),
+            dynamic: header & 0b1000 != 0,
+            sealed,
+        };
+        self.traits.push(traits.clone());
+        Ok(traits)
+    }
+
+    /// Read name/value pairs, terminated by the empty string.
+    fn read_dynamic_properties(&mut self) -> Result<Vec<(String, Value)>, Error> {
+        let mut properties = Vec::new();
+
+        loop {
+            let name = self.read_string()?;
+            if name.is_empty() {
+                return Ok(properties);
+            }
+
+            let value = self.read_value()?;
+            properties.push((name, value));
+        }
+    }
+
+    pub(super) fn read_string(&mut self) -> Result<String, Error> {
+        let header = self.read_u29()?;
+        let index = (header >> 1) as usize;
+        if header & 1 == 0 {
+            return self
+                .strings
+                .get(index)
+                .cloned()
+                .ok_or(Error::InvalidReference(index));
+        }
+
+        let value = self.reader.read_utf8(index)?;
+        // The empty string is never added to the reference table.
+        if !value.is_empty() {
+            self.strings.push(value.clone());
+        }
+        Ok(value)
+    }
+
+    /// Read a variable-length 29-bit unsigned integer.
+    fn read_u29(&mut self) -> Result<u32, Error> {
+        let mut value = 0;
+
+        for _ in 0..3 {
+            let byte = self.reader.read_u8()?;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Ok((value << 8) | u32::from(self.reader.read_u8()?))
+    }
+}
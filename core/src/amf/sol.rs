@@ -0,0 +1,164 @@
+//! `.sol` files, as used to store `SharedObject`s
+//!
+//! A `.sol` file holds the properties of a shared object's `data` as a series
+//! of named AMF values. The `data` object itself isn't stored, and can't be
+//! referred to, but it still counts as object 0 of our `Value` model.
+
+use super::{amf0, amf3, Error, Reader, Value};
+use std::convert::TryFrom;
+
+/// The version of AMF that a `.sol` file is encoded with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AmfVersion {
+    Amf0,
+    Amf3,
+}
+
+const MAGIC: [u8; 2] = [0x00, 0xBF];
+const SIGNATURE: &[u8] = b"TCSO\x00\x04\x00\x00\x00\x00";
+
+/// Encode the `properties` of a shared object called `name`.
+pub fn serialize(
+    name: &str,
+    version: AmfVersion,
+    properties: &[(String, Value)],
+) -> Result<Vec<u8>, Error> {
+    let mut body = SIGNATURE.to_vec();
+    let name_length = u16::try_from(name.len()).map_err(|_| Error::InvalidSol)?;
+    body.extend_from_slice(&name_length.to_be_bytes());
+    body.extend_from_slice(name.as_bytes());
+
+    match version {
+        AmfVersion::Amf0 => {
+            body.extend_from_slice(&[0, 0, 0, 0]);
+            let mut serializer = amf0::Serializer::new(1);
+            serializer.output = body;
+            for (name, value) in properties {
+                serializer.write_utf8(name);
+                serializer.write_value(value)?;
+                serializer.output.push(0);
+            }
+            body = serializer.output;
+        }
+        AmfVersion::Amf3 => {
+            body.extend_from_slice(&[0, 0, 0, 3]);
+            let mut serializer = amf3::Serializer::new(1);
+            serializer.output = body;
+            for (name, value) in properties {
+                serializer.write_string(name)?;
+                serializer.write_value(value)?;
+                serializer.output.push(0);
+            }
+            body = serializer.output;
+        }
+    }
+
+    let body_length = u32::try_from(body.len()).map_err(|_| Error::InvalidSol)?;
+    let mut output = MAGIC.to_vec();
+    output.extend_from_slice(&body_length.to_be_bytes());
+    output.extend_from_slice(&body);
+    Ok(output)
+}
+
+/// Decode the properties of a shared object.
+pub fn deserialize(data: &[u8]) -> Result<Vec<(String, Value)>, Error> {
+    let mut reader = Reader::new(data);
+    if reader.read_bytes(2)? != MAGIC {
+        return Err(Error::InvalidSol);
+    }
+
+    let body_length = reader.read_u32()? as usize;
+    let mut reader = Reader::new(reader.read_bytes(body_length)?);
+    if reader.read_bytes(SIGNATURE.len())? != SIGNATURE {
+        return Err(Error::InvalidSol);
+    }
+
+    let name_length = usize::from(reader.read_u16()?);
+    reader.read_bytes(name_length)?;
+
+    let mut properties = Vec::new();
+    match reader.read_u32()? {
+        0 => {
+            let mut deserializer = amf0::Deserializer::new(reader.data, 1);
+            while !deserializer.reader.data.is_empty() {
+                let name = deserializer.read_utf8()?;
+                let value = deserializer.read_value()?;
+                deserializer.reader.read_u8()?;
+                properties.push((name, value));
+            }
+        }
+        3 => {
+            let mut deserializer = amf3::Deserializer::new(reader.data, 1);
+            while !deserializer.reader.data.is_empty() {
+                let name = deserializer.read_string()?;
+                let value = deserializer.read_value()?;
+                deserializer.reader.read_u8()?;
+                properties.push((name, value));
+            }
+        }
+        _ => return Err(Error::InvalidSol),
+    }
+
+    Ok(properties)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn properties() -> Vec<(String, Value)> {
+        vec![
+            ("score".to_string(), Value::Number(42.0)),
+            ("name".to_string(), Value::String("ruffle".to_string())),
+            (
+                "items".to_string(),
+                Value::Array {
+                    length: 2,
+                    dense: vec![Value::String("name".to_string()), Value::Null],
+                    associative: vec![],
+                },
+            ),
+            ("again".to_string(), Value::Reference(1)),
+        ]
+    }
+
+    #[test]
+    fn amf0_round_trip() {
+        let data = serialize("save", AmfVersion::Amf0, &properties()).unwrap();
+        assert_eq!(&data[..2], &MAGIC);
+        assert_eq!(&data[6..16], SIGNATURE);
+        assert_eq!(&data[16..22], b"\x00\x04save");
+        assert_eq!(&data[22..26], &[0, 0, 0, 0]);
+        assert_eq!(deserialize(&data), Ok(properties()));
+    }
+
+    #[test]
+    fn amf3_round_trip() {
+        let data = serialize("save", AmfVersion::Amf3, &properties()).unwrap();
+        assert_eq!(&data[22..26], &[0, 0, 0, 3]);
+        assert_eq!(deserialize(&data), Ok(properties()));
+    }
+
+    #[test]
+    fn data_object_cannot_be_referenced() {
+        let properties = vec![("self".to_string(), Value::Reference(0))];
+        for version in [AmfVersion::Amf0, AmfVersion::Amf3].iter() {
+            assert_eq!(
+                serialize("save", *version, &properties),
+                Err(Error::InvalidReference(0))
+            );
+        }
+    }
+
+    #[test]
+    fn body_length_is_checked() {
+        let mut data = serialize("save", AmfVersion::Amf0, &properties()).unwrap();
+        data.pop();
+        assert_eq!(deserialize(&data), Err(Error::UnexpectedEof));
+    }
+
+    #[test]
+    fn other_data_is_rejected() {
+        assert_eq!(deserialize(b"{\"score\":42}"), Err(Error::InvalidSol));
+    }
+}
@@ -43,8 +43,9 @@ pub use object::stage_object::StageObject;
 pub use object::{Object, ObjectPtr, TObject};
 use scope::Scope;
 use smallvec::alloc::borrow::Cow;
+use std::collections::HashMap;
 pub use string::AvmString;
-pub use timer::Timers;
+pub use timer::{TimerCallback, Timers};
 pub use value::Value;
 
 macro_rules! avm_debug {
@@ -66,6 +67,19 @@ macro_rules! avm_warn {
     )
 }
 
+/// Warn that an unimplemented builtin was called, and record it in the
+/// player's diagnostics report.
+#[macro_export]
+macro_rules! avm1_stub {
+    ($activation: ident, $name: expr) => {{
+        avm_warn!($activation, "{}: Unimplemented", $name);
+        $activation
+            .context
+            .diagnostics
+            .record_unimplemented_builtin($name);
+    }};
+}
+
 #[macro_export]
 macro_rules! avm_error {
     ($activation: ident, $($arg:tt)*) => (
@@ -118,6 +132,13 @@ pub struct Avm1<'gc> {
     /// Used to prevent scrolling on web.
     has_mouse_listener: bool,
 
+    /// The `LocalConnection` objects listening on each connection name.
+    local_connections: HashMap<String, Object<'gc>>,
+
+    /// The SWF version that scripts behave as, regardless of the version of
+    /// the movie they came from.
+    force_swf_version: Option<u8>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -143,6 +164,8 @@ impl<'gc> Avm1<'gc> {
             halted: false,
             max_recursion_depth: 255,
             has_mouse_listener: false,
+            local_connections: HashMap::new(),
+            force_swf_version: None,
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -423,6 +446,14 @@ impl<'gc> Avm1<'gc> {
         self.max_recursion_depth = max_recursion_depth
     }
 
+    pub fn force_swf_version(&self) -> Option<u8> {
+        self.force_swf_version
+    }
+
+    pub fn set_force_swf_version(&mut self, force_swf_version: Option<u8>) {
+        self.force_swf_version = force_swf_version
+    }
+
     pub fn broadcaster_functions(&self) -> BroadcasterFunctions<'gc> {
         self.broadcaster_functions
     }
@@ -7,11 +7,13 @@ use crate::avm1::scope::Scope;
 use crate::avm1::{
     fscommand, globals, scope, skip_actions, start_drag, AvmString, ScriptObject, Value,
 };
+use crate::backend::log::LogCategory;
 use crate::backend::navigator::{NavigationMethod, RequestOptions};
 use crate::context::UpdateContext;
 use crate::display_object::{DisplayObject, MovieClip, TDisplayObject, TDisplayObjectContainer};
 use crate::ecma_conversions::f64_to_wrapping_u32;
-use crate::tag_utils::SwfSlice;
+use crate::security;
+use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::Instantiator;
 use crate::{avm_error, avm_warn};
 use gc_arena::{Gc, GcCell, MutationContext};
@@ -22,6 +24,7 @@ use std::borrow::Cow;
 use std::cell::{Ref, RefMut};
 use std::convert::TryFrom;
 use std::fmt;
+use std::sync::Arc;
 use swf::avm1::read::Reader;
 use swf::avm1::types::{Action, CatchVar, Function, TryBlock};
 use swf::SwfStr;
@@ -430,7 +433,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     pub fn run_actions(&mut self, code: SwfSlice) -> Result<ReturnType<'gc>, Error<'gc>> {
-        let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version());
+        let mut read = Reader::new(&code.movie.data()[code.start..], self.swf_version);
 
         loop {
             let result = self.do_action(&code, &mut read);
@@ -448,11 +451,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         data: &'b SwfSlice,
         reader: &mut Reader<'b>,
     ) -> Result<FrameControl<'gc>, Error<'gc>> {
-        self.actions_since_timeout_check += 1;
-        if self.actions_since_timeout_check >= 2000 {
-            self.actions_since_timeout_check = 0;
-            if self.context.update_start.elapsed() >= self.context.max_execution_duration {
-                return Err(Error::ExecutionTimeout);
+        if let Some(max_execution_duration) = self.context.max_execution_duration {
+            self.actions_since_timeout_check += 1;
+            if self.actions_since_timeout_check >= 2000 {
+                self.actions_since_timeout_check = 0;
+                if self.context.update_start.elapsed() >= max_execution_duration {
+                    return Err(Error::ExecutionTimeout);
+                }
             }
         }
 
@@ -997,10 +1002,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let name_val = self.context.avm1.pop();
         let name = name_val.coerce_to_string(self)?;
         let scope = self.scope_cell();
-        scope
-            .write(self.context.gc_context)
-            .locals()
-            .set(&name, value, self)?;
+        scope.read().define_local(&name, value, self)?;
         Ok(FrameControl::Continue)
     }
 
@@ -1010,12 +1012,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let name_val = self.context.avm1.pop();
         let name = name_val.coerce_to_string(self)?;
         let scope = self.scope_cell();
-        if !scope.read().locals().has_property(self, &name) {
-            scope
-                .write(self.context.gc_context)
-                .locals()
-                .set(&name, Value::Undefined, self)?;
-        }
+        scope.read().declare_local(&name, self)?;
         Ok(FrameControl::Continue)
     }
 
@@ -1205,7 +1202,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             *self.context.time_offset += 1;
         }
 
-        let time = self.context.navigator.time_since_launch().as_millis() as u32;
+        let time = self.context.clock.time_since_launch().as_millis() as u32;
         self.context
             .avm1
             .push(time.wrapping_add(*self.context.time_offset));
@@ -1233,7 +1230,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         if target.starts_with("_level") && target.len() > 6 {
             match target[6..].parse::<i32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let movie = self.base_movie();
+                    let fetch =
+                        security::fetch(&mut self.context, &movie, &url, RequestOptions::get());
                     let level = self.resolve_level(level_id);
 
                     if url.is_empty() {
@@ -1316,7 +1315,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                     Cow::Borrowed(&url),
                     NavigationMethod::from_send_vars_method(swf_method),
                 );
-                let fetch = self.context.navigator.fetch(&url, opts);
+                let movie = self.base_movie();
+                let fetch = security::fetch(&mut self.context, &movie, &url, opts);
                 let process = self.context.load_manager.load_form_into_object(
                     self.context.player.clone().unwrap(),
                     target_obj,
@@ -1340,7 +1340,8 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                         mc.replace_with_movie(self.context.gc_context, None)
                     }
                 } else {
-                    let fetch = self.context.navigator.fetch(&url, opts);
+                    let movie = self.base_movie();
+                    let fetch = security::fetch(&mut self.context, &movie, &url, opts);
                     let process = self.context.load_manager.load_movie_into_clip(
                         self.context.player.clone().unwrap(),
                         clip_target,
@@ -1357,7 +1358,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             // target of `_level#` indicates a `loadMovieNum` call.
             match window_target[6..].parse::<i32>() {
                 Ok(level_id) => {
-                    let fetch = self.context.navigator.fetch(&url, RequestOptions::get());
+                    let movie = self.base_movie();
+                    let fetch =
+                        security::fetch(&mut self.context, &movie, &url, RequestOptions::get());
                     let level = self.resolve_level(level_id);
 
                     let process = self.context.load_manager.load_movie_into_clip(
@@ -1380,8 +1383,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
 
             return Ok(FrameControl::Continue);
         } else {
+            // The variables of the target timeline are sent, even when
+            // called from within a function.
             let vars = match NavigationMethod::from_send_vars_method(swf_method) {
-                Some(method) => Some((method, self.locals_into_form_values())),
+                Some(method) => {
+                    let object = self.target_clip_or_root()?.object().coerce_to_object(self);
+                    Some((method, self.object_into_form_values(object)))
+                }
                 None => None,
             };
 
@@ -2189,7 +2197,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         } else {
             val.coerce_to_string(self)?
         };
-        self.context.log.avm_trace(&out);
+        self.context.log.log_event(LogCategory::AvmTrace, &out);
         Ok(FrameControl::Continue)
     }
 
@@ -2313,7 +2321,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             }
         }
 
-        if let Some(actions) = try_block.finally {
+        // Halting errors abort the script entirely, so `finally` is skipped.
+        let is_halting = matches!(&result, Err(e) if e.is_halting());
+        if let Some(actions) = try_block.finally.filter(|_| !is_halting) {
             if let ReturnType::Explicit(value) =
                 self.run_actions(parent_data.to_unbounded_subslice(actions).unwrap())?
             {
@@ -2893,8 +2903,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     }
 
     /// Returns the SWF version of the action or function being executed.
+    ///
+    /// This is the version that version-specific behavior, such as value
+    /// coercions, follows. It may be overridden for the whole player with
+    /// `Avm1::set_force_swf_version`.
     pub fn swf_version(&self) -> u8 {
-        self.swf_version
+        self.context
+            .avm1
+            .force_swf_version()
+            .unwrap_or(self.swf_version)
     }
 
     /// Returns AVM local variable scope.
@@ -2924,6 +2941,15 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.base_clip
     }
 
+    /// The movie that the code of this stack frame comes from.
+    ///
+    /// Loads made by this code are subject to the movie's sandbox.
+    pub fn base_movie(&self) -> Arc<SwfMovie> {
+        self.base_clip
+            .movie()
+            .unwrap_or_else(|| self.context.swf.clone())
+    }
+
     /// Gets the current target clip of this stack frame.
     /// This is the movie clip to which `GotoFrame` and other actions apply.
     /// Changed via `ActionSetTarget`/`ActionSetTarget2`.
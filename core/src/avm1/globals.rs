@@ -34,6 +34,7 @@ pub mod gradient_bevel_filter;
 pub mod gradient_glow_filter;
 mod key;
 mod load_vars;
+mod local_connection;
 mod math;
 mod matrix;
 pub(crate) mod mouse;
@@ -371,6 +372,11 @@ pub fn clear_timeout<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `updateAfterEvent`
+///
+/// This only requests a render: the host redraws once the current event has
+/// been handled, so calling it repeatedly from one handler costs a single
+/// redraw.
 pub fn update_after_event<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
@@ -381,6 +387,19 @@ pub fn update_after_event<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `getVersion`, which returns the same string as `$version`.
+pub fn get_version<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let version_string = activation
+        .context
+        .system
+        .get_version_string(activation.context.avm1);
+    Ok(AvmString::new(activation.context.gc_context, version_string).into())
+}
+
 pub fn escape<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Object<'gc>,
@@ -565,6 +584,8 @@ pub fn create_globals<'gc>(
         boolean::create_proto(gc_context, object_proto, function_proto);
     let load_vars_proto: Object<'gc> =
         load_vars::create_proto(gc_context, object_proto, function_proto);
+    let local_connection_proto: Object<'gc> =
+        local_connection::create_proto(gc_context, object_proto, function_proto);
     let matrix_proto: Object<'gc> = matrix::create_proto(gc_context, object_proto, function_proto);
     let point_proto: Object<'gc> = point::create_proto(gc_context, object_proto, function_proto);
     let rectangle_proto: Object<'gc> =
@@ -641,6 +662,13 @@ pub fn create_globals<'gc>(
         Some(function_proto),
         load_vars_proto,
     );
+    let local_connection = FunctionObject::constructor(
+        gc_context,
+        Executable::Native(local_connection::constructor),
+        constructor_to_fn!(local_connection::constructor),
+        Some(function_proto),
+        local_connection_proto,
+    );
     let movie_clip = FunctionObject::constructor(
         gc_context,
         Executable::Native(movie_clip::constructor),
@@ -959,6 +987,12 @@ pub fn create_globals<'gc>(
         load_vars.into(),
         Attribute::DONT_ENUM,
     );
+    globals.define_value(
+        gc_context,
+        "LocalConnection",
+        local_connection.into(),
+        Attribute::DONT_ENUM,
+    );
     globals.define_value(
         gc_context,
         "MovieClip",
@@ -1191,6 +1225,13 @@ pub fn create_globals<'gc>(
         Attribute::DONT_ENUM,
         Some(function_proto),
     );
+    globals.force_set_function(
+        "getVersion",
+        get_version,
+        gc_context,
+        Attribute::DONT_ENUM,
+        Some(function_proto),
+    );
     globals.force_set_function(
         "escape",
         escape,
@@ -1351,6 +1392,8 @@ mod tests {
             [f64::NAN] => true,
             [""] => true,
             ["Hello"] => true,
+            ["abc"] => true,
+            ["10"] => false,
             [" "] => true,
             ["  5  "] => true,
             ["0"] => false,
@@ -1378,6 +1421,8 @@ mod tests {
             [f64::NAN] => false,
             [""] => false,
             ["Hello"] => false,
+            ["abc"] => false,
+            ["10"] => true,
             [" "] => false,
             ["  5  "] => false,
             ["0"] => true,
@@ -1470,4 +1515,35 @@ mod tests {
             [Value::Null] => f64::NAN
         }
     );
+
+    test_method!(parse_int_function, "parseInt", setup,
+        [19] => {
+            ["0xFF"] => 255.0,
+            ["0x10"] => 16.0,
+            ["10", 2] => 2.0,
+            ["ff", 16] => 255.0,
+            ["  42"] => 42.0,
+            ["\t-7"] => -7.0,
+            ["12abc"] => 12.0,
+            ["123", 10] => 123.0,
+            ["2", 2] => f64::NAN,
+            ["abc"] => f64::NAN,
+            [""] => f64::NAN,
+            ["10", 1] => f64::NAN,
+            ["10", 37] => f64::NAN,
+            [] => Value::Undefined
+        }
+    );
+
+    test_method!(parse_float_function, "parseFloat", setup,
+        [19] => {
+            ["3.14abc"] => 3.14,
+            ["  2.5"] => 2.5,
+            ["-1.5e2"] => -150.0,
+            [".5"] => 0.5,
+            ["abc"] => f64::NAN,
+            [""] => f64::NAN,
+            [] => f64::NAN
+        }
+    );
 }
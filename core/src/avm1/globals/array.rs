@@ -85,26 +85,7 @@ pub fn constructor<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let mut consumed = false;
-
-    if args.len() == 1 {
-        let arg = args.get(0).unwrap();
-        if let Value::Number(length) = *arg {
-            if length >= 0.0 {
-                this.set_length(activation.context.gc_context, length as usize);
-                consumed = true;
-            } else if !length.is_nan() {
-                this.set_length(activation.context.gc_context, 0);
-                consumed = true;
-            }
-        }
-    }
-
-    if !consumed {
-        for (i, arg) in args.iter().enumerate() {
-            this.set_array_element(i, arg.to_owned(), activation.context.gc_context);
-        }
-    }
+    init_array(activation, this, args);
 
     Ok(this.into())
 }
@@ -115,31 +96,37 @@ pub fn array_function<'gc>(
     _this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let mut consumed = false;
-
     let prototype = activation.context.avm1.prototypes.array;
     let array_obj = prototype.create_bare_object(activation, prototype)?;
 
-    if args.len() == 1 {
-        let arg = args.get(0).unwrap();
-        if let Value::Number(length) = *arg {
-            if length >= 0.0 {
-                array_obj.set_length(activation.context.gc_context, length as usize);
-                consumed = true;
-            } else if !length.is_nan() {
-                array_obj.set_length(activation.context.gc_context, 0);
-                consumed = true;
-            }
-        }
-    }
+    init_array(activation, array_obj, args);
+
+    Ok(array_obj.into())
+}
 
-    if !consumed {
-        for (i, arg) in args.iter().enumerate() {
-            array_obj.set_array_element(i, arg.to_owned(), activation.context.gc_context);
+/// Fill a new array from the arguments of `new Array` or `Array()`.
+///
+/// A single number is the length of an array of holes, e.g. `new Array(5)`.
+/// Any other arguments become the elements, e.g. `new Array(1, 2, 3)` or
+/// `new Array("a")`.
+fn init_array<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    array: Object<'gc>,
+    args: &[Value<'gc>],
+) {
+    if let [Value::Number(length)] = args {
+        if *length >= 0.0 {
+            array.set_length(activation.context.gc_context, *length as usize);
+            return;
+        } else if !length.is_nan() {
+            array.set_length(activation.context.gc_context, 0);
+            return;
         }
     }
 
-    Ok(array_obj.into())
+    for (i, arg) in args.iter().enumerate() {
+        array.set_array_element(i, arg.to_owned(), activation.context.gc_context);
+    }
 }
 
 pub fn push<'gc>(
@@ -264,25 +251,19 @@ pub fn join<'gc>(
     this: Object<'gc>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let separator = args
-        .get(0)
-        .and_then(|v| v.coerce_to_string(activation).ok())
-        .unwrap_or_else(|| ",".into());
-    let values: Vec<Value<'gc>> = this.array();
+    let separator = match args.get(0) {
+        None | Some(Value::Undefined) => ",".to_string(),
+        Some(separator) => separator.coerce_to_string(activation)?.to_string(),
+    };
 
-    Ok(AvmString::new(
-        activation.context.gc_context,
-        values
-            .iter()
-            .map(|v| {
-                v.coerce_to_string(activation)
-                    .unwrap_or_else(|_| "undefined".into())
-                    .to_string()
-            })
-            .collect::<Vec<String>>()
-            .join(&separator),
-    )
-    .into())
+    let length = this.length();
+    let mut elements = Vec::with_capacity(length);
+    for i in 0..length {
+        let value = this.array_element(i);
+        elements.push(value.coerce_to_string(activation)?.to_string());
+    }
+
+    Ok(AvmString::new(activation.context.gc_context, elements.join(&separator)).into())
 }
 
 /// Handles an index parameter that may be positive (starting from beginning) or negaitve (starting from end).
@@ -308,6 +289,7 @@ pub fn slice<'gc>(
         .unwrap_or(0);
     let end = args
         .get(1)
+        .filter(|v| !matches!(v, Value::Undefined))
         .and_then(|v| v.coerce_to_f64(activation).ok())
         .map(|v| make_index_absolute(v as i32, this.length()))
         .unwrap_or_else(|| this.length());
@@ -824,3 +806,163 @@ fn sort_compare_custom<'gc>(
         _ => DEFAULT_ORDERING,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    /// Calls `new Array(args)` and `Array(args)`, returning both arrays.
+    fn new_arrays<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[Value<'gc>],
+    ) -> Result<[Object<'gc>; 2], Error<'gc>> {
+        let array_class = activation
+            .context
+            .avm1
+            .global_object_cell()
+            .get("Array", activation)?
+            .coerce_to_object(activation);
+        let constructed = array_class
+            .construct(activation, args)?
+            .coerce_to_object(activation);
+        let called = array_class
+            .call("Array", activation, array_class, None, args)?
+            .coerce_to_object(activation);
+        Ok([constructed, called])
+    }
+
+    #[test]
+    fn single_number_sets_length() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            for array in &new_arrays(activation, &[5.into()])? {
+                assert_eq!(array.length(), 5);
+                assert_eq!(array.get("length", activation)?, 5.into());
+                assert_eq!(array.array_element(0), Value::Undefined);
+                assert_eq!(array.array_element(4), Value::Undefined);
+            }
+
+            for array in &new_arrays(activation, &[(-1).into()])? {
+                assert_eq!(array.length(), 0);
+            }
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn multiple_arguments_become_elements() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            for array in &new_arrays(activation, &[1.into(), 2.into(), 3.into()])? {
+                assert_eq!(array.length(), 3);
+                assert_eq!(array.array(), vec![1.into(), 2.into(), 3.into()]);
+            }
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn single_non_number_becomes_element() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            for array in &new_arrays(activation, &["5".into()])? {
+                assert_eq!(array.length(), 1);
+                assert_eq!(array.array(), vec!["5".into()]);
+            }
+
+            for array in &new_arrays(activation, &[f64::NAN.into()])? {
+                assert_eq!(array.length(), 1);
+                assert!(array.array_element(0).coerce_to_f64(activation)?.is_nan());
+            }
+
+            Ok(())
+        });
+    }
+
+    /// Creates an array holding `values`.
+    fn array_of<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        values: &[Value<'gc>],
+    ) -> Object<'gc> {
+        let array = ScriptObject::array(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.array),
+        );
+        for (i, value) in values.iter().enumerate() {
+            array.set_array_element(i, *value, activation.context.gc_context);
+        }
+        array.into()
+    }
+
+    #[test]
+    fn concat_flattens_one_level() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            let nested = array_of(activation, &[4.into(), 5.into()]);
+            let first = array_of(activation, &[1.into(), 2.into()]);
+            let second = array_of(activation, &[3.into(), nested.into()]);
+
+            let result = first
+                .call_method("concat", &[second.into(), 6.into()], activation)?
+                .coerce_to_object(activation);
+            assert_eq!(
+                result.array(),
+                vec![1.into(), 2.into(), 3.into(), nested.into(), 6.into()]
+            );
+            assert_eq!(first.length(), 2);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn slice_with_negative_indices() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            let array = array_of(
+                activation,
+                &[1.into(), 2.into(), 3.into(), 4.into(), 5.into()],
+            );
+
+            let result = array
+                .call_method("slice", &[(-3).into(), (-1).into()], activation)?
+                .coerce_to_object(activation);
+            assert_eq!(result.array(), vec![3.into(), 4.into()]);
+
+            let result = array
+                .call_method("slice", &[(-2).into()], activation)?
+                .coerce_to_object(activation);
+            assert_eq!(result.array(), vec![4.into(), 5.into()]);
+
+            let result = array
+                .call_method("slice", &[1.into(), Value::Undefined], activation)?
+                .coerce_to_object(activation);
+            assert_eq!(result.length(), 4);
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn join_with_separator() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            let array = array_of(
+                activation,
+                &["a".into(), Value::Null, Value::Undefined, 2.into()],
+            );
+
+            assert_eq!(
+                array.call_method("join", &[" - ".into()], activation)?,
+                "a - null - undefined - 2".into()
+            );
+            assert_eq!(
+                array.call_method("join", &[], activation)?,
+                "a,null,undefined,2".into()
+            );
+            assert_eq!(
+                array.call_method("join", &[Value::Undefined], activation)?,
+                "a,null,undefined,2".into()
+            );
+
+            Ok(())
+        });
+    }
+}
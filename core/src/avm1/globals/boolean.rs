@@ -117,3 +117,50 @@ pub fn value_of<'gc>(
 
     Ok(Value::Undefined)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup_true<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::Bool(true).coerce_to_object(activation)
+    }
+
+    fn setup_false<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::Bool(false).coerce_to_object(activation)
+    }
+
+    fn setup_non_boolean<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        let object = Value::from("true").coerce_to_object(activation);
+        let proto = activation.context.avm1.prototypes().boolean;
+        for name in &["toString", "valueOf"] {
+            let method = proto.get(name, activation).unwrap();
+            object.set(name, method, activation).unwrap();
+        }
+        object
+    }
+
+    test_method!(to_string_true, "toString", setup_true,
+        [5, 6, 7, 8] => { [] => "true" }
+    );
+
+    test_method!(to_string_false, "toString", setup_false,
+        [5, 6, 7, 8] => { [] => "false" }
+    );
+
+    test_method!(value_of_true, "valueOf", setup_true,
+        [5, 6, 7, 8] => { [] => true }
+    );
+
+    test_method!(value_of_false, "valueOf", setup_false,
+        [5, 6, 7, 8] => { [] => false }
+    );
+
+    test_method!(to_string_non_boolean, "toString", setup_non_boolean,
+        [5, 6, 7, 8] => { [] => Value::Undefined }
+    );
+
+    test_method!(value_of_non_boolean, "valueOf", setup_non_boolean,
+        [5, 6, 7, 8] => { [] => Value::Undefined }
+    );
+}
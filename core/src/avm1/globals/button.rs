@@ -75,6 +75,7 @@ pub fn create_proto<'gc>(
     let object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
+    display_object::define_tab_properties(gc_context, object, fn_proto);
 
     with_button_props!(
         object, gc_context, fn_proto,
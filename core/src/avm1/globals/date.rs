@@ -592,7 +592,7 @@ fn constructor<'gc>(
     } else {
         this.set_date_time(
             activation.context.gc_context,
-            Some(activation.context.locale.get_current_date_time()),
+            Some(activation.context.clock.date_time()),
         )
     }
 
@@ -112,6 +112,104 @@ pub fn define_display_object_proto<'gc>(
     );
 }
 
+/// Add the `tabEnabled` and `tabIndex` properties to the prototype of an
+/// object that can take part in the tab order.
+pub fn define_tab_properties<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    mut object: ScriptObject<'gc>,
+    fn_proto: Object<'gc>,
+) {
+    object.add_property(
+        gc_context,
+        "tabEnabled",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(tab_enabled),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_tab_enabled),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        Attribute::DONT_DELETE | Attribute::DONT_ENUM,
+    );
+
+    object.add_property(
+        gc_context,
+        "tabIndex",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(tab_index),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        Some(FunctionObject::function(
+            gc_context,
+            Executable::Native(set_tab_index),
+            Some(fn_proto),
+            fn_proto,
+        )),
+        Attribute::DONT_DELETE | Attribute::DONT_ENUM,
+    );
+}
+
+pub fn tab_enabled<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|display_object| display_object.tab_enabled())
+        .map(Value::Bool)
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_tab_enabled<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let tab_enabled = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined => None,
+            value => Some(value.as_bool(activation.swf_version())),
+        };
+        display_object.set_tab_enabled(activation.context.gc_context, tab_enabled);
+    }
+    Ok(Value::Undefined)
+}
+
+pub fn tab_index<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this
+        .as_display_object()
+        .and_then(|display_object| display_object.tab_index())
+        .map(Value::from)
+        .unwrap_or(Value::Undefined))
+}
+
+pub fn set_tab_index<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(display_object) = this.as_display_object() {
+        let tab_index = match args.get(0).unwrap_or(&Value::Undefined) {
+            Value::Undefined | Value::Null => None,
+            value => Some(value.coerce_to_i32(activation)?),
+        };
+        display_object.set_tab_index(activation.context.gc_context, tab_index);
+    }
+    Ok(Value::Undefined)
+}
+
 pub fn get_parent<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
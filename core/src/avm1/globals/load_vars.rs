@@ -7,6 +7,7 @@ use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, ScriptObject, TObject, Value};
 use crate::avm_warn;
 use crate::backend::navigator::{NavigationMethod, RequestOptions};
+use crate::security;
 use gc_arena::MutationContext;
 use std::borrow::Cow;
 
@@ -122,7 +123,7 @@ fn add_request_header<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "LoadVars.addRequestHeader: Unimplemented");
+    avm1_stub!(activation, "LoadVars.addRequestHeader");
     Ok(Value::Undefined)
 }
 
@@ -328,7 +329,8 @@ fn spawn_load_var_fetch<'gc>(
         (Cow::Borrowed(url.as_str()), RequestOptions::get())
     };
 
-    let fetch = activation.context.navigator.fetch(&url, request_options);
+    let movie = activation.base_movie();
+    let fetch = security::fetch(&mut activation.context, &movie, &url, request_options);
     let process = activation.context.load_manager.load_form_into_load_vars(
         activation.context.player.clone().unwrap(),
         loader_object,
@@ -0,0 +1,164 @@
+//! AVM1 LocalConnection object
+//! TODO: Connections between separate player instances, `domain`, `allowDomain`
+
+use crate::avm1::activation::Activation;
+use crate::avm1::error::Error;
+use crate::avm1::property::Attribute;
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::context::ActionType;
+use gc_arena::MutationContext;
+
+/// Method names that can't be called through a connection, as they belong
+/// to `LocalConnection` itself.
+const RESERVED_METHODS: &[&str] = &[
+    "send",
+    "connect",
+    "close",
+    "allowDomain",
+    "allowInsecureDomain",
+    "domain",
+];
+
+/// Implements `LocalConnection`
+pub fn constructor<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.into())
+}
+
+/// Implements `LocalConnection.connect`
+///
+/// Fails if this connection is already listening, or if the name is taken.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = match args.get(0) {
+        Some(Value::String(name)) => name.to_string(),
+        _ => return Ok(false.into()),
+    };
+
+    let local_connections = &mut activation.context.avm1.local_connections;
+    if local_connections.contains_key(&name)
+        || local_connections
+            .values()
+            .any(|receiver| Object::ptr_eq(*receiver, this))
+    {
+        return Ok(false.into());
+    }
+
+    local_connections.insert(name, this);
+    Ok(true.into())
+}
+
+/// Implements `LocalConnection.send`
+///
+/// The method is called on the receiving connection once queued actions
+/// run, followed by `onStatus` on this connection.
+pub fn send<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let (name, method) = match (args.get(0), args.get(1)) {
+        (Some(Value::String(name)), Some(Value::String(method))) => {
+            (name.to_string(), method.to_string())
+        }
+        _ => return Ok(false.into()),
+    };
+
+    if RESERVED_METHODS.contains(&method.as_str()) {
+        return Ok(false.into());
+    }
+
+    let root = activation.context.stage.root_clip();
+    let receiver = activation
+        .context
+        .avm1
+        .local_connections
+        .get(&name)
+        .copied();
+    let level = if let Some(receiver) = receiver {
+        activation.context.action_queue.queue_actions(
+            root,
+            ActionType::LocalConnectionMessage {
+                receiver,
+                method,
+                args: args[2..].to_vec(),
+            },
+            false,
+        );
+        "status"
+    } else {
+        "error"
+    };
+
+    let info = ScriptObject::object(
+        activation.context.gc_context,
+        Some(activation.context.avm1.prototypes().object),
+    );
+    info.set("level", level.into(), activation)?;
+    activation.context.action_queue.queue_actions(
+        root,
+        ActionType::Method {
+            object: this,
+            name: "onStatus",
+            args: vec![info.into()],
+        },
+        false,
+    );
+
+    Ok(true.into())
+}
+
+/// Implements `LocalConnection.close`
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    activation
+        .context
+        .avm1
+        .local_connections
+        .retain(|_, receiver| !Object::ptr_eq(*receiver, this));
+
+    Ok(Value::Undefined)
+}
+
+pub fn create_proto<'gc>(
+    gc_context: MutationContext<'gc, '_>,
+    proto: Object<'gc>,
+    fn_proto: Object<'gc>,
+) -> Object<'gc> {
+    let mut object = ScriptObject::object(gc_context, Some(proto));
+
+    object.force_set_function(
+        "connect",
+        connect,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "send",
+        send,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.force_set_function(
+        "close",
+        close,
+        gc_context,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+        Some(fn_proto),
+    );
+
+    object.into()
+}
@@ -358,6 +358,9 @@ mod tests {
             [-23.2] => -23.0,
             [-23.5] => -23.0,
             [-23.7] => -24.0,
+            [-0.5] => 0.0,
+            [-1.5] => -1.0,
+            [-0.4] => 0.0,
             [f64::NAN] => f64::NAN,
             [f64::INFINITY] => f64::INFINITY,
             [f64::NEG_INFINITY] => f64::NEG_INFINITY
@@ -379,6 +382,15 @@ mod tests {
         }
     );
 
+    test_method!(test_atan2, "atan2", setup,
+        [19] => {
+            [] => f64::NAN,
+            [1.0, 1.0] => std::f64::consts::FRAC_PI_4,
+            [1.0, -1.0] => f64::atan2(1.0, -1.0),
+            [1.0] => std::f64::consts::FRAC_PI_2
+        }
+    );
+
     test_method!(test_sqrt, "sqrt", setup,
         [19] => {
             [] => f64::NAN,
@@ -516,4 +528,18 @@ mod tests {
             Ok(())
         });
     }
+
+    #[test]
+    fn test_constants() {
+        with_avm(19, |activation, _root| -> Result<(), Error> {
+            let math = setup(activation);
+            assert_eq!(math.get("PI", activation)?, std::f64::consts::PI.into());
+            assert_eq!(math.get("E", activation)?, std::f64::consts::E.into());
+            assert_eq!(
+                math.get("SQRT2", activation)?,
+                std::f64::consts::SQRT_2.into()
+            );
+            Ok(())
+        });
+    }
 }
@@ -15,6 +15,7 @@ use crate::display_object::{
 };
 use crate::ecma_conversions::f64_to_wrapping_i32;
 use crate::prelude::*;
+use crate::security;
 use crate::shape_utils::DrawCommand;
 use crate::tag_utils::SwfSlice;
 use crate::vminterface::Instantiator;
@@ -168,6 +169,7 @@ pub fn create_proto<'gc>(
     let mut object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
+    display_object::define_tab_properties(gc_context, object, fn_proto);
 
     with_movie_clip!(
         gc_context,
@@ -224,7 +226,10 @@ pub fn create_proto<'gc>(
         "transform" => [transform, set_transform],
         "enabled" => [enabled, set_enabled],
         "focusEnabled" => [focus_enabled, set_focus_enabled],
+        "tabChildren" => [tab_children, set_tab_children],
         "_lockroot" => [lock_root, set_lock_root],
+        "cacheAsBitmap" => [cache_as_bitmap, set_cache_as_bitmap],
+        "scrollRect" => [scroll_rect, set_scroll_rect],
         "useHandCursor" => [use_hand_cursor, set_use_hand_cursor],
     );
 
@@ -819,11 +824,28 @@ pub fn duplicate_movie_clip_with_bias<'gc>(
     }
 }
 
+/// Implements `MovieClip.getBytesLoaded`.
+///
+/// While a movie is being loaded into the clip, this reports the download
+/// progress from the `LoadManager`.
+///
+/// TODO: Movies aren't streamed yet. Navigators only deliver complete
+/// downloads and the root movie is handed to the player in one piece, so
+/// this jumps straight from zero to `getBytesTotal` instead of increasing
+/// as the movie arrives.
 fn get_bytes_loaded<'gc>(
     movie_clip: MovieClip<'gc>,
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some((bytes_loaded, _)) = activation
+        .context
+        .load_manager
+        .movie_load_progress(movie_clip.into())
+    {
+        return Ok(bytes_loaded.into());
+    }
+
     let bytes_loaded = if movie_clip.is_swf() {
         movie_clip
             .movie()
@@ -837,9 +859,19 @@ fn get_bytes_loaded<'gc>(
 
 fn get_bytes_total<'gc>(
     movie_clip: MovieClip<'gc>,
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
+    // A movie that is still being loaded into the clip reports the size
+    // of the download.
+    if let Some((_, bytes_total)) = activation
+        .context
+        .load_manager
+        .movie_load_progress(movie_clip.into())
+    {
+        return Ok(bytes_total.into());
+    }
+
     // For a loaded SWF, returns the uncompressed size of the SWF.
     // Otherwise, returns the size of the tag list in the clip's DefineSprite tag.
     let bytes_total = if movie_clip.is_swf() {
@@ -1210,7 +1242,7 @@ fn get_rect<'gc>(
 
 #[allow(unused_must_use)] //can't use errors yet
 pub fn get_url<'gc>(
-    _movie_clip: MovieClip<'gc>,
+    movie_clip: MovieClip<'gc>,
     activation: &mut Activation<'_, 'gc, '_>,
     args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
@@ -1236,7 +1268,14 @@ pub fn get_url<'gc>(
             Some(Value::String(s)) if *s == "POST" => Some(NavigationMethod::Post),
             _ => None,
         };
-        let vars_method = method.map(|m| (m, activation.locals_into_form_values()));
+        // The variables sent are those of this clip, not the caller's.
+        let vars_method = match method {
+            Some(method) => {
+                let object = movie_clip.object().coerce_to_object(activation);
+                Some((method, activation.object_into_form_values(object)))
+            }
+            None => None,
+        };
 
         activation
             .context
@@ -1290,7 +1329,8 @@ fn load_movie<'gc>(
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
     let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
+    let movie = activation.base_movie();
+    let fetch = security::fetch(&mut activation.context, &movie, &url, opts);
     let process = activation.context.load_manager.load_movie_into_clip(
         activation.context.player.clone().unwrap(),
         DisplayObject::MovieClip(target),
@@ -1315,7 +1355,8 @@ fn load_variables<'gc>(
     let method = args.get(1).cloned().unwrap_or(Value::Undefined);
     let method = NavigationMethod::from_method_str(&method.coerce_to_string(activation)?);
     let (url, opts) = activation.locals_into_request_options(Cow::Borrowed(&url), method);
-    let fetch = activation.context.navigator.fetch(&url, opts);
+    let movie = activation.base_movie();
+    let fetch = security::fetch(&mut activation.context, &movie, &url, opts);
     let target = target.object().coerce_to_object(activation);
     let process = activation.context.load_manager.load_form_into_object(
         activation.context.player.clone().unwrap(),
@@ -1394,6 +1435,23 @@ fn set_focus_enabled<'gc>(
     Ok(())
 }
 
+fn tab_children<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.tab_children().into())
+}
+
+fn set_tab_children<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let tab_children = value.as_bool(activation.swf_version());
+    this.set_tab_children(activation.context.gc_context, tab_children);
+    Ok(())
+}
+
 fn lock_root<'gc>(
     this: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
@@ -1411,6 +1469,65 @@ fn set_lock_root<'gc>(
     Ok(())
 }
 
+fn cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    _activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(this.cache_as_bitmap().into())
+}
+
+fn set_cache_as_bitmap<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let cache_as_bitmap = value.as_bool(activation.swf_version());
+    this.set_cache_as_bitmap(activation.context.gc_context, cache_as_bitmap);
+    Ok(())
+}
+
+fn scroll_rect<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+) -> Result<Value<'gc>, Error<'gc>> {
+    if let Some(scroll_rect) = this.scroll_rect() {
+        let args = [
+            Value::Number(scroll_rect.x_min.to_pixels()),
+            Value::Number(scroll_rect.y_min.to_pixels()),
+            Value::Number(scroll_rect.width().to_pixels()),
+            Value::Number(scroll_rect.height().to_pixels()),
+        ];
+        let constructor = activation.context.avm1.prototypes.rectangle_constructor;
+        constructor.construct(activation, &args)
+    } else {
+        Ok(Value::Undefined)
+    }
+}
+
+fn set_scroll_rect<'gc>(
+    this: MovieClip<'gc>,
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+) -> Result<(), Error<'gc>> {
+    let scroll_rect = if let Value::Object(rect) = value {
+        let x = rect.get("x", activation)?.coerce_to_f64(activation)?;
+        let y = rect.get("y", activation)?.coerce_to_f64(activation)?;
+        let width = rect.get("width", activation)?.coerce_to_f64(activation)?;
+        let height = rect.get("height", activation)?.coerce_to_f64(activation)?;
+        Some(BoundingBox {
+            x_min: Twips::from_pixels(x),
+            y_min: Twips::from_pixels(y),
+            x_max: Twips::from_pixels(x + width),
+            y_max: Twips::from_pixels(y + height),
+            valid: true,
+        })
+    } else {
+        None
+    };
+    this.set_scroll_rect(activation.context.gc_context, scroll_rect);
+    Ok(())
+}
+
 fn use_hand_cursor<'gc>(
     this: MovieClip<'gc>,
     _activation: &mut Activation<'_, 'gc, '_>,
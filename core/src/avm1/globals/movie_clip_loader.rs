@@ -9,6 +9,8 @@ use crate::avm1::property::Attribute;
 use crate::avm1::{Object, Value};
 use crate::backend::navigator::RequestOptions;
 use crate::display_object::{DisplayObject, TDisplayObject};
+use crate::loader::MovieLoaderEventHandler;
+use crate::security;
 use gc_arena::MutationContext;
 
 pub fn constructor<'gc>(
@@ -45,17 +47,16 @@ pub fn load_clip<'gc>(
             .as_display_object()
             .and_then(|dobj| dobj.as_movie_clip())
         {
-            let fetch = activation
-                .context
-                .navigator
-                .fetch(&url, RequestOptions::get());
+            let movie = activation.base_movie();
+            let fetch =
+                security::fetch(&mut activation.context, &movie, &url, RequestOptions::get());
             let process = activation.context.load_manager.load_movie_into_clip(
                 activation.context.player.clone().unwrap(),
                 DisplayObject::MovieClip(movieclip),
                 fetch,
                 url.to_string(),
                 None,
-                Some(this),
+                Some(MovieLoaderEventHandler::Avm1Broadcast(this)),
             );
 
             activation.context.navigator.spawn_future(process);
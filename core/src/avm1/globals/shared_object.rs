@@ -5,9 +5,14 @@ use crate::avm1::property::Attribute;
 use crate::avm1::{AvmString, Object, TObject, Value};
 use crate::avm_warn;
 use crate::display_object::TDisplayObject;
+use crate::tag_utils::SwfMovie;
 use gc_arena::MutationContext;
 
+use crate::amf::sol::{self, AmfVersion};
+use crate::amf::Value as AmfValue;
+use crate::avm1::object::date_object::DateObject;
 use crate::avm1::object::shared_object::SharedObject;
+use chrono::{TimeZone, Utc};
 
 use json::JsonValue;
 
@@ -16,7 +21,7 @@ pub fn delete_all<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.deleteAll() not implemented");
+    avm1_stub!(activation, "SharedObject.deleteAll");
     Ok(Value::Undefined)
 }
 
@@ -25,48 +30,179 @@ pub fn get_disk_usage<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getDiskUsage() not implemented");
+    avm1_stub!(activation, "SharedObject.getDiskUsage");
     Ok(Value::Undefined)
 }
 
-/// Serialize an Object and any children to a JSON object
-/// It would be best if this was implemented via serde but due to avm and context it can't
-/// Undefined fields aren't serialized
-fn recursive_serialize<'gc>(
+/// Convert an AVM1 value to AMF.
+///
+/// Functions can't be stored, and yield `None`. `objects` holds every object
+/// and array converted so far, so that repeated and circular references are
+/// stored as AMF references.
+fn serialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: Value<'gc>,
+    objects: &mut Vec<Object<'gc>>,
+) -> Option<AmfValue> {
+    match value {
+        Value::Undefined => Some(AmfValue::Undefined),
+        Value::Null => Some(AmfValue::Null),
+        Value::Bool(b) => Some(AmfValue::Bool(b)),
+        Value::Number(f) => Some(AmfValue::Number(f)),
+        Value::String(s) => Some(AmfValue::String(s.to_string())),
+        Value::Object(o) => {
+            let function = activation.context.avm1.prototypes.function;
+            let array = activation.context.avm1.prototypes.array;
+
+            if o.is_instance_of(activation, o, function)
+                .unwrap_or_default()
+            {
+                return None;
+            }
+
+            if let Some(index) = objects.iter().position(|other| Object::ptr_eq(*other, o)) {
+                return Some(AmfValue::Reference(index));
+            }
+
+            if let Some(date) = o.as_date_object() {
+                let time = date
+                    .date_time()
+                    .map(|date_time| date_time.timestamp_millis() as f64)
+                    .unwrap_or(f64::NAN);
+                return Some(AmfValue::Date(time));
+            }
+
+            objects.push(o);
+
+            if o.is_instance_of(activation, o, array).unwrap_or_default() {
+                let length = o.length();
+                let dense = (0..length)
+                    .map(|i| {
+                        serialize_value(activation, o.array_element(i), objects)
+                            .unwrap_or(AmfValue::Undefined)
+                    })
+                    .collect();
+                let associative = serialize_properties(
+                    activation,
+                    o,
+                    objects,
+                    |k| !matches!(k.parse::<usize>(), Ok(i) if i < length),
+                );
+                Some(AmfValue::Array {
+                    length,
+                    dense,
+                    associative,
+                })
+            } else {
+                let properties = serialize_properties(activation, o, objects, |_| true);
+                Some(AmfValue::Object {
+                    class_name: None,
+                    properties,
+                })
+            }
+        }
+    }
+}
+
+/// Convert the properties of an AVM1 object to AMF.
+fn serialize_properties<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     obj: Object<'gc>,
-    json_obj: &mut JsonValue,
-) {
-    for k in &obj.get_keys(activation) {
-        if let Ok(elem) = obj.get(k, activation) {
-            match elem {
-                Value::Undefined => {}
-                Value::Null => json_obj[k] = JsonValue::Null,
-                Value::Bool(b) => json_obj[k] = b.into(),
-                Value::Number(f) => json_obj[k] = f.into(),
-                Value::String(s) => json_obj[k] = s.to_string().into(),
-                Value::Object(o) => {
-                    // Don't attempt to serialize functions
-                    let function = activation.context.avm1.prototypes.function;
-                    let array = activation.context.avm1.prototypes.array;
-                    if !o
-                        .is_instance_of(activation, o, function)
-                        .unwrap_or_default()
-                    {
-                        let mut sub_data_json = JsonValue::new_object();
-                        recursive_serialize(activation, o, &mut sub_data_json);
-                        if o.is_instance_of(activation, o, array).unwrap_or_default() {
-                            sub_data_json["__proto__"] = "Array".into();
-                            sub_data_json["length"] = o.length().into();
-                        }
-                        json_obj[k] = sub_data_json;
-                    }
+    objects: &mut Vec<Object<'gc>>,
+    filter: impl Fn(&str) -> bool,
+) -> Vec<(String, AmfValue)> {
+    let mut properties = Vec::new();
+
+    for k in obj.get_keys(activation) {
+        if !filter(&k) {
+            continue;
+        }
+
+        if let Ok(elem) = obj.get(&k, activation) {
+            if let Some(value) = serialize_value(activation, elem, objects) {
+                properties.push((k, value));
+            }
+        }
+    }
+
+    properties
+}
+
+/// Convert an AMF value to AVM1.
+///
+/// `objects` holds every object and array created so far, in the order that
+/// AMF references count them.
+fn deserialize_value<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    value: AmfValue,
+    objects: &mut Vec<Object<'gc>>,
+) -> Value<'gc> {
+    match value {
+        AmfValue::Undefined => Value::Undefined,
+        AmfValue::Null => Value::Null,
+        AmfValue::Bool(b) => Value::Bool(b),
+        AmfValue::Number(f) => Value::Number(f),
+        AmfValue::String(s) => Value::String(AvmString::new(activation.context.gc_context, s)),
+        AmfValue::Date(time) => {
+            let date_time = Utc.timestamp_millis_opt(time as i64).single();
+            DateObject::with_date_time(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.date),
+                date_time,
+            )
+            .into()
+        }
+        AmfValue::Object { properties, .. } => {
+            let obj_proto = activation.context.avm1.prototypes.object;
+            let obj = match obj_proto.create_bare_object(activation, obj_proto) {
+                Ok(obj) => obj,
+                Err(_) => return Value::Undefined,
+            };
+            objects.push(obj);
+
+            for (k, v) in properties {
+                let value = deserialize_value(activation, v, objects);
+                obj.define_value(activation.context.gc_context, &k, value, Attribute::empty());
+            }
+
+            obj.into()
+        }
+        AmfValue::Array {
+            length,
+            dense,
+            associative,
+        } => {
+            let array_constructor = activation.context.avm1.prototypes.array_constructor;
+            let obj = match array_constructor.construct(activation, &[length.into()]) {
+                Ok(Value::Object(obj)) => obj,
+                _ => return Value::Undefined,
+            };
+            objects.push(obj);
+
+            for (i, v) in dense.into_iter().enumerate() {
+                let value = deserialize_value(activation, v, objects);
+                obj.set_array_element(i, value, activation.context.gc_context);
+            }
+
+            for (k, v) in associative {
+                let value = deserialize_value(activation, v, objects);
+                if let Ok(i) = k.parse::<usize>() {
+                    obj.set_array_element(i, value, activation.context.gc_context);
+                } else {
+                    obj.define_value(activation.context.gc_context, &k, value, Attribute::empty());
                 }
             }
+
+            obj.into()
         }
+        AmfValue::Reference(index) => objects
+            .get(index)
+            .map_or(Value::Undefined, |obj| (*obj).into()),
     }
 }
 
+/// Deserialize data saved by older versions of Ruffle, which stored
+/// SharedObjects as JSON.
 fn recursive_deserialize<'gc>(
     json_value: JsonValue,
     activation: &mut Activation<'_, 'gc, '_>,
@@ -152,36 +288,31 @@ fn deserialize_array<'gc>(
     }
 }
 
-pub fn get_local<'gc>(
-    activation: &mut Activation<'_, 'gc, '_>,
-    _this: Object<'gc>,
-    args: &[Value<'gc>],
-) -> Result<Value<'gc>, Error<'gc>> {
-    let name = args
-        .get(0)
-        .unwrap_or(&Value::Undefined)
-        .coerce_to_string(activation)?
-        .to_string();
-
+/// The storage name of the shared object called `name` that belongs to
+/// `movie`, such as `foo.com/folder/game.swf/name`.
+///
+/// Shared objects are sandboxed per domain. By default, they are keyed by
+/// the path of the movie, but a prefix of that path can be given as
+/// `local_path` to share objects between movies. Yields `None` if the
+/// object can't be accessed.
+fn storage_name(
+    movie: &SwfMovie,
+    name: &str,
+    local_path: Option<&str>,
+    secure: bool,
+) -> Option<String> {
     const INVALID_CHARS: &str = "~%&\\;:\"',<>?# ";
     if name.contains(|c| INVALID_CHARS.contains(c)) {
         log::error!("SharedObject::get_local: Invalid character in name");
-        return Ok(Value::Null);
+        return None;
     }
 
-    let movie = if let Some(movie) = activation.base_clip().movie() {
-        movie
-    } else {
-        log::error!("SharedObject::get_local: Movie was None");
-        return Ok(Value::Null);
-    };
-
     let mut movie_url = if let Some(url) = movie.url() {
         if let Ok(url) = url::Url::parse(url) {
             url
         } else {
             log::error!("SharedObject::get_local: Unable to parse movie URL");
-            return Ok(Value::Null);
+            return None;
         }
     } else {
         // No URL (loading local data). Use a dummy URL to allow SharedObjects to work.
@@ -190,21 +321,14 @@ pub fn get_local<'gc>(
     movie_url.set_query(None);
     movie_url.set_fragment(None);
 
-    let secure = args
-        .get(2)
-        .unwrap_or(&Value::Undefined)
-        .as_bool(activation.swf_version());
-
     // Secure parameter disallows using the shared object from non-HTTPS.
     if secure && movie_url.scheme() != "https" {
         log::warn!(
             "SharedObject.get_local: Tried to load a secure shared object from non-HTTPS origin"
         );
-        return Ok(Value::Null);
+        return None;
     }
 
-    // Shared objects are sandboxed per-domain.
-    // By default, they are keyed based on the SWF URL, but the `localHost` parameter can modify this path.
     let mut movie_path = movie_url.path();
     // Remove leading/trailing slashes.
     movie_path = movie_path.strip_prefix("/").unwrap_or(movie_path);
@@ -220,14 +344,14 @@ pub fn get_local<'gc>(
         movie_url.host_str().unwrap_or_default()
     };
 
-    let local_path = if let Some(Value::String(local_path)) = args.get(1) {
+    let local_path = if let Some(local_path) = local_path {
         // Empty local path always fails.
         if local_path.is_empty() {
-            return Ok(Value::Null);
+            return None;
         }
 
         // Remove leading/trailing slashes.
-        let mut local_path = local_path.as_str().strip_prefix("/").unwrap_or(local_path);
+        let mut local_path = local_path.strip_prefix("/").unwrap_or(local_path);
         local_path = local_path.strip_suffix("/").unwrap_or(local_path);
 
         // Verify that local_path is a prefix of the SWF path.
@@ -239,14 +363,49 @@ pub fn get_local<'gc>(
             local_path
         } else {
             log::warn!("SharedObject.get_local: localPath parameter does not match SWF path");
-            return Ok(Value::Null);
+            return None;
         }
     } else {
         movie_path
     };
 
     // Final SO path: foo.com/folder/game.swf/SOName
-    let full_name = format!("{}/{}/{}", movie_host, local_path, name);
+    Some(format!("{}/{}/{}", movie_host, local_path, name))
+}
+
+pub fn get_local<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let name = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .to_string();
+
+    let movie = if let Some(movie) = activation.base_clip().movie() {
+        movie
+    } else {
+        log::error!("SharedObject::get_local: Movie was None");
+        return Ok(Value::Null);
+    };
+
+    let local_path = if let Some(Value::String(local_path)) = args.get(1) {
+        Some(local_path.as_str())
+    } else {
+        None
+    };
+
+    let secure = args
+        .get(2)
+        .unwrap_or(&Value::Undefined)
+        .as_bool(activation.swf_version());
+
+    let full_name = match storage_name(&movie, &name, local_path, secure) {
+        Some(full_name) => full_name,
+        None => return Ok(Value::Null),
+    };
 
     // Check if this is referencing an existing shared object
     if let Some(so) = activation.context.shared_objects.get(&full_name) {
@@ -267,9 +426,21 @@ pub fn get_local<'gc>(
     let mut data = Value::Undefined;
 
     // Load the data object from storage if it existed prior
-    if let Some(saved) = activation.context.storage.get_string(&full_name) {
-        if let Ok(json_data) = json::parse(&saved) {
-            data = recursive_deserialize(json_data, activation);
+    if let Some(saved) = activation.context.storage.get_bytes(&full_name) {
+        if let Ok(properties) = sol::deserialize(&saved) {
+            let amf_data = AmfValue::Object {
+                class_name: None,
+                properties,
+            };
+            data = deserialize_value(activation, amf_data, &mut Vec::new());
+        }
+    }
+
+    if data == Value::Undefined {
+        if let Some(saved) = activation.context.storage.get_string(&full_name) {
+            if let Ok(json_data) = json::parse(&saved) {
+                data = recursive_deserialize(json_data, activation);
+            }
         }
     }
 
@@ -295,7 +466,7 @@ pub fn get_remote<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getRemote() not implemented");
+    avm1_stub!(activation, "SharedObject.getRemote");
     Ok(Value::Undefined)
 }
 
@@ -304,7 +475,7 @@ pub fn get_max_size<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getMaxSize() not implemented");
+    avm1_stub!(activation, "SharedObject.getMaxSize");
     Ok(Value::Undefined)
 }
 
@@ -313,7 +484,7 @@ pub fn add_listener<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.addListener() not implemented");
+    avm1_stub!(activation, "SharedObject.addListener");
     Ok(Value::Undefined)
 }
 
@@ -322,7 +493,7 @@ pub fn remove_listener<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.removeListener() not implemented");
+    avm1_stub!(activation, "SharedObject.removeListener");
     Ok(Value::Undefined)
 }
 
@@ -423,7 +594,7 @@ pub fn close<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.close() not implemented");
+    avm1_stub!(activation, "SharedObject.close");
     Ok(Value::Undefined)
 }
 
@@ -432,7 +603,7 @@ pub fn connect<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.connect() not implemented");
+    avm1_stub!(activation, "SharedObject.connect");
     Ok(Value::Undefined)
 }
 
@@ -441,19 +612,32 @@ pub fn flush<'gc>(
     this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    let data = this.get("data", activation)?.coerce_to_object(activation);
-
-    let mut data_json = JsonValue::new_object();
-    recursive_serialize(activation, data, &mut data_json);
+    let data = this.get("data", activation)?;
+    let properties = match serialize_value(activation, data, &mut Vec::new()) {
+        Some(AmfValue::Object { properties, .. }) => properties,
+        _ => Vec::new(),
+    };
 
     let this_obj = this.as_shared_object().unwrap();
     let name = this_obj.get_name();
+    let file_name = name.rsplit('/').next().unwrap_or_default();
+
+    let bytes = match sol::serialize(file_name, AmfVersion::Amf0, &properties) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            log::error!("SharedObject.flush: Unable to encode data: {}", e);
+            return Ok(false.into());
+        }
+    };
 
-    Ok(activation
-        .context
-        .storage
-        .put_string(&name, data_json.dump())
-        .into())
+    if activation.context.storage.put_bytes(&name, &bytes) {
+        Ok(true.into())
+    } else {
+        // The backend refused the write, most likely because it's out of
+        // space. Flash reports this as "pending" while it asks the user for
+        // more storage.
+        Ok(AvmString::new(activation.context.gc_context, "pending".to_string()).into())
+    }
 }
 
 pub fn get_size<'gc>(
@@ -461,7 +645,7 @@ pub fn get_size<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.getSize() not implemented");
+    avm1_stub!(activation, "SharedObject.getSize");
     Ok(Value::Undefined)
 }
 
@@ -470,7 +654,7 @@ pub fn send<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.send() not implemented");
+    avm1_stub!(activation, "SharedObject.send");
     Ok(Value::Undefined)
 }
 
@@ -479,7 +663,7 @@ pub fn set_fps<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.setFps() not implemented");
+    avm1_stub!(activation, "SharedObject.setFps");
     Ok(Value::Undefined)
 }
 
@@ -488,7 +672,7 @@ pub fn on_status<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.onStatus() not implemented");
+    avm1_stub!(activation, "SharedObject.onStatus");
     Ok(Value::Undefined)
 }
 
@@ -497,7 +681,7 @@ pub fn on_sync<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "SharedObject.onSync() not implemented");
+    avm1_stub!(activation, "SharedObject.onSync");
     Ok(Value::Undefined)
 }
 
@@ -585,3 +769,93 @@ pub fn constructor<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     Ok(this.into())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::{with_avm, with_avm_and_builder};
+    use crate::backend::storage::StorageBackend;
+    use crate::player::PlayerBuilder;
+
+    /// A storage backend that has no space left.
+    struct FullStorageBackend;
+
+    impl StorageBackend for FullStorageBackend {
+        fn get_string(&self, _name: &str) -> Option<String> {
+            None
+        }
+
+        fn put_string(&mut self, _name: &str, _value: String) -> bool {
+            false
+        }
+
+        fn remove_key(&mut self, _name: &str) {}
+
+        fn keys(&self) -> Vec<String> {
+            Vec::new()
+        }
+    }
+
+    fn load_shared_object<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        name: &'static str,
+    ) -> Result<Object<'gc>, Error<'gc>> {
+        let constructor = activation.context.avm1.prototypes.shared_object_constructor;
+        Ok(constructor
+            .call_method("getLocal", &[name.into()], activation)?
+            .coerce_to_object(activation))
+    }
+
+    #[test]
+    fn get_local_returns_same_instance() {
+        with_avm(19, |activation, _root| -> Result<(), Error> {
+            let first = load_shared_object(activation, "test")?;
+            let second = load_shared_object(activation, "test")?;
+            assert!(Object::ptr_eq(first, second));
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn flushed_data_is_reloaded() {
+        with_avm(19, |activation, _root| -> Result<(), Error> {
+            let so = load_shared_object(activation, "test")?;
+            let data = so.get("data", activation)?.coerce_to_object(activation);
+            data.set("score", 42.0.into(), activation)?;
+            assert_eq!(so.call_method("flush", &[], activation)?, true.into());
+
+            // Forget the live instance, so that the next `getLocal` has to
+            // read the data back from storage.
+            activation.context.shared_objects.clear();
+
+            let reloaded = load_shared_object(activation, "test")?;
+            assert!(!Object::ptr_eq(so, reloaded));
+            let data = reloaded
+                .get("data", activation)?
+                .coerce_to_object(activation);
+            assert_eq!(data.get("score", activation)?, 42.0.into());
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn flush_is_pending_when_storage_is_full() {
+        with_avm_and_builder(
+            19,
+            PlayerBuilder::new().with_storage(Box::new(FullStorageBackend)),
+            |activation, _root| -> Result<(), Error> {
+                let so = load_shared_object(activation, "test")?;
+                let data = so.get("data", activation)?.coerce_to_object(activation);
+                data.set("score", 42.0.into(), activation)?;
+                assert_eq!(
+                    so.call_method("flush", &[], activation)?,
+                    AvmString::new(activation.context.gc_context, "pending".to_string()).into()
+                );
+
+                Ok(())
+            },
+        );
+    }
+}
@@ -259,7 +259,7 @@ fn get_bytes_loaded<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.swf_version() >= 6 {
-        avm_warn!(activation, "Sound.getBytesLoaded: Unimplemented");
+        avm1_stub!(activation, "Sound.getBytesLoaded");
         Ok(1.into())
     } else {
         Ok(Value::Undefined)
@@ -272,7 +272,7 @@ fn get_bytes_total<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.swf_version() >= 6 {
-        avm_warn!(activation, "Sound.getBytesTotal: Unimplemented");
+        avm1_stub!(activation, "Sound.getBytesTotal");
         Ok(1.into())
     } else {
         Ok(Value::Undefined)
@@ -351,7 +351,7 @@ fn id3<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.swf_version() >= 6 {
-        avm_warn!(activation, "Sound.id3: Unimplemented");
+        avm1_stub!(activation, "Sound.id3");
     }
     Ok(Value::Undefined)
 }
@@ -362,7 +362,7 @@ fn load_sound<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
     if activation.swf_version() >= 6 {
-        avm_warn!(activation, "Sound.loadSound: Unimplemented");
+        avm1_stub!(activation, "Sound.loadSound");
     }
     Ok(Value::Undefined)
 }
@@ -378,7 +378,7 @@ fn position<'gc>(
             // the previous valid position.
             // Needs some audio backend work for this.
             if sound_object.sound().is_some() {
-                avm_warn!(activation, "Sound.position: Unimplemented");
+                avm1_stub!(activation, "Sound.position");
                 return Ok(sound_object.position().into());
             }
         } else {
@@ -22,8 +22,6 @@ pub fn string<'gc>(
     };
 
     if let Some(mut vbox) = this.as_value_object() {
-        let len = value.encode_utf16().count();
-        vbox.set_length(activation.context.gc_context, len);
         vbox.replace_value(activation.context.gc_context, value.into());
     }
 
@@ -79,6 +77,18 @@ pub fn create_proto<'gc>(
     let string_proto = ValueObject::empty_box(gc_context, Some(proto));
     let mut object = string_proto.as_script_object().unwrap();
 
+    object.add_property(
+        gc_context,
+        "length",
+        FunctionObject::function(
+            gc_context,
+            Executable::Native(length),
+            Some(fn_proto),
+            fn_proto,
+        ),
+        None,
+        Attribute::DONT_DELETE | Attribute::READ_ONLY | Attribute::DONT_ENUM,
+    );
     object.force_set_function(
         "toString",
         to_string_value_of,
@@ -230,6 +240,17 @@ fn char_code_at<'gc>(
     Ok(ret.into())
 }
 
+/// `String.length` getter
+fn length<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let this_val = Value::from(this);
+    let this = this_val.coerce_to_string(activation)?;
+    Ok(this.encode_utf16().count().into())
+}
+
 fn concat<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Object<'gc>,
@@ -389,8 +410,6 @@ fn split<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_val = Value::from(this);
     let this = this_val.coerce_to_string(activation)?;
-    let delimiter_val = args.get(0).unwrap_or(&Value::Undefined);
-    let delimiter = delimiter_val.coerce_to_string(activation)?;
     let limit = match args.get(1) {
         None | Some(Value::Undefined) => usize::MAX,
         Some(n) => std::cmp::max(0, n.coerce_to_i32(activation)?) as usize,
@@ -399,6 +418,16 @@ fn split<'gc>(
         activation.context.gc_context,
         Some(activation.context.avm1.prototypes.array),
     );
+    let delimiter = match args.get(0) {
+        None | Some(Value::Undefined) => {
+            // Without a delimiter, the whole string is the only element.
+            if limit > 0 {
+                array.set_array_element(0, this.into(), activation.context.gc_context);
+            }
+            return Ok(array.into());
+        }
+        Some(delimiter) => delimiter.coerce_to_string(activation)?,
+    };
     if !delimiter.is_empty() {
         for (i, token) in this.split(delimiter.as_ref()).take(limit).enumerate() {
             array.set_array_element(
@@ -410,11 +439,12 @@ fn split<'gc>(
     } else {
         // When using an empty "" delimiter, Rust's str::split adds an extra beginning and trailing item, but Flash does not.
         // e.g., split("foo", "") returns ["", "f", "o", "o", ""] in Rust but ["f, "o", "o"] in Flash.
-        // Special case this to match Flash's behavior.
-        for (i, token) in this.chars().take(limit).enumerate() {
+        // Flash also splits into UTF-16 code units, so surrogate pairs are broken up.
+        for (i, unit) in this.encode_utf16().take(limit).enumerate() {
+            let token = string_utils::utf16_iter_to_string(std::iter::once(unit));
             array.set_array_element(
                 i,
-                AvmString::new(activation.context.gc_context, token.to_string()).into(),
+                AvmString::new(activation.context.gc_context, token).into(),
                 activation.context.gc_context,
             );
         }
@@ -484,13 +514,15 @@ fn to_lower_case<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_val = Value::from(this);
     let this = this_val.coerce_to_string(activation)?;
-    Ok(AvmString::new(
-        activation.context.gc_context,
+    let mapped = if activation.swf_version() >= 7 {
         this.chars()
             .map(string_utils::swf_char_to_lowercase)
-            .collect::<String>(),
-    )
-    .into())
+            .collect()
+    } else {
+        // SWF6 and below only change the case of ASCII letters.
+        this.to_ascii_lowercase()
+    };
+    Ok(AvmString::new(activation.context.gc_context, mapped).into())
 }
 
 /// `String.toString` / `String.valueOf` impl
@@ -518,13 +550,15 @@ fn to_upper_case<'gc>(
 ) -> Result<Value<'gc>, Error<'gc>> {
     let this_val = Value::from(this);
     let this = this_val.coerce_to_string(activation)?;
-    Ok(AvmString::new(
-        activation.context.gc_context,
+    let mapped = if activation.swf_version() >= 7 {
         this.chars()
             .map(string_utils::swf_char_to_uppercase)
-            .collect::<String>(),
-    )
-    .into())
+            .collect()
+    } else {
+        // SWF6 and below only change the case of ASCII letters.
+        this.to_ascii_uppercase()
+    };
+    Ok(AvmString::new(activation.context.gc_context, mapped).into())
 }
 
 /// Normalizes an  index parameter used in `String` functions such as `substring`.
@@ -584,3 +618,332 @@ fn string_index_substr(s: i32, e: i32, len: usize) -> usize {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::test_utils::with_avm;
+
+    fn setup<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::from("a\u{1F600}b").coerce_to_object(activation)
+    }
+
+    fn setup_constructor<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        create_string_object(
+            activation.context.gc_context,
+            activation.context.avm1.prototypes().string,
+            Some(activation.context.avm1.prototypes().function),
+        )
+    }
+
+    fn setup_repeated<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::from("abcabc").coerce_to_object(activation)
+    }
+
+    fn setup_hello<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::from("hello").coerce_to_object(activation)
+    }
+
+    fn setup_a<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::from("a").coerce_to_object(activation)
+    }
+
+    fn setup_abc<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::from("ABC").coerce_to_object(activation)
+    }
+
+    test_method!(char_at_index, "charAt", setup,
+        [5, 6] => {
+            [0] => "a",
+            [3] => "b",
+            [1.5] => "\u{FFFD}",
+            [3.9] => "b",
+            [-1] => "",
+            [-10.5] => "",
+            [4] => "",
+            [100] => "",
+            [] => "a"
+        }
+    );
+
+    test_method!(char_code_at_ascii, "charCodeAt", setup_abc,
+        [5, 6] => {
+            [0] => 65,
+            [1] => 66,
+            [2.5] => 67,
+            [3] => f64::NAN,
+            [10] => f64::NAN,
+            [-1] => f64::NAN
+        }
+    );
+
+    test_method!(char_code_at_surrogates, "charCodeAt", setup,
+        [5, 6] => {
+            [1] => 0xD83D,
+            [2] => 0xDE00,
+            [3] => 98
+        }
+    );
+
+    test_method!(from_char_code, "fromCharCode", setup_constructor,
+        [5, 6] => {
+            [72, 73] => "HI",
+            [0x10048, 0x20049] => "HI",
+            [72.9] => "H",
+            [72, 0, 73] => "H",
+            [] => ""
+        }
+    );
+
+    test_method!(index_of, "indexOf", setup_repeated,
+        [5, 6] => {
+            ["bc"] => 1,
+            ["bc", 1] => 1,
+            ["bc", 2] => 4,
+            ["bc", 5] => -1,
+            ["bc", -3] => 1,
+            ["x"] => -1,
+            [""] => 0,
+            ["", 2] => 2,
+            [1] => -1,
+            [] => Value::Undefined
+        }
+    );
+
+    test_method!(last_index_of, "lastIndexOf", setup_repeated,
+        [5, 6] => {
+            ["bc"] => 4,
+            ["bc", 4] => 4,
+            ["bc", 3] => 1,
+            ["bc", 0] => -1,
+            ["bc", Value::Undefined] => 4,
+            ["x"] => -1,
+            [""] => 6,
+            ["", 2] => 2,
+            [] => Value::Undefined
+        }
+    );
+
+    fn setup_accented<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Object<'gc> {
+        Value::from("Caf\u{E9} \u{C9}t\u{E9}").coerce_to_object(activation)
+    }
+
+    test_method!(to_lower_case, "toLowerCase", setup_accented,
+        [5, 6] => {
+            [] => "caf\u{E9} \u{C9}t\u{E9}"
+        },
+        [7, 8] => {
+            [] => "caf\u{E9} \u{E9}t\u{E9}"
+        }
+    );
+
+    test_method!(to_upper_case, "toUpperCase", setup_accented,
+        [5, 6] => {
+            [] => "CAF\u{E9} \u{C9}T\u{E9}"
+        },
+        [7, 8] => {
+            [] => "CAF\u{C9} \u{C9}T\u{C9}"
+        }
+    );
+
+    test_method!(concat, "concat", setup_a,
+        [6, 7] => {
+            [] => "a",
+            ["b", 3, true] => "ab3true",
+            [1.5, "", false] => "a1.5false",
+            [f64::NAN] => "aNaN"
+        },
+        [6] => {
+            [Value::Undefined] => "a"
+        },
+        [7] => {
+            [Value::Undefined] => "aundefined"
+        }
+    );
+
+    test_method!(substr, "substr", setup_hello,
+        [5, 6] => {
+            [-3, 2] => "ll",
+            [2] => "llo",
+            [2, Value::Undefined] => "llo",
+            [1, 3] => "ell",
+            [1, 10] => "ello",
+            [1.9, 2.9] => "el",
+            [1, 0] => "",
+            [1, f64::NAN] => "",
+            [-1] => "o",
+            [5] => "",
+            [] => Value::Undefined
+        }
+    );
+
+    test_method!(slice, "slice", setup_hello,
+        [5, 6] => {
+            [-3, -1] => "ll",
+            [2, 1] => "",
+            [1, 1] => "",
+            [1] => "ello",
+            [1, Value::Undefined] => "ello",
+            [-2] => "lo",
+            [1.9, 3.9] => "el",
+            [0, 100] => "hello",
+            [-1, 2] => "",
+            [] => Value::Undefined
+        }
+    );
+
+    #[test]
+    fn length_is_read_only_property() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            let literal = Value::from("hello").coerce_to_object(activation);
+            let string_class = activation
+                .context
+                .avm1
+                .global_object_cell()
+                .get("String", activation)?
+                .coerce_to_object(activation);
+            let instance = string_class
+                .construct(activation, &["a\u{1F600}".into()])?
+                .coerce_to_object(activation);
+
+            for (object, length) in &[(literal, 5), (instance, 3)] {
+                assert_eq!(object.get("length", activation)?, (*length).into());
+
+                object.set("length", 10.into(), activation)?;
+                assert!(!object.delete(activation, "length"));
+                assert_eq!(object.get("length", activation)?, (*length).into());
+                assert!(!object.get_keys(activation).contains(&"length".to_string()));
+            }
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn constructor_boxes_argument() {
+        with_avm(8, |activation, root| -> Result<(), Error> {
+            let string_class = activation
+                .context
+                .avm1
+                .global_object_cell()
+                .get("String", activation)?
+                .coerce_to_object(activation);
+
+            let instance = string_class
+                .construct(activation, &[42.into()])?
+                .coerce_to_object(activation);
+            assert_eq!(
+                instance.call_method("valueOf", &[], activation)?,
+                "42".into()
+            );
+
+            let empty = string_class
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            assert_eq!(empty.call_method("valueOf", &[], activation)?, "".into());
+
+            assert_eq!(
+                string_class.call("String", activation, root, None, &[true.into()])?,
+                "true".into()
+            );
+            assert_eq!(
+                string_class.call("String", activation, root, None, &[])?,
+                "".into()
+            );
+
+            Ok(())
+        });
+    }
+
+    fn split_string<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        string: &'static str,
+        args: &[Value<'gc>],
+    ) -> Result<Vec<Value<'gc>>, Error<'gc>> {
+        let object = Value::from(string).coerce_to_object(activation);
+        let result = split(activation, object, args)?.coerce_to_object(activation);
+        assert_eq!(
+            result.get("length", activation)?,
+            (result.length() as i32).into()
+        );
+        Ok(result.array())
+    }
+
+    #[test]
+    fn split_on_separator() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            assert_eq!(
+                split_string(activation, "a,b,c", &[",".into()])?,
+                vec!["a".into(), "b".into(), "c".into()]
+            );
+            assert_eq!(
+                split_string(activation, "a::b", &["::".into()])?,
+                vec!["a".into(), "b".into()]
+            );
+            assert_eq!(
+                split_string(activation, ",a,", &[",".into()])?,
+                vec!["".into(), "a".into(), "".into()]
+            );
+            assert_eq!(
+                split_string(activation, "abc", &["x".into()])?,
+                vec!["abc".into()]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn split_on_empty_separator() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            assert_eq!(
+                split_string(activation, "abc", &["".into()])?,
+                vec!["a".into(), "b".into(), "c".into()]
+            );
+            assert_eq!(
+                split_string(activation, "a\u{1F600}", &["".into()])?,
+                vec!["a".into(), "\u{FFFD}".into(), "\u{FFFD}".into()]
+            );
+            assert_eq!(split_string(activation, "", &["".into()])?, vec![]);
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn split_without_separator() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            assert_eq!(split_string(activation, "a,b", &[])?, vec!["a,b".into()]);
+            assert_eq!(
+                split_string(activation, "undefined", &[Value::Undefined])?,
+                vec!["undefined".into()]
+            );
+            assert_eq!(
+                split_string(activation, "a,b", &[Value::Undefined, 0.into()])?,
+                vec![]
+            );
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn split_with_limit() {
+        with_avm(8, |activation, _root| -> Result<(), Error> {
+            assert_eq!(
+                split_string(activation, "a,b,c", &[",".into(), 2.into()])?,
+                vec!["a".into(), "b".into()]
+            );
+            assert_eq!(
+                split_string(activation, "abc", &["".into(), 2.into()])?,
+                vec!["a".into(), "b".into()]
+            );
+            assert_eq!(
+                split_string(activation, "a,b,c", &[",".into(), 0.into()])?,
+                vec![]
+            );
+            assert_eq!(
+                split_string(activation, "a,b,c", &[",".into(), (-1).into()])?,
+                vec![]
+            );
+            Ok(())
+        });
+    }
+}
@@ -31,6 +31,7 @@ impl fmt::Display for CpuArchitecture {
 }
 
 /// Available type of sandbox for a given SWF
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum SandboxType {
     Remote,
     LocalWithFile,
@@ -268,14 +269,24 @@ pub struct SystemProperties {
     pub os: OperatingSystem,
     /// The type of the player sandbox
     pub sandbox_type: SandboxType,
+    /// Whether movies run from local files are trusted, instead of being
+    /// limited to either file or network access
+    pub trust_local_content: bool,
     /// The cpu architecture of the platform
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// A version string to report instead of the real one, such as
+    /// `WIN 9,0,115,0`, for content that checks for a particular player
+    pub version_override: Option<String>,
 }
 
 impl SystemProperties {
     pub fn get_version_string(&self, avm: &mut Avm1) -> String {
+        if let Some(version) = &self.version_override {
+            return version.clone();
+        }
+
         format!(
             "{} {},0,0,0",
             self.manufacturer.get_platform_name(),
@@ -393,9 +404,11 @@ impl Default for SystemProperties {
             dpi: 1_f32,
             manufacturer: Manufacturer::Linux,
             os: OperatingSystem::Linux,
-            sandbox_type: SandboxType::LocalTrusted,
+            sandbox_type: SandboxType::LocalWithFile,
+            trust_local_content: false,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            version_override: None,
         }
     }
 }
@@ -431,9 +444,10 @@ pub fn show_settings<'gc>(
 
     let panel = SettingsPanel::try_from(panel_pos as u8).unwrap_or(SettingsPanel::Privacy);
 
-    avm_warn!(
-        activation,
-        "System.showSettings({:?}) not not implemented",
+    avm1_stub!(activation, "System.showSettings");
+    avm_debug!(
+        activation.context.avm1,
+        "System.showSettings: {:?} panel requested",
         panel
     );
     Ok(Value::Undefined)
@@ -492,7 +506,7 @@ pub fn on_status<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.onStatus() not implemented");
+    avm1_stub!(activation, "System.onStatus");
     Ok(Value::Undefined)
 }
 
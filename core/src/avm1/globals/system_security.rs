@@ -13,7 +13,7 @@ fn allow_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.security.allowDomain() not implemented");
+    avm1_stub!(activation, "System.security.allowDomain");
     Ok(Value::Undefined)
 }
 
@@ -22,10 +22,7 @@ fn allow_insecure_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
+    avm1_stub!(activation, "System.security.allowInsecureDomain");
     Ok(Value::Undefined)
 }
 
@@ -34,10 +31,7 @@ fn load_policy_file<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.allowInsecureDomain() not implemented"
-    );
+    avm1_stub!(activation, "System.security.loadPolicyFile");
     Ok(Value::Undefined)
 }
 
@@ -46,7 +40,7 @@ fn escape_domain<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "System.security.escapeDomain() not implemented");
+    avm1_stub!(activation, "System.security.escapeDomain");
     Ok(Value::Undefined)
 }
 
@@ -67,10 +61,7 @@ fn get_choose_local_swf_path<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.chooseLocalSwfPath() not implemented"
-    );
+    avm1_stub!(activation, "System.security.chooseLocalSwfPath");
     Ok(Value::Undefined)
 }
 
@@ -79,10 +70,7 @@ fn policy_file_resolver<'gc>(
     _this: Object<'gc>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(
-        activation,
-        "System.security.chooseLocalSwfPath() not implemented"
-    );
+    avm1_stub!(activation, "System.security.PolicyFileResolver");
     Ok(Value::Undefined)
 }
 
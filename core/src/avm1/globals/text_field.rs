@@ -106,6 +106,7 @@ pub fn create_proto<'gc>(
     let mut object = ScriptObject::object(gc_context, Some(proto));
 
     display_object::define_display_object_proto(gc_context, object, fn_proto);
+    display_object::define_tab_properties(gc_context, object, fn_proto);
 
     with_text_field!(
         gc_context,
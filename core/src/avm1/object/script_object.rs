@@ -496,6 +496,16 @@ impl<'gc> TObject<'gc> for ScriptObject<'gc> {
         if let Some(prop) = object.values.get(name, activation.is_case_sensitive()) {
             if prop.can_delete() {
                 object.values.remove(name, activation.is_case_sensitive());
+
+                // Deleting an array element leaves a hole, without shifting
+                // the later elements or changing the length.
+                if let (ArrayStorage::Vector(vector), Ok(index)) =
+                    (&mut object.array, name.parse::<usize>())
+                {
+                    if let Some(element) = vector.get_mut(index) {
+                        *element = Value::Undefined;
+                    }
+                }
                 return true;
             }
         }
@@ -823,98 +833,27 @@ mod tests {
 
     use crate::avm1::activation::ActivationIdentifier;
     use crate::avm1::function::Executable;
-    use crate::avm1::globals::system::SystemProperties;
     use crate::avm1::property::Attribute;
-    use crate::avm1::{Avm1, Timers};
-    use crate::avm2::Avm2;
-    use crate::backend::audio::{AudioManager, NullAudioBackend};
-    use crate::backend::locale::NullLocaleBackend;
-    use crate::backend::log::NullLogBackend;
-    use crate::backend::navigator::NullNavigatorBackend;
-    use crate::backend::render::NullRenderer;
-    use crate::backend::storage::MemoryStorageBackend;
-    use crate::backend::ui::NullUiBackend;
-    use crate::backend::video::NullVideoBackend;
-    use crate::context::UpdateContext;
-    use crate::display_object::{MovieClip, Stage};
-    use crate::focus_tracker::FocusTracker;
-    use crate::library::Library;
-    use crate::loader::LoadManager;
-    use crate::prelude::*;
-    use crate::tag_utils::{SwfMovie, SwfSlice};
-    use crate::vminterface::Instantiator;
-    use gc_arena::rootless_arena;
-    use instant::Instant;
-    use rand::{rngs::SmallRng, SeedableRng};
-    use std::collections::HashMap;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
     use std::sync::Arc;
-    use std::time::Duration;
 
     fn with_object<F, R>(swf_version: u8, test: F) -> R
     where
         F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc, '_>, Object<'gc>) -> R,
     {
-        rootless_arena(|gc_context| {
-            let mut avm1 = Avm1::new(gc_context, swf_version);
-            let mut avm2 = Avm2::new(gc_context);
-            let swf = Arc::new(SwfMovie::empty(swf_version));
-            let root: DisplayObject<'_> =
-                MovieClip::new(SwfSlice::empty(swf.clone()), gc_context).into();
-            root.set_depth(gc_context, 0);
-
-            let stage = Stage::empty(gc_context, 550, 400);
-            let mut frame_rate = 12.0;
-
-            let object = ScriptObject::object(gc_context, Some(avm1.prototypes().object)).into();
-            let globals = avm1.global_object_cell();
-
-            let mut context = UpdateContext {
-                gc_context,
-                player_version: 32,
-                swf: &swf,
-                stage,
-                rng: &mut SmallRng::from_seed([0u8; 32]),
-                action_queue: &mut crate::context::ActionQueue::new(),
-                audio: &mut NullAudioBackend::new(),
-                audio_manager: &mut AudioManager::new(),
-                ui: &mut NullUiBackend::new(),
-                library: &mut Library::empty(gc_context),
-                navigator: &mut NullNavigatorBackend::new(),
-                renderer: &mut NullRenderer::new(),
-                locale: &mut NullLocaleBackend::new(),
-                log: &mut NullLogBackend::new(),
-                video: &mut NullVideoBackend::new(),
-                mouse_hovered_object: None,
-                mouse_position: &(Twips::zero(), Twips::zero()),
-                drag_object: &mut None,
-                player: None,
-                load_manager: &mut LoadManager::new(),
-                system: &mut SystemProperties::default(),
-                instance_counter: &mut 0,
-                storage: &mut MemoryStorageBackend::default(),
-                shared_objects: &mut HashMap::new(),
-                unbound_text_fields: &mut Vec::new(),
-                timers: &mut Timers::new(),
-                current_context_menu: &mut None,
-                needs_render: &mut false,
-                avm1: &mut avm1,
-                avm2: &mut avm2,
-                external_interface: &mut Default::default(),
-                update_start: Instant::now(),
-                max_execution_duration: Duration::from_secs(15),
-                focus_tracker: FocusTracker::new(gc_context),
-                times_get_time_called: 0,
-                time_offset: &mut 0,
-                frame_rate: &mut frame_rate,
-            };
-            context.stage.replace_at_depth(&mut context, root, 0);
-
-            root.post_instantiation(&mut context, root, None, Instantiator::Movie, false);
-            root.set_name(context.gc_context, "");
-
-            let swf_version = context.swf.version();
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(SwfMovie::empty(swf_version)));
+
+        player.update(|context| {
+            let object =
+                ScriptObject::object(context.gc_context, Some(context.avm1.prototypes().object))
+                    .into();
+            let globals = context.avm1.global_object_cell();
+            let root = context.stage.root_clip();
             let mut activation = Activation::from_nothing(
-                context,
+                context.reborrow(),
                 ActivationIdentifier::root("[Test]"),
                 swf_version,
                 globals,
@@ -1086,6 +1025,35 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_delete_array_element() {
+        with_object(0, |activation, _object| {
+            let array: Object<'_> = ScriptObject::array(
+                activation.context.gc_context,
+                Some(activation.context.avm1.prototypes.array),
+            )
+            .into();
+            for (i, value) in ["a", "b", "c"].iter().enumerate() {
+                array.set_array_element(i, (*value).into(), activation.context.gc_context);
+            }
+
+            assert!(array.delete(activation, "1"));
+            assert!(!array.delete(activation, "5"));
+
+            assert_eq!(array.length(), 3);
+            assert_eq!(array.get("length", activation).unwrap(), 3.into());
+            assert_eq!(array.get("0", activation).unwrap(), "a".into());
+            assert_eq!(array.get("1", activation).unwrap(), Value::Undefined);
+            assert_eq!(array.get("2", activation).unwrap(), "c".into());
+            assert_eq!(
+                array.array(),
+                vec!["a".into(), Value::Undefined, "c".into()]
+            );
+            assert!(!array.has_own_property(activation, "1"));
+            assert_eq!(array.get_keys(activation), vec!["0", "2"]);
+        })
+    }
+
     #[test]
     fn test_iter_values() {
         with_object(0, |activation, object| {
@@ -1131,4 +1099,29 @@ mod tests {
             assert!(!keys.contains(&"virtual_hidden".to_string()));
         })
     }
+
+    #[test]
+    fn test_keys_in_insertion_order() {
+        with_object(0, |activation, object| {
+            for name in &["zeta", "alpha", "mid", "beta"] {
+                object.set(name, Value::Null, activation).unwrap();
+            }
+            object.as_script_object().unwrap().define_value(
+                activation.context.gc_context,
+                "hidden",
+                Value::Null,
+                Attribute::DONT_ENUM,
+            );
+
+            // Overwriting a property keeps its place, re-adding one moves it to the end.
+            object.set("alpha", 1.into(), activation).unwrap();
+            object.delete(activation, "mid");
+            object.set("mid", 2.into(), activation).unwrap();
+
+            assert_eq!(
+                object.get_keys(activation),
+                vec!["zeta", "alpha", "beta", "mid"]
+            );
+        })
+    }
 }
@@ -864,7 +864,7 @@ fn drop_target<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _droptarget");
+    avm1_stub!(activation, "_droptarget");
     Ok("".into())
 }
 
@@ -884,7 +884,7 @@ fn high_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
+    avm1_stub!(activation, "_highquality");
     Ok(1.into())
 }
 
@@ -893,7 +893,7 @@ fn set_high_quality<'gc>(
     _this: DisplayObject<'gc>,
     _val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _highquality");
+    avm1_stub!(activation, "_highquality");
     Ok(())
 }
 
@@ -901,7 +901,7 @@ fn focus_rect<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    avm1_stub!(activation, "_focusrect");
     Ok(Value::Null)
 }
 
@@ -910,7 +910,7 @@ fn set_focus_rect<'gc>(
     _this: DisplayObject<'gc>,
     _val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _focusrect");
+    avm1_stub!(activation, "_focusrect");
     Ok(())
 }
 
@@ -918,7 +918,7 @@ fn sound_buf_time<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _soundbuftime");
+    avm1_stub!(activation, "_soundbuftime");
     Ok(5.into())
 }
 
@@ -927,7 +927,7 @@ fn set_sound_buf_time<'gc>(
     _this: DisplayObject<'gc>,
     _val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _soundbuftime");
+    avm1_stub!(activation, "_soundbuftime");
     Ok(())
 }
 
@@ -935,16 +935,22 @@ fn quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
 ) -> Result<Value<'gc>, Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
-    Ok("HIGH".into())
+    let quality = activation.context.stage.quality().to_string();
+    Ok(AvmString::new(activation.context.gc_context, quality).into())
 }
 
 fn set_quality<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: DisplayObject<'gc>,
-    _val: Value<'gc>,
+    val: Value<'gc>,
 ) -> Result<(), Error<'gc>> {
-    avm_warn!(activation, "Unimplemented property _quality");
+    // Unknown quality names are ignored.
+    if let Ok(quality) = val.coerce_to_string(activation)?.parse() {
+        activation
+            .context
+            .stage
+            .set_quality(&mut activation.context, quality);
+    }
     Ok(())
 }
 
@@ -293,6 +293,49 @@ impl<'gc> Scope<'gc> {
         }
     }
 
+    /// Define a local variable, as if by a `var` statement.
+    ///
+    /// `with` scopes are skipped, so the variable is defined on the enclosing
+    /// function or timeline instead. The exception is if the `with` object
+    /// already has a property of that name, in which case it is overwritten.
+    pub fn define_local(
+        &self,
+        name: &str,
+        value: Value<'gc>,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if self.class == ScopeClass::With && !self.locals().has_property(activation, name) {
+            if let Some(scope) = self.parent() {
+                return scope.define_local(name, value, activation);
+            }
+        }
+
+        self.locals().set(name, value, activation)
+    }
+
+    /// Declare a local variable without a value, as if by a `var` statement
+    /// with no initializer.
+    ///
+    /// Existing variables are left unchanged. As with `define_local`, `with`
+    /// scopes are skipped unless the `with` object has a property of that name.
+    pub fn declare_local(
+        &self,
+        name: &str,
+        activation: &mut Activation<'_, 'gc, '_>,
+    ) -> Result<(), Error<'gc>> {
+        if self.locals().has_property(activation, name) {
+            return Ok(());
+        }
+
+        if self.class == ScopeClass::With {
+            if let Some(scope) = self.parent() {
+                return scope.declare_local(name, activation);
+            }
+        }
+
+        self.locals().set(name, Value::Undefined, activation)
+    }
+
     /// Set a particular value in the locals for this scope.
     ///
     /// By convention, the locals for a given function are always defined as
@@ -1,6 +1,138 @@
+use crate::avm1::activation::{Activation, ActivationIdentifier, ReturnType};
 use crate::avm1::error::Error;
-use crate::avm1::test_utils::with_avm;
-use crate::avm1::TObject;
+use crate::avm1::function::{Executable, ExecutionReason, FunctionObject};
+use crate::avm1::property::Attribute;
+use crate::avm1::scope::{Scope, ScopeClass};
+use crate::avm1::test_utils::{
+    with_avm, with_avm_and_builder, with_avm_and_clock, with_avm_and_movie,
+};
+use crate::avm1::{Object, ScriptObject, TObject, Value};
+use crate::backend::render::{BitmapHandle, RecordingRenderer, RenderBackend, RenderCommand};
+use crate::clock::ManualClock;
+use crate::context::RenderContext;
+use crate::display_object::{
+    DisplayObject, EditText, HitTestOptions, MovieClip, StageQuality, TDisplayObject,
+    TDisplayObjectContainer,
+};
+use crate::player::{Player, PlayerBuilder};
+use crate::prelude::Depth;
+use crate::shape_utils::DrawCommand;
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use crate::test_utils::{swf_header, swf_movie, RecordingNavigator};
+use crate::transform::TransformStack;
+use crate::vminterface::Instantiator;
+use chrono::{TimeZone, Utc};
+use gc_arena::GcCell;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use swf::avm1::types::{Action, CatchVar, TryBlock, Value as SwfValue};
+use swf::avm1::write::Writer;
+use swf::{Color, FillStyle, Matrix, SwfStr, Twips};
+
+/// Creates an empty movie clip and places it in `parent` at the given depth.
+fn new_clip<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    parent: DisplayObject<'gc>,
+    depth: Depth,
+) -> DisplayObject<'gc> {
+    let movie = parent.movie().unwrap();
+    let clip: DisplayObject =
+        MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context).into();
+    parent
+        .as_movie_clip()
+        .unwrap()
+        .replace_at_depth(&mut activation.context, clip, depth);
+    clip.post_instantiation(
+        &mut activation.context,
+        clip,
+        None,
+        Instantiator::Avm1,
+        false,
+    );
+    clip
+}
+
+/// Draws a filled rectangle with its top-left corner at the origin of `clip`.
+fn draw_rect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    clip: DisplayObject<'gc>,
+    width: f64,
+    height: f64,
+) {
+    let mut drawing = clip.as_drawing(activation.context.gc_context).unwrap();
+    drawing.set_fill_style(Some(FillStyle::Color(Color {
+        r: 255,
+        g: 0,
+        b: 0,
+        a: 255,
+    })));
+    for &(x, y) in &[(width, 0.0), (width, height), (0.0, height), (0.0, 0.0)] {
+        drawing.draw_command(DrawCommand::LineTo {
+            x: Twips::from_pixels(x),
+            y: Twips::from_pixels(y),
+        });
+    }
+    drawing.set_fill_style(None);
+}
+
+/// Renders a single frame of `object` with the given renderer.
+fn render<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    renderer: &mut RecordingRenderer,
+    object: DisplayObject<'gc>,
+) {
+    renderer.begin_frame(Color {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+    });
+    let mut transform_stack = TransformStack::new();
+    let mut render_context = RenderContext {
+        renderer,
+        ui: &mut *activation.context.ui,
+        library: &*activation.context.library,
+        transform_stack: &mut transform_stack,
+        stage: activation.context.stage,
+        clip_depth_stack: vec![],
+        allow_mask: true,
+    };
+    object.render(&mut render_context);
+}
+
+/// Encodes a list of actions into AVM1 bytecode.
+fn encode_actions(actions: &[Action], swf_version: u8) -> Vec<u8> {
+    let mut data = Vec::new();
+    let mut writer = Writer::new(&mut data, swf_version);
+    for action in actions {
+        writer.write_action(action).unwrap();
+    }
+    data
+}
+
+/// Runs a list of actions on the timeline of the root clip.
+fn run_actions<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    actions: &[Action],
+) -> Result<ReturnType<'gc>, Error<'gc>> {
+    let data = encode_actions(actions, activation.swf_version());
+    let movie = activation.base_clip().movie().unwrap();
+    let movie = Arc::new(movie.from_movie_and_subdata(data, &movie));
+
+    let root = activation.root_object()?.coerce_to_object(activation);
+    let scope = GcCell::allocate(
+        activation.context.gc_context,
+        Scope::new(activation.scope_cell(), ScopeClass::Target, root),
+    );
+    activation
+        .with_new_scope("[Test Actions]", scope)
+        .run_actions(SwfSlice::from(movie))
+}
+
+/// Pushes a string onto the AVM1 stack.
+fn push_str(value: &str) -> Action<'_> {
+    Action::Push(vec![SwfValue::Str(SwfStr::from_utf8_str(value))])
+}
 
 #[test]
 fn locals_into_form_values() {
@@ -23,3 +155,1134 @@ fn locals_into_form_values() {
         Ok(())
     });
 }
+
+#[test]
+fn get_url_sends_clip_variables() {
+    let opened = Arc::new(Mutex::new(Vec::new()));
+    with_avm_and_builder(
+        8,
+        PlayerBuilder::new().with_navigator(Box::new(RecordingNavigator::new(opened.clone()))),
+        |activation, _root| -> Result<(), Error> {
+            let root = activation.base_clip();
+            let clip = new_clip(activation, root, 1);
+            let object = clip.object().coerce_to_object(activation);
+            object.set("name", "Ruffle & Co".into(), activation)?;
+
+            // Only the clip's variables are sent, not those of the caller.
+            let locals = activation.scope().locals().to_owned();
+            locals.set("secret", "hidden".into(), activation)?;
+
+            let get_url = object.get("getURL", activation)?;
+            get_url.call(
+                "getURL",
+                activation,
+                object,
+                None,
+                &[
+                    "http://example.com/submit".into(),
+                    "_blank".into(),
+                    "GET".into(),
+                ],
+            )?;
+
+            Ok(())
+        },
+    );
+
+    assert_eq!(
+        *opened.lock().unwrap(),
+        vec![(
+            "http://example.com/submit?name=Ruffle+%26+Co".to_string(),
+            Some("_blank".to_string())
+        )]
+    );
+}
+
+#[test]
+fn text_field_variable_binding() {
+    with_avm(8, |activation, root| -> Result<(), Error> {
+        let movie = activation.base_clip().movie().unwrap();
+        let text_field = EditText::new(&mut activation.context, movie, 0.0, 0.0, 100.0, 20.0);
+        activation
+            .base_clip()
+            .as_movie_clip()
+            .unwrap()
+            .replace_at_depth(&mut activation.context, text_field.into(), 1);
+        text_field.post_instantiation(
+            &mut activation.context,
+            text_field.into(),
+            None,
+            Instantiator::Avm1,
+            false,
+        );
+
+        // The text field is bound before the variable exists.
+        text_field.set_variable(Some("foo".to_string()), activation);
+        assert!(!root.has_property(activation, "foo"));
+
+        // Setting the variable updates the text field.
+        root.set("foo", "hello".into(), activation)?;
+        assert_eq!(text_field.text(), "hello");
+
+        // Editing the text field updates the variable.
+        text_field
+            .set_text("world".to_string(), &mut activation.context)
+            .unwrap();
+        text_field.propagate_text_binding(activation);
+        assert_eq!(root.get("foo", activation)?, "world".into());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn negative_xscale_mirrors_shape() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        // A 100x50 rectangle to the right of the origin.
+        let root = activation.base_clip();
+        let clip = new_clip(activation, root, 1);
+        draw_rect(activation, clip, 100.0, 50.0);
+
+        let object = clip.object().coerce_to_object(activation);
+        object.set("_xscale", (-100.0).into(), activation)?;
+        assert_eq!(object.get("_xscale", activation)?, (-100.0).into());
+
+        // The rectangle is now to the left of the origin.
+        let bounds = clip.world_bounds();
+        assert_eq!(bounds.x_min, Twips::from_pixels(-100.0));
+        assert_eq!(bounds.x_max, Twips::zero());
+        assert_eq!(bounds.y_min, Twips::zero());
+        assert_eq!(bounds.y_max, Twips::from_pixels(50.0));
+
+        let options = HitTestOptions {
+            skip_mask: false,
+            skip_invisible: true,
+        };
+        let left = (Twips::from_pixels(-75.0), Twips::from_pixels(25.0));
+        let right = (Twips::from_pixels(75.0), Twips::from_pixels(25.0));
+        assert!(clip.hit_test_shape(&mut activation.context, left, options));
+        assert!(!clip.hit_test_shape(&mut activation.context, right, options));
+
+        Ok(())
+    });
+}
+
+#[test]
+fn drawing_api_bounds_cover_fills_and_strokes() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let clip = new_clip(activation, root, 1);
+        let object = clip.object().coerce_to_object(activation);
+        let px = |pixels: f64| Twips::from_pixels(pixels);
+
+        // A triangle whose fill is closed implicitly, so its first point is
+        // only ever a `moveTo`.
+        object.call_method("beginFill", &[0xFF0000.into()], activation)?;
+        object.call_method("moveTo", &[10.into(), 10.into()], activation)?;
+        object.call_method("lineTo", &[50.into(), 10.into()], activation)?;
+        object.call_method("lineTo", &[30.into(), 40.into()], activation)?;
+        object.call_method("endFill", &[], activation)?;
+        let bounds = clip.self_bounds();
+        assert_eq!(
+            (bounds.x_min, bounds.y_min, bounds.x_max, bounds.y_max),
+            (px(10.0), px(10.0), px(50.0), px(40.0))
+        );
+
+        // Strokes extend the bounds by half their width.
+        object.call_method("lineStyle", &[4.into()], activation)?;
+        object.call_method("lineTo", &[10.into(), 10.into()], activation)?;
+        let bounds = clip.self_bounds();
+        assert_eq!(
+            (bounds.x_min, bounds.y_min, bounds.x_max, bounds.y_max),
+            (px(8.0), px(8.0), px(50.0), px(42.0))
+        );
+
+        object.call_method("clear", &[], activation)?;
+        assert_eq!(clip.self_bounds().width(), Twips::zero());
+        Ok(())
+    });
+}
+
+#[test]
+fn tab_order_skips_disabled_objects_and_children() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let first = new_clip(activation, root, 1);
+        let disabled = new_clip(activation, root, 2);
+        let container = new_clip(activation, root, 3);
+        let child = new_clip(activation, container, 1);
+        for clip in &[first, disabled, child] {
+            let object = clip.object().coerce_to_object(activation);
+            object.set("focusEnabled", true.into(), activation)?;
+        }
+
+        fn tab_order<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+            let tracker = activation.context.focus_tracker;
+            tracker.tab_order(&activation.context)
+        }
+        fn same<'gc>(a: &[DisplayObject<'gc>], b: &[DisplayObject<'gc>]) -> bool {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| DisplayObject::ptr_eq(*a, *b))
+        }
+
+        let disabled_object = disabled.object().coerce_to_object(activation);
+        disabled_object.set("tabEnabled", false.into(), activation)?;
+        assert_eq!(disabled_object.get("tabEnabled", activation)?, false.into());
+        let container_object = container.object().coerce_to_object(activation);
+        container_object.set("tabChildren", false.into(), activation)?;
+        assert!(same(&tab_order(activation), &[first]));
+
+        container_object.set("tabChildren", true.into(), activation)?;
+        assert!(same(&tab_order(activation), &[first, child]));
+
+        // Once any object has a `tabIndex`, only those objects are used.
+        let child_object = child.object().coerce_to_object(activation);
+        child_object.set("tabIndex", 1.into(), activation)?;
+        let first_object = first.object().coerce_to_object(activation);
+        first_object.set("tabIndex", 2.into(), activation)?;
+        assert!(same(&tab_order(activation), &[child, first]));
+
+        let tracker = activation.context.focus_tracker;
+        tracker.cycle(&mut activation.context, false);
+        assert!(DisplayObject::ptr_eq(tracker.get().unwrap(), child));
+        tracker.cycle(&mut activation.context, false);
+        assert!(DisplayObject::ptr_eq(tracker.get().unwrap(), first));
+        tracker.cycle(&mut activation.context, false);
+        assert!(DisplayObject::ptr_eq(tracker.get().unwrap(), child));
+        tracker.cycle(&mut activation.context, true);
+        assert!(DisplayObject::ptr_eq(tracker.get().unwrap(), first));
+
+        Ok(())
+    });
+}
+
+#[test]
+fn cache_as_bitmap_rerenders_after_change() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let cached = new_clip(activation, root, 1);
+        let child = new_clip(activation, cached, 1);
+        draw_rect(activation, child, 100.0, 50.0);
+
+        let object = cached.object().coerce_to_object(activation);
+        object.set("cacheAsBitmap", true.into(), activation)?;
+        assert_eq!(object.get("cacheAsBitmap", activation)?, true.into());
+
+        let mut renderer = RecordingRenderer::new();
+        renderer.supports_offscreen = true;
+
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 1);
+
+        // The bitmap is reused, even when the cached object itself moves.
+        object.set("_x", 50.0.into(), activation)?;
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 0);
+        assert!(renderer
+            .commands
+            .iter()
+            .any(|command| matches!(command, RenderCommand::RenderBitmap { .. })));
+
+        // Changing a descendant re-renders the bitmap.
+        child.set_x(activation.context.gc_context, 10.0);
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 1);
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 0);
+
+        // Backends without offscreen rendering draw the object directly.
+        child.set_x(activation.context.gc_context, 20.0);
+        let mut renderer = RecordingRenderer::new();
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 0);
+        assert!(renderer
+            .commands
+            .iter()
+            .any(|command| matches!(command, RenderCommand::RenderShape { .. })));
+
+        Ok(())
+    });
+}
+
+/// The bitmaps drawn in the last frame, and the matrices they were drawn with.
+fn rendered_bitmaps(renderer: &RecordingRenderer) -> Vec<(BitmapHandle, Matrix)> {
+    renderer
+        .commands
+        .iter()
+        .filter_map(|command| match command {
+            RenderCommand::RenderBitmap { bitmap, matrix } => Some((*bitmap, *matrix)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn cache_as_bitmap_renders_at_stage_scale() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let cached = new_clip(activation, root, 1);
+        draw_rect(activation, cached, 100.0, 50.0);
+        let object = cached.object().coerce_to_object(activation);
+        object.set("cacheAsBitmap", true.into(), activation)?;
+
+        let mut renderer = RecordingRenderer::new();
+        renderer.supports_offscreen = true;
+        render(activation, &mut renderer, root);
+        let first = rendered_bitmaps(&renderer);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].1, Matrix::identity());
+
+        // Scaling the object re-renders it at the new scale, so the bitmap is still drawn
+        // pixel for pixel. The old bitmap no longer fits and is freed.
+        object.set("_xscale", 200.0.into(), activation)?;
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 1);
+        let second = rendered_bitmaps(&renderer);
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0].1, Matrix::identity());
+        assert_ne!(second[0].0, first[0].0);
+        assert_eq!(renderer.unregistered_bitmaps, vec![first[0].0]);
+
+        // Moving it reuses the bitmap.
+        object.set("_x", 10.0.into(), activation)?;
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.num_offscreen_renders(), 0);
+        assert_eq!(
+            rendered_bitmaps(&renderer),
+            vec![(
+                second[0].0,
+                Matrix::translate(Twips::from_pixels(10.0), Twips::zero())
+            )]
+        );
+
+        // Turning off the cache frees the bitmap.
+        object.set("cacheAsBitmap", false.into(), activation)?;
+        render(activation, &mut renderer, root);
+        assert!(rendered_bitmaps(&renderer).is_empty());
+        assert_eq!(renderer.unregistered_bitmaps, vec![first[0].0, second[0].0]);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn removing_cached_object_frees_its_bitmap() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let cached = new_clip(activation, root, 1);
+        draw_rect(activation, cached, 100.0, 50.0);
+        cached.set_cache_as_bitmap(activation.context.gc_context, true);
+
+        let mut renderer = RecordingRenderer::new();
+        renderer.supports_offscreen = true;
+        render(activation, &mut renderer, root);
+
+        assert_eq!(
+            cached.bitmap_cache().clone_state().handle,
+            Some(rendered_bitmaps(&renderer)[0].0)
+        );
+
+        // Unloading frees the bitmap with the player's renderer.
+        cached.unload(&mut activation.context);
+        assert_eq!(cached.bitmap_cache().clone_state().handle, None);
+
+        Ok(())
+    });
+}
+
+#[test]
+fn scroll_rect_clips_content() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let scroller = new_clip(activation, root, 1);
+        let inside = new_clip(activation, scroller, 1);
+        let outside = new_clip(activation, scroller, 2);
+        draw_rect(activation, inside, 50.0, 50.0);
+        draw_rect(activation, outside, 50.0, 50.0);
+        inside.set_x(activation.context.gc_context, 120.0);
+
+        let object = scroller.object().coerce_to_object(activation);
+        let rect = activation
+            .context
+            .avm1
+            .prototypes
+            .rectangle_constructor
+            .construct(activation, &[100.into(), 0.into(), 100.into(), 100.into()])?;
+        object.set("scrollRect", rect, activation)?;
+        assert_eq!(object.get("_width", activation)?, 100.into());
+
+        let mut renderer = RecordingRenderer::new();
+        render(activation, &mut renderer, root);
+
+        // Only the child inside of the rect is drawn, offset by the origin of the rect.
+        let shapes: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::RenderShape { matrix, .. } => Some(matrix.tx),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(shapes, vec![Twips::from_pixels(20.0)]);
+        assert!(matches!(
+            renderer.commands.first(),
+            Some(RenderCommand::PushMask)
+        ));
+
+        // Clearing the scroll rect draws everything again.
+        object.set("scrollRect", Value::Undefined, activation)?;
+        render(activation, &mut renderer, root);
+        assert!(!renderer
+            .commands
+            .iter()
+            .any(|command| matches!(command, RenderCommand::PushMask)));
+        assert_eq!(
+            renderer
+                .commands
+                .iter()
+                .filter(|command| matches!(command, RenderCommand::RenderShape { .. }))
+                .count(),
+            2
+        );
+
+        Ok(())
+    });
+}
+
+#[test]
+fn nested_scroll_rects_nest_their_clips() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let outer = new_clip(activation, root, 1);
+        let inner = new_clip(activation, outer, 1);
+        let content = new_clip(activation, inner, 1);
+        draw_rect(activation, content, 30.0, 30.0);
+        for &(clip, size) in &[(outer, 100), (inner, 50)] {
+            let rect = activation
+                .context
+                .avm1
+                .prototypes
+                .rectangle_constructor
+                .construct(activation, &[0.into(), 0.into(), size.into(), size.into()])?;
+            let object = clip.object().coerce_to_object(activation);
+            object.set("scrollRect", rect, activation)?;
+        }
+
+        let mut renderer = RecordingRenderer::new();
+        render(activation, &mut renderer, root);
+
+        // Each scroll rect is drawn as a mask around its content, so the
+        // content is clipped by both rects at once.
+        let commands: Vec<_> = renderer
+            .commands
+            .iter()
+            .map(|command| match command {
+                RenderCommand::DrawRect { matrix, .. } => format!("rect {}", matrix.a),
+                RenderCommand::RenderShape { .. } => "shape".to_string(),
+                RenderCommand::PushMask => "push".to_string(),
+                RenderCommand::ActivateMask => "activate".to_string(),
+                RenderCommand::DeactivateMask => "deactivate".to_string(),
+                RenderCommand::PopMask => "pop".to_string(),
+                command => format!("{:?}", command),
+            })
+            .collect();
+        assert_eq!(
+            commands,
+            vec![
+                "push",
+                "rect 100",
+                "activate",
+                "push",
+                "rect 50",
+                "activate",
+                "shape",
+                "deactivate",
+                "rect 50",
+                "pop",
+                "deactivate",
+                "rect 100",
+                "pop",
+            ]
+        );
+
+        Ok(())
+    });
+}
+
+/// Finds the topmost object at the given point on the stage, in pixels.
+fn pick<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    x: f64,
+    y: f64,
+    include_invisible: bool,
+) -> Option<DisplayObject<'gc>> {
+    let stage = activation.context.stage;
+    stage.object_at_point(
+        &mut activation.context,
+        Twips::from_pixels(x),
+        Twips::from_pixels(y),
+        include_invisible,
+    )
+}
+
+#[test]
+fn object_at_point_returns_topmost() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+
+        // Two overlapping 100x100 rectangles; `top` is offset by 50px.
+        let bottom = new_clip(activation, root, 1);
+        draw_rect(activation, bottom, 100.0, 100.0);
+        let top = new_clip(activation, root, 2);
+        top.set_x(activation.context.gc_context, 50.0);
+        draw_rect(activation, top, 100.0, 100.0);
+
+        let overlap = pick(activation, 75.0, 50.0, false).unwrap();
+        assert!(DisplayObject::ptr_eq(overlap, top));
+        let left = pick(activation, 25.0, 50.0, false).unwrap();
+        assert!(DisplayObject::ptr_eq(left, bottom));
+        assert!(pick(activation, 200.0, 50.0, false).is_none());
+
+        // Invisible objects are skipped unless explicitly requested.
+        top.set_visible(activation.context.gc_context, false);
+        let overlap = pick(activation, 75.0, 50.0, false).unwrap();
+        assert!(DisplayObject::ptr_eq(overlap, bottom));
+        let overlap = pick(activation, 75.0, 50.0, true).unwrap();
+        assert!(DisplayObject::ptr_eq(overlap, top));
+
+        Ok(())
+    });
+}
+
+#[test]
+fn object_at_point_skips_masked_out_children() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let parent = new_clip(activation, root, 1);
+        let child = new_clip(activation, parent, 1);
+        draw_rect(activation, child, 100.0, 100.0);
+        let mask = new_clip(activation, parent, 2);
+        draw_rect(activation, mask, 50.0, 50.0);
+        child.set_masker(activation.context.gc_context, Some(mask), true);
+        mask.set_maskee(activation.context.gc_context, Some(child), true);
+
+        let hit = pick(activation, 25.0, 25.0, false).unwrap();
+        assert!(DisplayObject::ptr_eq(hit, child));
+
+        // Outside of the mask, neither the child nor its empty parent is hit.
+        assert!(pick(activation, 75.0, 25.0, false).is_none());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn object_at_point_respects_scroll_rect() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let scroller = new_clip(activation, root, 1);
+        let inside = new_clip(activation, scroller, 1);
+        let outside = new_clip(activation, scroller, 2);
+        draw_rect(activation, inside, 50.0, 50.0);
+        draw_rect(activation, outside, 50.0, 50.0);
+        inside.set_x(activation.context.gc_context, 120.0);
+
+        let object = scroller.object().coerce_to_object(activation);
+        let rect = activation
+            .context
+            .avm1
+            .prototypes
+            .rectangle_constructor
+            .construct(activation, &[100.into(), 0.into(), 100.into(), 100.into()])?;
+        object.set("scrollRect", rect, activation)?;
+
+        // The content is offset by the origin of the scroll rect.
+        let hit = pick(activation, 30.0, 25.0, false).unwrap();
+        assert!(DisplayObject::ptr_eq(hit, inside));
+        assert!(pick(activation, 10.0, 25.0, false).is_none());
+
+        // Content outside of the scroll rect is clipped away.
+        assert!(pick(activation, 150.0, 25.0, false).is_none());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn invisible_objects_are_culled() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let root = activation.base_clip();
+        let container = new_clip(activation, root, 1);
+        let child = new_clip(activation, container, 1);
+        draw_rect(activation, child, 50.0, 50.0);
+
+        let mut renderer = RecordingRenderer::new();
+        render(activation, &mut renderer, root);
+        assert_eq!(renderer.commands.len(), 1);
+
+        // Hiding the container hides its entire subtree.
+        let object = container.object().coerce_to_object(activation);
+        object.set("_visible", false.into(), activation)?;
+        render(activation, &mut renderer, root);
+        assert!(renderer.commands.is_empty());
+
+        // Fully transparent objects aren't drawn either.
+        object.set("_visible", true.into(), activation)?;
+        object.set("_alpha", 0.into(), activation)?;
+        render(activation, &mut renderer, root);
+        assert!(renderer.commands.is_empty());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn recursion_past_limit_is_an_error() {
+    fn recurse<'gc>(id: &ActivationIdentifier<'_>, max_depth: u16) -> Result<(), Error<'gc>> {
+        let child = id.function("[Recursive]", ExecutionReason::FunctionCall, max_depth)?;
+        recurse(&child, max_depth)
+    }
+
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        activation.context.avm1.set_max_recursion_depth(16);
+        let max_depth = activation.context.avm1.max_recursion_depth();
+        let result = recurse(&activation.id, max_depth);
+        assert!(matches!(result, Err(Error::FunctionRecursionLimit(16))));
+        assert!(result.unwrap_err().is_halting());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn with_statement_scope() {
+    with_avm(8, |activation, root| -> Result<(), Error> {
+        let object = ScriptObject::object_cell(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.object),
+        );
+        object.set("a", 1.into(), activation)?;
+        root.set("obj", object.into(), activation)?;
+
+        // with (obj) { a = 5; var b = a; var a; }
+        let with_body = encode_actions(
+            &[
+                push_str("a"),
+                Action::Push(vec![SwfValue::Int(5)]),
+                Action::SetVariable,
+                push_str("b"),
+                push_str("a"),
+                Action::GetVariable,
+                Action::DefineLocal,
+                push_str("a"),
+                Action::DefineLocal2,
+            ],
+            activation.swf_version(),
+        );
+        run_actions(
+            activation,
+            &[
+                push_str("obj"),
+                Action::GetVariable,
+                Action::With {
+                    actions: &with_body,
+                },
+            ],
+        )?;
+
+        // Existing properties of the with object are read and written...
+        assert_eq!(object.get("a", activation)?, 5.into());
+
+        // ...but new locals are defined outside of the with block.
+        assert!(!object.has_property(activation, "b"));
+        assert_eq!(root.get("b", activation)?, 5.into());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn try_catch_finally() {
+    with_avm(7, |activation, root| -> Result<(), Error> {
+        let swf_version = activation.swf_version();
+        let set_true = |name: &str| {
+            encode_actions(
+                &[
+                    push_str(name),
+                    Action::Push(vec![SwfValue::Bool(true)]),
+                    Action::SetVariable,
+                ],
+                swf_version,
+            )
+        };
+        let throw = encode_actions(&[push_str("oops"), Action::Throw], swf_version);
+
+        // try { throw "oops"; } catch (e) { caught = e; }
+        let catch = encode_actions(
+            &[
+                push_str("caught"),
+                push_str("e"),
+                Action::GetVariable,
+                Action::SetVariable,
+            ],
+            swf_version,
+        );
+        run_actions(
+            activation,
+            &[Action::Try(TryBlock {
+                try_actions: &throw,
+                catch: Some((CatchVar::Var(SwfStr::from_utf8_str("e")), &catch)),
+                finally: None,
+            })],
+        )?;
+        assert_eq!(root.get("caught", activation)?, "oops".into());
+
+        // try { throw "oops"; } finally { cleaned = true; }
+        let cleanup = set_true("cleaned");
+        let result = run_actions(
+            activation,
+            &[Action::Try(TryBlock {
+                try_actions: &throw,
+                catch: None,
+                finally: Some(&cleanup),
+            })],
+        );
+        assert!(matches!(result, Err(Error::ThrownValue(_))));
+        assert_eq!(root.get("cleaned", activation)?, true.into());
+
+        // try { ran = true; } finally { finished = true; }
+        let body = set_true("ran");
+        let finish = set_true("finished");
+        run_actions(
+            activation,
+            &[Action::Try(TryBlock {
+                try_actions: &body,
+                catch: None,
+                finally: Some(&finish),
+            })],
+        )?;
+        assert_eq!(root.get("ran", activation)?, true.into());
+        assert_eq!(root.get("finished", activation)?, true.into());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn thrown_object_unwinds_to_catch() {
+    with_avm(7, |activation, root| -> Result<(), Error> {
+        let error = ScriptObject::object_cell(
+            activation.context.gc_context,
+            Some(activation.context.avm1.prototypes.object),
+        );
+        error.set("code", 42.into(), activation)?;
+        root.set("err", error.into(), activation)?;
+
+        let swf_version = activation.swf_version();
+
+        // function fail() { throw err; }
+        let fail = encode_actions(
+            &[push_str("err"), Action::GetVariable, Action::Throw],
+            swf_version,
+        );
+
+        // try { fail(); } catch (e) { caught = e; }
+        let body = encode_actions(
+            &[
+                Action::Push(vec![
+                    SwfValue::Int(0),
+                    SwfValue::Str(SwfStr::from_utf8_str("fail")),
+                ]),
+                Action::CallFunction,
+                Action::Pop,
+            ],
+            swf_version,
+        );
+        let catch = encode_actions(
+            &[
+                push_str("caught"),
+                push_str("e"),
+                Action::GetVariable,
+                Action::SetVariable,
+            ],
+            swf_version,
+        );
+        run_actions(
+            activation,
+            &[
+                Action::DefineFunction {
+                    name: SwfStr::from_utf8_str("fail"),
+                    params: vec![],
+                    actions: &fail,
+                },
+                Action::Try(TryBlock {
+                    try_actions: &body,
+                    catch: Some((CatchVar::Var(SwfStr::from_utf8_str("e")), &catch)),
+                    finally: None,
+                }),
+            ],
+        )?;
+
+        // The very same object is caught, rather than a copy or a host error.
+        let caught = root.get("caught", activation)?;
+        assert_eq!(caught, error.into());
+        let caught = caught.coerce_to_object(activation);
+        assert_eq!(caught.get("code", activation)?, 42.into());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn disabled_script_timeout_lets_long_loops_finish() {
+    // i = 0; do { i++; } while (i < 100000);
+    fn long_loop<'gc>(activation: &mut Activation<'_, 'gc, '_>) -> Result<(), Error<'gc>> {
+        let body = [
+            push_str("i"),
+            push_str("i"),
+            Action::GetVariable,
+            Action::Increment,
+            Action::SetVariable,
+            push_str("i"),
+            Action::GetVariable,
+            Action::Push(vec![SwfValue::Int(100000)]),
+            Action::Less2,
+        ];
+        // Jump back over the loop body and the 5-byte `If` action itself.
+        let offset = -(encode_actions(&body, activation.swf_version()).len() as i16 + 5);
+
+        let mut actions = vec![
+            push_str("i"),
+            Action::Push(vec![SwfValue::Int(0)]),
+            Action::SetVariable,
+        ];
+        actions.extend(body.iter().cloned());
+        actions.push(Action::If { offset });
+        run_actions(activation, &actions)?;
+
+        Ok(())
+    }
+
+    with_avm(6, |activation, root| -> Result<(), Error> {
+        activation.context.max_execution_duration = Some(Duration::from_secs(0));
+        assert!(matches!(
+            long_loop(activation),
+            Err(Error::ExecutionTimeout)
+        ));
+        assert!(root.get("i", activation)?.coerce_to_f64(activation)? < 100000.0);
+
+        Ok(())
+    });
+
+    with_avm(6, |activation, root| -> Result<(), Error> {
+        activation.context.max_execution_duration = None;
+        long_loop(activation)?;
+        assert_eq!(root.get("i", activation)?, 100000.into());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn new_date_reads_the_clock() {
+    // 2009-02-13 23:31:30 UTC
+    let epoch = 1_234_567_890_000i64;
+    let clock = ManualClock::launched_at(Utc.timestamp_millis(epoch));
+
+    with_avm_and_clock(
+        8,
+        Box::new(clock.clone()),
+        |activation, _root| -> Result<(), Error> {
+            let date_class = activation
+                .context
+                .avm1
+                .global_object_cell()
+                .get("Date", activation)?
+                .coerce_to_object(activation);
+
+            let date = date_class
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            assert_eq!(
+                date.call_method("getTime", &[], activation)?,
+                (epoch as f64).into()
+            );
+
+            clock.advance(Duration::from_millis(1500));
+            let date = date_class
+                .construct(activation, &[])?
+                .coerce_to_object(activation);
+            assert_eq!(
+                date.call_method("getTime", &[], activation)?,
+                ((epoch + 1500) as f64).into()
+            );
+
+            Ok(())
+        },
+    );
+}
+
+/// Stores the arguments it was called with on `this`.
+fn store_args<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set("received", args.len().into(), activation)?;
+    this.set(
+        "first",
+        args.get(0).cloned().unwrap_or(Value::Undefined),
+        activation,
+    )?;
+    this.set(
+        "second",
+        args.get(1).cloned().unwrap_or(Value::Undefined),
+        activation,
+    )?;
+
+    Ok(Value::Undefined)
+}
+
+#[test]
+fn local_connection_delivers_messages() {
+    with_avm(8, |activation, _root| -> Result<(), Error> {
+        let local_connection = activation
+            .context
+            .avm1
+            .global_object_cell()
+            .get("LocalConnection", activation)?
+            .coerce_to_object(activation);
+        let receiver = local_connection
+            .construct(activation, &[])?
+            .coerce_to_object(activation);
+        let sender = local_connection
+            .construct(activation, &[])?
+            .coerce_to_object(activation);
+
+        let fn_proto = activation.context.avm1.prototypes().function;
+        let on_message = FunctionObject::function(
+            activation.context.gc_context,
+            Executable::Native(store_args),
+            Some(fn_proto),
+            fn_proto,
+        );
+        receiver.set("onMessage", on_message.into(), activation)?;
+
+        assert_eq!(
+            receiver.call_method("connect", &["channel".into()], activation)?,
+            true.into()
+        );
+        assert_eq!(
+            sender.call_method("connect", &["channel".into()], activation)?,
+            false.into()
+        );
+
+        assert_eq!(
+            sender.call_method(
+                "send",
+                &[
+                    "channel".into(),
+                    "onMessage".into(),
+                    "hello".into(),
+                    42.into()
+                ],
+                activation
+            )?,
+            true.into()
+        );
+        assert_eq!(
+            sender.call_method("send", &["channel".into(), "close".into()], activation)?,
+            false.into()
+        );
+
+        // Messages are delivered once queued actions run.
+        assert_eq!(receiver.get("received", activation)?, Value::Undefined);
+        Player::run_actions(&mut activation.context);
+        assert_eq!(receiver.get("received", activation)?, 2.into());
+        assert_eq!(receiver.get("first", activation)?, "hello".into());
+        assert_eq!(receiver.get("second", activation)?, 42.into());
+
+        // Nothing is delivered once the receiver closes.
+        receiver.call_method("close", &[], activation)?;
+        sender.call_method("send", &["channel".into(), "onMessage".into()], activation)?;
+        Player::run_actions(&mut activation.context);
+        assert_eq!(receiver.get("received", activation)?, 2.into());
+
+        Ok(())
+    });
+}
+
+/// Builds a movie that exports an empty sprite as `Widget`.
+fn exported_symbol_movie() -> SwfMovie {
+    swf_movie(
+        swf_header(8, 10.0, 1),
+        vec![
+            swf::Tag::DefineSprite(swf::Sprite {
+                id: 1,
+                num_frames: 1,
+                tags: vec![swf::Tag::ShowFrame],
+            }),
+            swf::Tag::ExportAssets(vec![swf::ExportedAsset {
+                id: 1,
+                name: "Widget".into(),
+            }]),
+            swf::Tag::ShowFrame,
+        ],
+    )
+}
+
+/// Constructor of the `Widget` test class.
+fn widget_constructor<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    this.set("constructed", true.into(), activation)?;
+    Ok(Value::Undefined)
+}
+
+/// Implements `Widget.prototype.greet`.
+fn widget_greet<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Object<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok("hello".into())
+}
+
+#[test]
+fn registered_class_applies_to_attached_symbol() {
+    with_avm_and_movie(
+        exported_symbol_movie(),
+        |activation, root| -> Result<(), Error> {
+            let gc_context = activation.context.gc_context;
+            let object_proto = activation.context.avm1.prototypes().object;
+            let fn_proto = activation.context.avm1.prototypes().function;
+            let mut prototype = ScriptObject::object(gc_context, Some(object_proto));
+            prototype.force_set_function(
+                "greet",
+                widget_greet,
+                gc_context,
+                Attribute::empty(),
+                Some(fn_proto),
+            );
+            let widget = FunctionObject::constructor(
+                gc_context,
+                Executable::Native(widget_constructor),
+                Executable::Native(widget_constructor),
+                Some(fn_proto),
+                prototype.into(),
+            );
+
+            let object_class = activation
+                .context
+                .avm1
+                .global_object_cell()
+                .get("Object", activation)?
+                .coerce_to_object(activation);
+
+            // Object.registerClass("Widget", Widget);
+            let registered = object_class.call_method(
+                "registerClass",
+                &["Widget".into(), widget.into()],
+                activation,
+            )?;
+            assert_eq!(registered, true.into());
+            let instance = root
+                .call_method(
+                    "attachMovie",
+                    &["Widget".into(), "instance".into(), 1.into()],
+                    activation,
+                )?
+                .coerce_to_object(activation);
+            assert_eq!(instance.get("constructed", activation)?, true.into());
+            assert_eq!(
+                instance.call_method("greet", &[], activation)?,
+                "hello".into()
+            );
+
+            // Object.registerClass("Widget", null);
+            let unregistered = object_class.call_method(
+                "registerClass",
+                &["Widget".into(), Value::Null],
+                activation,
+            )?;
+            assert_eq!(unregistered, true.into());
+            let plain = root
+                .call_method(
+                    "attachMovie",
+                    &["Widget".into(), "plain".into(), 2.into()],
+                    activation,
+                )?
+                .coerce_to_object(activation);
+            assert_eq!(plain.get("constructed", activation)?, Value::Undefined);
+            assert_eq!(plain.get("greet", activation)?, Value::Undefined);
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn quality_property() {
+    with_avm(8, |activation, root| -> Result<(), Error> {
+        assert_eq!(activation.context.stage.quality(), StageQuality::High);
+
+        root.set("_quality", "low".into(), activation)?;
+        // Unknown qualities are ignored.
+        root.set("_quality", "ultra".into(), activation)?;
+        assert_eq!(root.get("_quality", activation)?, "LOW".into());
+        assert_eq!(activation.context.stage.quality(), StageQuality::Low);
+
+        let stage = activation.context.stage;
+        stage.set_quality(&mut activation.context, StageQuality::Best);
+        assert_eq!(root.get("_quality", activation)?, "BEST".into());
+
+        Ok(())
+    });
+}
+
+#[test]
+fn quality_reaches_renderer() {
+    with_avm_and_builder(
+        8,
+        PlayerBuilder::new().with_renderer(Box::new(RecordingRenderer::new())),
+        |activation, root| -> Result<(), Error> {
+            let qualities = |activation: &mut Activation| {
+                let renderer = activation
+                    .context
+                    .renderer
+                    .downcast_ref::<RecordingRenderer>()
+                    .unwrap();
+                renderer.qualities.clone()
+            };
+
+            root.set("_quality", "LOW".into(), activation)?;
+            assert_eq!(qualities(activation), vec![StageQuality::Low]);
+
+            let stage = activation.context.stage;
+            stage.set_quality(&mut activation.context, StageQuality::Best);
+            assert_eq!(
+                qualities(activation),
+                vec![StageQuality::Low, StageQuality::Best]
+            );
+
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn update_after_event_requests_render() {
+    with_avm(8, |activation, root| -> Result<(), Error> {
+        *activation.context.needs_render = false;
+        let update_after_event = activation
+            .context
+            .avm1
+            .global_object_cell()
+            .get("updateAfterEvent", activation)?;
+        update_after_event.call("updateAfterEvent", activation, root, None, &[])?;
+        assert!(*activation.context.needs_render);
+
+        Ok(())
+    });
+}
@@ -4,10 +4,11 @@
 //! The timers are stored in a priority queue, where we check if the nearest timer
 //! is ready to tick each frame.
 //!
-//! TODO: Could we use this for AVM2 timers as well?
+//! Both AVM1 and AVM2 timers live in the same queue, so they share one clock.
 
 use crate::avm1::object::search_prototype;
 use crate::avm1::{Activation, ActivationIdentifier, Object, TObject, Value};
+use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
 use crate::context::UpdateContext;
 use gc_arena::Collect;
 use std::collections::{binary_heap::PeekMut, BinaryHeap};
@@ -89,6 +90,18 @@ impl<'gc> Timers<'gc> {
             let callback = timer.callback.clone();
 
             let callback = match callback {
+                TimerCallback::Avm2Callable { callable, params } => {
+                    if let Err(e) = Avm2::run_stack_frame_for_callable(
+                        callable,
+                        None,
+                        &params,
+                        &mut activation.context,
+                    ) {
+                        log::error!("Unhandled AVM2 exception in timer callback: {}", e);
+                    }
+                    crate::player::Player::run_actions(&mut activation.context);
+                    None
+                }
                 TimerCallback::Function(f) => Some((undefined, None, f)),
                 TimerCallback::Method { this, method_name } => {
                     // Fetch the callback method from the object.
@@ -280,4 +293,10 @@ pub enum TimerCallback<'gc> {
         this: Object<'gc>,
         method_name: String,
     },
+
+    /// An AVM2 function, along with the arguments to call it with.
+    Avm2Callable {
+        callable: Avm2Object<'gc>,
+        params: Vec<Avm2Value<'gc>>,
+    },
 }
@@ -9,6 +9,7 @@ use crate::context::UpdateContext;
 use crate::tag_utils::SwfSlice;
 use gc_arena::{Collect, MutationContext};
 use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 use swf::avm2::read::Reader;
 
@@ -21,6 +22,19 @@ macro_rules! avm_debug {
     )
 }
 
+/// Warn that an unimplemented builtin was called, and record it in the
+/// player's diagnostics report.
+#[macro_export]
+macro_rules! avm2_stub {
+    ($activation: ident, $name: expr) => {{
+        log::warn!("{}: Unimplemented", $name);
+        $activation
+            .context
+            .diagnostics
+            .record_unimplemented_builtin($name);
+    }};
+}
+
 mod activation;
 mod array;
 mod bytearray;
@@ -40,15 +54,19 @@ mod scope;
 mod script;
 mod slot;
 mod string;
+#[cfg(test)]
+mod test_utils;
 mod traits;
 mod value;
 
 pub use crate::avm2::activation::Activation;
 pub use crate::avm2::array::ArrayStorage;
 pub use crate::avm2::domain::Domain;
-pub use crate::avm2::events::Event;
+pub use crate::avm2::events::{Event, EventData};
 pub use crate::avm2::names::{Namespace, QName};
-pub use crate::avm2::object::{ArrayObject, Object, ScriptObject, StageObject, TObject};
+pub use crate::avm2::object::{
+    ArrayObject, FunctionObject, LoaderStream, Object, ScriptObject, StageObject, TObject,
+};
 pub use crate::avm2::value::Value;
 
 const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstructed"];
@@ -59,6 +77,25 @@ const BROADCAST_WHITELIST: [&str; 3] = ["enterFrame", "exitFrame", "frameConstru
 /// with a proper Avm2Error enum.
 pub type Error = Box<dyn std::error::Error>;
 
+/// An error raised by a script-visible `throw`.
+///
+/// The thrown value itself is held by the AVM until a `catch` handler takes
+/// it; this error only carries where the AVM keeps it, and its string form
+/// for reporting uncaught exceptions.
+#[derive(Debug)]
+pub struct ThrownValue {
+    index: usize,
+    description: String,
+}
+
+impl fmt::Display for ThrownValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.description)
+    }
+}
+
+impl std::error::Error for ThrownValue {}
+
 /// The state of an AVM2 interpreter.
 #[derive(Collect)]
 #[collect(no_drop)]
@@ -82,6 +119,16 @@ pub struct Avm2<'gc> {
     /// collector does not support weak references.
     broadcast_list: HashMap<AvmString<'gc>, Vec<Object<'gc>>>,
 
+    /// The number of method calls currently on the call stack.
+    call_depth: u16,
+
+    /// The maximum call depth before a stack overflow error is thrown.
+    max_call_depth: u16,
+
+    /// Values thrown by scripts that are still unwinding the call stack, in
+    /// the order they were thrown.
+    thrown_values: Vec<Value<'gc>>,
+
     #[cfg(feature = "avm_debug")]
     pub debug_output: bool,
 }
@@ -96,6 +143,9 @@ impl<'gc> Avm2<'gc> {
             globals,
             system_prototypes: None,
             broadcast_list: HashMap::new(),
+            call_depth: 0,
+            max_call_depth: 256,
+            thrown_values: Vec::new(),
 
             #[cfg(feature = "avm_debug")]
             debug_output: false,
@@ -115,6 +165,52 @@ impl<'gc> Avm2<'gc> {
         self.system_prototypes.as_ref().unwrap()
     }
 
+    /// The number of method calls currently on the call stack.
+    pub fn call_depth(&self) -> u16 {
+        self.call_depth
+    }
+
+    pub fn max_call_depth(&self) -> u16 {
+        self.max_call_depth
+    }
+
+    pub fn set_max_call_depth(&mut self, max_call_depth: u16) {
+        self.max_call_depth = max_call_depth
+    }
+
+    /// Hold on to a thrown value, and return the error that unwinds the
+    /// stack until a `catch` handler takes the value back.
+    pub fn throw(&mut self, value: Value<'gc>, description: String) -> Error {
+        let index = self.thrown_values.len();
+        self.thrown_values.push(value);
+        ThrownValue { index, description }.into()
+    }
+
+    /// The value carried by an error, if it was raised by `throw` and has
+    /// not been caught or dropped since.
+    pub fn thrown_value(&self, error: &Error) -> Option<Value<'gc>> {
+        let thrown = error.downcast_ref::<ThrownValue>()?;
+        self.thrown_values.get(thrown.index).cloned()
+    }
+
+    /// Let go of the value carried by an error that has been caught.
+    ///
+    /// Any values thrown after it were thrown by calls that have since
+    /// returned, so nothing can catch them anymore.
+    fn release_thrown_value(&mut self, error: &Error) {
+        if let Some(thrown) = error.downcast_ref::<ThrownValue>() {
+            self.thrown_values.truncate(thrown.index);
+        }
+    }
+
+    /// Drop the values of errors that escaped every `catch` handler, once the
+    /// call stack has fully unwound.
+    fn drop_uncaught_values(&mut self) {
+        if self.call_depth == 0 {
+            self.thrown_values.clear();
+        }
+    }
+
     /// Run a script's initializer method.
     pub fn run_script_initializer(
         script: Script<'gc>,
@@ -144,7 +240,15 @@ impl<'gc> Avm2<'gc> {
         target: Object<'gc>,
     ) -> Result<bool, Error> {
         use crate::avm2::events::dispatch_event;
-        let event_proto = context.avm2.system_prototypes.as_ref().unwrap().event;
+        let prototypes = context.avm2.system_prototypes.as_ref().unwrap();
+        let event_proto = match event.event_data() {
+            EventData::Empty => prototypes.event,
+            EventData::Progress { .. } => prototypes.progress_event,
+            EventData::Text { .. } => prototypes.text_event,
+            EventData::IOError { .. } => prototypes.io_error_event,
+            EventData::SecurityError { .. } => prototypes.security_error_event,
+            EventData::NetStatus { .. } => prototypes.net_status_event,
+        };
         let event_object = EventObject::from_event(context.gc_context, Some(event_proto), event);
         let mut activation = Activation::from_nothing(context.reborrow());
 
@@ -2,6 +2,7 @@
 
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::error::{ErrorDef, ERROR_1041, ERROR_1065, ERROR_1069};
 use crate::avm2::method::BytecodeMethod;
 use crate::avm2::method::Method;
 use crate::avm2::names::{Multiname, Namespace, QName};
@@ -12,6 +13,7 @@ use crate::avm2::object::{Object, TObject};
 use crate::avm2::scope::Scope;
 use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
 use crate::avm2::value::Value;
 use crate::avm2::{value, Avm2, Error};
 use crate::context::UpdateContext;
@@ -19,10 +21,11 @@ use crate::swf::extensions::ReadSwfExt;
 use gc_arena::{Gc, GcCell, MutationContext};
 use smallvec::SmallVec;
 use std::convert::TryInto;
+use std::fmt;
 use swf::avm2::read::Reader;
 use swf::avm2::types::{
-    Class as AbcClass, Index, Method as AbcMethod, Multiname as AbcMultiname,
-    Namespace as AbcNamespace, Op,
+    Class as AbcClass, Exception as AbcException, Index, Method as AbcMethod,
+    Multiname as AbcMultiname, Namespace as AbcNamespace, Op,
 };
 
 /// Represents a particular register set.
@@ -334,7 +337,9 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
     pub fn run_stack_frame_for_script(&mut self, script: Script<'gc>) -> Result<(), Error> {
         let init = script.init().0.into_bytecode()?;
 
-        self.run_actions(init)?;
+        let result = self.run_actions(init);
+        self.context.avm2.drop_uncaught_values();
+        result?;
 
         Ok(())
     }
@@ -438,6 +443,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         self.context.avm2
     }
 
+    /// Throw a value, to be caught by the nearest enclosing `catch` handler.
+    ///
+    /// The returned error should be propagated to unwind the stack.
+    pub fn throw(&mut self, value: Value<'gc>) -> Error {
+        let description = match value.coerce_to_string(self) {
+            Ok(description) => description.to_string(),
+            Err(e) => return e,
+        };
+
+        self.context.avm2.throw(value, description)
+    }
+
+    /// Throw a new instance of a runtime error.
+    pub fn throw_error(&mut self, error: ErrorDef, args: &[&dyn fmt::Display]) -> Error {
+        match error.construct(self, args) {
+            Ok(object) => self.throw(object.into()),
+            Err(e) => e,
+        }
+    }
+
     /// Set the return value.
     pub fn set_return_value(&mut self, value: Value<'gc>) {
         self.return_value = Some(value);
@@ -547,15 +572,88 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
             .ok_or_else(|| "Cannot execute non-native method without body".into());
         let body = body?;
         let mut reader = Reader::new(&body.code);
+        let stack_depth = self.context.avm2.stack.len();
+        let scope = self.scope;
 
         loop {
+            let instruction_start = reader.pos(&body.code);
             let result = self.do_next_opcode(method, &mut reader, &body.code);
             match result {
                 Ok(FrameControl::Return(value)) => break Ok(value),
                 Ok(FrameControl::Continue) => {}
-                Err(e) => break Err(e),
+                Err(e) => {
+                    let value = match self.context.avm2.thrown_value(&e) {
+                        Some(value) => value,
+                        None => break Err(e),
+                    };
+
+                    match self.find_exception_handler(method, instruction_start, &value)? {
+                        Some(target) => {
+                            // The handler starts with only the thrown value on
+                            // the stack, and with the method's own scope.
+                            self.context.avm2.stack.truncate(stack_depth);
+                            self.context.avm2.release_thrown_value(&e);
+                            self.context.avm2.push(value);
+                            self.scope = scope;
+
+                            let offset = target as i32 - reader.pos(&body.code) as i32;
+                            reader.seek(&body.code, offset);
+                        }
+                        None => break Err(e),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Find the `catch` handler for a value thrown by the instruction at
+    /// `offset`, returning the offset of the handler.
+    fn find_exception_handler(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        offset: usize,
+        value: &Value<'gc>,
+    ) -> Result<Option<u32>, Error> {
+        let body: Result<_, Error> = method
+            .body()
+            .ok_or_else(|| "Cannot execute non-native method without body".into());
+
+        for exception in body?.exceptions.iter() {
+            let offset = offset as u32;
+            if offset < exception.from_offset || offset >= exception.to_offset {
+                continue;
+            }
+
+            // Type name zero catches everything.
+            if exception.type_name.0 == 0 {
+                return Ok(Some(exception.target_offset));
+            }
+
+            let multiname = self.pool_multiname_static(
+                method,
+                exception.type_name.clone(),
+                self.context.gc_context,
+            )?;
+            let found = if let Some(scope) = self.scope() {
+                scope
+                    .write(self.context.gc_context)
+                    .resolve(&multiname, self)?
+            } else {
+                None
+            };
+            let type_object = match found {
+                Some(found) => found.coerce_to_object(self)?,
+                None => continue,
+            };
+
+            if let Value::Object(object) = value {
+                if object.is_instance_of(self, type_object, true)? {
+                    return Ok(Some(exception.target_offset));
+                }
             }
         }
+
+        Ok(None)
     }
 
     /// Run a single action from a given action reader.
@@ -565,11 +663,13 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         reader: &mut Reader<'b>,
         full_data: &'b [u8],
     ) -> Result<FrameControl<'gc>, Error> {
-        if self.context.update_start.elapsed() >= self.context.max_execution_duration {
-            return Err(
-                "A script in this movie has taken too long to execute and has been terminated."
-                    .into(),
-            );
+        if let Some(max_execution_duration) = self.context.max_execution_duration {
+            if self.context.update_start.elapsed() >= max_execution_duration {
+                return Err(
+                    "A script in this movie has taken too long to execute and has been terminated."
+                        .into(),
+                );
+            }
         }
 
         let instruction_start = reader.pos(full_data);
@@ -638,6 +738,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 }
                 Op::ConstructSuper { num_args } => self.op_construct_super(num_args),
                 Op::NewActivation => self.op_new_activation(),
+                Op::NewCatch { index } => self.op_new_catch(method, index),
                 Op::NewObject { num_args } => self.op_new_object(num_args),
                 Op::NewFunction { index } => self.op_new_function(method, index),
                 Op::NewClass { index } => self.op_new_class(method, index),
@@ -711,6 +812,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
                 Op::AsType { type_name } => self.op_as_type(method, type_name),
                 Op::AsTypeLate => self.op_as_type_late(),
                 Op::InstanceOf => self.op_instance_of(),
+                Op::Throw => self.op_throw(),
                 Op::Label => Ok(FrameControl::Continue),
                 Op::Debug {
                     is_local_register,
@@ -1101,23 +1203,26 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let multiname = self.pool_multiname(method, index)?;
         let mut object = self.context.avm2.pop().coerce_to_object(self)?;
 
-        let name: Result<QName, Error> = object.resolve_multiname(&multiname)?.ok_or_else(|| {
-            format!("Could not resolve property {:?}", multiname.local_name()).into()
-        });
+        let name = match object.resolve_multiname(&multiname)? {
+            Some(name) => name,
+            None => {
+                // Special case for dynamic properties as scripts may attempt to get
+                // dynamic properties not yet set
+                let class = object.as_proto_class();
+                if !class.map(|c| c.read().is_sealed()).unwrap_or(false) {
+                    self.context.avm2.push(Value::Undefined);
+                    return Ok(FrameControl::Continue);
+                }
 
-        // Special case for dynamic properties as scripts may attempt to get
-        // dynamic properties not yet set
-        if name.is_err()
-            && !object
-                .as_proto_class()
-                .map(|c| c.read().is_sealed())
-                .unwrap_or(false)
-        {
-            self.context.avm2.push(Value::Undefined);
-            return Ok(FrameControl::Continue);
-        }
+                let property = multiname.local_name().unwrap_or_else(|| "*".into());
+                let class_name = class
+                    .map(|c| c.read().name().local_name())
+                    .unwrap_or_else(|| "Object".into());
+                return Err(ERROR_1069.format(&[&property, &class_name]).into());
+            }
+        };
 
-        let value = object.get_property(object, &name?, self)?;
+        let value = object.get_property(object, &name, self)?;
         self.context.avm2.push(value);
 
         Ok(FrameControl::Continue)
@@ -1352,7 +1457,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
+        .ok_or_else(|| {
+            let name = multiname.local_name().unwrap_or_else(|| "*".into());
+            ERROR_1065.format(&[&name]).into()
+        });
         let result: Value<'gc> = found?.into();
 
         self.context.avm2.push(result);
@@ -1374,7 +1482,10 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         } else {
             None
         }
-        .ok_or_else(|| format!("Property does not exist: {:?}", multiname).into());
+        .ok_or_else(|| {
+            let name = multiname.local_name().unwrap_or_else(|| "*".into());
+            ERROR_1065.format(&[&name]).into()
+        });
         let result: Value<'gc> = found?;
 
         self.context.avm2.push(result);
@@ -1489,6 +1600,56 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_new_catch(
+        &mut self,
+        method: Gc<'gc, BytecodeMethod<'gc>>,
+        index: Index<AbcException>,
+    ) -> Result<FrameControl<'gc>, Error> {
+        let body: Result<_, Error> = method
+            .body()
+            .ok_or_else(|| "Cannot execute non-native method without body".into());
+        let exception: Result<_, Error> = body?
+            .exceptions
+            .get(index.0 as usize)
+            .ok_or_else(|| format!("Exception {} does not exist", index.0).into());
+        let exception = exception?;
+
+        // The catch scope holds the thrown value in its one slot.
+        let name = self.pool_string(
+            &method,
+            exception.variable_name.clone(),
+            self.context.gc_context,
+        )?;
+        let type_name = if exception.type_name.0 == 0 {
+            Multiname::any()
+        } else {
+            self.pool_multiname_static(
+                method,
+                exception.type_name.clone(),
+                self.context.gc_context,
+            )?
+        };
+        let class = Class::new(
+            QName::dynamic_name(name),
+            None,
+            Method::from_builtin(|_, _, _| Err("Do not call catch scope initializers!".into())),
+            Method::from_builtin(|_, _, _| {
+                Err("Do not call catch scope class initializers!".into())
+            }),
+            self.context.gc_context,
+        );
+        class
+            .write(self.context.gc_context)
+            .define_instance_trait(Trait::from_slot(QName::dynamic_name(name), type_name, None));
+
+        let proto = ScriptObject::bare_prototype(self.context.gc_context, class, self.scope);
+        self.context
+            .avm2
+            .push(ScriptObject::object(self.context.gc_context, proto));
+
+        Ok(FrameControl::Continue)
+    }
+
     fn op_new_object(&mut self, num_args: u32) -> Result<FrameControl<'gc>, Error> {
         let mut object = ScriptObject::object(
             self.context.gc_context,
@@ -1911,6 +2072,12 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         Ok(FrameControl::Continue)
     }
 
+    fn op_throw(&mut self) -> Result<FrameControl<'gc>, Error> {
+        let value = self.context.avm2.pop();
+
+        Err(self.throw(value))
+    }
+
     fn op_jump<'b>(
         &mut self,
         offset: i32,
@@ -2369,7 +2536,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let class = found?.coerce_to_object(self)?;
 
         if class.as_class().is_none() {
-            return Err("TypeError: The right-hand side of operator must be a class.".into());
+            return Err(ERROR_1041.format(&[]).into());
         }
 
         let is_instance_of = value.is_instance_of(self, class, true)?;
@@ -2388,7 +2555,7 @@ impl<'a, 'gc, 'gc_context> Activation<'a, 'gc, 'gc_context> {
         let value = self.context.avm2.pop().coerce_to_object(self)?;
 
         if class.as_class().is_none() {
-            return Err("TypeError: The right-hand side of operator must be a class.".into());
+            return Err(ERROR_1041.format(&[]).into());
         }
 
         let is_instance_of = value.is_instance_of(self, class, true)?;
@@ -2,7 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::{Object, TObject};
+use crate::avm2::object::{Object, ScriptObject, TObject};
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
@@ -41,6 +41,31 @@ pub enum PropagationMode {
     StopImmediatePropagation,
 }
 
+/// Data specific to a particular kind of event, such as a `ProgressEvent`.
+#[derive(Clone, Collect, Debug)]
+#[collect(no_drop)]
+pub enum EventData<'gc> {
+    /// A plain `Event` with no extra data.
+    Empty,
+
+    /// A `ProgressEvent`.
+    Progress { bytes_loaded: f64, bytes_total: f64 },
+
+    /// A `TextEvent`, or one of its error event subclasses.
+    ///
+    /// `error_id` is only exposed to scripts on `ErrorEvent`s.
+    Text { text: AvmString<'gc>, error_id: i32 },
+
+    /// An `IOErrorEvent`.
+    IOError { text: AvmString<'gc>, error_id: i32 },
+
+    /// A `SecurityErrorEvent`.
+    SecurityError { text: AvmString<'gc>, error_id: i32 },
+
+    /// A `NetStatusEvent`, whose `info` object describes the status change.
+    NetStatus { info: Object<'gc> },
+}
+
 /// Represents data fields of an event that can be fired on an object that
 /// implements `IEventDispatcher`.
 #[derive(Clone, Collect, Debug)]
@@ -71,11 +96,22 @@ pub struct Event<'gc> {
 
     /// The name of the event being triggered.
     event_type: AvmString<'gc>,
+
+    /// Data specific to this kind of event.
+    event_data: EventData<'gc>,
 }
 
 impl<'gc> Event<'gc> {
     /// Construct a new event of a given type.
     pub fn new<S>(event_type: S) -> Self
+    where
+        S: Into<AvmString<'gc>>,
+    {
+        Self::with_data(event_type, EventData::Empty)
+    }
+
+    /// Construct a new event of a given type, carrying event-specific data.
+    pub fn with_data<S>(event_type: S, event_data: EventData<'gc>) -> Self
     where
         S: Into<AvmString<'gc>>,
     {
@@ -88,7 +124,32 @@ impl<'gc> Event<'gc> {
             event_phase: EventPhase::AtTarget,
             target: None,
             event_type: event_type.into(),
+            event_data,
+        }
+    }
+
+    /// Construct a `netStatus` event reporting the given status `code`.
+    ///
+    /// `level` is one of `status`, `warning` or `error`.
+    pub fn net_status(
+        activation: &mut Activation<'_, 'gc, '_>,
+        code: &str,
+        level: &str,
+    ) -> Result<Self, Error> {
+        let object_proto = activation.context.avm2.prototypes().object;
+        let mut info = ScriptObject::object(activation.context.gc_context, object_proto);
+
+        for &(name, value) in &[("code", code), ("level", level)] {
+            let value = AvmString::new(activation.context.gc_context, value.to_string());
+            info.set_property(
+                info,
+                &QName::new(Namespace::public(), name),
+                value.into(),
+                activation,
+            )?;
         }
+
+        Ok(Self::with_data("netStatus", EventData::NetStatus { info }))
     }
 
     pub fn event_type(&self) -> AvmString<'gc> {
@@ -169,6 +230,14 @@ impl<'gc> Event<'gc> {
     pub fn set_current_target(&mut self, current_target: Object<'gc>) {
         self.current_target = Some(current_target)
     }
+
+    pub fn event_data(&self) -> &EventData<'gc> {
+        &self.event_data
+    }
+
+    pub fn event_data_mut(&mut self) -> &mut EventData<'gc> {
+        &mut self.event_data
+    }
 }
 
 /// A set of handlers organized by event type, priority, and order added.
@@ -485,3 +554,171 @@ pub fn dispatch_event<'gc>(
 
     Ok(was_not_cancelled)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::method::NativeMethod;
+    use crate::avm2::object::FunctionObject;
+    use crate::avm2::Avm2;
+    use crate::display_object::TDisplayObjectContainer;
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use crate::test_utils::{as3_file_attributes, place_character, swf_header, swf_movie};
+    use std::cell::RefCell;
+    use std::sync::Arc;
+
+    thread_local! {
+        static CALLS: RefCell<Vec<&'static str>> = RefCell::new(Vec::new());
+    }
+
+    macro_rules! recording_handler {
+        ($name:ident) => {
+            fn $name<'gc>(
+                _activation: &mut Activation<'_, 'gc, '_>,
+                _this: Option<Object<'gc>>,
+                _args: &[Value<'gc>],
+            ) -> Result<Value<'gc>, Error> {
+                CALLS.with(|calls| calls.borrow_mut().push(stringify!($name)));
+                Ok(Value::Undefined)
+            }
+        };
+    }
+
+    recording_handler!(low);
+    recording_handler!(high);
+    recording_handler!(capture);
+    recording_handler!(at_target);
+    recording_handler!(bubble);
+
+    fn stop_immediately<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        CALLS.with(|calls| calls.borrow_mut().push("stop_immediately"));
+        let event = args.get(0).cloned().unwrap_or(Value::Undefined);
+        event
+            .coerce_to_object(activation)?
+            .as_event_mut(activation.context.gc_context)
+            .unwrap()
+            .stop_immediate_propagation();
+        Ok(Value::Undefined)
+    }
+
+    fn take_calls() -> Vec<&'static str> {
+        CALLS.with(|calls| std::mem::take(&mut *calls.borrow_mut()))
+    }
+
+    /// An AS3 movie with a sprite placed on the root timeline.
+    fn nested_clip_movie() -> SwfMovie {
+        swf_movie(
+            swf_header(10, 10.0, 1),
+            vec![
+                as3_file_attributes(),
+                swf::Tag::DefineSprite(swf::Sprite {
+                    id: 1,
+                    num_frames: 1,
+                    tags: vec![swf::Tag::ShowFrame],
+                }),
+                swf::Tag::PlaceObject(Box::new(place_character(1))),
+                swf::Tag::ShowFrame,
+            ],
+        )
+    }
+
+    /// Calls `addEventListener("test", handler, use_capture, priority)` on
+    /// `target`.
+    fn add_listener<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mut target: Object<'gc>,
+        handler: NativeMethod,
+        use_capture: bool,
+        priority: i32,
+    ) {
+        let fn_proto = activation.avm2().prototypes().function;
+        let handler =
+            FunctionObject::from_builtin(activation.context.gc_context, handler, fn_proto);
+        target
+            .get_property(
+                target,
+                &QName::new(Namespace::public(), "addEventListener"),
+                activation,
+            )
+            .unwrap()
+            .coerce_to_object(activation)
+            .unwrap()
+            .call(
+                Some(target),
+                &[
+                    "test".into(),
+                    handler.into(),
+                    use_capture.into(),
+                    priority.into(),
+                ],
+                activation,
+                None,
+            )
+            .unwrap();
+    }
+
+    #[test]
+    fn listeners_run_in_priority_order() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(nested_clip_movie()));
+        player.run_frame();
+        take_calls();
+
+        player.update(|context| {
+            let root = context.stage.root_clip().object2();
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let root = root.coerce_to_object(&mut activation).unwrap();
+            add_listener(&mut activation, root, low, false, -5);
+            add_listener(&mut activation, root, high, false, 10);
+            Avm2::dispatch_event(&mut activation.context, Event::new("test"), root).unwrap();
+            assert_eq!(take_calls(), vec!["high", "low"]);
+
+            // Listeners at the same priority run in the order they were added,
+            // and stopping immediate propagation skips the rest.
+            add_listener(&mut activation, root, stop_immediately, false, 10);
+            Avm2::dispatch_event(&mut activation.context, Event::new("test"), root).unwrap();
+            assert_eq!(take_calls(), vec!["high", "stop_immediately"]);
+        });
+    }
+
+    #[test]
+    fn bubbling_events_reach_ancestors() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(nested_clip_movie()));
+        player.run_frame();
+        take_calls();
+
+        player.update(|context| {
+            let root_clip = context.stage.root_clip();
+            let child = root_clip
+                .as_container()
+                .unwrap()
+                .child_by_depth(1)
+                .unwrap()
+                .object2();
+            let root = root_clip.object2();
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let root = root.coerce_to_object(&mut activation).unwrap();
+            let child = child.coerce_to_object(&mut activation).unwrap();
+            add_listener(&mut activation, root, capture, true, 0);
+            add_listener(&mut activation, root, bubble, false, 0);
+            add_listener(&mut activation, child, at_target, false, 0);
+
+            let mut event = Event::new("test");
+            event.set_bubbles(true);
+            Avm2::dispatch_event(&mut activation.context, event, child).unwrap();
+            assert_eq!(take_calls(), vec!["capture", "at_target", "bubble"]);
+
+            // Events that don't bubble still go through the capture phase.
+            Avm2::dispatch_event(&mut activation.context, Event::new("test"), child).unwrap();
+            assert_eq!(take_calls(), vec!["capture", "at_target"]);
+        });
+    }
+}
@@ -1,6 +1,7 @@
 //! AVM2 executables.
 
 use crate::avm2::activation::Activation;
+use crate::avm2::globals::error::ERROR_1023;
 use crate::avm2::method::{BytecodeMethod, Method, NativeMethod};
 use crate::avm2::object::Object;
 use crate::avm2::scope::Scope;
@@ -77,6 +78,9 @@ impl<'gc> Executable<'gc> {
     ///
     /// It is a panicking logic error to attempt to execute user code while any
     /// reachable object is currently under a GcCell write lock.
+    ///
+    /// Calls nested deeper than the AVM's maximum call depth fail with a
+    /// stack overflow error instead of exhausting the native stack.
     pub fn exec(
         &self,
         unbound_reciever: Option<Object<'gc>>,
@@ -84,6 +88,34 @@ impl<'gc> Executable<'gc> {
         activation: &mut Activation<'_, 'gc, '_>,
         base_proto: Option<Object<'gc>>,
         callee: Object<'gc>,
+    ) -> Result<Value<'gc>, Error> {
+        let avm2 = &mut activation.context.avm2;
+        if avm2.call_depth >= avm2.max_call_depth {
+            // Constructing the error calls its initializer, which needs room
+            // on the call stack of its own.
+            let max_call_depth = std::mem::replace(&mut avm2.max_call_depth, u16::MAX);
+            let error = activation.throw_error(ERROR_1023, &[]);
+            activation.context.avm2.max_call_depth = max_call_depth;
+
+            return Err(error);
+        }
+
+        avm2.call_depth += 1;
+        let result =
+            self.exec_unguarded(unbound_reciever, arguments, activation, base_proto, callee);
+        activation.context.avm2.call_depth -= 1;
+        activation.context.avm2.drop_uncaught_values();
+
+        result
+    }
+
+    fn exec_unguarded(
+        &self,
+        unbound_reciever: Option<Object<'gc>>,
+        arguments: &[Value<'gc>],
+        activation: &mut Activation<'_, 'gc, '_>,
+        base_proto: Option<Object<'gc>>,
+        callee: Object<'gc>,
     ) -> Result<Value<'gc>, Error> {
         match self {
             Executable::Native(nf, receiver) => {
@@ -133,3 +165,234 @@ impl<'gc> fmt::Debug for Executable<'gc> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::method::{BytecodeMethod, Method};
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::{FunctionObject, Object, TObject};
+    use crate::avm2::script::TranslationUnit;
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+    use crate::player::PlayerBuilder;
+    use std::rc::Rc;
+    use swf::avm2::types::{
+        AbcFile, ConstantPool, Exception, Index, Method as AbcMethod, MethodBody, MethodParam,
+    };
+
+    /// A builtin that calls itself forever.
+    fn recurse<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let this = this.unwrap();
+        this.call(Some(this), &[], activation, None)
+    }
+
+    /// A builtin that throws `"inner"`.
+    fn throw_inner<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        Err(activation.throw("inner".into()))
+    }
+
+    /// A builtin that throws `"outer"`, but lets the function in its
+    /// `catcher` property catch a value of its own before the error unwinds.
+    fn throw_around_nested_catch<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let outer = activation.throw("outer".into());
+
+        let mut this = this.unwrap();
+        let catcher = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public(), "catcher"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let fn_proto = activation.avm2().prototypes().function;
+        let inner =
+            FunctionObject::from_builtin(activation.context.gc_context, throw_inner, fn_proto);
+        let caught = catcher.call(None, &[inner.into()], activation, None)?;
+        assert_eq!(caught, "inner".into());
+
+        Err(outer)
+    }
+
+    /// Checks that a thrown value is the stack overflow error.
+    fn assert_stack_overflow<'gc>(activation: &mut Activation<'_, 'gc, '_>, value: Value<'gc>) {
+        let mut error = value.coerce_to_object(activation).unwrap();
+        let mut get = |name: &'static str| {
+            error
+                .get_property(error, &QName::new(Namespace::public(), name), activation)
+                .unwrap()
+                .coerce_to_string(activation)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(get("name"), "StackOverflowError");
+        assert_eq!(get("message"), "Error #1023: Stack overflow occurred.");
+    }
+
+    /// A method that calls its argument inside of a `try` block, and returns
+    /// whatever gets caught.
+    fn catching_method() -> AbcFile {
+        let code = vec![
+            0xd1, // getlocal1
+            0xd1, // getlocal1
+            0x41, 0x00, // call 0
+            0x48, // returnvalue
+            0x48, // returnvalue
+        ];
+
+        AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![],
+                namespaces: vec![],
+                namespace_sets: vec![],
+                multinames: vec![],
+            },
+            methods: vec![AbcMethod {
+                name: Index::new(0),
+                params: vec![MethodParam {
+                    name: None,
+                    kind: Index::new(0),
+                    default_value: None,
+                }],
+                return_type: Index::new(0),
+                needs_arguments_object: false,
+                needs_activation: false,
+                needs_rest: false,
+                needs_dxns: false,
+            }],
+            metadata: vec![],
+            instances: vec![],
+            classes: vec![],
+            scripts: vec![],
+            method_bodies: vec![MethodBody {
+                method: Index::new(0),
+                max_stack: 2,
+                num_locals: 2,
+                init_scope_depth: 0,
+                max_scope_depth: 0,
+                code,
+                exceptions: vec![Exception {
+                    from_offset: 0,
+                    to_offset: 4,
+                    target_offset: 5,
+                    variable_name: Index::new(0),
+                    type_name: Index::new(0),
+                }],
+                traits: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn unbounded_recursion_overflows_stack() {
+        let player = PlayerBuilder::new().build().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            context.avm2.set_max_call_depth(32);
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let fn_proto = activation.avm2().prototypes().function;
+            let function =
+                FunctionObject::from_builtin(activation.context.gc_context, recurse, fn_proto);
+
+            for _ in 0..2 {
+                let error = function
+                    .call(Some(function), &[], &mut activation, None)
+                    .unwrap_err();
+                assert!(error.to_string().contains("Error #1023"));
+
+                // The call depth unwinds along with the error, and nothing is
+                // left to hold on to the uncaught value.
+                assert_eq!(activation.context.avm2.call_depth(), 0);
+                assert!(activation.context.avm2.thrown_value(&error).is_none());
+            }
+        });
+    }
+
+    #[test]
+    fn script_catches_stack_overflow() {
+        let player = PlayerBuilder::new().build().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            context.avm2.set_max_call_depth(32);
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let gc_context = activation.context.gc_context;
+            let fn_proto = activation.avm2().prototypes().function;
+            let recursion = FunctionObject::from_builtin(gc_context, recurse, fn_proto);
+
+            let domain = activation.context.avm2.global_domain();
+            let unit = TranslationUnit::from_abc(Rc::new(catching_method()), domain, gc_context);
+            let method =
+                BytecodeMethod::from_method_index(unit, Index::new(0), gc_context).unwrap();
+            let function = FunctionObject::from_method(
+                gc_context,
+                Method::Entry(method),
+                None,
+                fn_proto,
+                None,
+            );
+
+            let caught = function
+                .call(None, &[recursion.into()], &mut activation, None)
+                .unwrap();
+            assert_stack_overflow(&mut activation, caught);
+            assert_eq!(activation.context.avm2.call_depth(), 0);
+        });
+    }
+
+    #[test]
+    fn nested_catch_keeps_outer_thrown_value() {
+        let player = PlayerBuilder::new().build().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let gc_context = activation.context.gc_context;
+            let fn_proto = activation.avm2().prototypes().function;
+
+            let domain = activation.context.avm2.global_domain();
+            let unit = TranslationUnit::from_abc(Rc::new(catching_method()), domain, gc_context);
+            let method =
+                BytecodeMethod::from_method_index(unit, Index::new(0), gc_context).unwrap();
+            let catcher = FunctionObject::from_method(
+                gc_context,
+                Method::Entry(method),
+                None,
+                fn_proto,
+                None,
+            );
+
+            let mut thrower =
+                FunctionObject::from_builtin(gc_context, throw_around_nested_catch, fn_proto);
+            thrower
+                .set_property(
+                    thrower,
+                    &QName::new(Namespace::public(), "catcher"),
+                    catcher.into(),
+                    &mut activation,
+                )
+                .unwrap();
+
+            let caught = catcher
+                .call(None, &[thrower.into()], &mut activation, None)
+                .unwrap();
+            assert_eq!(caught, "outer".into());
+            assert!(activation.context.avm2.thrown_values.is_empty());
+        });
+    }
+}
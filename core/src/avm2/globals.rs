@@ -14,11 +14,13 @@ use crate::avm2::script::Script;
 use crate::avm2::string::AvmString;
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::backend::log::LogCategory;
 use gc_arena::{Collect, GcCell, MutationContext};
 
 mod array;
 mod boolean;
 mod class;
+pub mod error;
 mod flash;
 mod function;
 mod global_scope;
@@ -49,7 +51,10 @@ fn trace<'gc>(
         }
     }
 
-    activation.context.log.avm_trace(&message);
+    activation
+        .context
+        .log
+        .log_event(LogCategory::AvmTrace, &message);
 
     Ok(Value::Undefined)
 }
@@ -98,6 +103,11 @@ pub struct SystemPrototypes<'gc> {
     pub scene: Object<'gc>,
     pub application_domain: Object<'gc>,
     pub event: Object<'gc>,
+    pub progress_event: Object<'gc>,
+    pub text_event: Object<'gc>,
+    pub io_error_event: Object<'gc>,
+    pub security_error_event: Object<'gc>,
+    pub net_status_event: Object<'gc>,
     pub video: Object<'gc>,
     pub xml: Object<'gc>,
     pub xml_list: Object<'gc>,
@@ -110,6 +120,7 @@ pub struct SystemPrototypes<'gc> {
     pub loaderinfo: Object<'gc>,
     pub bytearray: Object<'gc>,
     pub stage: Object<'gc>,
+    pub url_variables: Object<'gc>,
 }
 
 impl<'gc> SystemPrototypes<'gc> {
@@ -143,6 +154,11 @@ impl<'gc> SystemPrototypes<'gc> {
             scene: empty,
             application_domain: empty,
             event: empty,
+            progress_event: empty,
+            text_event: empty,
+            io_error_event: empty,
+            security_error_event: empty,
+            net_status_event: empty,
             video: empty,
             xml: empty,
             xml_list: empty,
@@ -155,6 +171,7 @@ impl<'gc> SystemPrototypes<'gc> {
             loaderinfo: empty,
             bytearray: empty,
             stage: empty,
+            url_variables: empty,
         }
     }
 }
@@ -485,6 +502,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        error::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
     class(
         activation,
         regexp::create_class(mc),
@@ -571,6 +595,78 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .progress_event = class(
+        activation,
+        flash::events::progressevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .text_event = class(
+        activation,
+        flash::events::textevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::events::errorevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .io_error_event = class(
+        activation,
+        flash::events::ioerrorevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .security_error_event = class(
+        activation,
+        flash::events::securityerrorevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .net_status_event = class(
+        activation,
+        flash::events::netstatusevent::create_class(mc),
+        flash::events::event::event_deriver,
+        domain,
+        script,
+    )?;
     // package `flash.utils`
     activation
         .context
@@ -603,6 +699,42 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    function(
+        mc,
+        "flash.utils",
+        "setInterval",
+        flash::utils::set_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "setTimeout",
+        flash::utils::set_timeout,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "clearInterval",
+        flash::utils::clear_interval,
+        fn_proto,
+        domain,
+        script,
+    )?;
+    function(
+        mc,
+        "flash.utils",
+        "clearTimeout",
+        flash::utils::clear_timeout,
+        fn_proto,
+        domain,
+        script,
+    )?;
 
     // package `flash.display`
     activation
@@ -738,6 +870,13 @@ pub fn load_player_globals<'gc>(
         domain,
         script,
     )?;
+    class(
+        activation,
+        flash::display::loader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
     class(
         activation,
         flash::display::actionscriptversion::create_class(mc),
@@ -794,6 +933,56 @@ pub fn load_player_globals<'gc>(
         script,
     )?;
 
+    // package `flash.net`
+    class(
+        activation,
+        flash::net::urlloader::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::urlloaderdataformat::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::urlrequest::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    activation
+        .context
+        .avm2
+        .system_prototypes
+        .as_mut()
+        .unwrap()
+        .url_variables = class(
+        activation,
+        flash::net::urlvariables::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::netconnection::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+    class(
+        activation,
+        flash::net::netstream::create_class(mc),
+        implicit_deriver,
+        domain,
+        script,
+    )?;
+
     // package `flash.geom`
     activation
         .context
@@ -875,3 +1064,31 @@ pub fn load_player_globals<'gc>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::trace;
+    use crate::avm2::activation::Activation;
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::CategorizedLogBackend;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn trace_joins_arguments() {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let player = PlayerBuilder::new()
+            .with_log(Box::new(CategorizedLogBackend(output.clone())))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            trace(&mut activation, None, &["hello".into(), 2.into()]).unwrap();
+        });
+
+        assert_eq!(
+            *output.lock().unwrap(),
+            vec!["[avm-trace] hello 2".to_string()]
+        );
+    }
+}
@@ -0,0 +1,429 @@
+//! `Error` impl
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::Class;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::string::AvmString;
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use gc_arena::{GcCell, MutationContext};
+use std::fmt::{self, Write};
+
+/// A runtime error that the AVM2 runtime can raise.
+///
+/// The ids and messages match the ActionScript 3.0 run-time error reference,
+/// so that scripts inspecting `errorID` or `message` see what Flash Player
+/// would give them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ErrorDef {
+    /// The error number, as printed after `Error #`.
+    pub id: i32,
+
+    /// The name of the error class that gets thrown, such as `TypeError`.
+    pub class_name: &'static str,
+
+    /// The message template, with a `{}` placeholder for each argument.
+    pub message: &'static str,
+}
+
+impl ErrorDef {
+    /// Format this error as Flash Player prints it, e.g.
+    /// `ReferenceError: Error #1065: Variable foo is not defined.`
+    pub fn format(&self, args: &[&dyn fmt::Display]) -> String {
+        format!("{}: {}", self.class_name, self.message(args))
+    }
+
+    /// The `message` of a thrown instance of this error, e.g.
+    /// `Error #1065: Variable foo is not defined.`
+    pub fn message(&self, args: &[&dyn fmt::Display]) -> String {
+        format!("Error #{}: {}", self.id, format_message(self.message, args))
+    }
+
+    /// Construct the error object that a script sees for this error.
+    ///
+    /// Only `Error` itself is implemented, so the instance gets the name of
+    /// the actual error class instead.
+    pub fn construct<'gc>(
+        &self,
+        activation: &mut Activation<'_, 'gc, '_>,
+        args: &[&dyn fmt::Display],
+    ) -> Result<Object<'gc>, Error> {
+        let mut ctor = activation
+            .context
+            .avm2
+            .global_domain()
+            .get_defined_value(activation, QName::new(Namespace::public(), "Error"))?
+            .coerce_to_object(activation)?;
+        let proto = ctor
+            .get_property(
+                ctor,
+                &QName::new(Namespace::public(), "prototype"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let message = AvmString::new(activation.context.gc_context, self.message(args));
+        let ctor_args = [message.into()];
+        let mut error = proto.construct(activation, &ctor_args)?;
+        ctor.call(Some(error), &ctor_args, activation, error.proto())?;
+        error.set_property(
+            error,
+            &QName::new(Namespace::public(), "name"),
+            self.class_name.into(),
+            activation,
+        )?;
+
+        Ok(error)
+    }
+}
+
+/// Substitute positional arguments into an error message template.
+///
+/// Each `{}` in the template is replaced by the next argument in turn. Any
+/// placeholders left over once the arguments run out are kept as-is.
+pub fn format_message(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut pieces = template.split("{}");
+    let mut args = args.iter();
+    let mut message = String::with_capacity(template.len());
+    message.push_str(pieces.next().unwrap_or_default());
+    for piece in pieces {
+        match args.next() {
+            Some(arg) => {
+                let _ = write!(message, "{}", arg);
+            }
+            None => message.push_str("{}"),
+        }
+        message.push_str(piece);
+    }
+    message
+}
+
+macro_rules! error_defs {
+    ($($name:ident = $id:literal, $class_name:literal, $message:literal;)*) => {
+        $(
+            pub const $name: ErrorDef = ErrorDef {
+                id: $id,
+                class_name: $class_name,
+                message: $message,
+            };
+        )*
+
+        /// Every runtime error known to Ruffle, ordered by id.
+        pub const ERROR_DEFS: &[ErrorDef] = &[$($name),*];
+    };
+}
+
+error_defs! {
+    ERROR_1000 = 1000, "Error", "The system is out of memory.";
+    ERROR_1001 = 1001, "Error", "The method {} is not implemented.";
+    ERROR_1002 = 1002, "RangeError", "The precision argument must be between {} and {}; {} is invalid.";
+    ERROR_1003 = 1003, "RangeError", "The radix argument must be between {} and {}; {} is invalid.";
+    ERROR_1004 = 1004, "TypeError", "Method {} was invoked on an incompatible object.";
+    ERROR_1005 = 1005, "RangeError", "Array index is not a positive integer ({}).";
+    ERROR_1006 = 1006, "TypeError", "{} is not a function.";
+    ERROR_1007 = 1007, "TypeError", "Instantiation attempted on a non-constructor.";
+    ERROR_1009 = 1009, "TypeError", "Cannot access a property or method of a null object reference.";
+    ERROR_1010 = 1010, "TypeError", "A term is undefined and has no properties.";
+    ERROR_1014 = 1014, "VerifyError", "Class {} could not be found.";
+    ERROR_1023 = 1023, "StackOverflowError", "Stack overflow occurred.";
+    ERROR_1034 = 1034, "TypeError", "Type Coercion failed: cannot convert {} to {}.";
+    ERROR_1037 = 1037, "ReferenceError", "Cannot assign to a method {} on {}.";
+    ERROR_1040 = 1040, "TypeError", "The right-hand side of instanceof must be a class or function.";
+    ERROR_1041 = 1041, "TypeError", "The right-hand side of operator must be a class.";
+    ERROR_1056 = 1056, "ReferenceError", "Cannot create property {} on {}.";
+    ERROR_1063 = 1063, "ArgumentError", "Argument count mismatch on {}. Expected {}, got {}.";
+    ERROR_1065 = 1065, "ReferenceError", "Variable {} is not defined.";
+    ERROR_1069 = 1069, "ReferenceError", "Property {} not found on {} and there is no default value.";
+    ERROR_1074 = 1074, "ReferenceError", "Illegal write to read-only property {} on {}.";
+    ERROR_1076 = 1076, "TypeError", "Math is not a constructor.";
+    ERROR_1077 = 1077, "ReferenceError", "Illegal read of write-only property {} on {}.";
+    ERROR_1115 = 1115, "TypeError", "{} is not a constructor.";
+    ERROR_1125 = 1125, "RangeError", "The index {} is out of range {}.";
+    ERROR_2007 = 2007, "TypeError", "Parameter {} must be non-null.";
+    ERROR_2108 = 2108, "ArgumentError", "Scene {} was not found.";
+    ERROR_2109 = 2109, "ArgumentError", "Frame label {} not found in scene {}.";
+}
+
+/// Look up a runtime error by its id.
+///
+/// The runtime throws errors through their `ERROR_*` constants, so this is
+/// only needed where an id arrives as data. Nothing does that yet, as
+/// `flash.utils.getDefinitionByName` is not implemented.
+pub fn error_def_by_id(id: i32) -> Option<ErrorDef> {
+    ERROR_DEFS
+        .binary_search_by_key(&id, |def| def.id)
+        .ok()
+        .map(|index| ERROR_DEFS[index])
+}
+
+/// Implements `Error`'s instance initializer.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let message: Value<'gc> = match args.get(0) {
+            Some(Value::Undefined) | None => "".into(),
+            Some(message) => message.coerce_to_string(activation)?.into(),
+        };
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "message"),
+            message,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "name"),
+            "Error".into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error`'s class initializer.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `Error.getStackTrace`
+///
+/// Release builds of Flash Player don't keep stack traces, and always return
+/// `null` here.
+pub fn get_stack_trace<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Null)
+}
+
+/// Implements `Error.toString`
+///
+/// This is also what string coercions of errors end up calling.
+pub fn to_string<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let name = this
+            .get_property(this, &QName::new(Namespace::public(), "name"), activation)?
+            .coerce_to_string(activation)?;
+        let message = this
+            .get_property(
+                this,
+                &QName::new(Namespace::public(), "message"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+
+        // An empty name or message is left out, along with the separator.
+        if message.is_empty() {
+            return Ok(name.into());
+        }
+        if name.is_empty() {
+            return Ok(message.into());
+        }
+
+        return Ok(AvmString::new(
+            activation.context.gc_context,
+            format!("{}: {}", name, message),
+        )
+        .into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Error`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::public(), "Error"),
+        Some(QName::new(Namespace::public(), "Object").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    const ITEMS: &[&str] = &["message", "name"];
+    for &name in ITEMS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), "String").into(),
+            None,
+        ));
+    }
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] =
+        &[("getStackTrace", get_stack_trace), ("toString", to_string)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::PlayerBuilder;
+
+    #[test]
+    fn constructs_with_message() {
+        let player = PlayerBuilder::new().build().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let mut ctor = activation
+                .context
+                .avm2
+                .global_domain()
+                .get_defined_value(&mut activation, QName::new(Namespace::public(), "Error"))
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let proto = ctor
+                .get_property(
+                    ctor,
+                    &QName::new(Namespace::public(), "prototype"),
+                    &mut activation,
+                )
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let args = ["Something broke".into()];
+            let mut error = proto.construct(&mut activation, &args).unwrap();
+            ctor.call(Some(error), &args, &mut activation, error.proto())
+                .unwrap();
+
+            let mut get = |name: &'static str| {
+                error
+                    .get_property(
+                        error,
+                        &QName::new(Namespace::public(), name),
+                        &mut activation,
+                    )
+                    .unwrap()
+            };
+            let message = get("message");
+            let name = get("name");
+            let get_stack_trace = get("getStackTrace");
+            let to_string = get("toString");
+
+            for (value, expected) in &[(message, "Something broke"), (name, "Error")] {
+                assert_eq!(
+                    value.coerce_to_string(&mut activation).unwrap().to_string(),
+                    *expected
+                );
+            }
+
+            let stack_trace = get_stack_trace
+                .coerce_to_object(&mut activation)
+                .unwrap()
+                .call(Some(error), &[], &mut activation, None)
+                .unwrap();
+            assert!(matches!(stack_trace, Value::Null));
+
+            let string = to_string
+                .coerce_to_object(&mut activation)
+                .unwrap()
+                .call(Some(error), &[], &mut activation, None)
+                .unwrap()
+                .coerce_to_string(&mut activation)
+                .unwrap();
+            assert_eq!(string.to_string(), "Error: Something broke");
+        });
+    }
+
+    #[test]
+    fn to_string_leaves_out_empty_parts() {
+        let player = PlayerBuilder::new().build().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let mut ctor = activation
+                .context
+                .avm2
+                .global_domain()
+                .get_defined_value(&mut activation, QName::new(Namespace::public(), "Error"))
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let proto = ctor
+                .get_property(
+                    ctor,
+                    &QName::new(Namespace::public(), "prototype"),
+                    &mut activation,
+                )
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let args = ["boom".into()];
+            let mut error = proto.construct(&mut activation, &args).unwrap();
+            ctor.call(Some(error), &args, &mut activation, error.proto())
+                .unwrap();
+
+            let mut set_and_coerce = |name: &'static str, value: &'static str| {
+                error
+                    .set_property(
+                        error,
+                        &QName::new(Namespace::public(), name),
+                        value.into(),
+                        &mut activation,
+                    )
+                    .unwrap();
+                Value::Object(error)
+                    .coerce_to_string(&mut activation)
+                    .unwrap()
+                    .to_string()
+            };
+
+            assert_eq!(set_and_coerce("name", "Error"), "Error: boom");
+            assert_eq!(set_and_coerce("name", ""), "boom");
+            assert_eq!(set_and_coerce("message", ""), "");
+            assert_eq!(set_and_coerce("name", "Error"), "Error");
+        });
+    }
+
+    #[test]
+    fn error_defs_are_ordered_by_id() {
+        for pair in ERROR_DEFS.windows(2) {
+            assert!(pair[0].id < pair[1].id);
+        }
+        for def in ERROR_DEFS {
+            assert_eq!(error_def_by_id(def.id), Some(*def));
+        }
+        assert_eq!(error_def_by_id(1069), Some(ERROR_1069));
+        assert_eq!(error_def_by_id(1008), None);
+    }
+
+    #[test]
+    fn format_message_substitutes_arguments_in_order() {
+        assert_eq!(
+            format_message("Expected {}, got {}.", &[&1, &"two"]),
+            "Expected 1, got two."
+        );
+        assert_eq!(format_message("{} and {}", &[&"a"]), "a and {}");
+        assert_eq!(format_message("No arguments.", &[&"a"]), "No arguments.");
+    }
+
+    #[test]
+    fn missing_property_is_a_reference_error() {
+        assert_eq!(
+            ERROR_1069.format(&[&"foo", &"Bar"]),
+            "ReferenceError: Error #1069: Property foo not found on Bar and there is no default value."
+        );
+    }
+}
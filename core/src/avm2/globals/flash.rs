@@ -4,6 +4,7 @@ pub mod display;
 pub mod events;
 pub mod geom;
 pub mod media;
+pub mod net;
 pub mod system;
 pub mod text;
 pub mod utils;
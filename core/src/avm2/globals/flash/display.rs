@@ -9,6 +9,7 @@ pub mod graphics;
 pub mod interactiveobject;
 pub mod jointstyle;
 pub mod linescalemode;
+pub mod loader;
 pub mod loaderinfo;
 pub mod movieclip;
 pub mod scene;
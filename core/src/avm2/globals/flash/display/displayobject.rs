@@ -452,6 +452,38 @@ pub fn set_visible<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `cacheAsBitmap`'s getter.
+pub fn cache_as_bitmap<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        return Ok(dobj.cache_as_bitmap().into());
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `cacheAsBitmap`'s setter.
+pub fn set_cache_as_bitmap<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(dobj) = this.and_then(|this| this.as_display_object()) {
+        let cache_as_bitmap = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_boolean();
+
+        dobj.set_cache_as_bitmap(activation.context.gc_context, cache_as_bitmap);
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Implements `mouseX`.
 pub fn mouse_x<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
@@ -611,6 +643,11 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
         ("root", Some(root), None),
         ("stage", Some(stage), None),
         ("visible", Some(visible), Some(set_visible)),
+        (
+            "cacheAsBitmap",
+            Some(cache_as_bitmap),
+            Some(set_cache_as_bitmap),
+        ),
         ("mouseX", Some(mouse_x), None),
         ("mouseY", Some(mouse_y), None),
         ("loaderInfo", Some(loader_info), None),
@@ -0,0 +1,231 @@
+//! `flash.display.Loader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::RequestOptions;
+use crate::display_object::{
+    DisplayObject, Lists, MovieClip, TDisplayObject, TDisplayObjectContainer,
+};
+use crate::loader::MovieLoaderEventHandler;
+use crate::security;
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use gc_arena::{GcCell, MutationContext};
+use std::sync::Arc;
+
+/// Implements `flash.display.Loader`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let mut proto = this
+                .proto()
+                .ok_or("Attempted to construct bare-object Loader")?;
+            let constr = proto
+                .get_property(proto, &QName::dynamic_name("constructor"), activation)?
+                .coerce_to_object(activation)?;
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let new_do = MovieClip::new_with_avm2(
+                SwfSlice::empty(movie),
+                this,
+                constr,
+                activation.context.gc_context,
+            );
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
+
+        // `LoaderInfo` cannot be constructed by user code, so we bypass its
+        // constructor and initialize it as a bare `EventDispatcher`.
+        let loaderinfo_proto = activation.context.avm2.prototypes().loaderinfo;
+        let loader_info = loaderinfo_proto.construct(activation, &[])?;
+        let mut dispatcher_proto = loaderinfo_proto
+            .proto()
+            .ok_or("LoaderInfo is not an EventDispatcher")?;
+        let dispatcher_constr = dispatcher_proto
+            .get_property(
+                loader_info,
+                &QName::new(Namespace::public(), "constructor"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        dispatcher_constr.call(Some(loader_info), &[], activation, Some(dispatcher_proto))?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "contentLoaderInfo"),
+            loader_info.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.display.Loader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `contentLoaderInfo`'s getter.
+pub fn content_loader_info<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "contentLoaderInfo"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `content`'s getter.
+pub fn content<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(ctr) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_container())
+    {
+        if let Some(content) = ctr.child_by_index(0) {
+            return Ok(content.object2());
+        }
+    }
+
+    Ok(Value::Null)
+}
+
+/// Implements `Loader.load`.
+///
+/// The loaded movie replaces any previously loaded content. Progress and
+/// errors are reported as events on `contentLoaderInfo`.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(mut ctr) = this
+            .as_display_object()
+            .and_then(|dobj| dobj.as_container())
+        {
+            let mut request = args
+                .get(0)
+                .cloned()
+                .unwrap_or(Value::Undefined)
+                .coerce_to_object(activation)?;
+            let url = request
+                .get_property(request, &QName::new(Namespace::public(), "url"), activation)?
+                .coerce_to_string(activation)?;
+            let loader_info =
+                content_loader_info(activation, Some(this), &[])?.coerce_to_object(activation)?;
+
+            unload(activation, Some(this), &[])?;
+
+            let loading_movie = DisplayObject::from(ctr)
+                .movie()
+                .unwrap_or_else(|| activation.context.swf.clone());
+            let movie = Arc::new(SwfMovie::empty(activation.context.swf.version()));
+            let content: DisplayObject<'gc> =
+                MovieClip::new(SwfSlice::empty(movie), activation.context.gc_context).into();
+            ctr.insert_at_index(&mut activation.context, content, 0);
+
+            let fetch = security::fetch(
+                &mut activation.context,
+                &loading_movie,
+                &url,
+                RequestOptions::get(),
+            );
+            let process = activation.context.load_manager.load_movie_into_clip(
+                activation.context.player.clone().unwrap(),
+                content,
+                fetch,
+                url.to_string(),
+                None,
+                Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)),
+            );
+
+            activation.context.navigator.spawn_future(process);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `Loader.unload`.
+pub fn unload<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut ctr) = this
+        .and_then(|this| this.as_display_object())
+        .and_then(|dobj| dobj.as_container())
+    {
+        while let Some(child) = ctr.child_by_index(0) {
+            ctr.remove_child(&mut activation.context, child, Lists::all());
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `Loader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.display"), "Loader"),
+        Some(
+            QName::new(
+                Namespace::package("flash.display"),
+                "DisplayObjectContainer",
+            )
+            .into(),
+        ),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(&str, Option<NativeMethod>, Option<NativeMethod>)] = &[
+        ("content", Some(content), None),
+        ("contentLoaderInfo", Some(content_loader_info), None),
+    ];
+    write.define_public_builtin_instance_properties(PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] = &[("load", load), ("unload", unload)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "contentLoaderInfo"),
+        QName::new(Namespace::package("flash.display"), "LoaderInfo").into(),
+        None,
+    ));
+
+    class
+}
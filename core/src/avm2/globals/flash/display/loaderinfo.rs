@@ -208,10 +208,11 @@ pub fn height<'gc>(
 
 /// `isURLInaccessible` getter stub
 pub fn is_url_inaccessible<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    avm2_stub!(activation, "LoaderInfo.isURLInaccessible");
     Ok(false.into())
 }
 
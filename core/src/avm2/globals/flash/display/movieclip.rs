@@ -3,6 +3,7 @@
 use crate::avm2::activation::Activation;
 use crate::avm2::array::ArrayStorage;
 use crate::avm2::class::Class;
+use crate::avm2::globals::error::{ERROR_2108, ERROR_2109};
 use crate::avm2::globals::flash::display::{framelabel, scene};
 use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
@@ -381,39 +382,35 @@ pub fn goto_frame<'gc>(
     let frame_or_label = args.get(0).cloned().unwrap_or(Value::Null);
 
     let scene = match args.get(1).cloned().unwrap_or(Value::Null) {
-        Value::Null => None,
-        v => mc
-            .scene_label_to_number(&v.coerce_to_string(activation)?)
-            .map(|v| v.saturating_sub(1)),
-    }
-    .unwrap_or(0) as u32;
+        Value::Null | Value::Undefined => None,
+        v => {
+            let scene_label = v.coerce_to_string(activation)?;
+            let scene = mc
+                .scene(&scene_label)
+                .ok_or_else(|| ERROR_2108.format(&[&scene_label]))?;
+
+            Some(scene)
+        }
+    };
+    let scene_offset = scene
+        .as_ref()
+        .map(|scene| scene.start.saturating_sub(1))
+        .unwrap_or(0) as u32;
     let frame = match frame_or_label {
-        Value::Integer(i) => i as u32 + scene,
-        Value::Unsigned(i) => i + scene,
+        Value::Integer(i) => i as u32 + scene_offset,
+        Value::Unsigned(i) => i + scene_offset,
         frame_or_label => {
             let frame_or_label = frame_or_label.coerce_to_string(activation)?;
             if let Ok(frame) = frame_or_label.parse::<u32>() {
-                frame + scene
+                frame + scene_offset
             } else {
-                if let Some(scene) = args.get(1).cloned() {
-                    //If the user specified a scene, we need to validate that
-                    //the requested frame exists within that scene.
-                    let scene = scene.coerce_to_string(activation)?;
-                    if !mc.frame_exists_within_scene(&frame_or_label, &scene) {
-                        return Err(format!(
-                            "ArgumentError: Frame label {} not found in scene {}",
-                            frame_or_label, scene
-                        )
-                        .into());
-                    }
-                }
+                // Labels are looked up in the requested scene, or else the
+                // current one, as different scenes may reuse a label.
+                let scene = scene.or_else(|| mc.current_scene()).unwrap_or_default();
 
-                mc.frame_label_to_number(&frame_or_label).ok_or_else(|| {
-                    format!(
-                        "ArgumentError: {} is not a valid frame label.",
-                        frame_or_label
-                    )
-                })? as u32
+                mc.frame_label_in_scene(&frame_or_label, &scene)
+                    .ok_or_else(|| ERROR_2109.format(&[&frame_or_label, &scene.name]))?
+                    as u32
             }
         }
     };
@@ -574,3 +571,82 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::goto_frame;
+    use crate::avm2::activation::Activation;
+    use crate::avm2::value::Value;
+    use crate::display_object::{MovieClip, TDisplayObject};
+    use crate::player::PlayerBuilder;
+    use crate::tag_utils::SwfMovie;
+    use crate::test_utils::{swf_header, swf_movie};
+    use std::sync::Arc;
+
+    /// Builds a movie with scenes "One" and "Two" of two frames each.
+    ///
+    /// Both scenes label their first frame "start". The second frames are
+    /// labelled "end" and "End" respectively.
+    fn labelled_movie() -> SwfMovie {
+        let label = |frame_num, label| swf::FrameLabelData {
+            frame_num,
+            label: swf::SwfStr::from_utf8_str(label),
+        };
+        let mut tags = vec![swf::Tag::DefineSceneAndFrameLabelData(
+            swf::DefineSceneAndFrameLabelData {
+                scenes: vec![label(0, "One"), label(2, "Two")],
+                frame_labels: vec![
+                    label(0, "start"),
+                    label(1, "end"),
+                    label(2, "start"),
+                    label(3, "End"),
+                ],
+            },
+        )];
+        tags.extend((0..4).map(|_| swf::Tag::ShowFrame));
+        swf_movie(swf_header(10, 10.0, 4), tags)
+    }
+
+    /// Calls `gotoAndStop` with `args`, returning the frame it stopped on.
+    fn goto_and_stop<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        mc: MovieClip<'gc>,
+        args: &[Value<'gc>],
+    ) -> Option<u16> {
+        goto_frame(activation, mc, args, true).ok()?;
+        Some(mc.current_frame())
+    }
+
+    #[test]
+    fn goto_label_resolves_within_scene() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(labelled_movie()));
+
+        player.update(|context| {
+            let mc = context.stage.root_clip().as_movie_clip().unwrap();
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let activation = &mut activation;
+
+            // Numeric strings are frame numbers, not labels.
+            assert_eq!(goto_and_stop(activation, mc, &["3".into()]), Some(3));
+
+            // Labels are looked up in the current scene, case sensitively.
+            assert_eq!(goto_and_stop(activation, mc, &["start".into()]), Some(3));
+            assert_eq!(goto_and_stop(activation, mc, &["End".into()]), Some(4));
+            assert_eq!(goto_and_stop(activation, mc, &["end".into()]), None);
+            assert_eq!(mc.current_frame(), 4);
+
+            // An explicit scene overrides the current one.
+            assert_eq!(
+                goto_and_stop(activation, mc, &["end".into(), "One".into()]),
+                Some(2)
+            );
+            assert_eq!(goto_and_stop(activation, mc, &["start".into()]), Some(1));
+            assert_eq!(
+                goto_and_stop(activation, mc, &["start".into(), "Three".into()]),
+                None
+            );
+        });
+    }
+}
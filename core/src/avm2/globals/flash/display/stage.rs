@@ -435,6 +435,9 @@ pub fn frame_rate<'gc>(
 }
 
 /// Implement `frameRate`'s setter
+///
+/// Flash Player clamps the frame rate to between 0.01 and 1000 FPS. The new
+/// rate takes effect on the player's next tick.
 pub fn set_frame_rate<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
@@ -445,7 +448,9 @@ pub fn set_frame_rate<'gc>(
         .cloned()
         .unwrap_or(Value::Undefined)
         .coerce_to_number(activation)?;
-    *activation.context.frame_rate = new_frame_rate;
+    if !new_frame_rate.is_nan() {
+        *activation.context.frame_rate = new_frame_rate.max(0.01).min(1000.0);
+    }
 
     Ok(Value::Undefined)
 }
@@ -565,10 +570,11 @@ pub fn set_stage_height<'gc>(
 ///
 /// TODO: This is a stub.
 pub fn allows_full_screen<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    avm2_stub!(activation, "Stage.allowsFullScreen");
     Ok(true.into())
 }
 
@@ -576,22 +582,50 @@ pub fn allows_full_screen<'gc>(
 ///
 /// TODO: This is a stub.
 pub fn allows_full_screen_interactive<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
+    avm2_stub!(activation, "Stage.allowsFullScreenInteractive");
     Ok(false.into())
 }
 
 /// Implement `quality`'s getter
-///
-/// TODO: This is a stub.
 pub fn quality<'gc>(
-    _activation: &mut Activation<'_, 'gc, '_>,
+    activation: &mut Activation<'_, 'gc, '_>,
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Ok("HIGH".into())
+    let quality = AvmString::new(
+        activation.context.gc_context,
+        activation.context.stage.quality().to_string(),
+    );
+    Ok(quality.into())
+}
+
+/// Implement `quality`'s setter
+pub fn set_quality<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Ok(quality) = args
+        .get(0)
+        .unwrap_or(&Value::Undefined)
+        .coerce_to_string(activation)?
+        .parse()
+    {
+        activation
+            .context
+            .stage
+            .set_quality(&mut activation.context, quality);
+    } else {
+        return Err(
+            "ArgumentError: Error #2008: Parameter quality must be one of the accepted values."
+                .into(),
+        );
+    }
+    Ok(Value::Undefined)
 }
 
 /// Construct `Stage`'s class.
@@ -690,9 +724,90 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
             Some(allows_full_screen_interactive),
             None,
         ),
-        ("quality", Some(quality), None),
+        ("quality", Some(quality), Some(set_quality)),
     ];
     write.define_public_builtin_instance_properties(PUBLIC_INSTANCE_PROPERTIES);
 
     class
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{allows_full_screen, color, set_color, set_frame_rate};
+    use crate::avm2::activation::Activation;
+    use crate::avm2::value::Value;
+    use crate::backend::render::RecordingRenderer;
+    use crate::display_object::TDisplayObject;
+    use crate::player::{Player, PlayerBuilder};
+    use std::time::Duration;
+    use swf::Color;
+
+    #[test]
+    fn setting_frame_rate_updates_player() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+
+        let mut set_stage_frame_rate = |frame_rate: f64| {
+            player.update(|context| {
+                let mut activation = Activation::from_nothing(context.reborrow());
+                set_frame_rate(&mut activation, None, &[Value::Number(frame_rate)]).unwrap();
+            });
+            player.frame_rate()
+        };
+
+        assert_eq!(set_stage_frame_rate(60.0), 60.0);
+        assert_eq!(set_stage_frame_rate(5000.0), 1000.0);
+        assert_eq!(set_stage_frame_rate(0.0), 0.01);
+        assert_eq!(set_stage_frame_rate(f64::NAN), 0.01);
+
+        set_stage_frame_rate(50.0);
+        assert_eq!(player.time_til_next_frame(), Duration::from_millis(20));
+    }
+
+    #[test]
+    fn setting_color_changes_next_clear() {
+        let player = PlayerBuilder::new()
+            .with_renderer(Box::new(RecordingRenderer::new()))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+
+        let stage_color = player.update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let stage = match activation.context.stage.object2() {
+                Value::Object(stage) => stage,
+                _ => panic!("Stage should have an AVM2 object"),
+            };
+            set_color(&mut activation, Some(stage), &[0x336699.into()]).unwrap();
+            color(&mut activation, Some(stage), &[])
+                .unwrap()
+                .coerce_to_u32(&mut activation)
+                .unwrap()
+        });
+        assert_eq!(stage_color, 0xFF336699);
+
+        player.render();
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        assert_eq!(renderer.clear_color, Some(Color::from_rgb(0x336699, 255)));
+    }
+
+    #[test]
+    fn stubbed_getters_are_reported() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        assert!(player.diagnostics_report().is_empty());
+
+        player.update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            allows_full_screen(&mut activation, None, &[]).unwrap();
+        });
+        let report = player.diagnostics_report();
+        assert_eq!(
+            report.unimplemented_builtins().collect::<Vec<_>>(),
+            vec!["Stage.allowsFullScreen"]
+        );
+    }
+}
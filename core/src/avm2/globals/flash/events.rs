@@ -1,5 +1,11 @@
 //! `flash.events` namespace
 
+pub mod errorevent;
 pub mod event;
 pub mod eventdispatcher;
 pub mod ieventdispatcher;
+pub mod ioerrorevent;
+pub mod netstatusevent;
+pub mod progressevent;
+pub mod securityerrorevent;
+pub mod textevent;
@@ -143,14 +143,13 @@ pub fn clone<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(evt) = this.unwrap().as_event() {
-        let evt_proto = activation.avm2().system_prototypes.as_ref().unwrap().event;
+        // Keep the subclass, so that e.g. a cloned `ProgressEvent` still has
+        // its `bytesLoaded`.
+        let evt_proto = this.unwrap().proto();
 
-        return Ok(EventObject::from_event(
-            activation.context.gc_context,
-            Some(evt_proto),
-            evt.clone(),
-        )
-        .into());
+        return Ok(
+            EventObject::from_event(activation.context.gc_context, evt_proto, evt.clone()).into(),
+        );
     }
 
     Ok(Value::Undefined)
@@ -2,21 +2,45 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
-use crate::avm2::method::Method;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
-use crate::avm2::object::Object;
+use crate::avm2::object::{Object, TObject};
 use crate::avm2::value::Value;
 use crate::avm2::Error;
+use crate::display_object::{TDisplayObject, Video};
 use gc_arena::{GcCell, MutationContext};
 
 /// Implements `flash.media.Video`'s instance constructor.
 pub fn instance_init<'gc>(
     activation: &mut Activation<'_, 'gc, '_>,
     this: Option<Object<'gc>>,
-    _args: &[Value<'gc>],
+    args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     if let Some(this) = this {
         activation.super_init(this, &[])?;
+
+        if this.as_display_object().is_none() {
+            let width = args
+                .get(0)
+                .cloned()
+                .unwrap_or_else(|| 320.into())
+                .coerce_to_i32(activation)?;
+            let height = args
+                .get(1)
+                .cloned()
+                .unwrap_or_else(|| 240.into())
+                .coerce_to_i32(activation)?;
+
+            let mut new_do = Video::new(
+                activation.context.gc_context,
+                width.max(0) as u16,
+                height.max(0) as u16,
+            );
+            new_do.set_object2(activation.context.gc_context, this);
+
+            this.init_display_object(activation.context.gc_context, new_do.into());
+        }
     }
 
     Ok(Value::Undefined)
@@ -31,11 +55,34 @@ pub fn class_init<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implements `Video.attachNetStream`.
+///
+/// Video the stream has already loaded is not shown; only subsequent calls
+/// to `play` feed this player.
+pub fn attach_net_stream<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(Value::Object(mut net_stream)) = args.get(0).cloned() {
+            net_stream.set_property(
+                net_stream,
+                &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "video"),
+                this.into(),
+                activation,
+            )?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
 /// Construct `Video`'s class.
 pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
     let class = Class::new(
         QName::new(Namespace::package("flash.media"), "Video"),
-        Some(QName::new(Namespace::package("flash.media"), "DisplayObject").into()),
+        Some(QName::new(Namespace::package("flash.display"), "DisplayObject").into()),
         Method::from_builtin(instance_init),
         Method::from_builtin(class_init),
         mc,
@@ -45,5 +92,9 @@ pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>
 
     write.set_attributes(ClassAttributes::SEALED);
 
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] =
+        &[("attachNetStream", attach_net_stream)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
     class
 }
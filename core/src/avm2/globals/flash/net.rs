@@ -0,0 +1,8 @@
+//! `flash.net` namespace
+
+pub mod netconnection;
+pub mod netstream;
+pub mod urlloader;
+pub mod urlloaderdataformat;
+pub mod urlrequest;
+pub mod urlvariables;
@@ -0,0 +1,227 @@
+//! `flash.net.NetConnection` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::events::Event;
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::{Avm2, Error};
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.NetConnection`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+            false.into(),
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetConnection`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.connect`.
+///
+/// Only `null` connections, which play local files and progressive
+/// downloads, are supported. Connections to Flash Media Servers fail.
+pub fn connect<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let command = args.get(0).cloned().unwrap_or(Value::Null);
+        let connected = matches!(command, Value::Null | Value::Undefined);
+
+        let event = if connected {
+            Event::net_status(activation, "NetConnection.Connect.Success", "status")?
+        } else {
+            let command = command.coerce_to_string(activation)?;
+            log::warn!(
+                "NetConnection.connect: Connecting to {} is not supported",
+                command
+            );
+            Event::net_status(activation, "NetConnection.Connect.Failed", "error")?
+        };
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+            connected.into(),
+            activation,
+        )?;
+        Avm2::dispatch_event(&mut activation.context, event, this)?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetConnection.close`.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        let was_connected = this
+            .get_property(
+                this,
+                &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+                activation,
+            )?
+            .coerce_to_boolean();
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+            false.into(),
+            activation,
+        )?;
+
+        if was_connected {
+            let event = Event::net_status(activation, "NetConnection.Connect.Closed", "status")?;
+            Avm2::dispatch_event(&mut activation.context, event, this)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `connected`'s getter.
+pub fn connected<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        return this.get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+            activation,
+        );
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetConnection`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetConnection"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_PROPERTIES: &[(&str, Option<NativeMethod>, Option<NativeMethod>)] =
+        &[("connected", Some(connected), None)];
+    write.define_public_builtin_instance_properties(PUBLIC_INSTANCE_PROPERTIES);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] =
+        &[("close", close), ("connect", connect)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connected"),
+        QName::new(Namespace::public(), "Boolean").into(),
+        None,
+    ));
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::{Object, TObject};
+    use crate::avm2::test_utils::{add_listener, call_method, construct};
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::TraceLogBackend;
+    use std::sync::{Arc, Mutex};
+
+    /// Event listener that traces the status event's `info.code`.
+    fn trace_code<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let mut info = event
+            .get_property(event, &QName::new(Namespace::public(), "info"), activation)?
+            .coerce_to_object(activation)?;
+        let code = info
+            .get_property(info, &QName::new(Namespace::public(), "code"), activation)?
+            .coerce_to_string(activation)?;
+
+        activation.context.log.avm_trace(&code);
+
+        Ok(Value::Undefined)
+    }
+
+    /// Connects a new `NetConnection` to `command`, returning the status
+    /// codes it dispatched.
+    fn connect(command: Option<&'static str>) -> Vec<String> {
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let player = PlayerBuilder::new()
+            .with_log(Box::new(TraceLogBackend(output.clone())))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let connection = construct(&mut activation, "flash.net", "NetConnection", &[]);
+            add_listener(&mut activation, connection, "netStatus", trace_code);
+            let command = command.map(Value::from).unwrap_or(Value::Null);
+            call_method(&mut activation, connection, "connect", &[command]);
+        });
+
+        let output = output.lock().unwrap().clone();
+        output
+    }
+
+    #[test]
+    fn connect_null_succeeds() {
+        assert_eq!(connect(None), vec!["NetConnection.Connect.Success"]);
+    }
+
+    #[test]
+    fn connect_to_server_fails() {
+        assert_eq!(
+            connect(Some("rtmp://example.com/app")),
+            vec!["NetConnection.Connect.Failed"]
+        );
+    }
+}
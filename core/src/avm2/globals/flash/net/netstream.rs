@@ -0,0 +1,272 @@
+//! `flash.net.NetStream` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::NS_RUFFLE_INTERNAL;
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::RequestOptions;
+use crate::display_object::{TDisplayObject, Video};
+use crate::security;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.NetStream`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        let connection = args.get(0).cloned().unwrap_or(Value::Null);
+        this.set_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "connection"),
+            connection,
+            activation,
+        )?;
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.NetStream`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// The `Video` attached to a stream, if any.
+fn attached_video<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut this: Object<'gc>,
+) -> Result<Option<Video<'gc>>, Error> {
+    Ok(this
+        .get_property(
+            this,
+            &QName::new(Namespace::private(NS_RUFFLE_INTERNAL), "video"),
+            activation,
+        )?
+        .coerce_to_object(activation)
+        .ok()
+        .and_then(|video| video.as_display_object())
+        .and_then(|video| video.as_video()))
+}
+
+/// Implements `NetStream.play`.
+///
+/// The FLV file at the given URL is downloaded in full, then played on the
+/// `Video` attached to this stream. Progress is reported as `netStatus`
+/// events.
+pub fn play<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let url = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let video = attached_video(activation, this)?;
+        if let Some(video) = video {
+            video.stop_net_stream(&mut activation.context);
+        }
+
+        // TODO: AVM2 doesn't track which movie the calling code came from,
+        // so loads are sandboxed by the root movie.
+        let movie = activation.context.swf.clone();
+        let fetch = security::fetch(&mut activation.context, &movie, &url, RequestOptions::get());
+        let process = activation.context.load_manager.load_flv_into_net_stream(
+            activation.context.player.clone().unwrap(),
+            this,
+            video,
+            fetch,
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `NetStream.close`.
+///
+/// Playback stops, and the stream's sound is released.
+pub fn close<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        if let Some(video) = attached_video(activation, this)? {
+            video.stop_net_stream(&mut activation.context);
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `NetStream`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "NetStream"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] = &[("close", close), ("play", play)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
+    // Scripts commonly assign a `client` to receive stream metadata.
+    write.define_instance_trait(Trait::from_slot(
+        QName::new(Namespace::public(), "client"),
+        QName::new(Namespace::public(), "Object").into(),
+        None,
+    ));
+
+    const PRIVATE_SLOTS: &[(&str, &str, &str)] = &[
+        ("connection", "flash.net", "NetConnection"),
+        ("video", "flash.media", "Video"),
+    ];
+    for &(name, package, type_name) in PRIVATE_SLOTS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::private(NS_RUFFLE_INTERNAL), name),
+            QName::new(Namespace::package(package), type_name).into(),
+            None,
+        ));
+    }
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::test_utils::{call_method, construct};
+    use crate::avm2::value::Value;
+    use crate::backend::navigator::{NullExecutor, NullNavigatorBackend};
+    use crate::backend::render::{BitmapInfo, RenderBackend};
+    use crate::backend::video::{
+        EncodedFrame, Error, FrameDependency, VideoBackend, VideoStreamHandle,
+    };
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{flv, TempDir};
+    use generational_arena::Arena;
+    use std::sync::{Arc, Mutex};
+    use swf::{VideoCodec, VideoDeblocking};
+
+    /// A video backend which records the streams and frames it is given.
+    struct RecordingVideoBackend {
+        streams: Arena<()>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl VideoBackend for RecordingVideoBackend {
+        fn register_video_stream(
+            &mut self,
+            num_frames: u32,
+            size: (u16, u16),
+            codec: VideoCodec,
+            _filter: VideoDeblocking,
+        ) -> Result<VideoStreamHandle, Error> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("register {} {:?} {:?}", num_frames, size, codec));
+            Ok(self.streams.insert(()))
+        }
+
+        fn preload_video_stream_frame(
+            &mut self,
+            _stream: VideoStreamHandle,
+            encoded_frame: EncodedFrame<'_>,
+        ) -> Result<FrameDependency, Error> {
+            self.log.lock().unwrap().push(format!(
+                "preload {} {:?}",
+                encoded_frame.frame_id, encoded_frame.data
+            ));
+            Ok(FrameDependency::Past)
+        }
+
+        fn decode_video_stream_frame(
+            &mut self,
+            _stream: VideoStreamHandle,
+            encoded_frame: EncodedFrame<'_>,
+            _renderer: &mut dyn RenderBackend,
+        ) -> Result<BitmapInfo, Error> {
+            self.log.lock().unwrap().push(format!(
+                "decode {} {:?}",
+                encoded_frame.frame_id, encoded_frame.data
+            ));
+            Err("Video decoding not implemented".into())
+        }
+    }
+
+    #[test]
+    fn video_is_fed_to_attached_player() {
+        const TAG_VIDEO: u8 = 9;
+
+        let base_path = TempDir::new("net_stream_video");
+        let data = flv(&[
+            (TAG_VIDEO, 0, vec![0x12, 1, 2, 3]),
+            (TAG_VIDEO, 40, vec![0x22, 4, 5]),
+        ]);
+        std::fs::write(base_path.path().join("video.flv"), data).unwrap();
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let (mut executor, channel) = NullExecutor::new();
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(NullNavigatorBackend::with_base_path(
+                base_path.path(),
+                channel,
+            )))
+            .with_video(Box::new(RecordingVideoBackend {
+                streams: Arena::new(),
+                log: log.clone(),
+            }))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let stream = construct(&mut activation, "flash.net", "NetStream", &[Value::Null]);
+            let video = construct(
+                &mut activation,
+                "flash.media",
+                "Video",
+                &[160.into(), 120.into()],
+            );
+            call_method(&mut activation, video, "attachNetStream", &[stream.into()]);
+            call_method(&mut activation, stream, "play", &["video.flv".into()]);
+        });
+
+        executor.block_all().unwrap();
+
+        // Without `onMetaData`, the stream takes the size of the player.
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "register 2 (160, 120) H263",
+                "preload 0 [1, 2, 3]",
+                "preload 1 [4, 5]",
+                "decode 0 [1, 2, 3]",
+            ]
+        );
+    }
+}
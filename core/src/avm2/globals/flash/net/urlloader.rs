@@ -0,0 +1,281 @@
+//! `flash.net.URLLoader` builtin/prototype
+
+use crate::avm2::activation::Activation;
+use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::method::{Method, NativeMethod};
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{Object, TObject};
+use crate::avm2::traits::Trait;
+use crate::avm2::value::Value;
+use crate::avm2::Error;
+use crate::backend::navigator::RequestOptions;
+use crate::security;
+use gc_arena::{GcCell, MutationContext};
+
+/// Implements `flash.net.URLLoader`'s instance constructor.
+pub fn instance_init<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(mut this) = this {
+        activation.super_init(this, &[])?;
+
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "data"),
+            Value::Undefined,
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "dataFormat"),
+            "text".into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "bytesLoaded"),
+            0.into(),
+            activation,
+        )?;
+        this.set_property(
+            this,
+            &QName::new(Namespace::public(), "bytesTotal"),
+            0.into(),
+            activation,
+        )?;
+
+        if let Some(Value::Object(_)) = args.get(0) {
+            load(activation, Some(this), args)?;
+        }
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.net.URLLoader`'s class constructor.
+pub fn class_init<'gc>(
+    _activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    Ok(Value::Undefined)
+}
+
+/// Implements `URLLoader.load`.
+///
+/// The request is fetched asynchronously; its results are delivered as
+/// `complete` or `ioError` events rather than thrown.
+pub fn load<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    if let Some(this) = this {
+        let mut request = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let url = request
+            .get_property(request, &QName::new(Namespace::public(), "url"), activation)?
+            .coerce_to_string(activation)?;
+
+        // TODO: AVM2 doesn't track which movie the calling code came from,
+        // so loads are sandboxed by the root movie.
+        let movie = activation.context.swf.clone();
+        let fetch = security::fetch(&mut activation.context, &movie, &url, RequestOptions::get());
+        let process = activation.context.load_manager.load_data_into_url_loader(
+            activation.context.player.clone().unwrap(),
+            this,
+            fetch,
+            url.to_string(),
+        );
+
+        activation.context.navigator.spawn_future(process);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Construct `URLLoader`'s class.
+pub fn create_class<'gc>(mc: MutationContext<'gc, '_>) -> GcCell<'gc, Class<'gc>> {
+    let class = Class::new(
+        QName::new(Namespace::package("flash.net"), "URLLoader"),
+        Some(QName::new(Namespace::package("flash.events"), "EventDispatcher").into()),
+        Method::from_builtin(instance_init),
+        Method::from_builtin(class_init),
+        mc,
+    );
+
+    let mut write = class.write(mc);
+
+    write.set_attributes(ClassAttributes::SEALED);
+
+    const PUBLIC_INSTANCE_METHODS: &[(&str, NativeMethod)] = &[("load", load)];
+    write.define_public_builtin_instance_methods(PUBLIC_INSTANCE_METHODS);
+
+    const ITEMS: &[(&str, &str)] = &[
+        ("bytesLoaded", "uint"),
+        ("bytesTotal", "uint"),
+        ("data", "Object"),
+        ("dataFormat", "String"),
+    ];
+    for &(name, type_name) in ITEMS {
+        write.define_instance_trait(Trait::from_slot(
+            QName::new(Namespace::public(), name),
+            QName::new(Namespace::public(), type_name).into(),
+            None,
+        ));
+    }
+
+    class
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::{Object, TObject};
+    use crate::avm2::test_utils::{add_listener, call_method, construct};
+    use crate::avm2::value::Value;
+    use crate::avm2::Error;
+    use crate::backend::navigator::{NullExecutor, NullNavigatorBackend};
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{TempDir, TraceLogBackend};
+    use std::sync::{Arc, Mutex};
+
+    /// Event listener that traces the event type and the target's `data`,
+    /// described according to the target's `dataFormat`.
+    fn trace_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        let mut event = args
+            .get(0)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_object(activation)?;
+        let event_type = event
+            .get_property(event, &QName::new(Namespace::public(), "type"), activation)?
+            .coerce_to_string(activation)?;
+        let mut target = event
+            .get_property(
+                event,
+                &QName::new(Namespace::public(), "target"),
+                activation,
+            )?
+            .coerce_to_object(activation)?;
+        let data_format = target
+            .get_property(
+                target,
+                &QName::new(Namespace::public(), "dataFormat"),
+                activation,
+            )?
+            .coerce_to_string(activation)?;
+        let data =
+            target.get_property(target, &QName::new(Namespace::public(), "data"), activation)?;
+        let data = match (data_format.as_str(), data) {
+            ("binary", Value::Object(data)) => match data.as_bytearray() {
+                Some(bytes) => format!("{:?}", bytes.bytes()),
+                None => "not a ByteArray".to_string(),
+            },
+            ("variables", Value::Object(mut data)) => {
+                let mut pairs = Vec::new();
+                for &name in &["a", "b"] {
+                    let value = data
+                        .get_property(data, &QName::new(Namespace::public(), name), activation)?
+                        .coerce_to_string(activation)?;
+                    pairs.push(format!("{}={}", name, value));
+                }
+                pairs.join(", ")
+            }
+            (_, data) => data.coerce_to_string(activation)?.to_string(),
+        };
+
+        activation
+            .context
+            .log
+            .avm_trace(&format!("{}: {}", event_type, data));
+
+        Ok(Value::Undefined)
+    }
+
+    /// Loads `url` with a `URLLoader` using the given `dataFormat`, returning
+    /// the events traced by its listeners.
+    fn load_url(url: &'static str, data_format: &'static str) -> Vec<String> {
+        let base_path = TempDir::new(&format!("url_loader_{}_{}", data_format, url));
+        std::fs::write(base_path.path().join("data.txt"), "a=1&b=two").unwrap();
+
+        let output = Arc::new(Mutex::new(Vec::new()));
+        let (mut executor, channel) = NullExecutor::new();
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(NullNavigatorBackend::with_base_path(
+                base_path.path(),
+                channel,
+            )))
+            .with_log(Box::new(TraceLogBackend(output.clone())))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let request = construct(&mut activation, "flash.net", "URLRequest", &[url.into()]);
+            let mut loader = construct(&mut activation, "flash.net", "URLLoader", &[]);
+            loader
+                .set_property(
+                    loader,
+                    &QName::new(Namespace::public(), "dataFormat"),
+                    data_format.into(),
+                    &mut activation,
+                )
+                .unwrap();
+
+            for &event_type in &["progress", "complete", "ioError"] {
+                add_listener(&mut activation, loader, event_type, trace_event);
+            }
+
+            call_method(&mut activation, loader, "load", &[request.into()]);
+        });
+
+        executor.block_all().unwrap();
+
+        let output = output.lock().unwrap().clone();
+        output
+    }
+
+    #[test]
+    fn url_loader_loads_text() {
+        assert_eq!(
+            load_url("data.txt", "text"),
+            vec!["progress: a=1&b=two", "complete: a=1&b=two"]
+        );
+    }
+
+    #[test]
+    fn url_loader_loads_binary() {
+        let bytes = format!("{:?}", b"a=1&b=two");
+        assert_eq!(
+            load_url("data.txt", "binary"),
+            vec![
+                format!("progress: {}", bytes),
+                format!("complete: {}", bytes)
+            ]
+        );
+    }
+
+    #[test]
+    fn url_loader_loads_variables() {
+        assert_eq!(
+            load_url("data.txt", "variables"),
+            vec!["progress: a=1, b=two", "complete: a=1, b=two"]
+        );
+    }
+
+    #[test]
+    fn url_loader_missing_url_dispatches_io_error() {
+        assert_eq!(load_url("missing.txt", "text"), vec!["ioError: undefined"]);
+    }
+}
@@ -1,5 +1,6 @@
 //! `flash.utils` namespace
 
+use crate::avm1::TimerCallback;
 use crate::avm2::{Activation, Error, Object, Value};
 
 pub mod bytearray;
@@ -11,5 +12,175 @@ pub fn get_timer<'gc>(
     _this: Option<Object<'gc>>,
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
-    Ok((activation.context.navigator.time_since_launch().as_millis() as u32).into())
+    Ok((activation.context.clock.time_since_launch().as_millis() as u32).into())
+}
+
+/// Implements `flash.utils.setInterval`
+pub fn set_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    create_timer(activation, args, false)
+}
+
+/// Implements `flash.utils.setTimeout`
+pub fn set_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    create_timer(activation, args, true)
+}
+
+/// Registers `args[0]` to be called after `args[1]` milliseconds, with any
+/// remaining arguments passed on to it.
+fn create_timer<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    args: &[Value<'gc>],
+    is_timeout: bool,
+) -> Result<Value<'gc>, Error> {
+    let callable = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_object(activation)?;
+    let interval = args
+        .get(1)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_i32(activation)?;
+    let params = args.get(2..).unwrap_or_default().to_vec();
+
+    let id = activation.context.timers.add_timer(
+        TimerCallback::Avm2Callable { callable, params },
+        interval,
+        vec![],
+        is_timeout,
+    );
+
+    Ok((id as u32).into())
+}
+
+/// Implements `flash.utils.clearInterval`
+pub fn clear_interval<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let id = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    if !activation.context.timers.remove(id as i32) {
+        log::info!("clearInterval: Timer {} does not exist", id);
+    }
+
+    Ok(Value::Undefined)
+}
+
+/// Implements `flash.utils.clearTimeout`
+pub fn clear_timeout<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    _this: Option<Object<'gc>>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error> {
+    let id = args
+        .get(0)
+        .cloned()
+        .unwrap_or(Value::Undefined)
+        .coerce_to_u32(activation)?;
+    if !activation.context.timers.remove(id as i32) {
+        log::info!("clearTimeout: Timer {} does not exist", id);
+    }
+
+    Ok(Value::Undefined)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm2::FunctionObject;
+    use crate::player::PlayerBuilder;
+    use std::cell::Cell;
+
+    thread_local! {
+        static CALLS: Cell<u32> = Cell::new(0);
+    }
+
+    fn count_call<'gc>(
+        _activation: &mut Activation<'_, 'gc, '_>,
+        _this: Option<Object<'gc>>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Error> {
+        CALLS.with(|calls| calls.set(calls.get() + 1));
+        Ok(Value::Undefined)
+    }
+
+    fn take_calls() -> u32 {
+        CALLS.with(|calls| calls.replace(0))
+    }
+
+    /// Calls `set_timer` with a callback that counts its calls, returning the
+    /// new timer's ID.
+    fn add_counting_timer(
+        player: &mut crate::player::Player,
+        set_timer: crate::avm2::method::NativeMethod,
+        interval: i32,
+    ) -> u32 {
+        player.update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let fn_proto = activation.avm2().prototypes().function;
+            let callback =
+                FunctionObject::from_builtin(activation.context.gc_context, count_call, fn_proto);
+            set_timer(&mut activation, None, &[callback.into(), interval.into()])
+                .unwrap()
+                .coerce_to_u32(&mut activation)
+                .unwrap()
+        })
+    }
+
+    /// Calls `clear_timer` with the given timer ID.
+    fn clear(
+        player: &mut crate::player::Player,
+        clear_timer: crate::avm2::method::NativeMethod,
+        id: u32,
+    ) {
+        player.update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            clear_timer(&mut activation, None, &[id.into()]).unwrap();
+        });
+    }
+
+    #[test]
+    fn timeout_fires_once() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        take_calls();
+
+        add_counting_timer(&mut player, set_timeout, 100);
+        player.update_timers(50.0);
+        assert_eq!(take_calls(), 0);
+        player.update_timers(60.0);
+        assert_eq!(take_calls(), 1);
+        player.update_timers(500.0);
+        assert_eq!(take_calls(), 0);
+    }
+
+    #[test]
+    fn interval_repeats_until_cleared() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        take_calls();
+
+        let id = add_counting_timer(&mut player, set_interval, 100);
+        player.update_timers(110.0);
+        player.update_timers(100.0);
+        assert_eq!(take_calls(), 2);
+
+        clear(&mut player, clear_interval, id);
+        player.update_timers(500.0);
+        assert_eq!(take_calls(), 0);
+    }
 }
@@ -2,6 +2,7 @@
 
 use crate::avm2::activation::Activation;
 use crate::avm2::class::{Class, ClassAttributes};
+use crate::avm2::globals::error::ERROR_1076;
 use crate::avm2::method::{Method, NativeMethod};
 use crate::avm2::names::{Namespace, QName};
 use crate::avm2::object::Object;
@@ -29,7 +30,7 @@ pub fn instance_init<'gc>(
     _args: &[Value<'gc>],
 ) -> Result<Value<'gc>, Error> {
     // TODO: Replace with actual error type.
-    Err("TypeError: Error #1076: Math is not a constructor.".into())
+    Err(ERROR_1076.format(&[]).into())
 }
 
 /// Implements `Math`'s class initializer.
@@ -180,3 +181,43 @@ pub fn random<'gc>(
 ) -> Result<Value<'gc>, Error> {
     Ok(activation.context.rng.gen_range(0.0f64..1.0f64).into())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::avm2::activation::Activation;
+    use crate::avm2::names::{Namespace, QName};
+    use crate::avm2::object::TObject;
+    use crate::player::PlayerBuilder;
+
+    #[test]
+    fn sqrt_of_sixteen() {
+        let player = PlayerBuilder::new().build().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation = Activation::from_nothing(context.reborrow());
+            let mut math = activation
+                .context
+                .avm2
+                .global_domain()
+                .get_defined_value(&mut activation, QName::new(Namespace::public(), "Math"))
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let sqrt = math
+                .get_property(
+                    math,
+                    &QName::new(Namespace::public(), "sqrt"),
+                    &mut activation,
+                )
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let result = sqrt
+                .call(Some(math), &[16.into()], &mut activation, None)
+                .unwrap()
+                .coerce_to_i32(&mut activation)
+                .unwrap();
+            assert_eq!(result, 4);
+        });
+    }
+}
@@ -994,6 +994,11 @@ pub trait TObject<'gc>: 'gc + Collect + Debug + Into<Object<'gc>> + Clone + Copy
     fn as_loader_stream(&self) -> Option<Ref<LoaderStream<'gc>>> {
         None
     }
+
+    /// Associate this object with a loader stream, if it can hold one.
+    ///
+    /// If not, then this function does nothing.
+    fn set_loader_stream(&self, _mc: MutationContext<'gc, '_>, _stream: LoaderStream<'gc>) {}
 }
 
 pub enum ObjectPtr {}
@@ -170,4 +170,8 @@ impl<'gc> TObject<'gc> for LoaderInfoObject<'gc> {
             None
         }
     }
+
+    fn set_loader_stream(&self, mc: MutationContext<'gc, '_>, stream: LoaderStream<'gc>) {
+        self.0.write(mc).loaded_stream = Some(stream);
+    }
 }
@@ -0,0 +1,81 @@
+//! Helpers for tests which drive AVM2 objects from Rust.
+
+use crate::avm2::activation::Activation;
+use crate::avm2::method::NativeMethod;
+use crate::avm2::names::{Namespace, QName};
+use crate::avm2::object::{FunctionObject, Object, TObject};
+use crate::avm2::value::Value;
+
+/// Looks up the class `name` in `package`.
+pub fn class<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+) -> Object<'gc> {
+    activation
+        .context
+        .avm2
+        .global_domain()
+        .get_defined_value(activation, QName::new(Namespace::package(package), name))
+        .unwrap()
+        .coerce_to_object(activation)
+        .unwrap()
+}
+
+/// Constructs an instance of the class `name` in `package`.
+pub fn construct<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    package: &'static str,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Object<'gc> {
+    let ctor = class(activation, package, name);
+    let proto = get(activation, ctor, "prototype")
+        .coerce_to_object(activation)
+        .unwrap();
+    let object = proto.construct(activation, args).unwrap();
+    ctor.call(Some(object), args, activation, object.proto())
+        .unwrap();
+    object
+}
+
+/// Reads the public property `name` of `object`.
+pub fn get<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    mut object: Object<'gc>,
+    name: &'static str,
+) -> Value<'gc> {
+    object
+        .get_property(object, &QName::new(Namespace::public(), name), activation)
+        .unwrap()
+}
+
+/// Calls the public method `name` on `object`.
+pub fn call_method<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    object: Object<'gc>,
+    name: &'static str,
+    args: &[Value<'gc>],
+) -> Value<'gc> {
+    let method = get(activation, object, name)
+        .coerce_to_object(activation)
+        .unwrap();
+    method.call(Some(object), args, activation, None).unwrap()
+}
+
+/// Adds a native event `listener` for `event_type` to `target`.
+pub fn add_listener<'gc>(
+    activation: &mut Activation<'_, 'gc, '_>,
+    target: Object<'gc>,
+    event_type: &'static str,
+    listener: NativeMethod,
+) {
+    let fn_proto = activation.context.avm2.prototypes().function;
+    let listener = FunctionObject::from_builtin(activation.context.gc_context, listener, fn_proto);
+    call_method(
+        activation,
+        target,
+        "addEventListener",
+        &[event_type.into(), listener.into()],
+    );
+}
@@ -26,8 +26,27 @@ type Error = Box<dyn std::error::Error>;
 pub trait AudioBackend: Downcast {
     fn play(&mut self);
     fn pause(&mut self);
+
+    /// Whether audio output is currently muted.
+    ///
+    /// Backends that cannot mute are never muted.
+    fn muted(&self) -> bool {
+        false
+    }
+
+    /// Mutes or unmutes audio output.
+    ///
+    /// Unlike pausing, sounds continue to play silently while muted, so that
+    /// they remain in sync with the movie when unmuted.
+    fn set_muted(&mut self, _muted: bool) {}
+
     fn register_sound(&mut self, swf_sound: &swf::Sound) -> Result<SoundHandle, Error>;
 
+    /// Releases the data of a sound that is no longer needed.
+    ///
+    /// Any instances of the sound should be stopped first.
+    fn unregister_sound(&mut self, _sound: SoundHandle) {}
+
     /// Used by the web backend to pre-decode sound streams.
     /// Returns the sound handle to be used to add data to the stream.
     /// Other backends return `None`.
@@ -104,6 +123,15 @@ pub trait AudioBackend: Downcast {
     /// what the stage frame rate is. Otherwise, you are free to avoid
     /// implementing it.
     fn set_frame_rate(&mut self, _frame_rate: f64) {}
+
+    /// Returns a snapshot of all currently playing sound instances, for
+    /// debugging audio issues.
+    ///
+    /// Backends that are unable to inspect their playing sounds return an
+    /// empty list.
+    fn debug_state(&self) -> Vec<SoundInstanceDebugInfo> {
+        Vec::new()
+    }
 }
 
 impl_downcast!(AudioBackend);
@@ -111,12 +139,14 @@ impl_downcast!(AudioBackend);
 /// Audio backend that ignores all audio.
 pub struct NullAudioBackend {
     sounds: Arena<()>,
+    muted: bool,
 }
 
 impl NullAudioBackend {
     pub fn new() -> NullAudioBackend {
         NullAudioBackend {
             sounds: Arena::new(),
+            muted: false,
         }
     }
 }
@@ -124,10 +154,20 @@ impl NullAudioBackend {
 impl AudioBackend for NullAudioBackend {
     fn play(&mut self) {}
     fn pause(&mut self) {}
+    fn muted(&self) -> bool {
+        self.muted
+    }
+    fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+    }
     fn register_sound(&mut self, _sound: &swf::Sound) -> Result<SoundHandle, Error> {
         Ok(self.sounds.insert(()))
     }
 
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.sounds.remove(sound);
+    }
+
     fn start_sound(
         &mut self,
         _sound: SoundHandle,
@@ -242,6 +282,7 @@ impl<'gc> AudioManager<'gc> {
                 instance: handle,
                 display_object,
                 avm1_object,
+                stream_start_frame: None,
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -317,6 +358,7 @@ impl<'gc> AudioManager<'gc> {
                 instance: handle,
                 display_object: Some(movie_clip.into()),
                 avm1_object: None,
+                stream_start_frame: Some(movie_clip.current_frame()),
             };
             audio.set_sound_transform(handle, self.transform_for_sound(&instance));
             self.sounds.push(instance);
@@ -326,6 +368,27 @@ impl<'gc> AudioManager<'gc> {
         }
     }
 
+    /// Returns how many frames the timeline furthest behind its stream sound has fallen
+    /// behind, as measured by the playback position of each stream.
+    ///
+    /// This is negative if every timeline is ahead of its audio.
+    /// Returns `None` if no stream sound is playing.
+    pub fn stream_frames_behind(&self, audio: &dyn AudioBackend, frame_rate: f64) -> Option<f64> {
+        self.sounds
+            .iter()
+            .filter_map(|sound| {
+                let start_frame = sound.stream_start_frame?;
+                let movie_clip = sound.display_object?.as_movie_clip()?;
+                let position = audio.get_sound_position(sound.instance)?;
+                let audio_frame =
+                    f64::from(start_frame) + f64::from(position) * frame_rate / 1000.0;
+                Some(audio_frame - f64::from(movie_clip.current_frame()))
+            })
+            .fold(None, |max, frames_behind| {
+                Some(max.map_or(frames_behind, |max: f64| max.max(frames_behind)))
+            })
+    }
+
     pub fn global_sound_transform(&self) -> &DisplayObjectSoundTransform {
         &self.global_sound_transform
     }
@@ -390,6 +453,30 @@ pub struct SoundInstance<'gc> {
 
     /// The AVM1 `Sound` object associated with this sound, if any.
     pub avm1_object: Option<SoundObject<'gc>>,
+
+    /// The timeline frame that this stream sound started playing on.
+    /// This will be `None` for event sounds.
+    stream_start_frame: Option<u16>,
+}
+
+/// The state of a playing sound instance, as reported by
+/// `AudioBackend::debug_state`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SoundInstanceDebugInfo {
+    /// The handle of this sound instance.
+    pub instance: SoundInstanceHandle,
+
+    /// The sound being played, or `None` if this is a stream sound.
+    pub sound: Option<SoundHandle>,
+
+    /// The sample rate of the source audio.
+    pub sample_rate: u16,
+
+    /// The number of sample frames that have been output so far.
+    pub position: u32,
+
+    /// The volume transform applied to this sound instance.
+    pub transform: SoundTransform,
 }
 
 /// A sound transform for a playing sound, for use by audio backends.
@@ -1,5 +1,31 @@
+/// The category of a message sent to a `LogBackend`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogCategory {
+    /// Output of the ActionScript `trace` function, from either AVM.
+    AvmTrace,
+}
+
+impl LogCategory {
+    /// The name of this category, as used for filtering log output.
+    pub fn name(self) -> &'static str {
+        match self {
+            LogCategory::AvmTrace => "avm-trace",
+        }
+    }
+}
+
 pub trait LogBackend {
     fn avm_trace(&self, message: &str);
+
+    /// Log a message belonging to the given category.
+    ///
+    /// Both AVMs route their output through this method, so that backends
+    /// can filter messages consistently regardless of their origin.
+    fn log_event(&self, category: LogCategory, message: &str) {
+        match category {
+            LogCategory::AvmTrace => self.avm_trace(message),
+        }
+    }
 }
 
 /// Logging backend that just reroutes traces to the log crate
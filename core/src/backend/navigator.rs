@@ -12,6 +12,7 @@ use std::ptr::null;
 use std::sync::mpsc::{channel, Receiver, Sender};
 use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
 use std::time::Duration;
+use std::time::Duration;
 use swf::avm1::types::SendVarsMethod;
 use url::{ParseError, Url};
 
@@ -98,6 +99,7 @@ impl NavigationMethod {
 }
 
 /// Represents request options to be sent as part of a fetch.
+#[derive(Clone)]
 pub struct RequestOptions {
     /// The HTTP method to be used to make the request.
     method: NavigationMethod,
@@ -107,6 +109,9 @@ pub struct RequestOptions {
     ///
     /// The body consists of data and a mime type.
     body: Option<(Vec<u8>, String)>,
+
+    /// How long to wait for the request to complete before failing it.
+    timeout: Option<Duration>,
 }
 
 impl RequestOptions {
@@ -115,6 +120,7 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::Get,
             body: None,
+            timeout: None,
         }
     }
 
@@ -123,9 +129,21 @@ impl RequestOptions {
         Self {
             method: NavigationMethod::Post,
             body,
+            timeout: None,
         }
     }
 
+    /// Fail the request if it takes longer than the given duration.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Retrieve how long the request may take, if it is limited.
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Retrieve the navigation method for this request.
     pub fn method(&self) -> NavigationMethod {
         self.method
@@ -141,6 +159,37 @@ impl RequestOptions {
 /// result of type `Result<T, E>`.
 pub type OwnedFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + 'static>>;
 
+/// How failed fetches are retried.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// The number of times a failed fetch is retried before giving up.
+    pub max_retries: u32,
+
+    /// How long to wait before the first retry. Each following retry waits
+    /// twice as long as the one before it.
+    pub initial_backoff: Duration,
+
+    /// The timeout given to requests that don't set their own.
+    pub timeout: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// How long to wait before the given retry, counting from zero.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff * 2u32.saturating_pow(retry)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(500),
+            timeout: None,
+        }
+    }
+}
+
 /// A backend interacting with a browser environment.
 pub trait NavigatorBackend {
     /// Cause a browser navigation to a given URL.
@@ -176,8 +225,13 @@ pub trait NavigatorBackend {
     fn fetch(&self, url: &str, request_options: RequestOptions) -> OwnedFuture<Vec<u8>, Error>;
 
     /// Get the amount of time since the SWF was launched.
-    /// Used by the `getTimer` ActionScript call.
-    fn time_since_launch(&mut self) -> Duration;
+    ///
+    /// The player no longer asks its navigator for the time, and never calls
+    /// this. Hosts should give the player a `Clock` instead.
+    #[deprecated(note = "the player reads the time from its `Clock`; see `Player::set_clock`")]
+    fn time_since_launch(&mut self) -> Duration {
+        Duration::from_millis(0)
+    }
 
     /// Arrange for a future to be run at some point in the... well, future.
     ///
@@ -189,6 +243,15 @@ pub trait NavigatorBackend {
     /// This seems highly limiting.
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>);
 
+    /// Wait for the given amount of time, such as between retries of a failed
+    /// fetch.
+    ///
+    /// Backends without timers may resolve this immediately, which is what
+    /// the default implementation does.
+    fn sleep(&self, _duration: Duration) -> OwnedFuture<(), Error> {
+        Box::pin(async { Ok(()) })
+    }
+
     /// Resolve a relative URL.
     ///
     /// This function must not change URLs which are already protocol, domain,
@@ -366,10 +429,6 @@ impl NavigatorBackend for NullNavigatorBackend {
         Box::pin(async move { fs::read(path).map_err(Error::NetworkError) })
     }
 
-    fn time_since_launch(&mut self) -> Duration {
-        Duration::from_millis(0)
-    }
-
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         if let Some(channel) = self.channel.as_ref() {
             channel.send(future).unwrap();
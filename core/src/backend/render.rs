@@ -1,13 +1,22 @@
 use crate::shape_utils::DistilledShape;
-pub use crate::{library::MovieLibrary, transform::Transform, Color};
+pub use crate::{display_object::StageQuality, library::MovieLibrary, transform::Transform, Color};
 use downcast_rs::Downcast;
 use gc_arena::Collect;
 use std::io::Read;
 pub use swf;
 use swf::Matrix;
 
+#[cfg(test)]
+mod recording;
+#[cfg(test)]
+pub use recording::{RecordingRenderer, RenderCommand};
+
 pub trait RenderBackend: Downcast {
     fn set_viewport_dimensions(&mut self, width: u32, height: u32);
+
+    /// Change the quality that later frames are rendered at.
+    fn set_quality(&mut self, _quality: StageQuality) {}
+
     fn register_shape(
         &mut self,
         shape: DistilledShape,
@@ -46,6 +55,27 @@ pub trait RenderBackend: Downcast {
     fn deactivate_mask(&mut self);
     fn pop_mask(&mut self);
 
+    /// Redirects all rendering into an offscreen bitmap of the given size, until `end_offscreen`
+    /// is called. This is used to render display objects with `cacheAsBitmap` set.
+    ///
+    /// `reuse` is a bitmap previously returned by this method with the same size, which may be
+    /// rendered into again instead of allocating a new bitmap.
+    /// Returns `None` if this backend does not support offscreen rendering.
+    fn begin_offscreen(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        _reuse: Option<BitmapHandle>,
+    ) -> Option<BitmapHandle> {
+        None
+    }
+
+    /// Finishes rendering into the bitmap returned by `begin_offscreen`.
+    fn end_offscreen(&mut self) {}
+
+    /// Frees a bitmap returned by `begin_offscreen` that will not be rendered again.
+    fn unregister_bitmap(&mut self, _bitmap: BitmapHandle) {}
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap>;
     fn register_bitmap_raw(
         &mut self,
@@ -0,0 +1,222 @@
+//! A render backend that records the draw calls made to it, for use in tests.
+
+use super::{Bitmap, BitmapHandle, BitmapInfo, Error, RenderBackend, ShapeHandle};
+use crate::bounding_box::BoundingBox;
+use crate::color_transform::ColorTransform;
+use crate::display_object::StageQuality;
+use crate::library::MovieLibrary;
+use crate::shape_utils::DistilledShape;
+use crate::transform::Transform;
+use crate::Color;
+use swf::Matrix;
+
+/// A draw call made to a `RecordingRenderer`.
+#[derive(Debug, Clone)]
+pub enum RenderCommand {
+    RenderBitmap {
+        bitmap: BitmapHandle,
+        matrix: Matrix,
+    },
+    RenderShape {
+        shape: ShapeHandle,
+        matrix: Matrix,
+        color_transform: ColorTransform,
+    },
+    DrawRect {
+        color: Color,
+        matrix: Matrix,
+    },
+    PushMask,
+    ActivateMask,
+    DeactivateMask,
+    PopMask,
+    BeginOffscreen {
+        bitmap: BitmapHandle,
+    },
+    EndOffscreen,
+}
+
+/// Render backend that records every draw call instead of drawing anything.
+///
+/// Each shape registered with this backend gets the bounds of its
+/// `DistilledShape`, so that tests can tell which shapes were drawn.
+#[derive(Default)]
+pub struct RecordingRenderer {
+    /// The draw calls made since the last call to `begin_frame`.
+    pub commands: Vec<RenderCommand>,
+
+    /// Whether this backend supports offscreen rendering.
+    pub supports_offscreen: bool,
+
+    /// Every quality passed to `set_quality`, in order.
+    pub qualities: Vec<StageQuality>,
+
+    /// The color the current frame was cleared to.
+    pub clear_color: Option<Color>,
+
+    /// The bitmaps that have been freed with `unregister_bitmap`.
+    pub unregistered_bitmaps: Vec<BitmapHandle>,
+
+    shapes: Vec<BoundingBox>,
+    num_bitmaps: usize,
+}
+
+impl RecordingRenderer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The bounds of the given registered shape.
+    pub fn shape_bounds(&self, shape: ShapeHandle) -> &BoundingBox {
+        &self.shapes[shape.0]
+    }
+
+    /// The number of offscreen bitmaps rendered since the last call to `begin_frame`.
+    pub fn num_offscreen_renders(&self) -> usize {
+        self.commands
+            .iter()
+            .filter(|command| matches!(command, RenderCommand::BeginOffscreen { .. }))
+            .count()
+    }
+
+    fn next_bitmap(&mut self) -> BitmapHandle {
+        self.num_bitmaps += 1;
+        BitmapHandle(self.num_bitmaps - 1)
+    }
+
+    fn next_bitmap_info(&mut self) -> Result<BitmapInfo, Error> {
+        Ok(BitmapInfo {
+            handle: self.next_bitmap(),
+            width: 0,
+            height: 0,
+        })
+    }
+}
+
+impl RenderBackend for RecordingRenderer {
+    fn set_viewport_dimensions(&mut self, _width: u32, _height: u32) {}
+    fn set_quality(&mut self, quality: StageQuality) {
+        self.qualities.push(quality);
+    }
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        _library: Option<&MovieLibrary<'_>>,
+    ) -> ShapeHandle {
+        self.shapes.push(shape.shape_bounds);
+        ShapeHandle(self.shapes.len() - 1)
+    }
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        _library: Option<&MovieLibrary<'_>>,
+        handle: ShapeHandle,
+    ) {
+        self.shapes[handle.0] = shape.shape_bounds;
+    }
+    fn register_glyph_shape(&mut self, _shape: &swf::Glyph) -> ShapeHandle {
+        self.shapes.push(Default::default());
+        ShapeHandle(self.shapes.len() - 1)
+    }
+    fn register_bitmap_jpeg(
+        &mut self,
+        _data: &[u8],
+        _jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        self.next_bitmap_info()
+    }
+    fn register_bitmap_jpeg_2(&mut self, _data: &[u8]) -> Result<BitmapInfo, Error> {
+        self.next_bitmap_info()
+    }
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        _data: &[u8],
+        _alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        self.next_bitmap_info()
+    }
+    fn register_bitmap_png(
+        &mut self,
+        _swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        self.next_bitmap_info()
+    }
+    fn begin_frame(&mut self, clear: Color) {
+        self.commands.clear();
+        self.clear_color = Some(clear);
+    }
+    fn end_frame(&mut self) {}
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, _smoothing: bool) {
+        self.commands.push(RenderCommand::RenderBitmap {
+            bitmap,
+            matrix: transform.matrix,
+        });
+    }
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        self.commands.push(RenderCommand::RenderShape {
+            shape,
+            matrix: transform.matrix,
+            color_transform: transform.color_transform,
+        });
+    }
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        self.commands.push(RenderCommand::DrawRect {
+            color,
+            matrix: *matrix,
+        });
+    }
+    fn push_mask(&mut self) {
+        self.commands.push(RenderCommand::PushMask);
+    }
+    fn activate_mask(&mut self) {
+        self.commands.push(RenderCommand::ActivateMask);
+    }
+    fn deactivate_mask(&mut self) {
+        self.commands.push(RenderCommand::DeactivateMask);
+    }
+    fn pop_mask(&mut self) {
+        self.commands.push(RenderCommand::PopMask);
+    }
+
+    fn begin_offscreen(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        reuse: Option<BitmapHandle>,
+    ) -> Option<BitmapHandle> {
+        if !self.supports_offscreen {
+            return None;
+        }
+        let bitmap = reuse.unwrap_or_else(|| self.next_bitmap());
+        self.commands.push(RenderCommand::BeginOffscreen { bitmap });
+        Some(bitmap)
+    }
+    fn end_offscreen(&mut self) {
+        self.commands.push(RenderCommand::EndOffscreen);
+    }
+    fn unregister_bitmap(&mut self, bitmap: BitmapHandle) {
+        self.unregistered_bitmaps.push(bitmap);
+    }
+
+    fn get_bitmap_pixels(&mut self, _bitmap: BitmapHandle) -> Option<Bitmap> {
+        None
+    }
+    fn register_bitmap_raw(
+        &mut self,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self.next_bitmap())
+    }
+
+    fn update_texture(
+        &mut self,
+        bitmap: BitmapHandle,
+        _width: u32,
+        _height: u32,
+        _rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(bitmap)
+    }
+}
@@ -1,5 +1,6 @@
 use downcast_rs::Downcast;
 use std::collections::HashMap;
+use std::convert::TryFrom;
 
 pub trait StorageBackend: Downcast {
     fn get_string(&self, name: &str) -> Option<String>;
@@ -10,7 +11,27 @@ pub trait StorageBackend: Downcast {
         self.get_string(name).map(|x| x.as_bytes().len())
     }
 
+    /// Retrieve binary data, such as a `SharedObject`'s `.sol` file.
+    ///
+    /// By default, binary data is stored as a string with one character per
+    /// byte, for backends that can only store strings. Backends that can
+    /// store bytes as is should do so, so that the data can be used by other
+    /// tools.
+    fn get_bytes(&self, name: &str) -> Option<Vec<u8>> {
+        self.get_string(name)?
+            .chars()
+            .map(|c| u8::try_from(u32::from(c)).ok())
+            .collect()
+    }
+
+    fn put_bytes(&mut self, name: &str, value: &[u8]) -> bool {
+        self.put_string(name, value.iter().copied().map(char::from).collect())
+    }
+
     fn remove_key(&mut self, name: &str);
+
+    /// The names of everything in storage.
+    fn keys(&self) -> Vec<String>;
 }
 impl_downcast!(StorageBackend);
 
@@ -39,4 +60,8 @@ impl StorageBackend for MemoryStorageBackend {
     fn remove_key(&mut self, name: &str) {
         self.map.remove(name);
     }
+
+    fn keys(&self) -> Vec<String> {
+        self.map.keys().cloned().collect()
+    }
 }
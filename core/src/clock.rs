@@ -0,0 +1,100 @@
+//! Sources of time for the player
+//!
+//! Everything in the player that asks what time it is, such as `getTimer`,
+//! `new Date()` and `Player::advance_clock`, reads from a `Clock`. Hosts
+//! normally use the `WallClock`; a `ManualClock` lets the time be stepped
+//! explicitly, for deterministic replay and headless rendering.
+
+use chrono::{DateTime, TimeZone, Utc};
+use instant::Instant;
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A source of the current time.
+pub trait Clock {
+    /// The amount of time that passed since the player was launched.
+    fn time_since_launch(&self) -> Duration;
+
+    /// The current date and time.
+    fn date_time(&self) -> DateTime<Utc>;
+}
+
+/// A clock that follows real time.
+#[derive(Debug, Clone, Copy)]
+pub struct WallClock {
+    launched: Instant,
+}
+
+impl WallClock {
+    /// Construct a clock that starts at zero now.
+    pub fn new() -> Self {
+        Self {
+            launched: Instant::now(),
+        }
+    }
+}
+
+impl Default for WallClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for WallClock {
+    fn time_since_launch(&self) -> Duration {
+        self.launched.elapsed()
+    }
+
+    fn date_time(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when it is told to.
+///
+/// Clones of a `ManualClock` share the same time, so a host can keep a
+/// handle to the clock it gave to the player and advance it from outside.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    time: Rc<Cell<Duration>>,
+
+    /// The date and time at which the player was launched.
+    launched_at: DateTime<Utc>,
+}
+
+impl ManualClock {
+    /// Construct a clock that is stopped at zero, launched at the Unix epoch.
+    pub fn new() -> Self {
+        Self::launched_at(Utc.timestamp(0, 0))
+    }
+
+    /// Construct a clock that is stopped at zero, launched at the given date.
+    pub fn launched_at(launched_at: DateTime<Utc>) -> Self {
+        Self {
+            time: Rc::new(Cell::new(Duration::default())),
+            launched_at,
+        }
+    }
+
+    /// Move the clock forward by the given amount of time.
+    pub fn advance(&self, dt: Duration) {
+        self.time.set(self.time.get() + dt);
+    }
+}
+
+impl Clock for ManualClock {
+    fn time_since_launch(&self) -> Duration {
+        self.time.get()
+    }
+
+    fn date_time(&self) -> DateTime<Utc> {
+        self.launched_at + chrono::Duration::milliseconds(self.time.get().as_millis() as i64)
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -40,6 +40,12 @@ impl ColorTransform {
             && self.a_add == 0
     }
 
+    /// Returns whether this color transform makes every color fully transparent.
+    pub fn is_fully_transparent(&self) -> bool {
+        // Output alpha is linear in input alpha, so check both extremes.
+        self.a_add <= 0 && 255.0 * self.a_mult.to_f32() + f32::from(self.a_add) <= 0.0
+    }
+
     /// Returns the multiplicative component of this color transform in RGBA order
     /// with the values normalized [0.0, 1.0].
     pub fn mult_rgba_normalized(&self) -> [f32; 4] {
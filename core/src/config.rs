@@ -30,3 +30,40 @@ impl Default for Letterbox {
         Letterbox::Fullscreen
     }
 }
+
+/// How the content is scaled along one axis of the viewport.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum AxisScale {
+    /// The axis is scaled along with the other one to keep the content's
+    /// aspect ratio, leaving margins if the viewport is larger.
+    Fit,
+
+    /// The axis is stretched to fill the viewport.
+    Stretch,
+}
+
+/// Scales each axis of the content independently.
+///
+/// This only applies in the default `StageScaleMode::ShowAll`, which scales
+/// both axes with `AxisScale::Fit`. Content that picks another scale mode is
+/// left alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct AxisScaling {
+    pub horizontal: AxisScale,
+    pub vertical: AxisScale,
+}
+
+impl Default for AxisScaling {
+    fn default() -> Self {
+        Self {
+            horizontal: AxisScale::Fit,
+            vertical: AxisScale::Fit,
+        }
+    }
+}
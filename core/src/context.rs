@@ -2,7 +2,7 @@
 
 use crate::avm1::globals::system::SystemProperties;
 use crate::avm1::{Avm1, Object as Avm1Object, Timers, Value as Avm1Value};
-use crate::avm2::{Avm2, Object as Avm2Object, Value as Avm2Value};
+use crate::avm2::{Avm2, Event as Avm2Event, Object as Avm2Object, Value as Avm2Value};
 use crate::backend::{
     audio::{AudioBackend, AudioManager, SoundHandle, SoundInstanceHandle},
     locale::LocaleBackend,
@@ -13,7 +13,9 @@ use crate::backend::{
     ui::UiBackend,
     video::VideoBackend,
 };
+use crate::clock::Clock;
 use crate::context_menu::ContextMenuState;
+use crate::diagnostics::DiagnosticsReport;
 use crate::display_object::{EditText, MovieClip, SoundTransform, Stage};
 use crate::external::ExternalInterface;
 use crate::focus_tracker::FocusTracker;
@@ -21,6 +23,7 @@ use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::player::Player;
 use crate::prelude::*;
+use crate::security::PolicyCache;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::transform::TransformStack;
 use core::fmt;
@@ -98,6 +101,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// The location of the mouse when it was last over the player.
     pub mouse_position: &'a (Twips, Twips),
 
+    /// Whether the mouse button is held down.
+    pub is_mouse_down: &'a bool,
+
     /// The object being dragged via a `startDrag` action.
     pub drag_object: &'a mut Option<crate::player::DragObject<'gc>>,
 
@@ -122,6 +128,12 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// Shared objects cache
     pub shared_objects: &'a mut HashMap<String, Avm1Object<'gc>>,
 
+    /// Cross-domain policy files fetched by this player.
+    pub policy_cache: &'a mut PolicyCache,
+
+    /// Unsupported features encountered by this player.
+    pub diagnostics: &'a mut DiagnosticsReport,
+
     /// Text fields with unbound variable bindings.
     pub unbound_text_fields: &'a mut Vec<EditText<'gc>>,
 
@@ -144,7 +156,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
-    pub max_execution_duration: Duration,
+    ///
+    /// `None` disables the timeout, and scripts are never checked.
+    pub max_execution_duration: Option<Duration>,
 
     /// A tracker for the current keyboard focused element
     pub focus_tracker: FocusTracker<'gc>,
@@ -155,6 +169,9 @@ pub struct UpdateContext<'a, 'gc, 'gc_context> {
     /// This frame's current fake time offset, used to pretend passage of time in time functions
     pub time_offset: &'a mut u32,
 
+    /// The source of time for the movie.
+    pub clock: &'a dyn Clock,
+
     /// The current stage frame rate.
     pub frame_rate: &'a mut f64,
 }
@@ -233,6 +250,36 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
     pub fn set_sound_transforms_dirty(&mut self) {
         self.audio_manager.set_sound_transforms_dirty()
     }
+
+    /// The position of the mouse on the stage.
+    pub fn mouse_position(&self) -> (Twips, Twips) {
+        *self.mouse_position
+    }
+
+    /// Whether the mouse button is held down.
+    pub fn is_mouse_down(&self) -> bool {
+        *self.is_mouse_down
+    }
+
+    /// The position of the mouse in the local space of a display object.
+    ///
+    /// Yields `None` if the object is not on the stage, or if it is scaled to
+    /// nothing and so has no local space to convert into.
+    pub fn mouse_position_in(&self, object: DisplayObject<'gc>) -> Option<(Twips, Twips)> {
+        let mut top = object;
+        while let Some(parent) = top.parent() {
+            top = parent;
+        }
+        if !DisplayObject::ptr_eq(top, self.stage.into()) {
+            return None;
+        }
+
+        let matrix = object.local_to_global_matrix();
+        if matrix.a * matrix.d - matrix.b * matrix.c == 0.0 {
+            return None;
+        }
+        Some(object.global_to_local(*self.mouse_position))
+    }
 }
 
 impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
@@ -267,12 +314,15 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             stage: self.stage,
             mouse_hovered_object: self.mouse_hovered_object,
             mouse_position: self.mouse_position,
+            is_mouse_down: self.is_mouse_down,
             drag_object: self.drag_object,
             player: self.player.clone(),
             load_manager: self.load_manager,
             system: self.system,
             instance_counter: self.instance_counter,
             shared_objects: self.shared_objects,
+            policy_cache: self.policy_cache,
+            diagnostics: self.diagnostics,
             unbound_text_fields: self.unbound_text_fields,
             timers: self.timers,
             current_context_menu: self.current_context_menu,
@@ -284,9 +334,23 @@ impl<'a, 'gc, 'gc_context> UpdateContext<'a, 'gc, 'gc_context> {
             focus_tracker: self.focus_tracker,
             times_get_time_called: self.times_get_time_called,
             time_offset: self.time_offset,
+            clock: self.clock,
             frame_rate: self.frame_rate,
         }
     }
+
+    /// Run a closure against the root movie clip, handing it a reborrowed
+    /// update context alongside.
+    ///
+    /// Yields `None` without running the closure if the root is not a movie
+    /// clip.
+    pub fn with_root_clip<R>(
+        &mut self,
+        f: impl FnOnce(&mut UpdateContext<'_, 'gc, 'gc_context>, MovieClip<'gc>) -> R,
+    ) -> Option<R> {
+        let root = self.stage.root_clip().as_movie_clip()?;
+        Some(f(&mut self.reborrow(), root))
+    }
 }
 
 /// A queued ActionScript call.
@@ -309,11 +373,17 @@ pub struct QueuedActions<'gc> {
 pub struct ActionQueue<'gc> {
     /// Each priority is kept in a separate bucket.
     action_queue: Vec<VecDeque<QueuedActions<'gc>>>,
+
+    /// The highest priority that may have actions queued.
+    ///
+    /// All buckets above this one are empty, so popping can start here
+    /// instead of at the top.
+    highest_priority: usize,
 }
 
 impl<'gc> ActionQueue<'gc> {
     const DEFAULT_CAPACITY: usize = 32;
-    const NUM_PRIORITIES: usize = 3;
+    const NUM_PRIORITIES: usize = 4;
 
     /// Crates a new `ActionQueue` with an empty queue.
     pub fn new() -> Self {
@@ -321,7 +391,10 @@ impl<'gc> ActionQueue<'gc> {
         for _ in 0..Self::NUM_PRIORITIES {
             action_queue.push(VecDeque::with_capacity(Self::DEFAULT_CAPACITY))
         }
-        Self { action_queue }
+        Self {
+            action_queue,
+            highest_priority: 0,
+        }
     }
 
     /// Queues ActionScript to run for the given movie clip.
@@ -341,20 +414,31 @@ impl<'gc> ActionQueue<'gc> {
         };
         debug_assert!(priority < Self::NUM_PRIORITIES);
         if let Some(queue) = self.action_queue.get_mut(priority) {
-            queue.push_back(action)
+            queue.push_back(action);
+            self.highest_priority = self.highest_priority.max(priority);
         }
     }
 
     /// Sorts and drains the actions from the queue.
     pub fn pop_action(&mut self) -> Option<QueuedActions<'gc>> {
-        for queue in self.action_queue.iter_mut().rev() {
-            let action = queue.pop_front();
+        for priority in (0..=self.highest_priority).rev() {
+            let action = self.action_queue[priority].pop_front();
             if action.is_some() {
+                self.highest_priority = priority;
                 return action;
             }
         }
+        self.highest_priority = 0;
         None
     }
+
+    /// The number of actions waiting to run, across all priorities.
+    ///
+    /// A count that keeps growing between frames means that scripts are
+    /// queueing actions faster than they're run.
+    pub fn pending_count(&self) -> usize {
+        self.action_queue.iter().map(VecDeque::len).sum()
+    }
 }
 
 impl<'gc> Default for ActionQueue<'gc> {
@@ -412,6 +496,13 @@ pub enum ActionType<'gc> {
         args: Vec<Avm1Value<'gc>>,
     },
 
+    /// A method called on a `LocalConnection` by a `send` from another connection.
+    LocalConnectionMessage {
+        receiver: Avm1Object<'gc>,
+        method: String,
+        args: Vec<Avm1Value<'gc>>,
+    },
+
     /// A system listener method,
     NotifyListeners {
         listener: &'static str,
@@ -425,14 +516,32 @@ pub enum ActionType<'gc> {
         reciever: Option<Avm2Object<'gc>>,
         args: Vec<Avm2Value<'gc>>,
     },
+
+    /// An AVM2 event to be dispatched onto a target object.
+    Event2 {
+        event: Avm2Event<'gc>,
+        target: Avm2Object<'gc>,
+    },
+
+    /// Broadcast the AVM2 `exitFrame` event, once everything else queued for
+    /// the frame has run.
+    ///
+    /// `Player::run_frame` does not queue this yet: it still broadcasts
+    /// `exitFrame` directly at the start of the next frame, which is the
+    /// order our Flash-recorded display event tests expect.
+    ExitFrame,
 }
 
 impl ActionType<'_> {
+    /// The bucket this action is queued in. Higher priorities run first:
+    /// initialize actions, then constructors, then everything else, and
+    /// `exitFrame` last of all.
     fn priority(&self) -> usize {
         match self {
-            ActionType::Initialize { .. } => 2,
-            ActionType::Construct { .. } => 1,
-            _ => 0,
+            ActionType::Initialize { .. } => 3,
+            ActionType::Construct { .. } => 2,
+            ActionType::ExitFrame => 0,
+            _ => 1,
         }
     }
 }
@@ -462,6 +571,16 @@ impl fmt::Debug for ActionType<'_> {
                 .field("name", name)
                 .field("args", args)
                 .finish(),
+            ActionType::LocalConnectionMessage {
+                receiver,
+                method,
+                args,
+            } => f
+                .debug_struct("ActionType::LocalConnectionMessage")
+                .field("receiver", receiver)
+                .field("method", method)
+                .field("args", args)
+                .finish(),
             ActionType::NotifyListeners {
                 listener,
                 method,
@@ -482,6 +601,232 @@ impl fmt::Debug for ActionType<'_> {
                 .field("reciever", reciever)
                 .field("args", args)
                 .finish(),
+            ActionType::Event2 { event, target } => f
+                .debug_struct("ActionType::Event2")
+                .field("event", event)
+                .field("target", target)
+                .finish(),
+            ActionType::ExitFrame => f.write_str("ActionType::ExitFrame"),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::audio::SoundTransform;
+    use crate::events::PlayerEvent;
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{
+        empty_movie, movie_placing_squares, place_character, square_movie, square_shape,
+        SoundTransformAudioBackend,
+    };
+
+    #[test]
+    fn action_queue_drains_by_priority() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            let bytecode = crate::tag_utils::SwfSlice::empty(context.swf.clone());
+            let normal = |method| ActionType::NotifyListeners {
+                listener: "Test",
+                method,
+                args: vec![],
+            };
+            let name = |action_type: &ActionType| match action_type {
+                ActionType::NotifyListeners { method, .. } => *method,
+                ActionType::Construct { .. } => "construct",
+                ActionType::Initialize { .. } => "initialize",
+                _ => unreachable!(),
+            };
+
+            let mut queue = ActionQueue::new();
+            queue.queue_actions(root, normal("first"), false);
+            queue.queue_actions(
+                root,
+                ActionType::Construct {
+                    constructor: None,
+                    events: vec![],
+                },
+                false,
+            );
+            queue.queue_actions(root, normal("second"), false);
+            assert_eq!(queue.pending_count(), 3);
+
+            assert_eq!(name(&queue.pop_action().unwrap().action_type), "construct");
+            assert_eq!(queue.pending_count(), 2);
+
+            // Higher priority actions queued while draining still go first.
+            queue.queue_actions(root, ActionType::Initialize { bytecode }, false);
+            let mut drained = vec![];
+            while let Some(action) = queue.pop_action() {
+                drained.push(name(&action.action_type));
+            }
+            assert_eq!(drained, vec!["initialize", "first", "second"]);
+            assert_eq!(queue.pending_count(), 0);
+
+            queue.queue_actions(root, normal("third"), false);
+            assert_eq!(name(&queue.pop_action().unwrap().action_type), "third");
+            assert!(queue.pop_action().is_none());
+        });
+    }
+
+    #[test]
+    fn exit_frame_runs_after_other_actions() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            let normal = ActionType::NotifyListeners {
+                listener: "Test",
+                method: "normal",
+                args: vec![],
+            };
+
+            let mut queue = ActionQueue::new();
+            queue.queue_actions(root, ActionType::ExitFrame, false);
+            queue.queue_actions(root, normal, false);
+            assert!(matches!(
+                queue.pop_action().unwrap().action_type,
+                ActionType::NotifyListeners { .. }
+            ));
+            assert!(matches!(
+                queue.pop_action().unwrap().action_type,
+                ActionType::ExitFrame
+            ));
+            assert!(queue.pop_action().is_none());
+        });
+    }
+
+    #[test]
+    fn with_root_clip_walks_root_children() {
+        let places = vec![
+            place_character(1),
+            swf::PlaceObject {
+                depth: 2,
+                ..place_character(1)
+            },
+        ];
+        let bounds = square_shape().shape_bounds;
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie_placing_squares(bounds, places)));
+        player.run_frame();
+
+        let num_children = player.update(|context| {
+            context.with_root_clip(|_context, root| root.iter_render_list().count())
+        });
+        assert_eq!(num_children, Some(2));
+    }
+
+    #[test]
+    fn clip_sound_transform_attenuates_owned_sounds() {
+        let transform = Arc::new(Mutex::new(None));
+        let player = PlayerBuilder::new()
+            .with_audio(Box::new(SoundTransformAudioBackend::new(transform.clone())))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.run_frame();
+
+        let half_volume = crate::display_object::SoundTransform {
+            volume: 50,
+            ..Default::default()
+        };
+        let attenuated = |volume| {
+            Some(SoundTransform {
+                left_to_left: volume,
+                left_to_right: 0.0,
+                right_to_left: 0.0,
+                right_to_right: volume,
+            })
+        };
+
+        // A sound owned by the square, placed inside the root clip.
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            let square = root.as_container().unwrap().child_by_depth(1).unwrap();
+            let sound = context
+                .audio
+                .register_sound(&swf::Sound {
+                    id: 2,
+                    format: swf::SoundFormat {
+                        compression: swf::AudioCompression::Uncompressed,
+                        sample_rate: 44100,
+                        is_stereo: true,
+                        is_16_bit: true,
+                    },
+                    num_samples: 0,
+                    data: &[],
+                })
+                .unwrap();
+            let settings = swf::SoundInfo {
+                event: swf::SoundEvent::Event,
+                in_sample: None,
+                out_sample: None,
+                num_loops: 1,
+                envelope: None,
+            };
+            context.start_sound(sound, &settings, Some(square), None);
+            square.set_sound_transform(context, half_volume.clone());
+            context.update_sounds();
+        });
+        assert_eq!(*transform.lock().unwrap(), attenuated(0.5));
+
+        // Transforms of the ancestors of the owner apply as well.
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            root.set_sound_transform(context, half_volume);
+            context.update_sounds();
+        });
+        assert_eq!(*transform.lock().unwrap(), attenuated(0.25));
+
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            let square = root.as_container().unwrap().child_by_depth(1).unwrap();
+            square.set_sound_transform(context, Default::default());
+            context.update_sounds();
+        });
+        assert_eq!(*transform.lock().unwrap(), attenuated(0.5));
+    }
+
+    #[test]
+    fn mouse_position_in_local_space() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.set_viewport_dimensions(10, 10, 1.0);
+        player.handle_event(PlayerEvent::MouseMove { x: 5.0, y: 5.0 });
+
+        player.update(|context| {
+            let px = Twips::from_pixels;
+            assert_eq!(context.mouse_position(), (px(5.0), px(5.0)));
+            assert!(!context.is_mouse_down());
+
+            let swf = crate::tag_utils::SwfSlice::empty(context.swf.clone());
+            let clip: DisplayObject<'_> = MovieClip::new(swf, context.gc_context).into();
+            clip.set_x(context.gc_context, 2.0);
+            clip.set_y(context.gc_context, 3.0);
+            assert_eq!(context.mouse_position_in(clip), None);
+
+            context
+                .stage
+                .root_clip()
+                .as_container()
+                .unwrap()
+                .replace_at_depth(context, clip, 5);
+            assert_eq!(context.mouse_position_in(clip), Some((px(3.0), px(2.0))));
+
+            let mut squashed = *clip.matrix();
+            squashed.a = 0.0;
+            clip.set_matrix(context.gc_context, &squashed);
+            assert_eq!(context.mouse_position_in(clip), None);
+        });
+    }
+}
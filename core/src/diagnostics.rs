@@ -0,0 +1,61 @@
+//! Tracking of content features that Ruffle doesn't support yet
+
+use std::collections::BTreeSet;
+
+/// A summary of the unsupported features a player has run into.
+///
+/// Entries are recorded as they are encountered and never cleared, so the
+/// report covers everything that has happened since the player was created.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DiagnosticsReport {
+    /// ActionScript builtins that were called but are only stubbed out,
+    /// named like `Sound.loadSound`.
+    unimplemented_builtins: BTreeSet<String>,
+
+    /// Tag codes that were found in a movie but aren't known to Ruffle.
+    unknown_tags: BTreeSet<u16>,
+
+    /// Audio or video codecs that a backend refused to decode.
+    unsupported_codecs: BTreeSet<String>,
+}
+
+impl DiagnosticsReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether no unsupported features have been encountered.
+    pub fn is_empty(&self) -> bool {
+        self.unimplemented_builtins.is_empty()
+            && self.unknown_tags.is_empty()
+            && self.unsupported_codecs.is_empty()
+    }
+
+    pub fn unimplemented_builtins(&self) -> impl Iterator<Item = &str> {
+        self.unimplemented_builtins.iter().map(String::as_str)
+    }
+
+    pub fn unknown_tags(&self) -> impl Iterator<Item = u16> + '_ {
+        self.unknown_tags.iter().copied()
+    }
+
+    pub fn unsupported_codecs(&self) -> impl Iterator<Item = &str> {
+        self.unsupported_codecs.iter().map(String::as_str)
+    }
+
+    pub fn record_unimplemented_builtin(&mut self, name: &str) {
+        if !self.unimplemented_builtins.contains(name) {
+            self.unimplemented_builtins.insert(name.to_string());
+        }
+    }
+
+    pub fn record_unknown_tag(&mut self, tag_code: u16) {
+        self.unknown_tags.insert(tag_code);
+    }
+
+    pub fn record_unsupported_codec(&mut self, codec: &str) {
+        if !self.unsupported_codecs.contains(codec) {
+            self.unsupported_codecs.insert(codec.to_string());
+        }
+    }
+}
@@ -4,18 +4,19 @@ use crate::avm1::{
 use crate::avm2::{
     Avm2, Event as Avm2Event, Object as Avm2Object, TObject as Avm2TObject, Value as Avm2Value,
 };
+use crate::backend::render::{BitmapHandle, RenderBackend};
 use crate::context::{RenderContext, UpdateContext};
 use crate::drawing::Drawing;
 use crate::player::NEWEST_PLAYER_VERSION;
 use crate::prelude::*;
 use crate::tag_utils::SwfMovie;
-use crate::transform::Transform;
+use crate::transform::{Transform, TransformStack};
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmType, Instantiator};
 use bitflags::bitflags;
 use gc_arena::{Collect, MutationContext};
 use ruffle_macros::enum_trait_object;
-use std::cell::{Ref, RefMut};
+use std::cell::{Cell, Ref, RefMut};
 use std::fmt::Debug;
 use std::sync::Arc;
 use swf::Fixed8;
@@ -43,7 +44,7 @@ pub use edit_text::{AutoSizeMode, EditText, TextSelection};
 pub use graphic::Graphic;
 pub use morph_shape::{MorphShape, MorphShapeStatic};
 pub use movie_clip::{MovieClip, Scene};
-pub use stage::{Stage, StageAlign, StageScaleMode};
+pub use stage::{Stage, StageAlign, StageQuality, StageScaleMode};
 pub use text::Text;
 pub use video::Video;
 
@@ -84,6 +85,20 @@ pub struct DisplayObjectBase<'gc> {
     /// The display object we are currently masking.
     maskee: Option<DisplayObject<'gc>>,
 
+    /// The offscreen bitmap this object is rendered into when `cacheAsBitmap` is set.
+    bitmap_cache: BitmapCache,
+
+    /// The rectangle of this object's content that is displayed (`scrollRect`).
+    /// The content is clipped to this rectangle and offset by its origin.
+    scroll_rect: Option<BoundingBox>,
+
+    /// Whether this object is in the tab order (`tabEnabled`).
+    /// `None` until set by ActionScript, in which case focusable objects are included.
+    tab_enabled: Option<bool>,
+
+    /// The position of this object in the tab order (`tabIndex`), if set.
+    tab_index: Option<i32>,
+
     /// Bit flags for various display object properites.
     flags: DisplayObjectFlags,
 }
@@ -106,7 +121,11 @@ impl<'gc> Default for DisplayObjectBase<'gc> {
             masker: None,
             maskee: None,
             sound_transform: Default::default(),
-            flags: DisplayObjectFlags::VISIBLE,
+            bitmap_cache: Default::default(),
+            scroll_rect: None,
+            tab_enabled: None,
+            tab_index: None,
+            flags: DisplayObjectFlags::VISIBLE | DisplayObjectFlags::TAB_CHILDREN,
         }
     }
 }
@@ -116,7 +135,7 @@ impl<'gc> DisplayObjectBase<'gc> {
     /// Reset all properties that would be adjusted by a movie load.
     fn reset_for_movie_load(&mut self) {
         let flags_to_keep = self.flags & DisplayObjectFlags::LOCK_ROOT;
-        self.flags = flags_to_keep | DisplayObjectFlags::VISIBLE;
+        self.flags = flags_to_keep | DisplayObjectFlags::VISIBLE | DisplayObjectFlags::TAB_CHILDREN;
     }
 
     fn id(&self) -> CharacterId {
@@ -374,6 +393,53 @@ impl<'gc> DisplayObjectBase<'gc> {
         self.flags.set(DisplayObjectFlags::LOCK_ROOT, value);
     }
 
+    fn tab_enabled(&self) -> Option<bool> {
+        self.tab_enabled
+    }
+
+    fn set_tab_enabled(&mut self, value: Option<bool>) {
+        self.tab_enabled = value;
+    }
+
+    fn tab_children(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::TAB_CHILDREN)
+    }
+
+    fn set_tab_children(&mut self, value: bool) {
+        self.flags.set(DisplayObjectFlags::TAB_CHILDREN, value);
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_tab_index(&mut self, value: Option<i32>) {
+        self.tab_index = value;
+    }
+
+    fn cache_as_bitmap(&self) -> bool {
+        self.flags.contains(DisplayObjectFlags::CACHE_AS_BITMAP)
+    }
+
+    fn set_cache_as_bitmap(&mut self, value: bool) {
+        if value != self.cache_as_bitmap() {
+            self.bitmap_cache.invalidate();
+        }
+        self.flags.set(DisplayObjectFlags::CACHE_AS_BITMAP, value);
+    }
+
+    fn bitmap_cache(&self) -> &BitmapCache {
+        &self.bitmap_cache
+    }
+
+    fn scroll_rect(&self) -> Option<&BoundingBox> {
+        self.scroll_rect.as_ref()
+    }
+
+    fn set_scroll_rect(&mut self, scroll_rect: Option<BoundingBox>) {
+        self.scroll_rect = scroll_rect;
+    }
+
     fn transformed_by_script(&self) -> bool {
         self.flags
             .contains(DisplayObjectFlags::TRANSFORMED_BY_SCRIPT)
@@ -431,6 +497,17 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     if this.maskee().is_some() {
         return;
     }
+
+    // Skip objects that wouldn't draw anything. Masks are exempt, as they
+    // still clip their maskees regardless of their own visibility.
+    if context.allow_mask {
+        let color_transform =
+            context.transform_stack.transform().color_transform * *this.color_transform();
+        if !this.visible() || color_transform.is_fully_transparent() {
+            return;
+        }
+    }
+
     context.transform_stack.push(&*this.transform());
 
     let mask = this.masker();
@@ -446,7 +523,42 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
         context.allow_mask = true;
         context.renderer.activate_mask();
     }
-    this.render_self(context);
+
+    // Clip to the scroll rect, which is positioned at our origin.
+    let scroll_rect_mask = this.scroll_rect().map(|scroll_rect| {
+        Matrix::create_box(
+            scroll_rect.width().to_pixels() as f32,
+            scroll_rect.height().to_pixels() as f32,
+            0.0,
+            Twips::zero(),
+            Twips::zero(),
+        )
+    });
+    if let Some(scroll_rect_mask) = scroll_rect_mask {
+        context.renderer.push_mask();
+        context.renderer.draw_rect(
+            Color::from_rgb(0, 0xff),
+            &(context.transform_stack.transform().matrix * scroll_rect_mask),
+        );
+        context.renderer.activate_mask();
+    }
+
+    if this.cache_as_bitmap() {
+        render_cached_bitmap(this, context);
+    } else {
+        this.bitmap_cache().release(context.renderer);
+        render_scrolled(this, context);
+    }
+
+    if let Some(scroll_rect_mask) = scroll_rect_mask {
+        context.renderer.deactivate_mask();
+        context.renderer.draw_rect(
+            Color::from_rgb(0, 0xff),
+            &(context.transform_stack.transform().matrix * scroll_rect_mask),
+        );
+        context.renderer.pop_mask();
+    }
+
     if let Some(m) = mask {
         context.renderer.deactivate_mask();
         context.allow_mask = false;
@@ -460,6 +572,121 @@ pub fn render_base<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_
     context.transform_stack.pop();
 }
 
+/// Renders the content of an object, offset by the origin of its scroll rect (if any).
+fn render_scrolled<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    if this.scroll_rect().is_some() {
+        context.transform_stack.push(&Transform {
+            matrix: this.scroll_offset_matrix(),
+            color_transform: Default::default(),
+        });
+        this.render_self(context);
+        context.transform_stack.pop();
+    } else {
+        this.render_self(context);
+    }
+}
+
+/// Renders an object with `cacheAsBitmap` set.
+///
+/// The object is rendered into an offscreen bitmap at its current scale, rotation and skew, so
+/// that the bitmap is drawn pixel for pixel on the stage. The bitmap is then reused while the
+/// object only moves, until it is transformed some other way or its content changes. If the
+/// backend does not support offscreen rendering, the object is rendered normally.
+fn render_cached_bitmap<'gc>(this: DisplayObject<'gc>, context: &mut RenderContext<'_, 'gc>) {
+    let matrix = context.transform_stack.transform().matrix;
+    let scale = Matrix {
+        tx: Twips::zero(),
+        ty: Twips::zero(),
+        ..matrix
+    };
+    let bounds = this.bounds_with_transform(&scale);
+    if !bounds.valid {
+        return;
+    }
+
+    let origin = (bounds.x_min, bounds.y_min);
+    let size = (
+        (bounds.x_max - bounds.x_min).to_pixels().ceil() as u32,
+        (bounds.y_max - bounds.y_min).to_pixels().ceil() as u32,
+    );
+    if size.0 == 0 || size.1 == 0 {
+        return;
+    }
+
+    let cache = this.bitmap_cache().clone_state();
+    let is_stale = cache.dirty || children_changed(this);
+    let handle = match cache.handle {
+        Some(handle)
+            if !is_stale
+                && cache.size == size
+                && cache.origin == origin
+                && cache.scale == scale =>
+        {
+            handle
+        }
+        _ => {
+            // A bitmap of a different size can't be rendered into again.
+            if cache.size != size {
+                this.bitmap_cache().release(context.renderer);
+            }
+            let reuse = cache.handle.filter(|_| cache.size == size);
+            let handle = match context.renderer.begin_offscreen(size.0, size.1, reuse) {
+                Some(handle) => handle,
+                None => {
+                    this.bitmap_cache().release(context.renderer);
+                    render_scrolled(this, context);
+                    return;
+                }
+            };
+
+            // Render the object without its translation, moved so that its
+            // top-left corner is at the corner of the bitmap.
+            let offset =
+                Matrix::translate(Twips::new(-origin.0.get()), Twips::new(-origin.1.get())) * scale;
+            let mut offscreen_stack = TransformStack::new();
+            std::mem::swap(context.transform_stack, &mut offscreen_stack);
+            context.transform_stack.push(&Transform {
+                matrix: offset,
+                color_transform: Default::default(),
+            });
+            render_scrolled(this, context);
+            std::mem::swap(context.transform_stack, &mut offscreen_stack);
+            context.renderer.end_offscreen();
+
+            this.bitmap_cache().store(handle, size, origin, scale);
+            mark_children_unchanged(this);
+            handle
+        }
+    };
+
+    let transform = Transform {
+        matrix: Matrix::translate(matrix.tx + origin.0, matrix.ty + origin.1),
+        color_transform: context.transform_stack.transform().color_transform,
+    };
+    context.renderer.render_bitmap(handle, &transform, false);
+}
+
+/// Whether anything below `this` in the display list changed since the cached bitmaps above
+/// it were rendered.
+fn children_changed(this: DisplayObject<'_>) -> bool {
+    this.as_container().map_or(false, |container| {
+        container.iter_render_list().any(|child| {
+            let cache = child.bitmap_cache().clone_state();
+            cache.dirty || cache.moved || children_changed(child)
+        })
+    })
+}
+
+/// Clears the changes below `this` in the display list, once they are in its cached bitmap.
+fn mark_children_unchanged(this: DisplayObject<'_>) {
+    if let Some(container) = this.as_container() {
+        for child in container.iter_render_list() {
+            child.bitmap_cache().mark_unchanged();
+            mark_children_unchanged(child);
+        }
+    }
+}
+
 #[enum_trait_object(
     #[derive(Clone, Collect, Debug, Copy)]
     #[collect(no_drop)]
@@ -511,6 +738,18 @@ pub trait TDisplayObject<'gc>:
     /// it to the bounding box. This gives a tighter AABB then if we simply transformed
     /// the overall AABB.
     fn bounds_with_transform(&self, matrix: &Matrix) -> BoundingBox {
+        // An object with a scroll rect is exactly the size of the rect, regardless of its content.
+        if let Some(scroll_rect) = self.scroll_rect() {
+            let bounds = BoundingBox {
+                x_min: Twips::zero(),
+                y_min: Twips::zero(),
+                x_max: scroll_rect.width(),
+                y_max: scroll_rect.height(),
+                valid: true,
+            };
+            return bounds.transform(matrix);
+        }
+
         let mut bounds = self.self_bounds().transform(matrix);
 
         if let Some(ctr) = self.as_container() {
@@ -543,7 +782,7 @@ pub trait TDisplayObject<'gc>:
         let mut node = self.parent();
         let mut matrix = *self.matrix();
         while let Some(display_object) = node {
-            matrix = *display_object.matrix() * matrix;
+            matrix = *display_object.matrix() * display_object.scroll_offset_matrix() * matrix;
             node = display_object.parent();
         }
         matrix
@@ -823,6 +1062,78 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_visible`/`visible` ActionScript properties.
     fn set_visible(&self, gc_context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether this display object is rendered once into a bitmap, which is reused until
+    /// the object changes.
+    /// Returned by the `cacheAsBitmap` ActionScript property.
+    fn cache_as_bitmap(&self) -> bool;
+
+    /// Sets whether this display object is rendered once into a bitmap, which is reused until
+    /// the object changes.
+    /// Set by the `cacheAsBitmap` ActionScript property.
+    fn set_cache_as_bitmap(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// The bitmap this display object was last rendered into, if `cacheAsBitmap` is set.
+    fn bitmap_cache(&self) -> Ref<BitmapCache>;
+
+    /// The rectangle of this object's content that is displayed, in local coordinates.
+    /// The content is clipped to this rectangle, and offset so that the top-left corner
+    /// of the rectangle is at the object's origin. `None` if the content is not clipped.
+    /// Returned by the `scrollRect` ActionScript property.
+    fn scroll_rect(&self) -> Option<BoundingBox>;
+
+    /// Sets the rectangle of this object's content that is displayed, in local coordinates.
+    /// Set by the `scrollRect` ActionScript property.
+    fn set_scroll_rect(
+        &self,
+        gc_context: MutationContext<'gc, '_>,
+        scroll_rect: Option<BoundingBox>,
+    );
+
+    /// The matrix that positions this object's content (its drawing and children) in its
+    /// local space. This offsets the content by the origin of the scroll rect, if any.
+    fn scroll_offset_matrix(&self) -> Matrix {
+        match self.scroll_rect() {
+            Some(scroll_rect) => Matrix::translate(
+                Twips::new(-scroll_rect.x_min.get()),
+                Twips::new(-scroll_rect.y_min.get()),
+            ),
+            None => Matrix::identity(),
+        }
+    }
+
+    /// Whether a point on the stage lies within this object's scroll rect.
+    /// Always true if this object has no scroll rect.
+    fn scroll_rect_contains(&self, point: (Twips, Twips)) -> bool {
+        match self.scroll_rect() {
+            Some(scroll_rect) => {
+                let (x, y) = self.global_to_local(point);
+                x >= Twips::zero()
+                    && y >= Twips::zero()
+                    && x <= scroll_rect.width()
+                    && y <= scroll_rect.height()
+            }
+            None => true,
+        }
+    }
+
+    /// Marks the content of this display object as changed, so that the cached bitmaps of it
+    /// and its ancestors are re-rendered.
+    /// This must be called whenever the appearance of this object changes.
+    fn invalidate_cached_bitmap(&self) {
+        self.bitmap_cache().invalidate();
+    }
+
+    /// Marks the placement of this display object as changed, so that the cached bitmaps of
+    /// its ancestors are re-rendered.
+    /// This must be called whenever the transform or visibility of this object changes,
+    /// which does not affect its own cached bitmap.
+    ///
+    /// Ancestors are only checked for changes when they are rendered, so this never borrows
+    /// any other display object.
+    fn invalidate_cached_transform(&self) {
+        self.bitmap_cache().invalidate_transform();
+    }
+
     /// The sound transform for sounds played inside this display object.
     fn sound_transform(&self) -> Ref<SoundTransform>;
 
@@ -841,6 +1152,36 @@ pub trait TDisplayObject<'gc>:
     /// Returned by the `_lockroot` ActionScript property.
     fn set_lock_root(&self, gc_context: MutationContext<'gc, '_>, value: bool);
 
+    /// Whether this display object has been explicitly added to or left out of the tab order.
+    /// Returned by the `tabEnabled` ActionScript property.
+    fn tab_enabled(&self) -> Option<bool>;
+
+    /// Sets whether this display object is part of the tab order.
+    /// Set by the `tabEnabled` ActionScript property.
+    fn set_tab_enabled(&self, gc_context: MutationContext<'gc, '_>, value: Option<bool>);
+
+    /// Whether the children of this display object can be reached by tabbing.
+    /// Returned by the `tabChildren` ActionScript property.
+    fn tab_children(&self) -> bool;
+
+    /// Sets whether the children of this display object can be reached by tabbing.
+    /// Set by the `tabChildren` ActionScript property.
+    fn set_tab_children(&self, gc_context: MutationContext<'gc, '_>, value: bool);
+
+    /// The position of this display object in the tab order, if it has one.
+    /// Returned by the `tabIndex` ActionScript property.
+    fn tab_index(&self) -> Option<i32>;
+
+    /// Sets the position of this display object in the tab order.
+    /// Set by the `tabIndex` ActionScript property.
+    fn set_tab_index(&self, gc_context: MutationContext<'gc, '_>, value: Option<i32>);
+
+    /// Whether this display object is part of the tab order.
+    /// Focusable objects are, unless `tabEnabled` says otherwise.
+    fn is_tab_enabled(&self) -> bool {
+        self.tab_enabled().unwrap_or_else(|| self.is_focusable())
+    }
+
     /// Whether this display object has been transformed by ActionScript.
     /// When this flag is set, changes from SWF `PlaceObject` tags are ignored.
     fn transformed_by_script(&self) -> bool;
@@ -990,6 +1331,7 @@ pub trait TDisplayObject<'gc>:
             }
         }
 
+        self.bitmap_cache().release(context.renderer);
         self.set_removed(context.gc_context, true);
     }
 
@@ -1323,6 +1665,7 @@ macro_rules! impl_display_object_sansbounds {
             &self,
             context: gc_arena::MutationContext<'gc, '_>,
         ) -> std::cell::RefMut<swf::Matrix> {
+            self.invalidate_cached_transform();
             std::cell::RefMut::map(self.0.write(context), |o| o.$field.matrix_mut())
         }
         fn color_transform(&self) -> std::cell::Ref<crate::color_transform::ColorTransform> {
@@ -1332,6 +1675,7 @@ macro_rules! impl_display_object_sansbounds {
             &self,
             context: gc_arena::MutationContext<'gc, '_>,
         ) -> std::cell::RefMut<crate::color_transform::ColorTransform> {
+            self.invalidate_cached_transform();
             std::cell::RefMut::map(self.0.write(context), |o| o.$field.color_transform_mut())
         }
         fn set_color_transform(
@@ -1342,31 +1686,36 @@ macro_rules! impl_display_object_sansbounds {
             self.0
                 .write(context)
                 .$field
-                .set_color_transform(color_transform)
+                .set_color_transform(color_transform);
+            self.invalidate_cached_transform();
         }
         fn rotation(&self, gc_context: gc_arena::MutationContext<'gc, '_>) -> Degrees {
             self.0.write(gc_context).$field.rotation()
         }
         fn set_rotation(&self, gc_context: gc_arena::MutationContext<'gc, '_>, degrees: Degrees) {
-            self.0.write(gc_context).$field.set_rotation(degrees)
+            self.0.write(gc_context).$field.set_rotation(degrees);
+            self.invalidate_cached_transform();
         }
         fn scale_x(&self, gc_context: gc_arena::MutationContext<'gc, '_>) -> Percent {
             self.0.write(gc_context).$field.scale_x()
         }
         fn set_scale_x(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: Percent) {
-            self.0.write(gc_context).$field.set_scale_x(value)
+            self.0.write(gc_context).$field.set_scale_x(value);
+            self.invalidate_cached_transform();
         }
         fn scale_y(&self, gc_context: gc_arena::MutationContext<'gc, '_>) -> Percent {
             self.0.write(gc_context).$field.scale_y()
         }
         fn set_scale_y(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: Percent) {
-            self.0.write(gc_context).$field.set_scale_y(value)
+            self.0.write(gc_context).$field.set_scale_y(value);
+            self.invalidate_cached_transform();
         }
         fn alpha(&self) -> f64 {
             self.0.read().$field.alpha()
         }
         fn set_alpha(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: f64) {
-            self.0.write(gc_context).$field.set_alpha(value)
+            self.0.write(gc_context).$field.set_alpha(value);
+            self.invalidate_cached_transform();
         }
         fn name(&self) -> std::cell::Ref<str> {
             std::cell::Ref::map(self.0.read(), |o| o.$field.name())
@@ -1382,7 +1731,8 @@ macro_rules! impl_display_object_sansbounds {
             context: gc_arena::MutationContext<'gc, '_>,
             depth: crate::prelude::Depth,
         ) {
-            self.0.write(context).$field.set_clip_depth(depth)
+            self.0.write(context).$field.set_clip_depth(depth);
+            self.invalidate_cached_transform();
         }
         fn avm2_parent(&self) -> Option<crate::display_object::DisplayObject<'gc>> {
             self.0.read().$field.avm2_parent()
@@ -1429,6 +1779,7 @@ macro_rules! impl_display_object_sansbounds {
                 }
             }
             self.0.write(context).$field.set_masker(node);
+            self.invalidate_cached_bitmap();
         }
         fn maskee(&self) -> Option<DisplayObject<'gc>> {
             self.0.read().$field.maskee()
@@ -1445,6 +1796,7 @@ macro_rules! impl_display_object_sansbounds {
                 }
             }
             self.0.write(context).$field.set_maskee(node);
+            self.invalidate_cached_bitmap();
         }
         fn removed(&self) -> bool {
             self.0.read().$field.removed()
@@ -1471,6 +1823,27 @@ macro_rules! impl_display_object_sansbounds {
         }
         fn set_visible(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_visible(value);
+            self.invalidate_cached_transform();
+        }
+        fn cache_as_bitmap(&self) -> bool {
+            self.0.read().$field.cache_as_bitmap()
+        }
+        fn set_cache_as_bitmap(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_cache_as_bitmap(value);
+        }
+        fn bitmap_cache(&self) -> std::cell::Ref<crate::display_object::BitmapCache> {
+            std::cell::Ref::map(self.0.read(), |o| o.$field.bitmap_cache())
+        }
+        fn scroll_rect(&self) -> Option<crate::bounding_box::BoundingBox> {
+            self.0.read().$field.scroll_rect().cloned()
+        }
+        fn set_scroll_rect(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            scroll_rect: Option<crate::bounding_box::BoundingBox>,
+        ) {
+            self.0.write(context).$field.set_scroll_rect(scroll_rect);
+            self.invalidate_cached_bitmap();
         }
         fn lock_root(&self) -> bool {
             self.0.read().$field.lock_root()
@@ -1478,6 +1851,28 @@ macro_rules! impl_display_object_sansbounds {
         fn set_lock_root(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
             self.0.write(context).$field.set_lock_root(value);
         }
+        fn tab_enabled(&self) -> Option<bool> {
+            self.0.read().$field.tab_enabled()
+        }
+        fn set_tab_enabled(
+            &self,
+            context: gc_arena::MutationContext<'gc, '_>,
+            value: Option<bool>,
+        ) {
+            self.0.write(context).$field.set_tab_enabled(value);
+        }
+        fn tab_children(&self) -> bool {
+            self.0.read().$field.tab_children()
+        }
+        fn set_tab_children(&self, context: gc_arena::MutationContext<'gc, '_>, value: bool) {
+            self.0.write(context).$field.set_tab_children(value);
+        }
+        fn tab_index(&self) -> Option<i32> {
+            self.0.read().$field.tab_index()
+        }
+        fn set_tab_index(&self, context: gc_arena::MutationContext<'gc, '_>, value: Option<i32>) {
+            self.0.write(context).$field.set_tab_index(value);
+        }
         fn transformed_by_script(&self) -> bool {
             self.0.read().$field.transformed_by_script()
         }
@@ -1536,16 +1931,19 @@ macro_rules! impl_display_object {
             self.0.read().$field.x()
         }
         fn set_x(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: f64) {
-            self.0.write(gc_context).$field.set_x(value)
+            self.0.write(gc_context).$field.set_x(value);
+            self.invalidate_cached_transform();
         }
         fn y(&self) -> f64 {
             self.0.read().$field.y()
         }
         fn set_y(&self, gc_context: gc_arena::MutationContext<'gc, '_>, value: f64) {
-            self.0.write(gc_context).$field.set_y(value)
+            self.0.write(gc_context).$field.set_y(value);
+            self.invalidate_cached_transform();
         }
         fn set_matrix(&self, context: gc_arena::MutationContext<'gc, '_>, matrix: &swf::Matrix) {
-            self.0.write(context).$field.set_matrix(matrix)
+            self.0.write(context).$field.set_matrix(matrix);
+            self.invalidate_cached_transform();
         }
     };
 }
@@ -1560,7 +1958,7 @@ bitflags! {
     /// Bit flags used by `DisplayObject`.
     #[derive(Collect)]
     #[collect(no_drop)]
-    struct DisplayObjectFlags: u8 {
+    struct DisplayObjectFlags: u16 {
         /// Whether this object has been removed from the display list.
         /// Necessary in AVM1 to throw away queued actions from removed movie clips.
         const REMOVED                  = 1 << 0;
@@ -1586,6 +1984,106 @@ bitflags! {
         /// Whether this object has `_lockroot` set to true, in which case
         /// it becomes the _root of itself and of any children
         const LOCK_ROOT                = 1 << 6;
+
+        /// Whether this object is rendered into a bitmap that is reused until it changes (`cacheAsBitmap`).
+        const CACHE_AS_BITMAP          = 1 << 7;
+
+        /// Whether the children of this object are part of the tab order (`tabChildren`).
+        const TAB_CHILDREN             = 1 << 8;
+    }
+}
+
+/// The offscreen bitmap that a display object with `cacheAsBitmap` set is rendered into.
+///
+/// This is updated during rendering, so it uses interior mutability.
+#[derive(Debug, Default, Collect)]
+#[collect(require_static)]
+pub struct BitmapCache {
+    /// The bitmap containing the rendered object, if it has been rendered.
+    handle: Cell<Option<BitmapHandle>>,
+
+    /// The size of the bitmap, in pixels.
+    size: Cell<(u32, u32)>,
+
+    /// The position of the top-left corner of the bitmap, relative to the object's origin on
+    /// the stage.
+    origin: Cell<(Twips, Twips)>,
+
+    /// The scale, rotation and skew that the object was rendered with.
+    scale: Cell<Matrix>,
+
+    /// Whether the content of the object has changed since it was last rendered.
+    dirty: Cell<bool>,
+
+    /// Whether the transform or visibility of the object has changed since its parent was
+    /// last rendered.
+    moved: Cell<bool>,
+}
+
+/// A snapshot of the state of a `BitmapCache`.
+#[derive(Debug, Clone, Copy)]
+pub struct BitmapCacheState {
+    pub handle: Option<BitmapHandle>,
+    pub size: (u32, u32),
+    pub origin: (Twips, Twips),
+    pub scale: Matrix,
+    pub dirty: bool,
+    pub moved: bool,
+}
+
+impl BitmapCache {
+    /// Returns the current state of this cache.
+    pub fn clone_state(&self) -> BitmapCacheState {
+        BitmapCacheState {
+            handle: self.handle.get(),
+            size: self.size.get(),
+            origin: self.origin.get(),
+            scale: self.scale.get(),
+            dirty: self.dirty.get(),
+            moved: self.moved.get(),
+        }
+    }
+
+    /// Marks the content of the object as changed, so that it is re-rendered on the next frame.
+    pub fn invalidate(&self) {
+        self.dirty.set(true);
+    }
+
+    /// Marks the transform or visibility of the object as changed.
+    pub fn invalidate_transform(&self) {
+        self.moved.set(true);
+    }
+
+    /// Clears all changes, once they have been rendered into the bitmap of an ancestor.
+    fn mark_unchanged(&self) {
+        self.dirty.set(false);
+        self.moved.set(false);
+    }
+
+    /// Records a freshly rendered bitmap.
+    fn store(&self, handle: BitmapHandle, size: (u32, u32), origin: (Twips, Twips), scale: Matrix) {
+        self.handle.set(Some(handle));
+        self.size.set(size);
+        self.origin.set(origin);
+        self.scale.set(scale);
+        self.dirty.set(false);
+    }
+
+    /// Frees the bitmap of this cache, if it has one.
+    ///
+    /// This must be called once the object is no longer rendered as a bitmap.
+    pub fn release(&self, renderer: &mut dyn RenderBackend) {
+        if let Some(handle) = self.handle.take() {
+            renderer.unregister_bitmap(handle);
+            self.dirty.set(true);
+        }
+    }
+}
+
+impl Clone for BitmapCache {
+    /// Cloned objects never share a bitmap, so the clone starts out empty.
+    fn clone(&self) -> Self {
+        Default::default()
     }
 }
 
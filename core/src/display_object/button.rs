@@ -150,6 +150,7 @@ impl<'gc> Button<'gc> {
         // Children that exist in both states should persist and not be recreated.
         // TODO: This behavior probably differs in AVM2 (I suspect they always get recreated).
         let mut children = Vec::new();
+        let mut transforms = Vec::new();
 
         for record in &write.static_data.read().records {
             if record.states.contains(swf_state) {
@@ -179,16 +180,18 @@ impl<'gc> Button<'gc> {
                     }
                 };
 
-                // Set transform of child (and modify previous child if it already existed)
-                child.set_matrix(context.gc_context, &record.matrix);
-                child.set_color_transform(
-                    context.gc_context,
-                    &record.color_transform.clone().into(),
-                );
+                transforms.push((child, record.matrix, record.color_transform.clone()));
             }
         }
         drop(write);
 
+        // Set transform of child (and modify previous child if it already existed).
+        // This must happen after releasing the lock, as it invalidates our cached bitmap.
+        for (child, matrix, color_transform) in transforms {
+            child.set_matrix(context.gc_context, &matrix);
+            child.set_color_transform(context.gc_context, &color_transform.into());
+        }
+
         // Kill children that no longer exist in this state.
         for depth in removed_depths {
             if let Some(child) = self.child_by_depth(depth) {
@@ -530,6 +533,7 @@ impl<'gc> TDisplayObject<'gc> for Button<'gc> {
         } else if let Some(node) = self.masker() {
             node.set_maskee(context.gc_context, None, true);
         }
+        self.bitmap_cache().release(context.renderer);
         self.set_removed(context.gc_context, true);
     }
 }
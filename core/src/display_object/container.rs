@@ -332,6 +332,10 @@ pub trait TDisplayObjectContainer<'gc>:
 
     /// Renders the children of this container in render list order.
     fn render_children(self, context: &mut RenderContext<'_, 'gc>) {
+        // Children entirely outside of our scroll rect would be clipped anyway.
+        let this: DisplayObjectContainer<'gc> = self.into();
+        let scroll_rect = DisplayObject::from(this).scroll_rect();
+
         let mut clip_depth = 0;
         let mut clip_depth_stack: Vec<(Depth, DisplayObject<'_>)> = vec![];
         for child in self.iter_render_list() {
@@ -359,7 +363,10 @@ pub trait TDisplayObjectContainer<'gc>:
                 child.render(context);
                 context.allow_mask = true;
                 context.renderer.activate_mask();
-            } else if child.visible() {
+            } else if scroll_rect
+                .as_ref()
+                .map_or(true, |rect| rect.intersects(&child.local_bounds()))
+            {
                 // Normal child.
                 child.render(context);
             }
@@ -376,6 +383,16 @@ pub trait TDisplayObjectContainer<'gc>:
     }
 }
 
+impl<'gc> From<DisplayObjectContainer<'gc>> for DisplayObject<'gc> {
+    fn from(container: DisplayObjectContainer<'gc>) -> Self {
+        match container {
+            DisplayObjectContainer::Stage(stage) => stage.into(),
+            DisplayObjectContainer::Button(button) => button.into(),
+            DisplayObjectContainer::MovieClip(movie_clip) => movie_clip.into(),
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! impl_display_object_container {
     ($field:ident) => {
@@ -475,6 +492,8 @@ macro_rules! impl_display_object_container {
                 removed_child.set_parent(context.gc_context, None);
             }
 
+            self.invalidate_cached_bitmap();
+
             removed_child
         }
 
@@ -498,6 +517,8 @@ macro_rules! impl_display_object_container {
                 child,
                 depth,
             );
+
+            self.invalidate_cached_bitmap();
         }
 
         fn insert_at_index(
@@ -531,6 +552,8 @@ macro_rules! impl_display_object_container {
                 .$field
                 .insert_at_id(context, child, index);
 
+            self.invalidate_cached_bitmap();
+
             if parent_changed {
                 dispatch_added_event(
                     DisplayObject::from(*self),
@@ -551,6 +574,8 @@ macro_rules! impl_display_object_container {
                 .write(context.gc_context)
                 .$field
                 .swap_at_id(index1, index2);
+
+            self.invalidate_cached_bitmap();
         }
 
         fn remove_child(
@@ -589,6 +614,8 @@ macro_rules! impl_display_object_container {
                 }
             }
 
+            self.invalidate_cached_bitmap();
+
             removed_from_render_list || removed_from_depth_list || removed_from_execution_list
         }
 
@@ -628,6 +655,10 @@ macro_rules! impl_display_object_container {
 
                 write = self.0.write(context.gc_context);
             }
+
+            drop(write);
+
+            self.invalidate_cached_bitmap();
         }
 
         fn clear(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) {
@@ -641,7 +672,9 @@ macro_rules! impl_display_object_container {
             self.0
                 .write(context.gc_context)
                 .$field
-                .clear(context.gc_context)
+                .clear(context.gc_context);
+
+            self.invalidate_cached_bitmap();
         }
 
         fn is_empty(self) -> bool {
@@ -15,7 +15,7 @@ use crate::display_object::{DisplayObjectBase, TDisplayObject};
 use crate::drawing::Drawing;
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode};
 use crate::font::{Glyph, TextRenderSettings};
-use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutContent, TextFormat};
+use crate::html::{BoxBounds, FormatSpans, LayoutBox, LayoutCache, LayoutContent, TextFormat};
 use crate::prelude::*;
 use crate::shape_utils::DrawCommand;
 use crate::string_utils;
@@ -126,6 +126,10 @@ pub struct EditTextData<'gc> {
     /// The calculated layout box.
     layout: Vec<LayoutBox<'gc>>,
 
+    /// The paragraphs of the calculated layout, used to only lay out the
+    /// paragraphs affected by later edits.
+    layout_cache: LayoutCache<'gc>,
+
     /// The intrinsic bounds of the laid-out text.
     intrinsic_bounds: BoxBounds<Twips>,
 
@@ -196,6 +200,7 @@ impl<'gc> EditText<'gc> {
 
         let bounds: BoundingBox = swf_tag.bounds.clone().into();
 
+        let mut layout_cache = LayoutCache::default();
         let (layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
             &text_spans,
             context,
@@ -203,6 +208,9 @@ impl<'gc> EditText<'gc> {
             bounds.width() - Twips::from_pixels(Self::INTERNAL_PADDING * 2.0),
             swf_tag.is_word_wrap,
             swf_tag.is_device_font,
+            Vec::new(),
+            &mut layout_cache,
+            0,
         );
 
         let has_background = swf_tag.has_border;
@@ -272,6 +280,7 @@ impl<'gc> EditText<'gc> {
                 drawing: Drawing::new(),
                 object: None,
                 layout,
+                layout_cache,
                 intrinsic_bounds,
                 bounds,
                 autosize: AutoSizeMode::None,
@@ -334,7 +343,8 @@ impl<'gc> EditText<'gc> {
             has_border: false,
             was_static: false,
             is_html: false,
-            is_device_font: false,
+            // Dynamically created text fields do not embed fonts by default.
+            is_device_font: true,
         };
 
         let text_field = Self::from_swf_tag(context, swf_movie, swf_tag);
@@ -361,11 +371,26 @@ impl<'gc> EditText<'gc> {
         let len = edit_text.text_spans.text().len();
         let tf = edit_text.text_spans.default_format().clone();
 
-        edit_text.text_spans.replace_text(0, len, &text, Some(&tf));
+        // Text that only appends to plain text (such as `text += line`) is
+        // added to the end, so that the existing text doesn't need to be laid
+        // out again.
+        let is_plain_text = edit_text
+            .text_spans
+            .iter_spans()
+            .all(|(start, end, _, span)| start == end || span.get_text_format() == tf);
+        let changed_from = if is_plain_text && text.starts_with(edit_text.text_spans.text()) {
+            edit_text
+                .text_spans
+                .replace_text(len, len, &text[len..], Some(&tf));
+            len
+        } else {
+            edit_text.text_spans.replace_text(0, len, &text, Some(&tf));
+            0
+        };
 
         drop(edit_text);
 
-        self.relayout(context);
+        self.relayout_from(context, changed_from);
 
         Ok(())
     }
@@ -589,11 +614,35 @@ impl<'gc> EditText<'gc> {
         text: &str,
         context: &mut UpdateContext<'_, 'gc, '_>,
     ) {
-        self.0
-            .write(context.gc_context)
-            .text_spans
-            .replace_text(from, to, text, None);
-        self.relayout(context);
+        if to < from {
+            return;
+        }
+
+        let mut edit_text = self.0.write(context.gc_context);
+        edit_text.text_spans.replace_text(from, to, text, None);
+        let length = edit_text.text_spans.text().len();
+
+        // Keep the selection on the same text, moving it along with any text
+        // after the edit.
+        if let Some(selection) = &mut edit_text.selection {
+            let move_position = |position: usize| {
+                if position <= from {
+                    position
+                } else if position >= to {
+                    position - (to - from) + text.len()
+                } else {
+                    from + text.len()
+                }
+            };
+            *selection = TextSelection::for_range(
+                move_position(selection.from),
+                move_position(selection.to),
+            );
+            selection.clamp(length);
+        }
+
+        drop(edit_text);
+        self.relayout_from(context, from);
     }
 
     /// Construct a base text transform for a particular `EditText` span.
@@ -682,6 +731,8 @@ impl<'gc> EditText<'gc> {
 
     /// Redraw the border of this `EditText`.
     fn redraw_border(self, gc_context: MutationContext<'gc, '_>) {
+        self.invalidate_cached_bitmap();
+
         let mut write = self.0.write(gc_context);
 
         write.drawing.clear();
@@ -743,6 +794,17 @@ impl<'gc> EditText<'gc> {
     /// have already been calculated and applied to HTML trees lowered into the
     /// text-span representation.
     fn relayout(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        self.relayout_from(context, 0)
+    }
+
+    /// Relayout the `EditText` after its text changed from the given position
+    /// onwards.
+    ///
+    /// Only the paragraphs from the one containing that position are laid
+    /// out again.
+    fn relayout_from(self, context: &mut UpdateContext<'_, 'gc, '_>, changed_from: usize) {
+        self.invalidate_cached_bitmap();
+
         let mut edit_text = self.0.write(context.gc_context);
         let autosize = edit_text.autosize;
         let is_word_wrap = edit_text.is_word_wrap;
@@ -757,6 +819,8 @@ impl<'gc> EditText<'gc> {
             edit_text.text_spans.clear_displayed_text();
         }
 
+        let previous_layout = std::mem::take(&mut edit_text.layout);
+        let mut layout_cache = std::mem::take(&mut edit_text.layout_cache);
         let (new_layout, intrinsic_bounds) = LayoutBox::lower_from_text_spans(
             &edit_text.text_spans,
             context,
@@ -764,9 +828,13 @@ impl<'gc> EditText<'gc> {
             width,
             is_word_wrap,
             edit_text.is_device_font,
+            previous_layout,
+            &mut layout_cache,
+            changed_from,
         );
 
         edit_text.layout = new_layout;
+        edit_text.layout_cache = layout_cache;
         edit_text.intrinsic_bounds = intrinsic_bounds;
 
         match autosize {
@@ -943,6 +1011,8 @@ impl<'gc> EditText<'gc> {
     /// If we don't find a display object, we register ourselves on a list of pending unbound text fields.
     /// Whenever a display object is created, the unbound list is checked to see if the new object should be bound.
     /// This is called when the text field is created, and, if the text field is in the unbound list, anytime a display object is created.
+    /// If the variable already exists when binding, the text field takes on its value. Otherwise, `set_initial_value` determines
+    /// whether the variable is created from the text field's current text.
     pub fn try_bind_text_field_variable(
         self,
         activation: &mut Avm1Activation<'_, 'gc, '_>,
@@ -969,30 +1039,28 @@ impl<'gc> EditText<'gc> {
                     if let Ok(Some((object, property))) =
                         activation.resolve_variable_path(parent, &variable)
                     {
-                        // If this text field was just created, we immediately propagate the text to the variable (or vice versa).
-                        if set_initial_value {
-                            // If the property exists on the object, we overwrite the text with the property's value.
-                            if object.has_property(activation, property) {
-                                let value = object.get(property, activation).unwrap();
-                                let _ = self.set_html_text(
-                                    value
-                                        .coerce_to_string(activation)
-                                        .unwrap_or_default()
-                                        .to_string(),
-                                    &mut activation.context,
+                        // If the property exists on the object, we overwrite the text with the property's value.
+                        // This also applies to text fields that are bound late, after the variable was set.
+                        if object.has_property(activation, property) {
+                            let value = object.get(property, activation).unwrap();
+                            let _ = self.set_html_text(
+                                value
+                                    .coerce_to_string(activation)
+                                    .unwrap_or_default()
+                                    .to_string(),
+                                &mut activation.context,
+                            );
+                        } else if set_initial_value {
+                            // If this text field was just created, we initialize the property with the text field's text, if it's non-empty.
+                            // Note that HTML text fields are often initialized with an empty <p> tag, which is not considered empty.
+                            let text = self.text();
+                            if !text.is_empty() {
+                                let _ = object.set(
+                                    property,
+                                    AvmString::new(activation.context.gc_context, self.text())
+                                        .into(),
+                                    activation,
                                 );
-                            } else {
-                                // Otherwise, we initialize the property with the text field's text, if it's non-empty.
-                                // Note that HTML text fields are often initialized with an empty <p> tag, which is not considered empty.
-                                let text = self.text();
-                                if !text.is_empty() {
-                                    let _ = object.set(
-                                        property,
-                                        AvmString::new(activation.context.gc_context, self.text())
-                                            .into(),
-                                        activation,
-                                    );
-                                }
                             }
                         }
 
@@ -1079,6 +1147,8 @@ impl<'gc> EditText<'gc> {
         selection: Option<TextSelection>,
         gc_context: MutationContext<'gc, '_>,
     ) {
+        self.invalidate_cached_bitmap();
+
         let mut text = self.0.write(gc_context);
         if let Some(mut selection) = selection {
             selection.clamp(text.text_spans.text().len());
@@ -1594,6 +1664,7 @@ impl<'gc> TDisplayObject<'gc> for EditText<'gc> {
                 .retain(|&text_field| !DisplayObject::ptr_eq(text_field.into(), (*self).into()));
         }
 
+        self.bitmap_cache().release(context.renderer);
         self.set_removed(context.gc_context, true);
     }
 
@@ -1806,3 +1877,83 @@ impl TextSelection {
         self.to == self.from
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::PlayerBuilder;
+
+    fn layout_bounds(text_field: EditText<'_>) -> (Vec<BoxBounds<Twips>>, BoxBounds<Twips>) {
+        let edit_text = text_field.0.read();
+        let boxes = edit_text.layout.iter().map(|lbox| lbox.bounds()).collect();
+        (boxes, edit_text.intrinsic_bounds)
+    }
+
+    #[test]
+    fn appending_lines_only_lays_out_new_text() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(SwfMovie::empty(8)));
+
+        player.update(|context| {
+            let movie = context.swf.clone();
+            let text_field = EditText::new(context, movie.clone(), 0.0, 0.0, 200.0, 100.0);
+            text_field.set_word_wrap(true, context);
+            text_field.set_selection(Some(TextSelection::for_position(0)), context.gc_context);
+
+            for i in 0..200 {
+                let len = text_field.text().len();
+                text_field.replace_text(len, len, &format!("line {}\n", i), context);
+                assert_eq!(text_field.0.read().layout_cache.last_layout_start(), len);
+            }
+
+            // Editing a line lays out that line and the ones after it.
+            let line_start = text_field.text().find("line 100").unwrap();
+            text_field.replace_text(line_start + 5, line_start + 8, "one hundred", context);
+            assert_eq!(
+                text_field.0.read().layout_cache.last_layout_start(),
+                line_start
+            );
+
+            let fresh = EditText::new(context, movie, 0.0, 0.0, 200.0, 100.0);
+            fresh.set_word_wrap(true, context);
+            fresh.set_text(text_field.text(), context).unwrap();
+            assert_eq!(fresh.0.read().layout_cache.last_layout_start(), 0);
+            assert_eq!(layout_bounds(text_field), layout_bounds(fresh));
+        });
+    }
+
+    #[test]
+    fn edits_move_selection() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(SwfMovie::empty(8)));
+
+        player.update(|context| {
+            let movie = context.swf.clone();
+            let text_field = EditText::new(context, movie, 0.0, 0.0, 200.0, 100.0);
+            text_field
+                .set_text("first\nsecond\nthird".to_string(), context)
+                .unwrap();
+
+            text_field.set_selection(Some(TextSelection::for_range(6, 12)), context.gc_context);
+            text_field.replace_text(0, 0, ">> ", context);
+            assert_eq!(
+                text_field.selection().map(|s| (s.from(), s.to())),
+                Some((9, 15))
+            );
+
+            text_field.replace_text(20, 20, "!", context);
+            assert_eq!(
+                text_field.selection().map(|s| (s.from(), s.to())),
+                Some((9, 15))
+            );
+
+            text_field.replace_text(11, 13, "", context);
+            assert_eq!(
+                text_field.selection().map(|s| (s.from(), s.to())),
+                Some((9, 13))
+            );
+        });
+    }
+}
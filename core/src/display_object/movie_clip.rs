@@ -259,6 +259,7 @@ impl<'gc> MovieClip<'gc> {
                 if let Err(e) = library.check_avm_type(avm_type) {
                     log::warn!("{}", e);
                 }
+                library.set_uses_network_sandbox(attributes.use_network_sandbox);
 
                 Ok(())
             }
@@ -377,6 +378,9 @@ impl<'gc> MovieClip<'gc> {
                 .write(context.gc_context)
                 .define_text(context, reader, 2),
             TagCode::DoInitAction => self.do_init_action(context, reader, tag_len),
+            TagCode::EnableDebugger | TagCode::EnableDebugger2 => {
+                self.0.write(context.gc_context).enable_debugger(context)
+            }
             TagCode::DoAbc => self.do_abc(context, reader, tag_len),
             TagCode::SymbolClass => self.symbol_class(context, reader),
             TagCode::DefineSceneAndFrameLabelData => {
@@ -397,6 +401,7 @@ impl<'gc> MovieClip<'gc> {
                 .0
                 .write(context.gc_context)
                 .jpeg_tables(context, reader, tag_len),
+            TagCode::Metadata => self.0.write(context.gc_context).metadata(context, reader),
             TagCode::PlaceObject => self.0.write(context.gc_context).preload_place_object(
                 context,
                 reader,
@@ -442,6 +447,7 @@ impl<'gc> MovieClip<'gc> {
                     .write(context.gc_context)
                     .preload_show_frame(context, reader, &mut cur_frame)
             }
+            TagCode::Protect => self.0.write(context.gc_context).protect(context),
             TagCode::ScriptLimits => self
                 .0
                 .write(context.gc_context)
@@ -477,7 +483,16 @@ impl<'gc> MovieClip<'gc> {
             }
             _ => Ok(()),
         };
-        let _ = tag_utils::decode_tags(&mut reader, tag_callback, TagCode::End);
+        let mut unknown_tags = Vec::new();
+        let _ = tag_utils::decode_tags_reporting_unknown(
+            &mut reader,
+            tag_callback,
+            |tag_code| unknown_tags.push(tag_code),
+            TagCode::End,
+        );
+        for tag_code in unknown_tags {
+            context.diagnostics.record_unknown_tag(tag_code);
+        }
 
         // Finalize audio stream.
         if let Some(stream) = preload_stream_handle {
@@ -676,10 +691,9 @@ impl<'gc> MovieClip<'gc> {
         }
 
         for FrameLabelData { frame_num, label } in sfl_data.frame_labels {
-            static_data.frame_labels.insert(
-                label.to_string_lossy(reader.encoding()),
-                frame_num as u16 + 1,
-            );
+            let label = label.to_string_lossy(reader.encoding());
+            static_data.add_frame_label(label.clone(), frame_num as u16 + 1);
+            static_data.frame_labels.insert(label, frame_num as u16 + 1);
         }
 
         Ok(())
@@ -889,7 +903,7 @@ impl<'gc> MovieClip<'gc> {
         let current_frame = read.current_frame();
         let mut best: Option<(&str, FrameNumber)> = None;
 
-        for (label, frame) in read.static_data.frame_labels.iter() {
+        for (label, frame) in read.static_data.all_frame_labels.iter() {
             if *frame > current_frame {
                 continue;
             }
@@ -910,7 +924,7 @@ impl<'gc> MovieClip<'gc> {
 
         let mut values: Vec<(String, FrameNumber)> = read
             .static_data
-            .frame_labels
+            .all_frame_labels
             .iter()
             .filter(|(_label, frame)| **frame >= from && **frame < to)
             .map(|(label, frame)| (label.clone(), *frame))
@@ -930,6 +944,14 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().static_data.total_frames
     }
 
+    /// Whether a point on the stage hits this clip's own drawing, ignoring its children.
+    pub fn hit_test_drawing(self, point: (Twips, Twips)) -> bool {
+        let mut local_matrix = self.local_to_global_matrix() * self.scroll_offset_matrix();
+        local_matrix.invert();
+        let point = local_matrix * point;
+        self.0.read().drawing.hit_test(point, &local_matrix)
+    }
+
     pub fn set_avm2_constructor(
         self,
         gc_context: MutationContext<'gc, '_>,
@@ -945,6 +967,16 @@ impl<'gc> MovieClip<'gc> {
         self.0.read().static_data.frame_labels.get(&label).copied()
     }
 
+    /// Find a scene by name.
+    pub fn scene(self, scene_label: &str) -> Option<Scene> {
+        self.0
+            .read()
+            .static_data
+            .scene_labels
+            .get(scene_label)
+            .cloned()
+    }
+
     pub fn scene_label_to_number(self, scene_label: &str) -> Option<FrameNumber> {
         //TODO: Are scene labels also case insensitive?
         self.0
@@ -956,36 +988,22 @@ impl<'gc> MovieClip<'gc> {
             .copied()
     }
 
-    pub fn frame_exists_within_scene(self, frame_label: &str, scene_label: &str) -> bool {
-        let scene = self.scene_label_to_number(scene_label);
-        let frame = self.frame_label_to_number(frame_label);
-
-        if scene.is_none() || frame.is_none() {
-            return false;
-        }
-
-        let scene = scene.unwrap();
-        let frame = frame.unwrap();
-
-        if scene <= frame {
-            let mut end = self.total_frames();
-            for (
-                _label,
-                Scene {
-                    start: new_scene_start,
-                    ..
-                },
-            ) in self.0.read().static_data.scene_labels.iter()
-            {
-                if *new_scene_start < end && *new_scene_start > scene {
-                    end = *new_scene_start;
-                }
-            }
+    /// Find the frame with the given label within a scene.
+    ///
+    /// Unlike `frame_label_to_number`, this is case sensitive, as labels are
+    /// in AVM2.
+    pub fn frame_label_in_scene(self, frame_label: &str, scene: &Scene) -> Option<FrameNumber> {
+        let scene_end = u32::from(scene.start) + u32::from(scene.length);
 
-            frame < end
-        } else {
-            false
-        }
+        self.0
+            .read()
+            .static_data
+            .all_frame_labels
+            .iter()
+            .find(|(label, frame)| {
+                label == frame_label && *frame >= scene.start && u32::from(*frame) < scene_end
+            })
+            .map(|(_, frame)| *frame)
     }
 
     /// Gets the clip events for this movieclip.
@@ -1869,6 +1887,10 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 }
             }
 
+            if !self.scroll_rect_contains(point) {
+                return false;
+            }
+
             let mut clip_depth = 0;
 
             for child in self.iter_render_list() {
@@ -1892,9 +1914,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
                 }
             }
 
-            let local_matrix = self.global_to_local_matrix();
-            let point = local_matrix * point;
-            if self.0.read().drawing.hit_test(point, &local_matrix) {
+            if self.hit_test_drawing(point) {
                 return true;
             }
         }
@@ -2037,6 +2057,8 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
     }
 
     fn as_drawing(&self, gc_context: MutationContext<'gc, '_>) -> Option<RefMut<'_, Drawing>> {
+        // The drawing is only borrowed mutably to change it.
+        self.invalidate_cached_bitmap();
         Some(RefMut::map(self.0.write(gc_context), |s| &mut s.drawing))
     }
 
@@ -2113,6 +2135,7 @@ impl<'gc> TDisplayObject<'gc> for MovieClip<'gc> {
             mc.stop_audio_stream(context);
             mc.run_clip_event((*self).into(), context, ClipEvent::Unload);
         }
+        self.bitmap_cache().release(context.renderer);
         self.set_removed(context.gc_context, true);
     }
 
@@ -2912,10 +2935,22 @@ impl<'gc, 'a> MovieClipData<'gc> {
     #[inline]
     fn define_font_4(
         &mut self,
-        _context: &mut UpdateContext<'_, 'gc, '_>,
-        _reader: &mut SwfStream<'a>,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
     ) -> DecodeResult {
-        log::warn!("DefineFont4 tag (TLF text) is not implemented");
+        let font = reader.read_define_font_4()?;
+        match Font::from_font4_tag(
+            context.gc_context,
+            context.renderer,
+            &font,
+            reader.encoding(),
+        ) {
+            Ok(font_object) => context
+                .library
+                .library_for_movie_mut(self.movie())
+                .register_character(font.id, Character::Font(font_object)),
+            Err(e) => log::warn!("Unable to load DefineFont4 font {}: {}", font.id, e),
+        }
         Ok(())
     }
 
@@ -2932,6 +2967,9 @@ impl<'gc, 'a> MovieClipData<'gc> {
                 .library_for_movie_mut(self.movie())
                 .register_character(sound.id, Character::Sound(handle));
         } else {
+            context
+                .diagnostics
+                .record_unsupported_codec(&format!("{:?}", sound.format.compression));
             log::error!(
                 "MovieClip::define_sound: Unable to register sound ID {}",
                 sound.id
@@ -3054,11 +3092,11 @@ impl<'gc, 'a> MovieClipData<'gc> {
         static_data: &mut MovieClipStatic,
     ) -> DecodeResult {
         let frame_label = reader.read_frame_label(tag_len)?;
-        // Frame labels are case insensitive (ASCII).
-        let label = frame_label
-            .label
-            .to_str_lossy(reader.encoding())
-            .to_ascii_lowercase();
+        let label = frame_label.label.to_str_lossy(reader.encoding());
+        static_data.add_frame_label(label.to_string(), cur_frame);
+
+        // Frame labels are case insensitive (ASCII) in AVM1.
+        let label = label.to_ascii_lowercase();
         if let std::collections::hash_map::Entry::Vacant(v) = static_data.frame_labels.entry(label)
         {
             v.insert(cur_frame);
@@ -3086,6 +3124,41 @@ impl<'gc, 'a> MovieClipData<'gc> {
         Ok(())
     }
 
+    #[inline]
+    fn enable_debugger(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) -> DecodeResult {
+        // The password is not kept, as Ruffle never asks for it.
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .set_debug_enabled();
+        Ok(())
+    }
+
+    #[inline]
+    fn metadata(
+        &mut self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        reader: &mut SwfStream<'a>,
+    ) -> DecodeResult {
+        // Metadata is always UTF-8 XML, whatever the SWF version.
+        let metadata = reader.read_str()?.to_string_lossy(swf::UTF_8);
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .set_metadata(metadata);
+        Ok(())
+    }
+
+    #[inline]
+    fn protect(&mut self, context: &mut UpdateContext<'_, 'gc, '_>) -> DecodeResult {
+        // The password is not kept, as Ruffle never asks for it.
+        context
+            .library
+            .library_for_movie_mut(self.movie())
+            .set_protected();
+        Ok(())
+    }
+
     #[inline]
     fn preload_remove_object(
         &mut self,
@@ -3319,7 +3392,7 @@ impl<'gc, 'a> MovieClip<'gc> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Scene {
     pub name: String,
     pub start: FrameNumber,
@@ -3344,6 +3417,9 @@ struct MovieClipStatic {
     id: CharacterId,
     swf: SwfSlice,
     frame_labels: HashMap<String, FrameNumber>,
+    /// Every frame label with its original case, including labels that are
+    /// reused across scenes.
+    all_frame_labels: Vec<(String, FrameNumber)>,
     scene_labels: HashMap<String, Scene>,
     audio_stream_info: Option<swf::SoundStreamHead>,
     audio_stream_handle: Option<SoundHandle>,
@@ -3364,12 +3440,24 @@ impl MovieClipStatic {
             swf,
             total_frames,
             frame_labels: HashMap::new(),
+            all_frame_labels: Vec::new(),
             scene_labels: HashMap::new(),
             audio_stream_info: None,
             audio_stream_handle: None,
             exported_name: RefCell::new(None),
         }
     }
+
+    /// Record a frame label, unless it was already declared by another tag.
+    fn add_frame_label(&mut self, label: String, frame: FrameNumber) {
+        if !self
+            .all_frame_labels
+            .iter()
+            .any(|(other, other_frame)| *other == label && *other_frame == frame)
+        {
+            self.all_frame_labels.push((label, frame));
+        }
+    }
 }
 
 /// Stores the placement settings for display objects during a
@@ -3583,3 +3671,200 @@ pub struct Avm2FrameScript<'gc> {
     /// The AVM2 callable object to invoke when the frame script runs.
     pub callable: Avm2Object<'gc>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{as3_file_attributes, swf_header, swf_movie};
+
+    /// Builds an AVM2 movie whose document class `Main` extends `MovieClip`.
+    fn document_class_movie() -> SwfMovie {
+        use swf::avm2::types::{
+            AbcFile, Class, ConstantPool, Index, Instance, Method, MethodBody, Multiname,
+            Namespace, Script, Trait, TraitKind,
+        };
+
+        let method = || Method {
+            name: Index::new(0),
+            params: vec![],
+            return_type: Index::new(0),
+            needs_arguments_object: false,
+            needs_activation: false,
+            needs_rest: false,
+            needs_dxns: false,
+        };
+        let body = |method, code| MethodBody {
+            method: Index::new(method),
+            max_stack: 2,
+            num_locals: 1,
+            init_scope_depth: 0,
+            max_scope_depth: 1,
+            code,
+            exceptions: vec![],
+            traits: vec![],
+        };
+
+        // Constant pool indices start at 1.
+        let abc = AbcFile {
+            major_version: 46,
+            minor_version: 16,
+            constant_pool: ConstantPool {
+                ints: vec![],
+                uints: vec![],
+                doubles: vec![],
+                strings: vec![
+                    "".to_string(),
+                    "flash.display".to_string(),
+                    "MovieClip".to_string(),
+                    "Main".to_string(),
+                ],
+                namespaces: vec![
+                    Namespace::Package(Index::new(1)),
+                    Namespace::Package(Index::new(2)),
+                ],
+                namespace_sets: vec![],
+                multinames: vec![
+                    // Main
+                    Multiname::QName {
+                        namespace: Index::new(1),
+                        name: Index::new(4),
+                    },
+                    // flash.display.MovieClip
+                    Multiname::QName {
+                        namespace: Index::new(2),
+                        name: Index::new(3),
+                    },
+                ],
+            },
+            methods: vec![method(), method(), method()],
+            metadata: vec![],
+            instances: vec![Instance {
+                name: Index::new(1),
+                super_name: Index::new(2),
+                is_sealed: false,
+                is_final: false,
+                is_interface: false,
+                protected_namespace: None,
+                interfaces: vec![],
+                init_method: Index::new(2),
+                traits: vec![],
+            }],
+            classes: vec![Class {
+                init_method: Index::new(1),
+                traits: vec![],
+            }],
+            scripts: vec![Script {
+                init_method: Index::new(0),
+                traits: vec![Trait {
+                    name: Index::new(1),
+                    kind: TraitKind::Class {
+                        slot_id: 1,
+                        class: Index::new(0),
+                    },
+                    metadata: vec![],
+                    is_final: false,
+                    is_override: false,
+                }],
+            }],
+            method_bodies: vec![
+                // getlocal0; pushscope; getscopeobject 0; getlex MovieClip;
+                // newclass Main; initproperty Main; returnvoid
+                body(
+                    0,
+                    vec![0xD0, 0x30, 0x65, 0, 0x60, 2, 0x58, 0, 0x68, 1, 0x47],
+                ),
+                // returnvoid
+                body(1, vec![0x47]),
+                // getlocal0; constructsuper 0; returnvoid
+                body(2, vec![0xD0, 0x49, 0, 0x47]),
+            ],
+        };
+        let mut abc_data = Vec::new();
+        swf::avm2::write::Writer::new(&mut abc_data)
+            .write(abc)
+            .unwrap();
+
+        swf_movie(
+            swf_header(10, 10.0, 1),
+            vec![
+                as3_file_attributes(),
+                swf::Tag::DoAbc(swf::DoAbc {
+                    name: "".into(),
+                    is_lazy_initialize: true,
+                    data: &abc_data,
+                }),
+                swf::Tag::SymbolClass(vec![swf::SymbolClassLink {
+                    id: 0,
+                    class_name: "Main".into(),
+                }]),
+                swf::Tag::ShowFrame,
+            ],
+        )
+    }
+
+    #[test]
+    fn document_class_applies_to_root() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(document_class_movie()));
+        player.run_frame();
+
+        let is_main = player.update(|context| {
+            let root = context.stage.root_clip();
+            let domain = context
+                .library
+                .library_for_movie_mut(root.movie().unwrap())
+                .avm2_domain();
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let main_class = domain
+                .get_defined_value(
+                    &mut activation,
+                    Avm2QName::new(Avm2Namespace::public(), "Main"),
+                )
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let root_object = root.object2().coerce_to_object(&mut activation).unwrap();
+            root_object
+                .is_instance_of(&mut activation, main_class, false)
+                .unwrap()
+        });
+        assert!(is_main);
+    }
+
+    /// Preloads a root movie with the given tags, returning whether it has
+    /// debugging enabled, whether it is protected, and its metadata.
+    fn publishing_settings(mut tags: Vec<swf::Tag<'_>>) -> (bool, bool, Option<String>) {
+        tags.push(swf::Tag::ShowFrame);
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(swf_movie(swf_header(10, 24.0, 1), tags)));
+
+        player.update(|context| {
+            let library = context.library.library_for_movie_mut(context.swf.clone());
+            (
+                library.is_debug_enabled(),
+                library.is_protected(),
+                library.metadata().map(str::to_string),
+            )
+        })
+    }
+
+    #[test]
+    fn preload_reads_publishing_tags() {
+        let metadata = "<rdf:RDF xmlns:rdf=\"http://www.w3.org/1999/02/22-rdf-syntax-ns#\"/>";
+        let settings = publishing_settings(vec![
+            swf::Tag::EnableDebugger(swf::SwfStr::from_utf8_str("")),
+            swf::Tag::Protect(None),
+            swf::Tag::Metadata(swf::SwfStr::from_utf8_str(metadata)),
+        ]);
+
+        assert_eq!(settings, (true, true, Some(metadata.to_string())));
+    }
+
+    #[test]
+    fn publishing_tags_are_optional() {
+        assert_eq!(publishing_settings(vec![]), (false, false, None));
+    }
+}
@@ -7,17 +7,22 @@ use crate::avm2::{
     StageObject as Avm2StageObject, Value as Avm2Value,
 };
 use crate::backend::ui::UiBackend;
-use crate::config::Letterbox;
-use crate::context::{RenderContext, UpdateContext};
+use crate::config::{AxisScale, AxisScaling, Letterbox};
+use crate::context::{ActionType, RenderContext, UpdateContext};
 use crate::display_object::container::{
     ChildContainer, DisplayObjectContainer, TDisplayObjectContainer,
 };
-use crate::display_object::{render_base, DisplayObject, DisplayObjectBase, TDisplayObject};
+use crate::display_object::{
+    render_base, DisplayObject, DisplayObjectBase, HitTestOptions, TDisplayObject,
+};
 use crate::prelude::*;
+use crate::transform::Transform;
 use crate::types::{Degrees, Percent};
 use crate::vminterface::{AvmType, Instantiator};
 use bitflags::bitflags;
 use gc_arena::{Collect, GcCell, MutationContext};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use std::str::FromStr;
 
@@ -51,6 +56,10 @@ pub struct StageData<'gc> {
     /// Determines how player content is resized to fit the stage.
     letterbox: Letterbox,
 
+    /// The color of the letterbox bars.
+    #[collect(require_static)]
+    letterbox_color: Color,
+
     /// The dimensions of the SWF file.
     #[collect(require_static)]
     movie_size: (u32, u32),
@@ -62,9 +71,15 @@ pub struct StageData<'gc> {
     /// The scale mode of the stage.
     scale_mode: StageScaleMode,
 
+    /// How each axis is scaled in `StageScaleMode::ShowAll`.
+    axis_scaling: AxisScaling,
+
     /// The alignment of the stage.
     align: StageAlign,
 
+    /// The quality at which the stage is rendered.
+    quality: StageQuality,
+
     /// The dimensions of the stage's containing viewport.
     #[collect(require_static)]
     viewport_size: (u32, u32),
@@ -92,10 +107,13 @@ impl<'gc> Stage<'gc> {
                 child: Default::default(),
                 background_color: None,
                 letterbox: Letterbox::Fullscreen,
+                letterbox_color: Color::from_rgb(0, 255),
                 movie_size: (width, height),
                 stage_size: (width, height),
                 scale_mode: Default::default(),
+                axis_scaling: Default::default(),
                 align: Default::default(),
+                quality: Default::default(),
                 viewport_size: (width, height),
                 viewport_scale_factor: 1.0,
                 view_bounds: Default::default(),
@@ -128,6 +146,16 @@ impl<'gc> Stage<'gc> {
         self.0.write(gc_context).letterbox = letterbox
     }
 
+    /// Get the color of the letterbox bars.
+    pub fn letterbox_color(self) -> Color {
+        self.0.read().letterbox_color.clone()
+    }
+
+    /// Set the color of the letterbox bars. This defaults to black.
+    pub fn set_letterbox_color(self, gc_context: MutationContext<'gc, '_>, color: Color) {
+        self.0.write(gc_context).letterbox_color = color
+    }
+
     /// Get the size of the SWF file.
     pub fn movie_size(self) -> (u32, u32) {
         self.0.read().movie_size
@@ -162,6 +190,21 @@ impl<'gc> Stage<'gc> {
         self.build_matrices(context);
     }
 
+    /// Get how each axis is scaled in `StageScaleMode::ShowAll`.
+    pub fn axis_scaling(self) -> AxisScaling {
+        self.0.read().axis_scaling
+    }
+
+    /// Set how each axis is scaled in `StageScaleMode::ShowAll`.
+    pub fn set_axis_scaling(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        axis_scaling: AxisScaling,
+    ) {
+        self.0.write(context.gc_context).axis_scaling = axis_scaling;
+        self.build_matrices(context);
+    }
+
     /// Get the stage alignment.
     pub fn align(self) -> StageAlign {
         self.0.read().align
@@ -174,6 +217,17 @@ impl<'gc> Stage<'gc> {
         self.build_matrices(context);
     }
 
+    /// Get the rendering quality of the stage.
+    pub fn quality(self) -> StageQuality {
+        self.0.read().quality
+    }
+
+    /// Set the rendering quality of the stage.
+    pub fn set_quality(self, context: &mut UpdateContext<'_, 'gc, '_>, quality: StageQuality) {
+        self.0.write(context.gc_context).quality = quality;
+        context.renderer.set_quality(quality);
+    }
+
     /// Get the current viewport size, in device pixels.
     pub fn viewport_size(self) -> (u32, u32) {
         self.0.read().viewport_size
@@ -201,7 +255,9 @@ impl<'gc> Stage<'gc> {
         write.viewport_scale_factor = scale_factor;
         drop(write);
 
-        self.build_matrices(context);
+        if self.update_matrices(context) {
+            self.queue_resize_event(context);
+        }
     }
 
     pub fn view_bounds(self) -> BoundingBox {
@@ -231,6 +287,16 @@ impl<'gc> Stage<'gc> {
 
     /// Update the stage's transform matrix in response to a root movie change.
     pub fn build_matrices(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        if self.update_matrices(context) {
+            self.fire_resize_event(context);
+        }
+    }
+
+    /// Recalculate the stage size and transform matrix.
+    ///
+    /// Returns whether the stage was resized, which only happens in
+    /// `StageScaleMode::NoScale`.
+    fn update_matrices(self, context: &mut UpdateContext<'_, 'gc, '_>) -> bool {
         let mut stage = self.0.write(context.gc_context);
         let scale_mode = stage.scale_mode;
         let align = stage.align;
@@ -267,7 +333,16 @@ impl<'gc> Stage<'gc> {
                 } else {
                     viewport_width / movie_width
                 };
-                (scale, scale)
+                // Stretched axes fill the viewport instead.
+                let scale_x = match stage.axis_scaling.horizontal {
+                    AxisScale::Fit => scale,
+                    AxisScale::Stretch => viewport_width / movie_width,
+                };
+                let scale_y = match stage.axis_scaling.vertical {
+                    AxisScale::Fit => scale,
+                    AxisScale::Stretch => viewport_height / movie_height,
+                };
+                (scale_x, scale_y)
             }
             StageScaleMode::NoBorder => {
                 // Keep aspect ratio, cropping off the edges.
@@ -341,15 +416,12 @@ impl<'gc> Stage<'gc> {
             }
         };
 
-        // Fire resize handler if stage size has changed.
-        if scale_mode == StageScaleMode::NoScale && stage_size_changed {
-            self.fire_resize_event(context);
-        }
+        scale_mode == StageScaleMode::NoScale && stage_size_changed
     }
 
     /// Draw the stage's letterbox.
     fn draw_letterbox(&self, context: &mut RenderContext<'_, 'gc>) {
-        let black = Color::from_rgb(0, 255);
+        let color = self.letterbox_color();
         let (viewport_width, viewport_height) = self.0.read().viewport_size;
         let viewport_width = viewport_width as f32;
         let viewport_height = viewport_height as f32;
@@ -370,7 +442,7 @@ impl<'gc> Stage<'gc> {
             // Top + bottom
             if margin_top > 0.0 {
                 context.renderer.draw_rect(
-                    black.clone(),
+                    color.clone(),
                     &Matrix::create_box(
                         viewport_width,
                         margin_top,
@@ -382,7 +454,7 @@ impl<'gc> Stage<'gc> {
             }
             if margin_bottom > 0.0 {
                 context.renderer.draw_rect(
-                    black,
+                    color,
                     &Matrix::create_box(
                         viewport_width,
                         margin_bottom,
@@ -396,7 +468,7 @@ impl<'gc> Stage<'gc> {
             // Left + right
             if margin_left > 0.0 {
                 context.renderer.draw_rect(
-                    black.clone(),
+                    color.clone(),
                     &Matrix::create_box(
                         margin_left,
                         viewport_height,
@@ -408,7 +480,7 @@ impl<'gc> Stage<'gc> {
             }
             if margin_right > 0.0 {
                 context.renderer.draw_rect(
-                    black,
+                    color,
                     &Matrix::create_box(
                         margin_right,
                         viewport_height,
@@ -429,6 +501,77 @@ impl<'gc> Stage<'gc> {
             .expect("Stage must always have a root movie")
     }
 
+    /// Find a display object by its path of instance names from the root
+    /// movie, such as `menu.icon` or `_root/menu/icon`.
+    pub fn object_by_path(self, path: &str) -> Option<DisplayObject<'gc>> {
+        path.split(|c| c == '.' || c == '/')
+            .filter(|name| !name.is_empty() && *name != "_root")
+            .try_fold(self.root_clip(), |object, name| {
+                object.as_container()?.child_by_name(name, false)
+            })
+    }
+
+    /// Render only `object` and its children, scaled to fit the viewport
+    /// in place of the rest of the stage.
+    pub fn render_subtree(self, context: &mut RenderContext<'_, 'gc>, object: DisplayObject<'gc>) {
+        let background_color = self
+            .background_color()
+            .unwrap_or_else(|| Color::from_rgb(0xffffff, 255));
+
+        context.renderer.begin_frame(background_color);
+
+        let bounds = object.local_bounds();
+        let width = (bounds.x_max - bounds.x_min).to_pixels();
+        let height = (bounds.y_max - bounds.y_min).to_pixels();
+        if bounds.valid && width > 0.0 && height > 0.0 {
+            let (viewport_width, viewport_height) = self.viewport_size();
+            let viewport_width = f64::from(viewport_width);
+            let viewport_height = f64::from(viewport_height);
+            let scale = f64::min(viewport_width / width, viewport_height / height);
+
+            // Center the object's bounds in the viewport.
+            let tx = (viewport_width - width * scale) / 2.0 - bounds.x_min.to_pixels() * scale;
+            let ty = (viewport_height - height * scale) / 2.0 - bounds.y_min.to_pixels() * scale;
+            let mut matrix = Matrix::scale(scale as f32, scale as f32);
+            matrix.tx = Twips::from_pixels(tx);
+            matrix.ty = Twips::from_pixels(ty);
+
+            context.transform_stack.push(&Transform {
+                matrix,
+                color_transform: Default::default(),
+            });
+            render_base(object, context);
+            context.transform_stack.pop();
+        }
+
+        context.renderer.end_frame();
+    }
+
+    /// Find the topmost display object under the given point, in stage
+    /// coordinates.
+    ///
+    /// The display list is walked front-to-back, and objects are tested
+    /// against their actual shape rather than their bounding box. Masks are
+    /// never returned, and invisible objects are only considered if
+    /// `include_invisible` is set.
+    pub fn object_at_point(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        x: Twips,
+        y: Twips,
+        include_invisible: bool,
+    ) -> Option<DisplayObject<'gc>> {
+        let options = HitTestOptions {
+            skip_mask: true,
+            skip_invisible: !include_invisible,
+        };
+        let levels: Vec<_> = self.iter_render_list().collect();
+        levels
+            .into_iter()
+            .rev()
+            .find_map(|level| object_at_point(context, level, (x, y), options))
+    }
+
     /// Fires `Stage.onResize` in AVM1 or `Event.RESIZE` in AVM2.
     fn fire_resize_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
         // This event fires immediately when scaleMode is changed;
@@ -444,14 +587,42 @@ impl<'gc> Stage<'gc> {
                 &[],
             );
         } else if let Avm2Value::Object(stage) = self.object2() {
-            let mut resized_event = Avm2Event::new("resize");
-            resized_event.set_bubbles(false);
-            resized_event.set_cancelable(false);
-            if let Err(e) = crate::avm2::Avm2::dispatch_event(context, resized_event, stage) {
+            if let Err(e) = crate::avm2::Avm2::dispatch_event(context, resize_event(), stage) {
                 log::error!("Encountered AVM2 error when dispatching event: {}", e);
             }
         }
     }
+
+    /// Queue up the resize handlers to run along with other actions, as
+    /// happens when the viewport is resized.
+    fn queue_resize_event(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let library = context.library.library_for_movie_mut(context.swf.clone());
+        let action = if library.avm_type() == AvmType::Avm1 {
+            ActionType::NotifyListeners {
+                listener: "Stage",
+                method: "onResize",
+                args: vec![],
+            }
+        } else if let Avm2Value::Object(stage) = self.object2() {
+            ActionType::Event2 {
+                event: resize_event(),
+                target: stage,
+            }
+        } else {
+            return;
+        };
+        context
+            .action_queue
+            .queue_actions(self.root_clip(), action, false);
+    }
+}
+
+/// The AVM2 `resize` event fired at the stage.
+fn resize_event<'gc>() -> Avm2Event<'gc> {
+    let mut resize_event = Avm2Event::new("resize");
+    resize_event.set_bubbles(false);
+    resize_event.set_cancelable(false);
+    resize_event
 }
 
 impl<'gc> TDisplayObject<'gc> for Stage<'gc> {
@@ -550,12 +721,99 @@ impl<'gc> TDisplayObjectContainer<'gc> for Stage<'gc> {
     impl_display_object_container!(child);
 }
 
+/// Find the topmost object under `point` within `object`, including `object`
+/// itself.
+fn object_at_point<'gc>(
+    context: &mut UpdateContext<'_, 'gc, '_>,
+    object: DisplayObject<'gc>,
+    point: (Twips, Twips),
+    options: HitTestOptions,
+) -> Option<DisplayObject<'gc>> {
+    if options.skip_invisible && !object.visible() {
+        return None;
+    }
+
+    if object.maskee().is_some() || !object.world_bounds().contains(point) {
+        return None;
+    }
+
+    if let Some(masker) = object.masker() {
+        if !masker.hit_test_shape(
+            context,
+            point,
+            HitTestOptions {
+                skip_mask: false,
+                skip_invisible: true,
+            },
+        ) {
+            return None;
+        }
+    }
+
+    if !object.scroll_rect_contains(point) {
+        return None;
+    }
+
+    if let Some(container) = object.as_container() {
+        // Children covered by a clip layer are only hit if the point also
+        // lies within the clipping mask.
+        let mut candidates = Vec::new();
+        let mut clip_depth = 0;
+        for child in container.iter_render_list() {
+            if child.clip_depth() > 0 {
+                if child.hit_test_shape(
+                    context,
+                    point,
+                    HitTestOptions {
+                        skip_mask: true,
+                        skip_invisible: true,
+                    },
+                ) {
+                    clip_depth = 0;
+                } else {
+                    clip_depth = child.clip_depth();
+                }
+            } else if child.depth() > clip_depth {
+                candidates.push(child);
+            }
+        }
+
+        for child in candidates.into_iter().rev() {
+            if let Some(result) = object_at_point(context, child, point, options) {
+                return Some(result);
+            }
+        }
+    }
+
+    // None of the children were hit, so this can only hit the object's own
+    // content. A movie clip's children must not be tested again, as that
+    // would find the children skipped above.
+    let is_hit = match object.as_movie_clip() {
+        Some(clip) => clip.hit_test_drawing(point),
+        None => object.hit_test_shape(context, point, options),
+    };
+    if is_hit {
+        Some(object)
+    } else {
+        None
+    }
+}
+
+#[derive(Debug)]
 pub struct ParseEnumError;
 
+impl Display for ParseEnumError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid value")
+    }
+}
+
 /// The scale mode of a stage.
 /// This controls the behavior when the player viewport size differs from the SWF size.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
 #[collect(require_static)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum StageScaleMode {
     /// The movie will be stretched to fit the container.
     ExactFit,
@@ -607,6 +865,75 @@ impl FromStr for StageScaleMode {
     }
 }
 
+/// The rendering quality of a stage.
+/// This controls anti-aliasing and bitmap smoothing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Collect)]
+#[collect(require_static)]
+pub enum StageQuality {
+    /// No anti-aliasing, and bitmaps are never smoothed.
+    Low,
+
+    /// Some anti-aliasing, but bitmaps are never smoothed.
+    Medium,
+
+    /// Anti-aliasing, and bitmaps are smoothed if they are static.
+    /// This is the default quality.
+    High,
+
+    /// Anti-aliasing, and bitmaps are always smoothed.
+    Best,
+}
+
+impl StageQuality {
+    /// The number of samples per pixel that shapes are anti-aliased with.
+    /// A count of 1 disables anti-aliasing.
+    ///
+    /// Backends may render with fewer samples than this if their device
+    /// doesn't support as many.
+    pub fn sample_count(self) -> u32 {
+        match self {
+            StageQuality::Low => 1,
+            StageQuality::Medium => 2,
+            StageQuality::High => 4,
+            StageQuality::Best => 8,
+        }
+    }
+}
+
+impl Default for StageQuality {
+    fn default() -> StageQuality {
+        StageQuality::High
+    }
+}
+
+impl Display for StageQuality {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Match string values returned by AS.
+        let s = match *self {
+            StageQuality::Low => "LOW",
+            StageQuality::Medium => "MEDIUM",
+            StageQuality::High => "HIGH",
+            StageQuality::Best => "BEST",
+        };
+        f.write_str(s)
+    }
+}
+
+impl FromStr for StageQuality {
+    type Err = ParseEnumError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let quality = match s.to_ascii_lowercase().as_str() {
+            "low" => StageQuality::Low,
+            "medium" => StageQuality::Medium,
+            "high" => StageQuality::High,
+            "best" => StageQuality::Best,
+            _ => return Err(ParseEnumError),
+        };
+        Ok(quality)
+    }
+}
+
 bitflags! {
     /// The alignment of the stage.
     /// This controls the position of the movie after scaling to fill the viewport.
@@ -650,3 +977,48 @@ impl FromStr for StageAlign {
         Ok(align)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quality_round_trips() {
+        for quality in &[
+            StageQuality::Low,
+            StageQuality::Medium,
+            StageQuality::High,
+            StageQuality::Best,
+        ] {
+            assert_eq!(
+                StageQuality::from_str(&quality.to_string()).ok(),
+                Some(*quality)
+            );
+            assert_eq!(
+                StageQuality::from_str(&quality.to_string().to_ascii_lowercase()).ok(),
+                Some(*quality)
+            );
+        }
+        assert!(StageQuality::from_str("ultra").is_err());
+    }
+
+    #[test]
+    fn scale_mode_round_trips() {
+        for scale_mode in &[
+            StageScaleMode::ExactFit,
+            StageScaleMode::NoBorder,
+            StageScaleMode::NoScale,
+            StageScaleMode::ShowAll,
+        ] {
+            assert_eq!(
+                StageScaleMode::from_str(&scale_mode.to_string()).ok(),
+                Some(*scale_mode)
+            );
+            assert_eq!(
+                StageScaleMode::from_str(&scale_mode.to_string().to_ascii_uppercase()).ok(),
+                Some(*scale_mode)
+            );
+        }
+        assert!(StageScaleMode::from_str("stretch").is_err());
+    }
+}
@@ -26,6 +26,26 @@ impl<'gc> Text<'gc> {
         swf: Arc<SwfMovie>,
         tag: &swf::Text,
     ) -> Self {
+        // Fonts are defined before the text that uses them, so a font that's
+        // missing now never shows up, and the glyphs using it aren't drawn.
+        if let Some(library) = context.library.library_for_movie(swf.clone()) {
+            let mut missing_fonts: Vec<CharacterId> = tag
+                .records
+                .iter()
+                .filter_map(|record| record.font_id)
+                .filter(|&font_id| library.get_font(font_id).is_none())
+                .collect();
+            if !missing_fonts.is_empty() {
+                missing_fonts.sort_unstable();
+                missing_fonts.dedup();
+                log::warn!(
+                    "Text {} uses undefined fonts {:?}, which won't be drawn",
+                    tag.id,
+                    missing_fonts
+                );
+            }
+        }
+
         Text(GcCell::allocate(
             context.gc_context,
             TextData {
@@ -76,11 +96,12 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
             ..Default::default()
         });
 
+        // Text without a color record is drawn in opaque black.
         let mut color = swf::Color {
             r: 0,
             g: 0,
             b: 0,
-            a: 0,
+            a: 255,
         };
         let mut font_id = 0;
         let mut height = Twips::zero();
@@ -105,6 +126,8 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
                 transform.matrix.a = scale;
                 transform.matrix.d = scale;
                 transform.color_transform.set_mult_color(&color);
+                // Each advance already includes any kerning between this glyph
+                // and the next, as it's applied when the text is authored.
                 for c in &block.glyphs {
                     if let Some(glyph) = font.get_glyph(c.index as usize) {
                         context.transform_stack.push(&transform);
@@ -200,7 +223,197 @@ impl<'gc> TDisplayObject<'gc> for Text<'gc> {
 struct TextStatic {
     swf: Arc<SwfMovie>,
     id: CharacterId,
+
+    /// The local bounds of the glyphs, as stored in the DefineText tag.
+    ///
+    /// The authoring tool computes these from the glyph advances and the
+    /// font metrics, so they're used as-is rather than recalculated.
     bounds: BoundingBox,
     text_transform: Matrix,
     text_blocks: Vec<swf::TextRecord>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::render::{RecordingRenderer, RenderCommand};
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{render_recorded, square_shape, static_text_movie, text_movie};
+
+    #[test]
+    fn static_text_without_color_is_opaque() {
+        let font = swf::Tag::DefineFont(Box::new(swf::FontV1 {
+            id: 1,
+            glyphs: vec![square_shape().shape],
+        }));
+        let record = swf::TextRecord {
+            font_id: Some(1),
+            color: None,
+            x_offset: None,
+            y_offset: None,
+            height: Some(Twips::from_pixels(10.0)),
+            glyphs: vec![swf::GlyphEntry {
+                index: 0,
+                advance: 200,
+            }],
+        };
+        let player = render_recorded(static_text_movie(font, record));
+        let player = player.lock().unwrap();
+
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let glyph_alphas: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::RenderShape {
+                    color_transform, ..
+                } => Some(color_transform.a_mult),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(glyph_alphas.len(), 1);
+        assert!(glyph_alphas[0].is_one());
+    }
+
+    #[test]
+    fn static_text_bounds_come_from_its_tag() {
+        let font = swf::Tag::DefineFont(Box::new(swf::FontV1 {
+            id: 1,
+            glyphs: vec![square_shape().shape],
+        }));
+        // Two glyphs at half size, whose advances add up to the 10px wide
+        // bounds in the DefineText tag.
+        let record = swf::TextRecord {
+            font_id: Some(1),
+            color: None,
+            x_offset: None,
+            y_offset: None,
+            height: Some(Twips::from_pixels(5.0)),
+            glyphs: vec![
+                swf::GlyphEntry {
+                    index: 0,
+                    advance: Twips::from_pixels(5.0).get(),
+                },
+                swf::GlyphEntry {
+                    index: 0,
+                    advance: Twips::from_pixels(5.0).get(),
+                },
+            ],
+        };
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(static_text_movie(font, record)));
+        player.run_frame();
+
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            let text = root.as_container().unwrap().child_by_depth(1).unwrap();
+            let bounds = text.self_bounds();
+            assert_eq!(bounds, BoundingBox::from(square_shape().shape_bounds));
+            assert_eq!(bounds.width(), Twips::from_pixels(10.0));
+            assert_eq!(text.world_bounds(), bounds);
+        });
+    }
+
+    #[test]
+    fn static_text_advances_already_include_kerning() {
+        let glyph = |code: char| swf::Glyph {
+            shape_records: square_shape().shape,
+            code: code as u16,
+            advance: Some(1024),
+            bounds: Some(square_shape().shape_bounds),
+        };
+        let font = swf::Tag::DefineFont2(Box::new(swf::Font {
+            version: 2,
+            id: 1,
+            name: "Kerned".into(),
+            language: swf::Language::Unknown,
+            layout: Some(swf::FontLayout {
+                ascent: 1024,
+                descent: 0,
+                leading: 0,
+                kerning: vec![swf::KerningRecord {
+                    left_code: 'A' as u16,
+                    right_code: 'V' as u16,
+                    adjustment: Twips::new(-256),
+                }],
+            }),
+            glyphs: vec![glyph('A'), glyph('V')],
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold: false,
+            is_italic: false,
+        }));
+        // The authoring tool applies the kerning pair when writing the
+        // advances, so "AV" at 20px steps 15px rather than 20px.
+        let record = swf::TextRecord {
+            font_id: Some(1),
+            color: None,
+            x_offset: Some(Twips::zero()),
+            y_offset: None,
+            height: Some(Twips::from_pixels(20.0)),
+            glyphs: vec![
+                swf::GlyphEntry {
+                    index: 0,
+                    advance: Twips::from_pixels(15.0).get(),
+                },
+                swf::GlyphEntry {
+                    index: 1,
+                    advance: Twips::from_pixels(20.0).get(),
+                },
+            ],
+        };
+        let player = render_recorded(static_text_movie(font, record));
+        let player = player.lock().unwrap();
+
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let glyph_xs: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::RenderShape { matrix, .. } => Some(matrix.tx),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(glyph_xs.len(), 2);
+        assert_eq!(glyph_xs[1] - glyph_xs[0], Twips::from_pixels(15.0));
+    }
+
+    #[test]
+    fn static_text_keeps_sub_pixel_advances() {
+        let player = PlayerBuilder::new()
+            .with_renderer(Box::new(RecordingRenderer::new()))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(text_movie()));
+        // Match the viewport to the movie, so that nothing is scaled.
+        player.set_viewport_dimensions(10, 10, 1.0);
+        player.run_frame();
+        player.render();
+
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let glyph_xs: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::RenderShape { matrix, .. } => Some(matrix.tx),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            glyph_xs,
+            vec![Twips::zero(), Twips::new(25), Twips::new(50)]
+        );
+    }
+}
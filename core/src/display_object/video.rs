@@ -2,12 +2,14 @@
 
 use crate::avm1::{Object as Avm1Object, StageObject as Avm1StageObject};
 use crate::avm2::{Object as Avm2Object, StageObject as Avm2StageObject};
+use crate::backend::audio::{SoundHandle, SoundInstanceHandle};
 use crate::backend::render::BitmapInfo;
 use crate::backend::video::{EncodedFrame, VideoStreamHandle};
 use crate::bounding_box::BoundingBox;
 use crate::collect::CollectWrapper;
 use crate::context::{RenderContext, UpdateContext};
 use crate::display_object::{DisplayObjectBase, TDisplayObject};
+use crate::flv::FlvVideo;
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::types::{Degrees, Percent};
@@ -16,7 +18,7 @@ use gc_arena::{Collect, GcCell, MutationContext};
 use std::borrow::{Borrow, BorrowMut};
 use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
-use swf::{CharacterId, DefineVideoStream, VideoFrame};
+use swf::{CharacterId, DefineVideoStream, SoundEvent, SoundInfo, VideoDeblocking, VideoFrame};
 
 /// A Video display object is a high-level interface to a video player.
 ///
@@ -85,6 +87,36 @@ pub enum VideoSource {
         /// to reconstruct a reference to the embedded bitstream.
         frames: BTreeMap<u32, (usize, usize)>,
     },
+
+    /// A video player created by ActionScript, which plays FLV video fed to
+    /// it by a `NetStream`.
+    NetStream {
+        /// The size of the video player.
+        width: u16,
+        height: u16,
+
+        /// The video currently being played, if any.
+        video: Option<FlvVideo>,
+
+        /// The audio track of the video currently being played, if any.
+        sound: Option<NetStreamSound>,
+
+        /// How far into the video playback has progressed, in milliseconds.
+        time: f64,
+    },
+}
+
+/// The audio track of a video played by a `NetStream`.
+#[derive(Clone, Debug)]
+pub struct NetStreamSound {
+    /// The sound holding the whole track.
+    handle: SoundHandle,
+
+    /// When the track starts playing, in milliseconds.
+    start_time: u32,
+
+    /// The playing instance of the track, once it has started.
+    instance: Option<SoundInstanceHandle>,
 }
 
 impl<'gc> Video<'gc> {
@@ -116,6 +148,181 @@ impl<'gc> Video<'gc> {
         ))
     }
 
+    /// Construct an empty Video player for ActionScript to attach a
+    /// `NetStream` to.
+    pub fn new(mc: MutationContext<'gc, '_>, width: u16, height: u16) -> Self {
+        let source = GcCell::allocate(
+            mc,
+            VideoSource::NetStream {
+                width,
+                height,
+                video: None,
+                sound: None,
+                time: 0.0,
+            },
+        );
+
+        Video(GcCell::allocate(
+            mc,
+            VideoData {
+                base: Default::default(),
+                source,
+                stream: VideoStream::Uninstantiated(0),
+                decoded_frame: None,
+                object: None,
+                keyframes: BTreeSet::new(),
+            },
+        ))
+    }
+
+    /// Start playing video loaded by a `NetStream`, from its first frame.
+    ///
+    /// Video players embedded in a SWF can't play external video, and ignore
+    /// this.
+    pub fn attach_flv(self, context: &mut UpdateContext<'_, 'gc, '_>, flv: FlvVideo) {
+        let size = match &*self.0.read().source.read() {
+            VideoSource::NetStream { width, height, .. } => flv.size.unwrap_or((*width, *height)),
+            VideoSource::Swf { .. } => {
+                log::warn!("Attempted to play a NetStream on an embedded video");
+                return;
+            }
+        };
+
+        let stream = match context.video.register_video_stream(
+            flv.frames.len() as u32,
+            size,
+            flv.codec,
+            VideoDeblocking::UseVideoPacketValue,
+        ) {
+            Ok(stream) => stream,
+            Err(e) => {
+                log::error!("Got error when registering NetStream video: {}", e);
+                context
+                    .diagnostics
+                    .record_unsupported_codec(&format!("{:?}", flv.codec));
+                return;
+            }
+        };
+
+        let mut keyframes = BTreeSet::new();
+        for (frame_id, frame) in flv.frames.iter().enumerate() {
+            let dep = context.video.preload_video_stream_frame(
+                stream,
+                EncodedFrame {
+                    codec: flv.codec,
+                    data: &frame.data,
+                    frame_id: frame_id as u32,
+                },
+            );
+
+            match dep {
+                Ok(d) if d.is_keyframe() || frame.is_keyframe => {
+                    keyframes.insert(frame_id as u32);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    log::error!("Got error when pre-loading video frame: {}", e);
+                }
+            }
+        }
+
+        let mut write = self.0.write(context.gc_context);
+        if let VideoSource::NetStream { video, time, .. } =
+            &mut *write.source.write(context.gc_context)
+        {
+            *video = Some(flv);
+            *time = 0.0;
+        }
+        write.stream = VideoStream::Instantiated(stream);
+        write.keyframes = keyframes;
+        write.decoded_frame = None;
+        drop(write);
+
+        self.seek(context, 0);
+    }
+
+    /// Play the audio track of a `NetStream`'s video, once playback reaches
+    /// `start_time` milliseconds.
+    ///
+    /// The sound is released when the stream stops.
+    pub fn attach_flv_sound(
+        self,
+        context: &mut UpdateContext<'_, 'gc, '_>,
+        handle: SoundHandle,
+        start_time: u32,
+    ) {
+        match &mut *self.0.read().source.write(context.gc_context) {
+            VideoSource::NetStream { sound, .. } => {
+                *sound = Some(NetStreamSound {
+                    handle,
+                    start_time,
+                    instance: None,
+                });
+            }
+            VideoSource::Swf { .. } => {
+                log::warn!("Attempted to play a NetStream on an embedded video");
+                context.audio.unregister_sound(handle);
+                return;
+            }
+        }
+
+        self.start_net_stream_sound(context);
+    }
+
+    /// Stop playing the video and audio of a `NetStream`, and release its
+    /// sound.
+    pub fn stop_net_stream(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let sound = match &mut *self.0.read().source.write(context.gc_context) {
+            VideoSource::NetStream {
+                video, sound, time, ..
+            } => {
+                *video = None;
+                *time = 0.0;
+                sound.take()
+            }
+            VideoSource::Swf { .. } => None,
+        };
+
+        if let Some(sound) = sound {
+            if let Some(instance) = sound.instance {
+                context.stop_sound(instance);
+            }
+            context.audio.unregister_sound(sound.handle);
+        }
+    }
+
+    /// Start the audio track of a `NetStream` once playback has reached it.
+    fn start_net_stream_sound(self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        let handle = match &*self.0.read().source.read() {
+            VideoSource::NetStream {
+                sound: Some(sound),
+                time,
+                ..
+            } if sound.instance.is_none() && *time >= f64::from(sound.start_time) => sound.handle,
+            _ => return,
+        };
+
+        let instance = context.start_sound(
+            handle,
+            &SoundInfo {
+                event: SoundEvent::Start,
+                in_sample: None,
+                out_sample: None,
+                num_loops: 1,
+                envelope: None,
+            },
+            Some(self.into()),
+            None,
+        );
+
+        if let VideoSource::NetStream {
+            sound: Some(sound), ..
+        } = &mut *self.0.read().source.write(context.gc_context)
+        {
+            sound.instance = instance;
+        }
+    }
+
     /// Preload frame data from an SWF.
     ///
     /// This function yields an error if this video player is not playing an
@@ -145,6 +352,9 @@ impl<'gc> Video<'gc> {
                     log::warn!("Invalid bitstream subslice on frame {}", tag.frame_num);
                 }
             }
+            VideoSource::NetStream { .. } => {
+                log::warn!("Attempted to preload SWF frame into a NetStream video");
+            }
         }
     }
 
@@ -167,11 +377,15 @@ impl<'gc> Video<'gc> {
         };
 
         let num_frames = match &*read.source.read() {
-            VideoSource::Swf { streamdef, .. } => Some(streamdef.num_frames),
+            VideoSource::Swf { streamdef, .. } => Some(u32::from(streamdef.num_frames)),
+            VideoSource::NetStream { video, .. } => video
+                .as_ref()
+                .map(|video| video.frames.len() as u32)
+                .filter(|num_frames| *num_frames > 0),
         };
 
         if let Some(num_frames) = num_frames {
-            frame_id %= num_frames as u32;
+            frame_id %= num_frames;
         }
 
         let last_frame = read.decoded_frame.as_ref().map(|(lf, _)| *lf);
@@ -216,6 +430,8 @@ impl<'gc> Video<'gc> {
         for fr in sweep_from..=frame_id {
             self.seek_internal(context, fr)
         }
+
+        self.invalidate_cached_bitmap();
     }
 
     /// Decode a single frame of video.
@@ -260,6 +476,27 @@ impl<'gc> Video<'gc> {
                     }
                 }
             },
+            VideoSource::NetStream { video, .. } => {
+                match video
+                    .as_ref()
+                    .and_then(|video| Some((video.codec, video.frames.get(frame_id as usize)?)))
+                {
+                    Some((codec, frame)) => {
+                        let encframe = EncodedFrame {
+                            codec,
+                            data: &frame.data,
+                            frame_id,
+                        };
+                        context
+                            .video
+                            .decode_video_stream_frame(*stream, encframe, context.renderer)
+                    }
+                    None => Err(Box::from(format!(
+                        "Attempted to seek to missing NetStream frame {}",
+                        frame_id
+                    ))),
+                }
+            }
         };
 
         drop(read);
@@ -308,6 +545,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                         "Got error when post-instantiating video: {}",
                         stream.unwrap_err()
                     );
+                    context
+                        .diagnostics
+                        .record_unsupported_codec(&format!("{:?}", streamdef.codec));
                     return;
                 }
 
@@ -337,6 +577,9 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
 
                 (stream, movie.clone(), keyframes)
             }
+            // NetStream videos are created by ActionScript, and their streams
+            // are registered once video is attached.
+            VideoSource::NetStream { .. } => return,
         };
 
         let starting_seek = if let VideoStream::Uninstantiated(seek_to) = write.stream {
@@ -373,6 +616,31 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
         }
     }
 
+    fn run_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
+        // NetStream video advances in real time, independently of the
+        // timeline.
+        let frame_id = match &mut *self.0.read().source.write(context.gc_context) {
+            VideoSource::NetStream {
+                video, sound, time, ..
+            } if video.is_some() || sound.is_some() => {
+                *time += 1000.0 / *context.frame_rate;
+                video.as_ref().and_then(|video| {
+                    video
+                        .frames
+                        .iter()
+                        .rposition(|frame| f64::from(frame.timestamp) <= *time)
+                })
+            }
+            _ => None,
+        };
+
+        self.start_net_stream_sound(context);
+
+        if let Some(frame_id) = frame_id {
+            self.seek(context, frame_id as u32);
+        }
+    }
+
     fn construct_frame(&self, context: &mut UpdateContext<'_, 'gc, '_>) {
         let vm_type = self.vm_type(context);
         if vm_type == AvmType::Avm2 && matches!(self.object2(), Avm2Value::Undefined) {
@@ -389,6 +657,7 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
     fn id(&self) -> CharacterId {
         match (*self.0.read().source.read()).borrow() {
             VideoSource::Swf { streamdef, .. } => streamdef.id,
+            VideoSource::NetStream { .. } => 0,
         }
     }
 
@@ -400,6 +669,10 @@ impl<'gc> TDisplayObject<'gc> for Video<'gc> {
                 bounding_box.set_width(Twips::from_pixels(streamdef.width as f64));
                 bounding_box.set_height(Twips::from_pixels(streamdef.height as f64));
             }
+            VideoSource::NetStream { width, height, .. } => {
+                bounding_box.set_width(Twips::from_pixels(*width as f64));
+                bounding_box.set_height(Twips::from_pixels(*height as f64));
+            }
         }
 
         bounding_box
@@ -135,7 +135,6 @@ impl Drawing {
     }
 
     pub fn draw_command(&mut self, command: DrawCommand) {
-        let mut include_last = false;
         let stroke_width = if let Some((style, _)) = &self.current_line {
             style.width
         } else {
@@ -144,15 +143,17 @@ impl Drawing {
 
         match command {
             DrawCommand::MoveTo { .. } => {}
-            DrawCommand::LineTo { .. } => {
-                stretch_bounding_box(&mut self.shape_bounds, &command, stroke_width);
-                stretch_bounding_box(&mut self.edge_bounds, &command, Twips::zero());
-                include_last = true;
-            }
-            DrawCommand::CurveTo { .. } => {
+            DrawCommand::LineTo { .. } | DrawCommand::CurveTo { .. } => {
+                // Moving the cursor doesn't add to the bounds, so include the
+                // start of the segment now that something is drawn from it.
+                let start = DrawCommand::MoveTo {
+                    x: self.cursor.0,
+                    y: self.cursor.1,
+                };
+                stretch_bounding_box(&mut self.shape_bounds, &start, stroke_width);
+                stretch_bounding_box(&mut self.edge_bounds, &start, Twips::zero());
                 stretch_bounding_box(&mut self.shape_bounds, &command, stroke_width);
                 stretch_bounding_box(&mut self.edge_bounds, &command, Twips::zero());
-                include_last = true;
             }
         }
 
@@ -165,26 +166,6 @@ impl Drawing {
             commands.push(command);
         }
 
-        if include_last {
-            if let Some(command) = self
-                .current_fill
-                .as_ref()
-                .and_then(|(_, commands)| commands.last())
-            {
-                stretch_bounding_box(&mut self.shape_bounds, command, stroke_width);
-                stretch_bounding_box(&mut self.edge_bounds, command, Twips::zero());
-            }
-
-            if let Some(command) = self
-                .current_line
-                .as_ref()
-                .and_then(|(_, commands)| commands.last())
-            {
-                stretch_bounding_box(&mut self.shape_bounds, command, stroke_width);
-                stretch_bounding_box(&mut self.edge_bounds, command, Twips::zero());
-            }
-        }
-
         self.dirty.set(true);
     }
 
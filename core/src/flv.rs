@@ -0,0 +1,371 @@
+//! FLV container parsing
+//!
+//! FLV files are demuxed into timestamped audio, video and script data tags,
+//! which are then gathered into the tracks that Ruffle can play.
+
+use crate::amf::{amf0, Value as AmfValue};
+use swf::{SoundFormat, VideoCodec};
+use thiserror::Error;
+
+const TAG_AUDIO: u8 = 8;
+const TAG_VIDEO: u8 = 9;
+const TAG_SCRIPT_DATA: u8 = 18;
+
+/// Frame type of video tags that carry commands rather than video data.
+const FRAME_TYPE_COMMAND: u8 = 5;
+
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Not an FLV file")]
+    InvalidHeader,
+
+    #[error("FLV file has no supported audio or video track")]
+    NoMedia,
+}
+
+/// A single tag of an FLV file.
+#[derive(Clone, Debug)]
+pub struct FlvTag {
+    /// When this tag should be presented, in milliseconds.
+    pub timestamp: u32,
+
+    pub data: FlvTagData,
+}
+
+/// The contents of an FLV tag.
+#[derive(Clone, Debug)]
+pub enum FlvTagData {
+    /// A packet of audio.
+    ///
+    /// The data is in the same form as a SWF `SoundStreamBlock` tag.
+    Audio { format: SoundFormat, data: Vec<u8> },
+
+    /// A single encoded frame of video.
+    ///
+    /// The data is in the same form as a SWF `VideoFrame` tag.
+    Video {
+        codec: VideoCodec,
+        is_keyframe: bool,
+        data: Vec<u8>,
+    },
+
+    /// A script callback, such as `onMetaData`, and its argument.
+    ScriptData { name: String, value: AmfValue },
+}
+
+/// A single encoded frame of video.
+#[derive(Clone, Debug)]
+pub struct FlvVideoFrame {
+    /// When this frame should be shown, in milliseconds.
+    pub timestamp: u32,
+
+    /// Whether the container flags this frame as a keyframe.
+    pub is_keyframe: bool,
+
+    /// The frame bitstream, in the same form as a SWF `VideoFrame` tag.
+    pub data: Vec<u8>,
+}
+
+/// The video track of an FLV file.
+#[derive(Clone, Debug)]
+pub struct FlvVideo {
+    pub codec: VideoCodec,
+
+    /// The video size declared by the file's `onMetaData`, if any.
+    pub size: Option<(u16, u16)>,
+
+    pub frames: Vec<FlvVideoFrame>,
+}
+
+/// The audio track of an FLV file.
+#[derive(Clone, Debug)]
+pub struct FlvAudio {
+    pub format: SoundFormat,
+
+    /// When the first packet of audio should be played, in milliseconds.
+    pub start_time: u32,
+
+    /// The audio packets of the file, in order.
+    ///
+    /// Some codecs, such as ADPCM, begin each packet with a header, so
+    /// packets must be decoded one by one.
+    pub packets: Vec<Vec<u8>>,
+}
+
+/// The playable contents of an FLV file.
+#[derive(Clone, Debug)]
+pub struct FlvMedia {
+    /// The length of the file in seconds, as declared by its `onMetaData`.
+    pub duration: Option<f64>,
+
+    pub video: Option<FlvVideo>,
+
+    pub audio: Option<FlvAudio>,
+}
+
+/// Split an FLV file into its tags.
+///
+/// Tags that can't be played, such as those in unsupported codecs, are
+/// skipped. Truncated files yield the tags that were fully loaded.
+pub fn demux(data: &[u8]) -> Result<Vec<FlvTag>, Error> {
+    if data.len() < 9 || &data[0..3] != b"FLV" {
+        return Err(Error::InvalidHeader);
+    }
+
+    let header_size = u32::from_be_bytes([data[5], data[6], data[7], data[8]]) as usize;
+    let mut input = data.get(header_size..).unwrap_or_default();
+    let mut tags = Vec::new();
+
+    // Each tag is preceded by the size of the previous tag, and has an
+    // 11-byte header.
+    while input.len() >= 15 {
+        let header = &input[4..15];
+        let tag_type = header[0] & 0x1F;
+        let body_size = u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        let timestamp = u32::from_be_bytes([header[7], header[4], header[5], header[6]]);
+        let body = match input.get(15..15 + body_size) {
+            Some(body) => body,
+            None => break,
+        };
+        input = &input[15 + body_size..];
+
+        let data = match tag_type {
+            TAG_AUDIO => demux_audio(body),
+            TAG_VIDEO => demux_video(body),
+            TAG_SCRIPT_DATA => demux_script_data(body),
+            _ => None,
+        };
+
+        if let Some(data) = data {
+            tags.push(FlvTag { timestamp, data });
+        }
+    }
+
+    Ok(tags)
+}
+
+fn demux_audio(body: &[u8]) -> Option<FlvTagData> {
+    // The audio header has the same layout as a SWF sound format.
+    let format = match swf::read::Reader::new(body.get(0..1)?, 0).read_sound_format() {
+        Ok(format) => format,
+        Err(_) => {
+            log::warn!("Unsupported FLV audio format {}", body[0] >> 4);
+            return None;
+        }
+    };
+
+    Some(FlvTagData::Audio {
+        format,
+        data: body[1..].to_vec(),
+    })
+}
+
+fn demux_video(body: &[u8]) -> Option<FlvTagData> {
+    let frame_type = body.get(0)? >> 4;
+    let (codec, payload) = match body[0] & 0xF {
+        2 => (VideoCodec::H263, &body[1..]),
+        3 => (VideoCodec::ScreenVideo, &body[1..]),
+        // FLV prefixes VP6 data with a size adjustment byte, which SWF
+        // video frames lack.
+        4 => (VideoCodec::Vp6, body.get(2..).unwrap_or_default()),
+        5 => (VideoCodec::Vp6WithAlpha, body.get(2..).unwrap_or_default()),
+        6 => (VideoCodec::ScreenVideoV2, &body[1..]),
+        codec_id => {
+            log::warn!("Unsupported FLV video codec {}", codec_id);
+            return None;
+        }
+    };
+
+    if frame_type == FRAME_TYPE_COMMAND {
+        return None;
+    }
+
+    Some(FlvTagData::Video {
+        codec,
+        is_keyframe: frame_type == 1,
+        data: payload.to_vec(),
+    })
+}
+
+fn demux_script_data(body: &[u8]) -> Option<FlvTagData> {
+    // The tag holds the callback name as an AMF0 string, followed by its
+    // argument.
+    let name_length = usize::from(u16::from_be_bytes([*body.get(1)?, *body.get(2)?]));
+    if body[0] != 0x02 {
+        return None;
+    }
+
+    let name = String::from_utf8(body.get(3..3 + name_length)?.to_vec()).ok()?;
+    let value = amf0::deserialize(&body[3 + name_length..]).ok()?;
+
+    Some(FlvTagData::ScriptData { name, value })
+}
+
+/// Extract the audio and video tracks from an FLV file.
+///
+/// Only the first codec seen on each track is played.
+pub fn parse(data: &[u8]) -> Result<FlvMedia, Error> {
+    let mut duration = None;
+    let mut size = None;
+    let mut video: Option<(VideoCodec, Vec<FlvVideoFrame>)> = None;
+    let mut audio: Option<FlvAudio> = None;
+
+    for tag in demux(data)? {
+        match tag.data {
+            FlvTagData::Audio { format, data } => match &mut audio {
+                None => {
+                    audio = Some(FlvAudio {
+                        format,
+                        start_time: tag.timestamp,
+                        packets: vec![data],
+                    })
+                }
+                Some(audio) if audio.format == format => audio.packets.push(data),
+                Some(_) => {}
+            },
+            FlvTagData::Video {
+                codec,
+                is_keyframe,
+                data,
+            } => {
+                let (track_codec, frames) = video.get_or_insert_with(|| (codec, Vec::new()));
+                if *track_codec == codec {
+                    frames.push(FlvVideoFrame {
+                        timestamp: tag.timestamp,
+                        is_keyframe,
+                        data,
+                    });
+                }
+            }
+            FlvTagData::ScriptData { name, value } if name == "onMetaData" => {
+                let properties = match value {
+                    AmfValue::Object { properties, .. } => properties,
+                    AmfValue::Array { associative, .. } => associative,
+                    _ => continue,
+                };
+                let number = |name: &str| {
+                    properties.iter().find_map(|(key, value)| match value {
+                        AmfValue::Number(n) if key == name => Some(*n),
+                        _ => None,
+                    })
+                };
+
+                duration = number("duration").filter(|duration| *duration > 0.0);
+                if let (Some(width), Some(height)) = (number("width"), number("height")) {
+                    if width >= 1.0 && height >= 1.0 {
+                        size = Some((width as u16, height as u16));
+                    }
+                }
+            }
+            FlvTagData::ScriptData { .. } => {}
+        }
+    }
+
+    let video = video.map(|(codec, frames)| FlvVideo {
+        codec,
+        size,
+        frames,
+    });
+
+    if video.is_none() && audio.is_none() {
+        return Err(Error::NoMedia);
+    }
+
+    Ok(FlvMedia {
+        duration,
+        video,
+        audio,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::flv;
+    use swf::AudioCompression;
+
+    fn metadata() -> Vec<u8> {
+        let mut metadata = vec![0x02, 0x00, 0x0A];
+        metadata.extend_from_slice(b"onMetaData");
+        let value = AmfValue::Array {
+            length: 0,
+            dense: vec![],
+            associative: vec![
+                ("duration".to_string(), AmfValue::Number(0.08)),
+                ("width".to_string(), AmfValue::Number(320.0)),
+                ("height".to_string(), AmfValue::Number(240.0)),
+            ],
+        };
+        metadata.extend_from_slice(&amf0::serialize(&value).unwrap());
+        metadata
+    }
+
+    /// A file with metadata, two video frames and two MP3 audio packets,
+    /// plus a tag of an unknown type.
+    fn sample() -> Vec<u8> {
+        flv(&[
+            (TAG_SCRIPT_DATA, 0, metadata()),
+            (TAG_VIDEO, 0, vec![0x12, 1, 2, 3]),
+            (TAG_AUDIO, 0, vec![0x2E, 10, 11]),
+            (TAG_AUDIO, 26, vec![0x2E, 12]),
+            (15, 30, vec![0xFF, 0xFF]),
+            (TAG_VIDEO, 40, vec![0x22, 4, 5]),
+        ])
+    }
+
+    #[test]
+    fn demuxes_tags_in_order() {
+        let tags: Vec<_> = demux(&sample())
+            .unwrap()
+            .into_iter()
+            .map(|tag| {
+                let kind = match tag.data {
+                    FlvTagData::Audio { .. } => "audio",
+                    FlvTagData::Video { .. } => "video",
+                    FlvTagData::ScriptData { .. } => "script",
+                };
+                (kind, tag.timestamp)
+            })
+            .collect();
+
+        assert_eq!(
+            tags,
+            vec![
+                ("script", 0),
+                ("video", 0),
+                ("audio", 0),
+                ("audio", 26),
+                ("video", 40),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_tracks_and_metadata() {
+        let media = parse(&sample()).unwrap();
+        assert_eq!(media.duration, Some(0.08));
+
+        let video = media.video.unwrap();
+        assert_eq!(video.codec, VideoCodec::H263);
+        assert_eq!(video.size, Some((320, 240)));
+        assert_eq!(video.frames.len(), 2);
+        assert_eq!(video.frames[0].timestamp, 0);
+        assert!(video.frames[0].is_keyframe);
+        assert_eq!(video.frames[0].data, vec![1, 2, 3]);
+        assert_eq!(video.frames[1].timestamp, 40);
+        assert!(!video.frames[1].is_keyframe);
+
+        let audio = media.audio.unwrap();
+        assert_eq!(audio.format.compression, AudioCompression::Mp3);
+        assert_eq!(audio.format.sample_rate, 44100);
+        assert!(audio.format.is_stereo);
+        assert!(audio.format.is_16_bit);
+        assert_eq!(audio.start_time, 0);
+        assert_eq!(audio.packets, vec![vec![10, 11], vec![12]]);
+    }
+
+    #[test]
+    fn rejects_non_flv_data() {
+        assert!(matches!(parse(b"FWS"), Err(Error::InvalidHeader)));
+    }
+}
@@ -56,4 +56,65 @@ impl<'gc> FocusTracker<'gc> {
             ],
         );
     }
+
+    /// The objects that tabbing moves focus through, in order.
+    ///
+    /// Visible objects take part if they are tab-enabled, except for the
+    /// descendants of containers with `tabChildren` turned off. If any of
+    /// them has a `tabIndex`, only those are used, ordered by index;
+    /// otherwise they are in display list order.
+    pub fn tab_order(&self, context: &UpdateContext<'_, 'gc, '_>) -> Vec<DisplayObject<'gc>> {
+        let mut tab_order = Vec::new();
+        for (_depth, level) in context.stage.iter_depth_list() {
+            fill_tab_order(level, &mut tab_order);
+        }
+
+        if tab_order.iter().any(|object| object.tab_index().is_some()) {
+            tab_order.retain(|object| object.tab_index().is_some());
+            tab_order.sort_by_key(|object| object.tab_index());
+        }
+
+        tab_order
+    }
+
+    /// Move focus to the next object in the tab order, or the previous one if
+    /// `reverse` is set, wrapping around at either end.
+    pub fn cycle(&self, context: &mut UpdateContext<'_, 'gc, '_>, reverse: bool) {
+        let tab_order = self.tab_order(context);
+        if tab_order.is_empty() {
+            return;
+        }
+
+        let len = tab_order.len();
+        let current = self.get().and_then(|focus| {
+            tab_order
+                .iter()
+                .position(|object| DisplayObject::ptr_eq(*object, focus))
+        });
+        let next = match current {
+            Some(i) if reverse => (i + len - 1) % len,
+            Some(i) => (i + 1) % len,
+            None if reverse => len - 1,
+            None => 0,
+        };
+        self.set(Some(tab_order[next]), context);
+    }
+}
+
+fn fill_tab_order<'gc>(object: DisplayObject<'gc>, tab_order: &mut Vec<DisplayObject<'gc>>) {
+    if !object.visible() {
+        return;
+    }
+
+    if object.is_tab_enabled() {
+        tab_order.push(object);
+    }
+
+    if object.tab_children() {
+        if let Some(container) = object.as_container() {
+            for child in container.iter_render_list() {
+                fill_tab_order(child, tab_order);
+            }
+        }
+    }
 }
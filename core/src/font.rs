@@ -152,6 +152,120 @@ impl<'gc> Font<'gc> {
         )))
     }
 
+    /// Load a TrueType or OpenType font file for use as a device font.
+    ///
+    /// Any styles requested in `synthesis` are not present in the font file
+    /// itself, and are approximated by altering the glyph outlines.
+    pub fn from_font_file(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        name: &str,
+        data: &[u8],
+        is_bold: bool,
+        is_italic: bool,
+        synthesis: FontSynthesis,
+    ) -> Result<Font<'gc>, Error> {
+        let face = ttf_parser::Face::from_slice(data, 0)?;
+        let units_per_em = face
+            .units_per_em()
+            .ok_or("Font file does not specify units per EM")?;
+
+        // Convert the font into the coordinate space used by `DefineFont3`.
+        let scale = 20480.0 / f32::from(units_per_em);
+        let stretch = if synthesis.bold {
+            FAUX_BOLD_STRETCH
+        } else {
+            1.0
+        };
+        let shear = if synthesis.italic {
+            FAUX_ITALIC_SHEAR
+        } else {
+            0.0
+        };
+
+        let mut glyphs = vec![];
+        for code in 0..=u16::MAX {
+            let glyph_id = std::char::from_u32(code.into()).and_then(|c| face.glyph_index(c));
+            let glyph_id = match glyph_id {
+                Some(glyph_id) if glyph_id.0 != 0 => glyph_id,
+                _ => continue,
+            };
+
+            let mut builder = GlyphOutlineBuilder::new(scale, stretch, shear);
+            face.outline_glyph(glyph_id, &mut builder);
+            let advance =
+                f32::from(face.glyph_hor_advance(glyph_id).unwrap_or(0)) * scale * stretch;
+            glyphs.push(swf::Glyph {
+                shape_records: builder.records,
+                code,
+                advance: Some(advance.min(f32::from(i16::MAX)) as i16),
+                bounds: None,
+            });
+        }
+
+        let tag = swf::Font {
+            version: 3,
+            id: 0,
+            name: swf::SwfStr::from_utf8_str(name),
+            language: swf::Language::Unknown,
+            layout: Some(swf::FontLayout {
+                ascent: (f32::from(face.ascender()) * scale) as u16,
+                descent: (-f32::from(face.descender()) * scale) as u16,
+                leading: (f32::from(face.line_gap()) * scale) as i16,
+                kerning: vec![],
+            }),
+            glyphs,
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold,
+            is_italic,
+        };
+
+        Self::from_swf_tag(gc_context, renderer, &tag, swf::UTF_8)
+    }
+
+    /// Load a font from a `DefineFont4` tag.
+    ///
+    /// These tags embed an OpenType font file, usually with CFF outlines.
+    /// Tags without font data only name a font expected to be installed, and
+    /// yield a font without glyphs, so that text using it falls back to
+    /// device fonts.
+    pub fn from_font4_tag(
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        tag: &swf::Font4,
+        encoding: &'static swf::Encoding,
+    ) -> Result<Font<'gc>, Error> {
+        if let Some(data) = tag.data {
+            let name = tag.name.to_str_lossy(encoding);
+            return Self::from_font_file(
+                gc_context,
+                renderer,
+                &name,
+                data,
+                tag.is_bold,
+                tag.is_italic,
+                FontSynthesis::default(),
+            );
+        }
+
+        let device_font = swf::Font {
+            version: 3,
+            id: tag.id,
+            name: tag.name,
+            language: swf::Language::Unknown,
+            layout: None,
+            glyphs: vec![],
+            is_small_text: false,
+            is_shift_jis: false,
+            is_ansi: false,
+            is_bold: tag.is_bold,
+            is_italic: tag.is_italic,
+        };
+        Self::from_swf_tag(gc_context, renderer, &device_font, encoding)
+    }
+
     /// Returns whether this font contains glyph shapes.
     /// If not, this font should be rendered as a device font.
     pub fn has_glyphs(&self) -> bool {
@@ -384,6 +498,197 @@ pub struct Glyph {
     pub advance: i16,
 }
 
+/// The horizontal shear applied to glyph outlines to synthesize italics.
+const FAUX_ITALIC_SHEAR: f32 = 0.2;
+
+/// The horizontal stretch applied to glyph outlines to synthesize bold.
+const FAUX_BOLD_STRETCH: f32 = 1.08;
+
+/// Styles that are missing from a font file and have to be approximated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FontSynthesis {
+    pub bold: bool,
+    pub italic: bool,
+}
+
+/// Font files for a device font family, as provided by the host.
+///
+/// Variants that are not provided will be synthesized from the closest
+/// variant that is.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceFontFiles {
+    pub regular: Vec<u8>,
+    pub bold: Option<Vec<u8>>,
+    pub italic: Option<Vec<u8>>,
+    pub bold_italic: Option<Vec<u8>>,
+}
+
+impl DeviceFontFiles {
+    /// Pick the font file to use for the given style, along with any
+    /// styles that the chosen file lacks.
+    pub fn variant(&self, is_bold: bool, is_italic: bool) -> (&[u8], FontSynthesis) {
+        let exact = match (is_bold, is_italic) {
+            (false, false) => Some(&self.regular),
+            (true, false) => self.bold.as_ref(),
+            (false, true) => self.italic.as_ref(),
+            (true, true) => self.bold_italic.as_ref(),
+        };
+        if let Some(data) = exact {
+            return (data, FontSynthesis::default());
+        }
+
+        if is_bold && is_italic {
+            if let Some(bold) = &self.bold {
+                let synthesis = FontSynthesis {
+                    bold: false,
+                    italic: true,
+                };
+                return (bold, synthesis);
+            } else if let Some(italic) = &self.italic {
+                let synthesis = FontSynthesis {
+                    bold: true,
+                    italic: false,
+                };
+                return (italic, synthesis);
+            }
+        }
+
+        let synthesis = FontSynthesis {
+            bold: is_bold,
+            italic: is_italic,
+        };
+        (&self.regular, synthesis)
+    }
+}
+
+/// Converts TrueType/OpenType glyph outlines into SWF shape records.
+struct GlyphOutlineBuilder {
+    scale: f32,
+    stretch: f32,
+    shear: f32,
+
+    records: Vec<swf::ShapeRecord>,
+
+    /// The start of the current contour, in twips.
+    contour_start: (i32, i32),
+
+    /// The current pen position, in twips.
+    position: (i32, i32),
+
+    /// The current pen position, in font units.
+    last_point: (f32, f32),
+}
+
+impl GlyphOutlineBuilder {
+    fn new(scale: f32, stretch: f32, shear: f32) -> Self {
+        Self {
+            scale,
+            stretch,
+            shear,
+            records: vec![],
+            contour_start: (0, 0),
+            position: (0, 0),
+            last_point: (0.0, 0.0),
+        }
+    }
+
+    fn to_twips(&self, x: f32, y: f32) -> (i32, i32) {
+        // Font files are Y-up, while SWF shapes are Y-down.
+        let x = (x + y * self.shear) * self.stretch * self.scale;
+        let y = -y * self.scale;
+        (x.round() as i32, y.round() as i32)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for GlyphOutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = self.to_twips(x, y);
+        // Only the first contour needs to select the fill style.
+        let fill_style_1 = if self.records.is_empty() {
+            Some(1)
+        } else {
+            None
+        };
+        self.records
+            .push(swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+                move_to: Some((Twips::new(point.0), Twips::new(point.1))),
+                fill_style_0: None,
+                fill_style_1,
+                line_style: None,
+                new_styles: None,
+            }));
+        self.contour_start = point;
+        self.position = point;
+        self.last_point = (x, y);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.to_twips(x, y);
+        self.records.push(swf::ShapeRecord::StraightEdge {
+            delta_x: Twips::new(point.0 - self.position.0),
+            delta_y: Twips::new(point.1 - self.position.1),
+        });
+        self.position = point;
+        self.last_point = (x, y);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let control = self.to_twips(x1, y1);
+        let anchor = self.to_twips(x, y);
+        self.records.push(swf::ShapeRecord::CurvedEdge {
+            control_delta_x: Twips::new(control.0 - self.position.0),
+            control_delta_y: Twips::new(control.1 - self.position.1),
+            anchor_delta_x: Twips::new(anchor.0 - control.0),
+            anchor_delta_y: Twips::new(anchor.1 - control.1),
+        });
+        self.position = anchor;
+        self.last_point = (x, y);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        // SWF shapes only support quadratic curves, so split the cubic curve
+        // in half and approximate each half with a single quadratic curve.
+        fn mid(a: (f32, f32), b: (f32, f32)) -> (f32, f32) {
+            ((a.0 + b.0) / 2.0, (a.1 + b.1) / 2.0)
+        }
+        fn quad_control(
+            p0: (f32, f32),
+            p1: (f32, f32),
+            p2: (f32, f32),
+            p3: (f32, f32),
+        ) -> (f32, f32) {
+            (
+                (3.0 * (p1.0 + p2.0) - p0.0 - p3.0) / 4.0,
+                (3.0 * (p1.1 + p2.1) - p0.1 - p3.1) / 4.0,
+            )
+        }
+
+        let p0 = self.last_point;
+        let (p1, p2, p3) = ((x1, y1), (x2, y2), (x, y));
+        let p01 = mid(p0, p1);
+        let p12 = mid(p1, p2);
+        let p23 = mid(p2, p3);
+        let p012 = mid(p01, p12);
+        let p123 = mid(p12, p23);
+        let midpoint = mid(p012, p123);
+
+        let c = quad_control(p0, p01, p012, midpoint);
+        self.quad_to(c.0, c.1, midpoint.0, midpoint.1);
+        let c = quad_control(midpoint, p123, p23, p3);
+        self.quad_to(c.0, c.1, p3.0, p3.1);
+    }
+
+    fn close(&mut self) {
+        if self.position != self.contour_start {
+            self.records.push(swf::ShapeRecord::StraightEdge {
+                delta_x: Twips::new(self.contour_start.0 - self.position.0),
+                delta_y: Twips::new(self.contour_start.1 - self.position.1),
+            });
+            self.position = self.contour_start;
+        }
+    }
+}
+
 /// Structure which identifies a particular font by name and properties.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Collect)]
 #[collect(require_static)]
@@ -488,8 +793,10 @@ impl From<swf::CsmTextSettings> for TextRenderSettings {
 #[cfg(test)]
 mod tests {
     use crate::backend::render::{NullRenderer, RenderBackend};
-    use crate::font::{EvalParameters, Font};
+    use crate::font::{DeviceFontFiles, EvalParameters, Font};
+    use crate::library::Library;
     use crate::player::{Player, DEVICE_FONT_TAG};
+    use crate::shape_utils::DistilledShape;
     use gc_arena::{rootless_arena, MutationContext};
     use std::ops::DerefMut;
     use swf::Twips;
@@ -664,4 +971,68 @@ mod tests {
             assert_eq!(None, breakpoint5);
         });
     }
+
+    #[test]
+    fn device_font_synthesizes_missing_bold() {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new();
+            let mut library = Library::empty(mc);
+            let files = DeviceFontFiles {
+                regular: include_bytes!("../assets/test-font.ttf").to_vec(),
+                ..Default::default()
+            };
+            library
+                .register_device_font_family(mc, &mut renderer, "Test Font", &files)
+                .unwrap();
+
+            let regular = library.get_device_font("Test Font", false, false).unwrap();
+            let bold = library.get_device_font("Test Font", true, false).unwrap();
+            assert!(bold.descriptor().bold());
+            assert!(!bold.descriptor().italic());
+
+            let regular_advance = regular.get_glyph_for_char('A').unwrap().advance;
+            let bold_advance = bold.get_glyph_for_char('A').unwrap().advance;
+            assert!(bold_advance > regular_advance);
+        })
+    }
+
+    #[test]
+    fn define_font_4_glyphs_render() {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new();
+            let tag = swf::Font4 {
+                id: 1,
+                is_italic: false,
+                is_bold: false,
+                name: swf::SwfStr::from_utf8_str("Test Font"),
+                data: Some(include_bytes!("../assets/test-font.ttf")),
+            };
+            let font = Font::from_font4_tag(mc, &mut renderer, &tag, swf::UTF_8).unwrap();
+            assert!(font.has_glyphs());
+            assert_eq!(font.descriptor().class(), "Test Font");
+
+            let glyph = font.get_glyph_for_char('A').unwrap();
+            assert!(glyph.advance > 0);
+            let shape = DistilledShape::from(&glyph.shape);
+            assert!(!shape.paths.is_empty());
+            assert!(shape.shape_bounds.x_max > shape.shape_bounds.x_min);
+        })
+    }
+
+    #[test]
+    fn define_font_4_without_data_is_device_font() {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new();
+            let tag = swf::Font4 {
+                id: 1,
+                is_italic: true,
+                is_bold: false,
+                name: swf::SwfStr::from_utf8_str("Arial"),
+                data: None,
+            };
+            let font = Font::from_font4_tag(mc, &mut renderer, &tag, swf::UTF_8).unwrap();
+            assert!(!font.has_glyphs());
+            assert!(font.descriptor().italic());
+        })
+    }
 }
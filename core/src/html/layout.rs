@@ -90,6 +90,94 @@ pub struct LayoutContext<'a, 'gc> {
     max_bounds: Twips,
 }
 
+/// The state of a layout operation at the start of a paragraph.
+///
+/// Text after an explicit newline can't change the layout of the text before
+/// it, so a layout can be resumed from here after the text that follows it
+/// is edited.
+#[derive(Clone, Debug, Collect)]
+#[collect(no_drop)]
+struct LayoutCheckpoint<'gc> {
+    /// The position in the text that the paragraph starts at.
+    position: usize,
+
+    /// The number of layout boxes that come before the paragraph.
+    num_boxes: usize,
+
+    #[collect(require_static)]
+    cursor: Position<Twips>,
+
+    font: Option<Font<'gc>>,
+
+    #[collect(require_static)]
+    max_font_size: Twips,
+
+    #[collect(require_static)]
+    exterior_bounds: Option<BoxBounds<Twips>>,
+
+    current_line_span: TextSpan,
+}
+
+/// The paragraphs found by the last layout of some text, so that later edits
+/// only need to lay out the paragraphs that they affect.
+#[derive(Clone, Debug, Default, Collect)]
+#[collect(no_drop)]
+pub struct LayoutCache<'gc> {
+    /// The width that the text was laid out in.
+    #[collect(require_static)]
+    max_bounds: Twips,
+
+    is_word_wrap: bool,
+
+    is_device_font: bool,
+
+    /// The start of every paragraph after the first, in text order.
+    checkpoints: Vec<LayoutCheckpoint<'gc>>,
+
+    /// The position in the text that the last layout started from.
+    last_layout_start: usize,
+}
+
+impl<'gc> LayoutCache<'gc> {
+    /// The position in the text that the last layout started from.
+    ///
+    /// Everything before this position kept the layout boxes from the layout
+    /// before it.
+    pub fn last_layout_start(&self) -> usize {
+        self.last_layout_start
+    }
+
+    /// Find the checkpoint to resume a layout from, after the text was
+    /// changed starting at `changed_from`.
+    ///
+    /// Checkpoints after the returned one are discarded, as is everything if
+    /// the text was laid out with other settings.
+    fn resume_point(
+        &mut self,
+        changed_from: usize,
+        max_bounds: Twips,
+        is_word_wrap: bool,
+        is_device_font: bool,
+    ) -> Option<LayoutCheckpoint<'gc>> {
+        if self.max_bounds != max_bounds
+            || self.is_word_wrap != is_word_wrap
+            || self.is_device_font != is_device_font
+        {
+            self.max_bounds = max_bounds;
+            self.is_word_wrap = is_word_wrap;
+            self.is_device_font = is_device_font;
+            self.checkpoints.clear();
+        }
+
+        let index = self
+            .checkpoints
+            .iter()
+            .rposition(|checkpoint| checkpoint.position <= changed_from);
+        self.checkpoints.truncate(index.map_or(0, |i| i + 1));
+        self.checkpoints.last().cloned()
+    }
+}
+
 impl<'a, 'gc> LayoutContext<'a, 'gc> {
     fn new(movie: Arc<SwfMovie>, max_bounds: Twips, text: &'a str) -> Self {
         Self {
@@ -108,6 +196,37 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         }
     }
 
+    /// Continue a layout from the start of a paragraph.
+    ///
+    /// `boxes` must hold at least the layout boxes that came before the
+    /// checkpoint when it was created.
+    fn resume(&mut self, checkpoint: LayoutCheckpoint<'gc>, mut boxes: Vec<LayoutBox<'gc>>) {
+        boxes.truncate(checkpoint.num_boxes);
+        self.boxes = boxes;
+        self.current_line = checkpoint.num_boxes;
+        self.cursor = checkpoint.cursor;
+        self.font = checkpoint.font;
+        self.max_font_size = checkpoint.max_font_size;
+        self.exterior_bounds = checkpoint.exterior_bounds;
+        self.current_line_span = checkpoint.current_line_span;
+        self.is_first_line = true;
+        self.has_line_break = true;
+    }
+
+    /// Save the state of the layout at the start of a paragraph that begins
+    /// at the given text position.
+    fn checkpoint(&self, position: usize) -> LayoutCheckpoint<'gc> {
+        LayoutCheckpoint {
+            position,
+            num_boxes: self.boxes.len(),
+            cursor: self.cursor,
+            font: self.font,
+            max_font_size: self.max_font_size,
+            exterior_bounds: self.exterior_bounds,
+            current_line_span: self.current_line_span.clone(),
+        }
+    }
+
     /// Calculate the font-provided leading present on this line.
     fn font_leading_adjustment(&self) -> Twips {
         // Flash appears to round up the font's leading to the nearest pixel
@@ -397,14 +516,32 @@ impl<'a, 'gc> LayoutContext<'a, 'gc> {
         span: &TextSpan,
         is_device_font: bool,
     ) -> Option<Font<'gc>> {
-        let library = context.library.library_for_movie_mut(self.movie.clone());
+        if !is_device_font {
+            // Embedded fonts are looked up by family and style in the movie's library.
+            // If no matching font was embedded, Flash renders nothing at all.
+            let library = context.library.library_for_movie_mut(self.movie.clone());
+            let font = library
+                .get_font_by_name(&span.font, span.bold, span.italic)
+                .filter(|f| f.has_glyphs());
+            if font.is_none() {
+                log::warn!(
+                    "Embedded font \"{}\" (bold: {}, italic: {}) not found",
+                    span.font,
+                    span.bold,
+                    span.italic
+                );
+            }
 
-        // If this text field is set to use device fonts, fallback to using our embedded Noto Sans.
+            self.font = font;
+            return self.font;
+        }
+
+        // Device fonts registered by the host are used if they match the requested family and style.
+        // Otherwise, fallback to using our embedded Noto Sans.
         // Note that the SWF can still contain a DefineFont tag with no glyphs/layout info in this case (see #451).
-        // In an ideal world, device fonts would search for a matching font on the system and render it in some way.
-        if let Some(font) = library
-            .get_font_by_name(&span.font, span.bold, span.italic)
-            .filter(|f| !is_device_font && f.has_glyphs())
+        if let Some(font) = context
+            .library
+            .get_device_font(&span.font, span.bold, span.italic)
             .or_else(|| context.library.device_font())
         {
             self.font = Some(font);
@@ -657,6 +794,14 @@ impl<'gc> LayoutBox<'gc> {
     ///
     /// The returned bounds will include both the text bounds itself, as well
     /// as left and right margins on any of the lines.
+    ///
+    /// When the text was laid out before, `previous_layout` and `cache` hold
+    /// the result of that layout and `changed_from` is the first position
+    /// in the text that changed since then. The boxes of every paragraph that
+    /// ends before that position are kept, and only the text after them is
+    /// laid out again. `cache` is updated with the paragraphs of the new
+    /// layout.
+    #[allow(clippy::too_many_arguments)]
     pub fn lower_from_text_spans(
         fs: &FormatSpans,
         context: &mut UpdateContext<'_, 'gc, '_>,
@@ -664,12 +809,40 @@ impl<'gc> LayoutBox<'gc> {
         bounds: Twips,
         is_word_wrap: bool,
         is_device_font: bool,
+        previous_layout: Vec<LayoutBox<'gc>>,
+        cache: &mut LayoutCache<'gc>,
+        changed_from: usize,
     ) -> (Vec<LayoutBox<'gc>>, BoxBounds<Twips>) {
         let mut layout_context = LayoutContext::new(movie, bounds, fs.displayed_text());
 
-        for (span_start, _end, span_text, span) in fs.iter_spans() {
+        let resume_from =
+            match cache.resume_point(changed_from, bounds, is_word_wrap, is_device_font) {
+                Some(checkpoint) => {
+                    let position = checkpoint.position;
+                    layout_context.resume(checkpoint, previous_layout);
+                    position
+                }
+                None => 0,
+            };
+        cache.last_layout_start = resume_from;
+
+        for (mut span_start, span_end, mut span_text, span) in fs.iter_spans() {
+            // Spans that started before the resumed paragraph were already
+            // entered before the checkpoint.
+            let is_resumed_span = span_start < resume_from;
+            if is_resumed_span && span_end <= resume_from {
+                continue;
+            }
+
+            if is_resumed_span {
+                span_text = &span_text[resume_from - span_start..];
+                span_start = resume_from;
+            }
+
             if let Some(font) = layout_context.resolve_font(context, &span, is_device_font) {
-                layout_context.newspan(span);
+                if !is_resumed_span {
+                    layout_context.newspan(span);
+                }
 
                 let params = EvalParameters::from_span(span);
 
@@ -684,14 +857,17 @@ impl<'gc> LayoutBox<'gc> {
                         None
                     };
 
+                    let start = span_start + slice_start;
+
                     match delimiter {
-                        Some('\n') | Some('\r') => layout_context.explicit_newline(context),
+                        Some('\n') | Some('\r') => {
+                            layout_context.explicit_newline(context);
+                            cache.checkpoints.push(layout_context.checkpoint(start));
+                        }
                         Some('\t') => layout_context.tab(),
                         _ => {}
                     }
 
-                    let start = span_start + slice_start;
-
                     let mut last_breakpoint = 0;
 
                     if is_word_wrap {
@@ -1,7 +1,10 @@
 //! Tests for HTML module
 
+use crate::character::Character;
 use crate::html::dimensions::{BoxBounds, Position, Size};
+use crate::html::layout::{LayoutBox, LayoutCache};
 use crate::html::text_format::{FormatSpans, TextFormat, TextSpan};
+use crate::player::{Player, PlayerBuilder, DEVICE_FONT_TAG};
 use swf::{Rectangle, Twips};
 
 #[test]
@@ -863,3 +866,59 @@ fn formatspans_replace_text_degenerate() {
     assert_eq!((0, 1), fs.get_span_boundaries(0, 5));
     assert_eq!((1, 2), fs.get_span_boundaries(5, 9));
 }
+
+/// Embed a font in a movie, then lay out a line of text that asks for that
+/// font by name but with the given boldness. Returns the number of boxes laid
+/// out.
+fn layout_embedded_text(font_name: Option<&str>, toggle_bold: bool) -> usize {
+    let player = PlayerBuilder::new().build().unwrap();
+    let mut player = player.lock().unwrap();
+    player.update(|context| {
+        let font = Player::load_device_font(context.gc_context, DEVICE_FONT_TAG, context.renderer)
+            .unwrap();
+        let descriptor = font.descriptor();
+        let movie = context.swf.clone();
+        context
+            .library
+            .library_for_movie_mut(movie.clone())
+            .register_character(1, Character::Font(font));
+
+        let fs = FormatSpans::from_str_and_spans(
+            "Hello",
+            &[TextSpan::with_length_and_format(
+                5,
+                TextFormat {
+                    font: Some(font_name.unwrap_or_else(|| descriptor.class()).to_string()),
+                    size: Some(12.0),
+                    bold: Some(descriptor.bold() != toggle_bold),
+                    italic: Some(descriptor.italic()),
+                    ..Default::default()
+                },
+            )],
+        );
+        let (boxes, _) = LayoutBox::lower_from_text_spans(
+            &fs,
+            context,
+            movie,
+            Twips::from_pixels(100.0),
+            false,
+            false,
+            vec![],
+            &mut LayoutCache::default(),
+            0,
+        );
+        boxes.len()
+    })
+}
+
+#[test]
+fn embedded_font_resolves_by_name_and_style() {
+    assert_ne!(layout_embedded_text(None, false), 0);
+}
+
+#[test]
+fn missing_embedded_font_lays_out_nothing() {
+    // Embedded text never falls back to a device font.
+    assert_eq!(layout_embedded_text(None, true), 0);
+    assert_eq!(layout_embedded_text(Some("Missing Font"), false), 0);
+}
@@ -111,7 +111,7 @@ fn process_html_entity(src: &str) -> Cow<str> {
 /// means that multiple regions of text apply. When setting the format of a
 /// particular region of text, `None` means that the existing setting for that
 /// property will be retained.
-#[derive(Clone, Debug, Collect, Default)]
+#[derive(Clone, Debug, Collect, Default, PartialEq)]
 #[collect(require_static)]
 pub struct TextFormat {
     pub font: Option<String>,
@@ -1552,23 +1552,32 @@ impl FormatSpans {
             ));
         }
 
-        let mut new_string = String::new();
-        if let Some(text) = self.text.get(0..from) {
-            new_string.push_str(text);
+        // `get` will fail if `from` exceeds the bounds of the text, rather
+        // than just giving all of it to us. In that case, we append to the
+        // entire string.
+        let prefix_end = if self.text.get(0..from).is_some() {
+            from
         } else {
-            // `get` will fail if `from` exceeds the bounds of the text, rather
-            // than just giving all of it to us. In that case, we append the
-            // entire string.
-            new_string.push_str(&self.text);
-        }
-        new_string.push_str(with);
+            self.text.len()
+        };
 
-        if let Some(text) = self.text.get(to..) {
-            new_string.push_str(text);
+        // Edit the text in place, so that appending to large text fields
+        // doesn't copy all of their text every time.
+        match self.text.get(to..).map(|_| to) {
+            Some(suffix_start) if suffix_start >= prefix_end => {
+                self.text.replace_range(prefix_end..suffix_start, with);
+            }
+            Some(suffix_start) => {
+                let suffix = self.text[suffix_start..].to_string();
+                self.text.push_str(with);
+                self.text.push_str(&suffix);
+            }
+            None => {
+                self.text.truncate(prefix_end);
+                self.text.push_str(with);
+            }
         }
 
-        self.text = new_string;
-
         self.normalize();
     }
 
@@ -20,17 +20,21 @@ extern crate downcast_rs;
 
 #[macro_use]
 mod avm1;
+pub mod amf;
 mod avm2;
 pub mod bitmap;
 mod bounding_box;
 mod character;
+pub mod clock;
 mod collect;
 pub mod color_transform;
 pub mod context;
 pub mod context_menu;
+pub mod diagnostics;
 mod drawing;
 mod ecma_conversions;
 pub mod events;
+mod flv;
 pub mod focus_tracker;
 mod font;
 mod html;
@@ -38,9 +42,13 @@ mod library;
 pub mod loader;
 mod player;
 mod prelude;
+pub mod replay;
+pub mod security;
 pub mod shape_utils;
 pub mod string_utils;
 pub mod tag_utils;
+#[cfg(test)]
+mod test_utils;
 mod transform;
 mod types;
 mod vminterface;
@@ -52,8 +60,10 @@ pub mod external;
 
 pub use chrono;
 pub use context_menu::ContextMenuItem;
+pub use display_object::{Scene, StageScaleMode};
 pub use events::PlayerEvent;
+pub use font::DeviceFontFiles;
 pub use indexmap;
-pub use player::Player;
+pub use player::{Player, PlayerBuilder};
 pub use swf;
 pub use swf::Color;
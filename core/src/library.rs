@@ -2,9 +2,10 @@ use crate::avm1::function::FunctionObject;
 use crate::avm1::property_map::PropertyMap as Avm1PropertyMap;
 use crate::avm2::{Domain as Avm2Domain, Object as Avm2Object};
 use crate::backend::audio::SoundHandle;
+use crate::backend::render::RenderBackend;
 use crate::character::Character;
 use crate::display_object::{Bitmap, TDisplayObject};
-use crate::font::{Font, FontDescriptor};
+use crate::font::{DeviceFontFiles, Font, FontDescriptor};
 use crate::prelude::*;
 use crate::tag_utils::{SwfMovie, SwfSlice};
 use crate::vminterface::AvmType;
@@ -140,6 +141,19 @@ pub struct MovieLibrary<'gc> {
     avm_type: AvmType,
     avm2_domain: Option<Avm2Domain<'gc>>,
 
+    /// Whether the SWF was published with remote debugging enabled.
+    debug_enabled: bool,
+
+    /// Whether the SWF was published with import protection.
+    protected: bool,
+
+    /// The RDF/XML metadata the SWF was published with, if any.
+    metadata: Option<String>,
+
+    /// Whether the SWF was published to use network access when run
+    /// locally.
+    uses_network_sandbox: bool,
+
     /// Shared reference to the constructor registry used for this movie.
     /// Should be `None` if this is an AVM2 movie.
     avm1_constructor_registry: Option<Gc<'gc, Avm1ConstructorRegistry<'gc>>>,
@@ -154,6 +168,10 @@ impl<'gc> MovieLibrary<'gc> {
             fonts: HashMap::new(),
             avm_type,
             avm2_domain: None,
+            debug_enabled: false,
+            protected: false,
+            metadata: None,
+            uses_network_sandbox: false,
             avm1_constructor_registry: None,
         }
     }
@@ -317,6 +335,48 @@ impl<'gc> MovieLibrary<'gc> {
         self.jpeg_tables.as_ref().map(|data| &data[..])
     }
 
+    /// Whether the SWF was published with an `EnableDebugger` tag, allowing
+    /// remote debugging.
+    ///
+    /// Like the rest of the publishing settings, this is only known once the
+    /// movie has been preloaded.
+    pub fn is_debug_enabled(&self) -> bool {
+        self.debug_enabled
+    }
+
+    pub fn set_debug_enabled(&mut self) {
+        self.debug_enabled = true;
+    }
+
+    /// Whether the SWF was published with a `Protect` tag, asking authoring
+    /// tools not to import it.
+    pub fn is_protected(&self) -> bool {
+        self.protected
+    }
+
+    pub fn set_protected(&mut self) {
+        self.protected = true;
+    }
+
+    /// The RDF/XML metadata from the SWF's `Metadata` tag, if any.
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
+    pub fn set_metadata(&mut self, metadata: String) {
+        self.metadata = Some(metadata);
+    }
+
+    /// Whether the SWF's `FileAttributes` ask for network access, instead
+    /// of file access, when the movie is run locally.
+    pub fn uses_network_sandbox(&self) -> bool {
+        self.uses_network_sandbox
+    }
+
+    pub fn set_uses_network_sandbox(&mut self, uses_network_sandbox: bool) {
+        self.uses_network_sandbox = uses_network_sandbox;
+    }
+
     /// Check if the current movie's VM type is compatible with running code on
     /// a particular VM. If it is not, then this yields an error.
     pub fn check_avm_type(&mut self, new_type: AvmType) -> Result<(), Error> {
@@ -370,6 +430,9 @@ pub struct Library<'gc> {
     /// The embedded device font.
     device_font: Option<Font<'gc>>,
 
+    /// Device fonts provided by the host, keyed by family and style.
+    device_fonts: HashMap<FontDescriptor, Font<'gc>>,
+
     constructor_registry_case_insensitive: Gc<'gc, Avm1ConstructorRegistry<'gc>>,
     constructor_registry_case_sensitive: Gc<'gc, Avm1ConstructorRegistry<'gc>>,
 
@@ -385,6 +448,9 @@ unsafe impl<'gc> gc_arena::Collect for Library<'gc> {
             val.trace(cc);
         }
         self.device_font.trace(cc);
+        for font in self.device_fonts.values() {
+            font.trace(cc);
+        }
         self.constructor_registry_case_insensitive.trace(cc);
         self.constructor_registry_case_sensitive.trace(cc);
         self.avm2_constructor_registry.trace(cc);
@@ -396,6 +462,7 @@ impl<'gc> Library<'gc> {
         Self {
             movie_libraries: PtrWeakKeyHashMap::new(),
             device_font: None,
+            device_fonts: HashMap::new(),
             constructor_registry_case_insensitive: Gc::allocate(
                 gc_context,
                 Avm1ConstructorRegistry::new(false, gc_context),
@@ -460,6 +527,45 @@ impl<'gc> Library<'gc> {
         self.device_font = font;
     }
 
+    /// Registers a device font provided by the host.
+    pub fn register_device_font(&mut self, font: Font<'gc>) {
+        self.device_fonts.insert(font.descriptor().clone(), font);
+    }
+
+    /// Registers all variants of a device font family from font files.
+    ///
+    /// Missing variants are synthesized from the closest available one.
+    pub fn register_device_font_family(
+        &mut self,
+        gc_context: MutationContext<'gc, '_>,
+        renderer: &mut dyn RenderBackend,
+        name: &str,
+        files: &DeviceFontFiles,
+    ) -> Result<(), Error> {
+        for &(is_bold, is_italic) in &[(false, false), (true, false), (false, true), (true, true)] {
+            let (data, synthesis) = files.variant(is_bold, is_italic);
+            if synthesis.bold || synthesis.italic {
+                log::warn!(
+                    "Device font \"{}\" has no {}{} variant, synthesizing it",
+                    name,
+                    if is_bold { "bold " } else { "" },
+                    if is_italic { "italic" } else { "regular" }
+                );
+            }
+            let font = Font::from_font_file(
+                gc_context, renderer, name, data, is_bold, is_italic, synthesis,
+            )?;
+            self.register_device_font(font);
+        }
+        Ok(())
+    }
+
+    /// Find a device font provided by the host by its name and style.
+    pub fn get_device_font(&self, name: &str, is_bold: bool, is_italic: bool) -> Option<Font<'gc>> {
+        let descriptor = FontDescriptor::from_parts(name, is_bold, is_italic);
+        self.device_fonts.get(&descriptor).copied()
+    }
+
     /// Gets the constructor registry to use for the given SWF version.
     /// Because SWFs v6 and v7+ use different case-sensitivity rules, Flash
     /// keeps two separate registries, one case-sensitive, the other not.
@@ -484,3 +590,35 @@ impl<'gc> Library<'gc> {
         &mut self.avm2_constructor_registry
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::render::NullRenderer;
+    use crate::character::Character;
+    use crate::library::MovieLibrary;
+    use crate::player::{Player, DEVICE_FONT_TAG};
+    use crate::vminterface::AvmType;
+    use gc_arena::rootless_arena;
+
+    #[test]
+    fn embedded_font_by_name_and_style() {
+        rootless_arena(|mc| {
+            let mut renderer = NullRenderer::new();
+            let font = Player::load_device_font(mc, DEVICE_FONT_TAG, &mut renderer).unwrap();
+            let name = font.descriptor().class().to_string();
+            let (is_bold, is_italic) = (font.descriptor().bold(), font.descriptor().italic());
+
+            let mut library = MovieLibrary::new(AvmType::Avm1);
+            library.register_character(1, Character::Font(font));
+
+            let found = library.get_font_by_name(&name, is_bold, is_italic).unwrap();
+            assert_eq!(found.descriptor(), font.descriptor());
+            assert!(library
+                .get_font_by_name(&name, !is_bold, is_italic)
+                .is_none());
+            assert!(library
+                .get_font_by_name("Missing Font", is_bold, is_italic)
+                .is_none());
+        })
+    }
+}
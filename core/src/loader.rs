@@ -2,19 +2,28 @@
 
 use crate::avm1::activation::{Activation, ActivationIdentifier};
 use crate::avm1::{Avm1, AvmString, Object, TObject, Value};
-use crate::avm2::Domain as Avm2Domain;
-use crate::backend::navigator::OwnedFuture;
-use crate::context::{ActionQueue, ActionType};
-use crate::display_object::{DisplayObject, MorphShape, TDisplayObject};
+use crate::avm2::{
+    Activation as Avm2Activation, Domain as Avm2Domain, Event as Avm2Event, EventData,
+    LoaderStream, Namespace as Avm2Namespace, Object as Avm2Object, QName as Avm2QName,
+    TObject as Avm2TObject, Value as Avm2Value,
+};
+use crate::backend::audio::decoders::AdpcmDecoder;
+use crate::backend::audio::SoundHandle;
+use crate::backend::navigator::{OwnedFuture, RetryPolicy};
+use crate::context::{ActionQueue, ActionType, UpdateContext};
+use crate::display_object::{DisplayObject, MorphShape, TDisplayObject, Video};
+use crate::flv;
 use crate::player::{Player, NEWEST_PLAYER_VERSION};
 use crate::tag_utils::SwfMovie;
 use crate::vminterface::Instantiator;
 use crate::xml::XmlNode;
 use encoding_rs::UTF_8;
-use gc_arena::{Collect, CollectionContext};
+use gc_arena::{Collect, CollectionContext, MutationContext};
 use generational_arena::{Arena, Index};
+use std::io::Cursor;
 use std::string::FromUtf8Error;
 use std::sync::{Arc, Mutex, Weak};
+use swf::{AudioCompression, SoundFormat};
 use thiserror::Error;
 use url::form_urlencoded;
 
@@ -40,6 +49,12 @@ pub enum Error {
     #[error("Non-XML loader spawned as XML loader")]
     NotXmlLoader,
 
+    #[error("Non-URLLoader loader spawned as URLLoader loader")]
+    NotUrlLoader,
+
+    #[error("Non-NetStream loader spawned as NetStream loader")]
+    NotNetStreamLoader,
+
     #[error("Could not fetch movie {0}")]
     FetchError(String),
 
@@ -55,10 +70,16 @@ pub enum Error {
     #[error("Network unavailable.")]
     NetworkUnavailable,
 
+    #[error("Security sandbox violation: {0}")]
+    SecurityError(String),
+
     // TODO: We can't support lifetimes on this error object yet (or we'll need some backends inside
     // the GC arena). We're losing info here. How do we fix that?
     #[error("Error running avm1 script: {0}")]
     Avm1Error(String),
+
+    #[error("Error running avm2 script: {0}")]
+    Avm2Error(String),
 }
 
 pub type FormLoadHandler<'gc> =
@@ -72,12 +93,23 @@ impl From<crate::avm1::error::Error<'_>> for Error {
     }
 }
 
+impl From<crate::avm2::Error> for Error {
+    fn from(error: crate::avm2::Error) -> Self {
+        Error::Avm2Error(error.to_string())
+    }
+}
+
 /// Holds all in-progress loads for the player.
-pub struct LoadManager<'gc>(Arena<Loader<'gc>>);
+pub struct LoadManager<'gc> {
+    loaders: Arena<Loader<'gc>>,
+
+    /// How fetches made on behalf of movies are retried when they fail.
+    retry_policy: RetryPolicy,
+}
 
 unsafe impl<'gc> Collect for LoadManager<'gc> {
     fn trace(&self, cc: CollectionContext) {
-        for (_, loader) in self.0.iter() {
+        for (_, loader) in self.loaders.iter() {
             loader.trace(cc)
         }
     }
@@ -86,7 +118,19 @@ unsafe impl<'gc> Collect for LoadManager<'gc> {
 impl<'gc> LoadManager<'gc> {
     /// Construct a new `LoadManager`.
     pub fn new() -> Self {
-        Self(Arena::new())
+        Self {
+            loaders: Arena::new(),
+            retry_policy: RetryPolicy::default(),
+        }
+    }
+
+    /// How failed fetches are retried.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
     }
 
     /// Add a new loader to the `LoadManager`.
@@ -96,8 +140,8 @@ impl<'gc> LoadManager<'gc> {
     /// finishes, the handle will be invalidated (and the underlying loader
     /// deleted).
     pub fn add_loader(&mut self, loader: Loader<'gc>) -> Handle {
-        let handle = self.0.insert(loader);
-        self.0
+        let handle = self.loaders.insert(loader);
+        self.loaders
             .get_mut(handle)
             .unwrap()
             .introduce_loader_handle(handle);
@@ -107,12 +151,12 @@ impl<'gc> LoadManager<'gc> {
 
     /// Retrieve a loader by handle.
     pub fn get_loader(&self, handle: Handle) -> Option<&Loader<'gc>> {
-        self.0.get(handle)
+        self.loaders.get(handle)
     }
 
     /// Retrieve a loader by handle for mutation.
     pub fn get_loader_mut(&mut self, handle: Handle) -> Option<&mut Loader<'gc>> {
-        self.0.get_mut(handle)
+        self.loaders.get_mut(handle)
     }
 
     /// Kick off the root movie load.
@@ -148,13 +192,15 @@ impl<'gc> LoadManager<'gc> {
         fetch: OwnedFuture<Vec<u8>, Error>,
         url: String,
         loader_url: Option<String>,
-        target_broadcaster: Option<Object<'gc>>,
+        event_handler: Option<MovieLoaderEventHandler<'gc>>,
     ) -> OwnedFuture<(), Error> {
         let loader = Loader::Movie {
             self_handle: None,
             target_clip,
-            target_broadcaster,
+            event_handler,
             loader_status: LoaderStatus::Pending,
+            bytes_loaded: 0,
+            bytes_total: 0,
         };
         let handle = self.add_loader(loader);
 
@@ -164,6 +210,27 @@ impl<'gc> LoadManager<'gc> {
         loader.movie_loader(player, fetch, url, loader_url)
     }
 
+    /// The download progress of a movie that is replacing the contents of
+    /// `clip`, as a pair of bytes loaded and bytes total.
+    ///
+    /// Returns `None` once the movie has been loaded into the clip, or if no
+    /// movie is being loaded into it.
+    ///
+    /// TODO: Navigators only hand over complete responses, so this stays at
+    /// zero until the whole movie has been downloaded.
+    pub fn movie_load_progress(&self, clip: DisplayObject<'gc>) -> Option<(usize, usize)> {
+        self.loaders.iter().find_map(|(_, loader)| match loader {
+            Loader::Movie {
+                target_clip,
+                loader_status: LoaderStatus::Pending,
+                bytes_loaded,
+                bytes_total,
+                ..
+            } if DisplayObject::ptr_eq(*target_clip, clip) => Some((*bytes_loaded, *bytes_total)),
+            _ => None,
+        })
+    }
+
     /// Indicates that a movie clip has initialized (ran its first frame).
     ///
     /// Interested loaders will be invoked from here.
@@ -175,14 +242,14 @@ impl<'gc> LoadManager<'gc> {
     ) {
         let mut invalidated_loaders = vec![];
 
-        for (index, loader) in self.0.iter_mut() {
+        for (index, loader) in self.loaders.iter_mut() {
             if loader.movie_clip_loaded(loaded_clip, clip_object, queue) {
                 invalidated_loaders.push(index);
             }
         }
 
         for index in invalidated_loaders {
-            self.0.remove(index);
+            self.loaders.remove(index);
         }
     }
 
@@ -250,6 +317,51 @@ impl<'gc> LoadManager<'gc> {
 
         loader.xml_loader(player, fetch)
     }
+
+    /// Kick off a data load into a `URLLoader`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_data_into_url_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::UrlLoader {
+            self_handle: None,
+            target_object,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.url_loader(player, fetch, url)
+    }
+
+    /// Kick off an FLV load for a `NetStream`.
+    ///
+    /// Returns the loader's async process, which you will need to spawn.
+    pub fn load_flv_into_net_stream(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        target_object: Avm2Object<'gc>,
+        video: Option<Video<'gc>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let loader = Loader::NetStream {
+            self_handle: None,
+            target_object,
+            video,
+        };
+        let handle = self.add_loader(loader);
+
+        let loader = self.get_loader_mut(handle).unwrap();
+        loader.introduce_loader_handle(handle);
+
+        loader.net_stream_loader(player, fetch)
+    }
 }
 
 impl<'gc> Default for LoadManager<'gc> {
@@ -270,6 +382,18 @@ pub enum LoaderStatus {
     Failed,
 }
 
+/// The object that receives events from a movie loader.
+#[derive(Clone, Copy, Collect)]
+#[collect(no_drop)]
+pub enum MovieLoaderEventHandler<'gc> {
+    /// An AVM1 broadcaster (typically a `MovieClipLoader`) whose listeners
+    /// are notified with `broadcastMessage`.
+    Avm1Broadcast(Object<'gc>),
+
+    /// An AVM2 `LoaderInfo` that events are dispatched on.
+    Avm2LoaderInfo(Avm2Object<'gc>),
+}
+
 /// A struct that holds garbage-collected pointers for asynchronous code.
 #[derive(Collect)]
 #[collect(no_drop)]
@@ -290,9 +414,9 @@ pub enum Loader<'gc> {
         /// The target movie clip to load the movie into.
         target_clip: DisplayObject<'gc>,
 
-        /// Event broadcaster (typically a `MovieClipLoader`) to fire events
-        /// into.
-        target_broadcaster: Option<Object<'gc>>,
+        /// Event broadcaster (typically a `MovieClipLoader`) or `LoaderInfo`
+        /// to fire events into.
+        event_handler: Option<MovieLoaderEventHandler<'gc>>,
 
         /// Indicates the completion status of this loader.
         ///
@@ -303,6 +427,12 @@ pub enum Loader<'gc> {
         /// or an error has occurred (in which case we don't care about the
         /// loader anymore).
         loader_status: LoaderStatus,
+
+        /// How much of the movie has been downloaded so far, in bytes.
+        bytes_loaded: usize,
+
+        /// The size of the movie, in bytes, once the server has responded.
+        bytes_total: usize,
     },
 
     /// Loader that is loading form data into an AVM1 object scope.
@@ -342,6 +472,29 @@ pub enum Loader<'gc> {
         /// The target node whose contents will be replaced with the parsed XML.
         target_node: XmlNode<'gc>,
     },
+
+    /// Loader that is loading data into an AVM2 `URLLoader`.
+    UrlLoader {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The target `URLLoader` to load data into.
+        target_object: Avm2Object<'gc>,
+    },
+
+    /// Loader that is loading an FLV file for an AVM2 `NetStream`.
+    NetStream {
+        /// The handle to refer to this loader instance.
+        #[collect(require_static)]
+        self_handle: Option<Handle>,
+
+        /// The `NetStream` that requested the video.
+        target_object: Avm2Object<'gc>,
+
+        /// The video player attached to the stream when playback started.
+        video: Option<Video<'gc>>,
+    },
 }
 
 impl<'gc> Loader<'gc> {
@@ -356,6 +509,8 @@ impl<'gc> Loader<'gc> {
             Loader::Form { self_handle, .. } => *self_handle = Some(handle),
             Loader::LoadVars { self_handle, .. } => *self_handle = Some(handle),
             Loader::Xml { self_handle, .. } => *self_handle = Some(handle),
+            Loader::UrlLoader { self_handle, .. } => *self_handle = Some(handle),
+            Loader::NetStream { self_handle, .. } => *self_handle = Some(handle),
         }
     }
 
@@ -439,12 +594,8 @@ impl<'gc> Loader<'gc> {
                 .update(|uc| -> Result<(), Error> {
                     url = uc.navigator.resolve_relative_url(&url).into_owned();
 
-                    let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                        Some(Loader::Movie {
-                            target_clip,
-                            target_broadcaster,
-                            ..
-                        }) => (*target_clip, *target_broadcaster),
+                    let clip = match uc.load_manager.get_loader(handle) {
+                        Some(Loader::Movie { target_clip, .. }) => *target_clip,
                         None => return Err(Error::Cancelled),
                         _ => unreachable!(),
                     };
@@ -457,152 +608,259 @@ impl<'gc> Loader<'gc> {
                         .unwrap()
                         .replace_with_movie(uc.gc_context, None);
 
-                    if let Some(broadcaster) = broadcaster {
-                        Avm1::run_stack_frame_for_method(
-                            clip,
-                            broadcaster,
-                            NEWEST_PLAYER_VERSION,
-                            uc,
-                            "broadcastMessage",
-                            &["onLoadStart".into(), Value::Object(broadcaster)],
-                        );
-                    }
-
                     Ok(())
                 })?;
 
-            let data = (fetch.await).and_then(|data| {
-                Ok((
-                    data.len(),
-                    SwfMovie::from_data(&data, Some(url.clone()), loader_url.clone())?,
-                ))
-            });
-            if let Ok((length, movie)) = data {
-                let movie = Arc::new(movie);
-                if replacing_root_movie {
-                    player.lock().unwrap().set_root_movie(movie);
-                    return Ok(());
-                }
+            let data = fetch.await;
 
-                player
-                    .lock()
-                    .expect("Could not lock player!!")
-                    .update(|uc| {
-                        let domain =
-                            Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain());
-                        uc.library
-                            .library_for_movie_mut(movie.clone())
-                            .set_avm2_domain(domain);
-
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
+            // Flash only fires `onLoadStart` once the server has responded.
+            if data.is_ok() {
+                player.lock().expect("Could not lock player!!").update(
+                    |uc| -> Result<(), Error> {
+                        let (clip, event_handler) = match uc.load_manager.get_loader(handle) {
                             Some(Loader::Movie {
                                 target_clip,
-                                target_broadcaster,
+                                event_handler,
                                 ..
-                            }) => (*target_clip, *target_broadcaster),
+                            }) => (*target_clip, *event_handler),
                             None => return Err(Error::Cancelled),
                             _ => unreachable!(),
                         };
 
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadProgress".into(),
-                                    Value::Object(broadcaster),
-                                    length.into(),
-                                    length.into(),
-                                ],
-                            );
-                        }
-
-                        let mut mc = clip
-                            .as_movie_clip()
-                            .expect("Attempted to load movie into not movie clip");
-
-                        mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
-                        mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
-
-                        let mut morph_shapes = fnv::FnvHashMap::default();
-                        mc.preload(uc, &mut morph_shapes);
-
-                        // Finalize morph shapes.
-                        for (id, static_data) in morph_shapes {
-                            let morph_shape = MorphShape::new(uc.gc_context, static_data);
-                            uc.library
-                                .library_for_movie_mut(movie.clone())
-                                .register_character(
-                                    id,
-                                    crate::character::Character::MorphShape(morph_shape),
+                        match event_handler {
+                            Some(MovieLoaderEventHandler::Avm1Broadcast(broadcaster)) => {
+                                Avm1::run_stack_frame_for_method(
+                                    clip,
+                                    broadcaster,
+                                    NEWEST_PLAYER_VERSION,
+                                    uc,
+                                    "broadcastMessage",
+                                    &["onLoadStart".into(), clip.object()],
                                 );
+                            }
+                            Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) => {
+                                uc.action_queue.queue_actions(
+                                    clip,
+                                    ActionType::Event2 {
+                                        event: Avm2Event::new("open"),
+                                        target: loader_info,
+                                    },
+                                    false,
+                                );
+                            }
+                            None => {}
                         }
 
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &["onLoadComplete".into(), Value::Object(broadcaster)],
-                            );
-                        }
-
-                        if let Some(Loader::Movie { loader_status, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *loader_status = LoaderStatus::Succeeded;
-                        };
-
                         Ok(())
-                    })
-            } else {
-                //TODO: Inspect the fetch error.
-                //This requires cooperation from the backend to send abstract
-                //error types we can actually inspect.
-                //This also can get errors from decoding an invalid SWF file,
-                //too. We should distinguish those to player code.
-                player
-                    .lock()
-                    .expect("Could not lock player!!")
-                    .update(|uc| -> Result<(), Error> {
-                        let (clip, broadcaster) = match uc.load_manager.get_loader(handle) {
-                            Some(Loader::Movie {
-                                target_clip,
-                                target_broadcaster,
-                                ..
-                            }) => (*target_clip, *target_broadcaster),
-                            None => return Err(Error::Cancelled),
-                            _ => unreachable!(),
-                        };
-
-                        if let Some(broadcaster) = broadcaster {
-                            Avm1::run_stack_frame_for_method(
-                                clip,
-                                broadcaster,
-                                NEWEST_PLAYER_VERSION,
-                                uc,
-                                "broadcastMessage",
-                                &[
-                                    "onLoadError".into(),
-                                    Value::Object(broadcaster),
-                                    "LoadNeverCompleted".into(),
-                                ],
-                            );
-                        }
+                    },
+                )?;
+            }
 
-                        if let Some(Loader::Movie { loader_status, .. }) =
-                            uc.load_manager.get_loader_mut(handle)
-                        {
-                            *loader_status = LoaderStatus::Failed;
-                        };
+            let data = data.and_then(|data| {
+                Ok((
+                    data.len(),
+                    SwfMovie::from_data(&data, Some(url.clone()), loader_url.clone())?,
+                ))
+            });
+            match data {
+                Ok((length, movie)) => {
+                    let movie = Arc::new(movie);
+                    if replacing_root_movie {
+                        player.lock().unwrap().set_root_movie(movie);
+                        return Ok(());
+                    }
 
-                        Ok(())
-                    })
+                    player
+                        .lock()
+                        .expect("Could not lock player!!")
+                        .update(|uc| {
+                            let domain =
+                                Avm2Domain::movie_domain(uc.gc_context, uc.avm2.global_domain());
+                            uc.library
+                                .library_for_movie_mut(movie.clone())
+                                .set_avm2_domain(domain);
+
+                            let (clip, event_handler) = match uc.load_manager.get_loader_mut(handle)
+                            {
+                                Some(Loader::Movie {
+                                    target_clip,
+                                    event_handler,
+                                    bytes_loaded,
+                                    bytes_total,
+                                    ..
+                                }) => {
+                                    *bytes_loaded = length;
+                                    *bytes_total = length;
+                                    (*target_clip, *event_handler)
+                                }
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
+
+                            match event_handler {
+                                Some(MovieLoaderEventHandler::Avm1Broadcast(broadcaster)) => {
+                                    Avm1::run_stack_frame_for_method(
+                                        clip,
+                                        broadcaster,
+                                        NEWEST_PLAYER_VERSION,
+                                        uc,
+                                        "broadcastMessage",
+                                        &[
+                                            "onLoadProgress".into(),
+                                            clip.object(),
+                                            length.into(),
+                                            length.into(),
+                                        ],
+                                    );
+                                }
+                                Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) => {
+                                    uc.action_queue.queue_actions(
+                                        clip,
+                                        ActionType::Event2 {
+                                            event: Avm2Event::with_data(
+                                                "progress",
+                                                EventData::Progress {
+                                                    bytes_loaded: length as f64,
+                                                    bytes_total: length as f64,
+                                                },
+                                            ),
+                                            target: loader_info,
+                                        },
+                                        false,
+                                    );
+                                }
+                                None => {}
+                            }
+
+                            let mut mc = clip
+                                .as_movie_clip()
+                                .expect("Attempted to load movie into not movie clip");
+
+                            mc.replace_with_movie(uc.gc_context, Some(movie.clone()));
+                            mc.post_instantiation(uc, clip, None, Instantiator::Movie, false);
+
+                            let mut morph_shapes = fnv::FnvHashMap::default();
+                            mc.preload(uc, &mut morph_shapes);
+
+                            // Finalize morph shapes.
+                            for (id, static_data) in morph_shapes {
+                                let morph_shape = MorphShape::new(uc.gc_context, static_data);
+                                uc.library
+                                    .library_for_movie_mut(movie.clone())
+                                    .register_character(
+                                        id,
+                                        crate::character::Character::MorphShape(morph_shape),
+                                    );
+                            }
+
+                            match event_handler {
+                                Some(MovieLoaderEventHandler::Avm1Broadcast(broadcaster)) => {
+                                    Avm1::run_stack_frame_for_method(
+                                        clip,
+                                        broadcaster,
+                                        NEWEST_PLAYER_VERSION,
+                                        uc,
+                                        "broadcastMessage",
+                                        &["onLoadComplete".into(), clip.object()],
+                                    );
+                                }
+                                Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) => {
+                                    loader_info.set_loader_stream(
+                                        uc.gc_context,
+                                        LoaderStream::Swf(movie.clone(), clip),
+                                    );
+
+                                    // AVM2 clips don't get a `Load` clip event, so
+                                    // `init` can't wait for `movie_clip_loaded`.
+                                    for event_type in &["init", "complete"] {
+                                        uc.action_queue.queue_actions(
+                                            clip,
+                                            ActionType::Event2 {
+                                                event: Avm2Event::new(*event_type),
+                                                target: loader_info,
+                                            },
+                                            false,
+                                        );
+                                    }
+                                }
+                                None => {}
+                            }
+
+                            if let Some(Loader::Movie { loader_status, .. }) =
+                                uc.load_manager.get_loader_mut(handle)
+                            {
+                                *loader_status = LoaderStatus::Succeeded;
+                            };
+
+                            Ok(())
+                        })
+                }
+                Err(error) => {
+                    // Flash reports `URLNotFound` for failed requests, and
+                    // `LoadNeverCompleted` for anything that isn't a valid movie.
+                    //TODO: Inspect the fetch error further.
+                    //This requires cooperation from the backend to send abstract
+                    //error types we can actually inspect.
+                    let error_code = match error {
+                        Error::InvalidSwf(_) => "LoadNeverCompleted",
+                        _ => "URLNotFound",
+                    };
+                    player.lock().expect("Could not lock player!!").update(
+                        |uc| -> Result<(), Error> {
+                            let (clip, event_handler) = match uc.load_manager.get_loader(handle) {
+                                Some(Loader::Movie {
+                                    target_clip,
+                                    event_handler,
+                                    ..
+                                }) => (*target_clip, *event_handler),
+                                None => return Err(Error::Cancelled),
+                                _ => unreachable!(),
+                            };
+
+                            match event_handler {
+                                Some(MovieLoaderEventHandler::Avm1Broadcast(broadcaster)) => {
+                                    Avm1::run_stack_frame_for_method(
+                                        clip,
+                                        broadcaster,
+                                        NEWEST_PLAYER_VERSION,
+                                        uc,
+                                        "broadcastMessage",
+                                        &["onLoadError".into(), clip.object(), error_code.into()],
+                                    );
+                                }
+                                Some(MovieLoaderEventHandler::Avm2LoaderInfo(loader_info)) => {
+                                    let event = match &error {
+                                        Error::SecurityError(message) => {
+                                            security_error_event(uc.gc_context, message)
+                                        }
+                                        _ => io_error_event(
+                                            uc.gc_context,
+                                            2035,
+                                            format!("Error #2035: URL Not Found. URL: {}", url),
+                                        ),
+                                    };
+                                    uc.action_queue.queue_actions(
+                                        clip,
+                                        ActionType::Event2 {
+                                            event,
+                                            target: loader_info,
+                                        },
+                                        false,
+                                    );
+                                }
+                                None => {}
+                            }
+
+                            if let Some(Loader::Movie { loader_status, .. }) =
+                                uc.load_manager.get_loader_mut(handle)
+                            {
+                                *loader_status = LoaderStatus::Failed;
+                            };
+
+                            Ok(())
+                        },
+                    )
+                }
             }
         })
     }
@@ -719,13 +977,13 @@ impl<'gc> Loader<'gc> {
         clip_object: Option<Object<'gc>>,
         queue: &mut ActionQueue<'gc>,
     ) -> bool {
-        let (clip, broadcaster, loader_status) = match self {
+        let (clip, event_handler, loader_status) = match self {
             Loader::Movie {
                 target_clip,
-                target_broadcaster,
+                event_handler,
                 loader_status,
                 ..
-            } => (*target_clip, *target_broadcaster, *loader_status),
+            } => (*target_clip, *event_handler, *loader_status),
             _ => return false,
         };
 
@@ -737,7 +995,7 @@ impl<'gc> Loader<'gc> {
             LoaderStatus::Pending => false,
             LoaderStatus::Failed => true,
             LoaderStatus::Succeeded => {
-                if let Some(broadcaster) = broadcaster {
+                if let Some(MovieLoaderEventHandler::Avm1Broadcast(broadcaster)) = event_handler {
                     queue.queue_actions(
                         clip,
                         ActionType::Method {
@@ -852,4 +1110,668 @@ impl<'gc> Loader<'gc> {
             Ok(())
         })
     }
+
+    /// Creates a future for a `URLLoader` load call.
+    ///
+    /// The loaded data is stored on the `URLLoader` in the format requested
+    /// by its `dataFormat`, and the outcome is reported as AVM2 events.
+    pub fn url_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+        url: String,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::UrlLoader { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotUrlLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let data = fetch.await;
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let target = match uc.load_manager.get_loader(handle) {
+                        Some(&Loader::UrlLoader { target_object, .. }) => target_object,
+                        None => return Err(Error::Cancelled),
+                        _ => return Err(Error::NotUrlLoader),
+                    };
+                    let root = uc.stage.root_clip();
+
+                    match data {
+                        Ok(data) => {
+                            let length = data.len() as f64;
+                            let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                            set_url_loader_data(&mut activation, target, &data)?;
+
+                            for event in vec![
+                                Avm2Event::with_data(
+                                    "progress",
+                                    EventData::Progress {
+                                        bytes_loaded: length,
+                                        bytes_total: length,
+                                    },
+                                ),
+                                Avm2Event::new("complete"),
+                            ] {
+                                uc.action_queue.queue_actions(
+                                    root,
+                                    ActionType::Event2 { event, target },
+                                    false,
+                                );
+                            }
+                        }
+                        Err(error) => {
+                            let event = match &error {
+                                Error::SecurityError(message) => {
+                                    security_error_event(uc.gc_context, message)
+                                }
+                                _ => io_error_event(
+                                    uc.gc_context,
+                                    2032,
+                                    format!("Error #2032: Stream Error. URL: {}", url),
+                                ),
+                            };
+                            uc.action_queue.queue_actions(
+                                root,
+                                ActionType::Event2 { event, target },
+                                false,
+                            );
+                        }
+                    }
+
+                    Ok(())
+                })
+        })
+    }
+
+    /// Creates a future for a `NetStream` play call.
+    ///
+    /// The downloaded FLV is handed to the stream's video player, and the
+    /// outcome is reported as `netStatus` events.
+    pub fn net_stream_loader(
+        &mut self,
+        player: Weak<Mutex<Player>>,
+        fetch: OwnedFuture<Vec<u8>, Error>,
+    ) -> OwnedFuture<(), Error> {
+        let handle = match self {
+            Loader::NetStream { self_handle, .. } => {
+                self_handle.expect("Loader not self-introduced")
+            }
+            _ => return Box::pin(async { Err(Error::NotNetStreamLoader) }),
+        };
+
+        let player = player
+            .upgrade()
+            .expect("Could not upgrade weak reference to player");
+
+        Box::pin(async move {
+            let data = fetch.await;
+
+            player
+                .lock()
+                .expect("Could not lock player!!")
+                .update(|uc| -> Result<(), Error> {
+                    let (target, video) = match uc.load_manager.get_loader(handle) {
+                        Some(&Loader::NetStream {
+                            target_object,
+                            video,
+                            ..
+                        }) => (target_object, video),
+                        None => return Err(Error::Cancelled),
+                        _ => return Err(Error::NotNetStreamLoader),
+                    };
+
+                    let (code, level) = match data.map(|data| flv::parse(&data)) {
+                        Ok(Ok(media)) => {
+                            // TODO: Without a `Video` to play on, the stream
+                            // has nothing to keep time, and isn't played.
+                            if let Some(video) = video {
+                                video.stop_net_stream(uc);
+                                if let Some(flv) = media.video {
+                                    video.attach_flv(uc, flv);
+                                }
+                                if let Some(audio) = media.audio {
+                                    let start_time = audio.start_time;
+                                    if let Some(sound) =
+                                        register_flv_audio(uc, audio, media.duration)
+                                    {
+                                        video.attach_flv_sound(uc, sound, start_time);
+                                    }
+                                }
+                            }
+                            ("NetStream.Play.Start", "status")
+                        }
+                        Ok(Err(e)) => {
+                            log::warn!("Unable to play NetStream video: {}", e);
+                            ("NetStream.Play.FileStructureInvalid", "error")
+                        }
+                        Err(_) => ("NetStream.Play.StreamNotFound", "error"),
+                    };
+
+                    let event = {
+                        let mut activation = Avm2Activation::from_nothing(uc.reborrow());
+                        Avm2Event::net_status(&mut activation, code, level)?
+                    };
+                    let root = uc.stage.root_clip();
+                    uc.action_queue.queue_actions(
+                        root,
+                        ActionType::Event2 { event, target },
+                        false,
+                    );
+
+                    Ok(())
+                })
+        })
+    }
+}
+
+/// Register the audio track of an FLV file as a single event sound.
+///
+/// ADPCM packets each begin with their own header, so they are decoded one by
+/// one and joined as uncompressed audio. Packets in other formats can simply
+/// be joined.
+fn register_flv_audio(
+    uc: &mut UpdateContext<'_, '_, '_>,
+    audio: flv::FlvAudio,
+    duration: Option<f64>,
+) -> Option<SoundHandle> {
+    let (format, data, num_samples) = match audio.format.compression {
+        AudioCompression::Adpcm => {
+            let mut data = Vec::new();
+            let mut num_samples = 0;
+            for packet in &audio.packets {
+                let decoder = AdpcmDecoder::new(
+                    Cursor::new(&packet[..]),
+                    audio.format.is_stereo,
+                    audio.format.sample_rate,
+                );
+                for frame in decoder {
+                    data.extend_from_slice(&frame[0].to_le_bytes());
+                    data.extend_from_slice(&frame[1].to_le_bytes());
+                    num_samples += 1;
+                }
+            }
+            let format = SoundFormat {
+                compression: AudioCompression::Uncompressed,
+                sample_rate: audio.format.sample_rate,
+                is_stereo: true,
+                is_16_bit: true,
+            };
+            (format, data, num_samples)
+        }
+        compression => {
+            // Event sounds in MP3 format begin with a latency seek, which FLV
+            // audio packets lack.
+            let mut data = if compression == AudioCompression::Mp3 {
+                vec![0, 0]
+            } else {
+                vec![]
+            };
+            for packet in &audio.packets {
+                data.extend_from_slice(packet);
+            }
+            let num_samples = duration
+                .map(|duration| (duration * f64::from(audio.format.sample_rate)) as u32)
+                .unwrap_or_default();
+            (audio.format, data, num_samples)
+        }
+    };
+
+    let sound = swf::Sound {
+        id: 0,
+        format,
+        num_samples,
+        data: &data,
+    };
+    match uc.audio.register_sound(&sound) {
+        Ok(handle) => Some(handle),
+        Err(e) => {
+            log::error!("Unable to register NetStream audio: {}", e);
+            None
+        }
+    }
+}
+
+/// Construct an AVM2 `IOErrorEvent` for a failed load.
+fn io_error_event<'gc>(
+    mc: MutationContext<'gc, '_>,
+    error_id: i32,
+    text: String,
+) -> Avm2Event<'gc> {
+    Avm2Event::with_data(
+        "ioError",
+        EventData::IOError {
+            text: AvmString::new(mc, text),
+            error_id,
+        },
+    )
+}
+
+/// Construct the AVM2 `SecurityErrorEvent` for a blocked load.
+fn security_error_event<'gc>(mc: MutationContext<'gc, '_>, message: &str) -> Avm2Event<'gc> {
+    Avm2Event::with_data(
+        "securityError",
+        EventData::SecurityError {
+            text: AvmString::new(
+                mc,
+                format!("Error #2048: Security sandbox violation: {}", message),
+            ),
+            error_id: 2048,
+        },
+    )
+}
+
+/// Store loaded data on a `URLLoader`, decoded according to its `dataFormat`.
+fn set_url_loader_data<'gc>(
+    activation: &mut Avm2Activation<'_, 'gc, '_>,
+    mut target: Avm2Object<'gc>,
+    data: &[u8],
+) -> Result<(), Error> {
+    let data_format = target
+        .get_property(
+            target,
+            &Avm2QName::new(Avm2Namespace::public(), "dataFormat"),
+            activation,
+        )?
+        .coerce_to_string(activation)?;
+
+    let data_object: Avm2Value<'gc> = match data_format.as_str() {
+        "binary" => {
+            let bytearray_proto = activation.context.avm2.prototypes().bytearray;
+            let bytearray = bytearray_proto.construct(activation, &[])?;
+            if let Some(mut storage) = bytearray.as_bytearray_mut(activation.context.gc_context) {
+                storage.write_bytes_at(data, 0);
+            }
+
+            bytearray.into()
+        }
+        "variables" => {
+            let variables_proto = activation.context.avm2.prototypes().url_variables;
+            let mut variables = variables_proto.construct(activation, &[])?;
+            let source = AvmString::new(activation.context.gc_context, UTF_8.decode(data).0);
+            let decode = variables
+                .get_property(
+                    variables,
+                    &Avm2QName::new(Avm2Namespace::public(), "decode"),
+                    activation,
+                )?
+                .coerce_to_object(activation)?;
+            decode.call(Some(variables), &[source.into()], activation, None)?;
+
+            variables.into()
+        }
+        _ => AvmString::new(activation.context.gc_context, UTF_8.decode(data).0).into(),
+    };
+
+    let length = data.len() as f64;
+    for (name, value) in vec![
+        ("data", data_object),
+        ("bytesLoaded", length.into()),
+        ("bytesTotal", length.into()),
+    ] {
+        target.set_property(
+            target,
+            &Avm2QName::new(Avm2Namespace::public(), name),
+            value,
+            activation,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::error::Error as Avm1Error;
+    use crate::avm1::property::Attribute;
+    use crate::avm1::ScriptObject;
+    use crate::backend::navigator::{NullExecutor, NullNavigatorBackend};
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{
+        flv, stage_size, swf_data, swf_header, RecordingAudioBackend, TempDir,
+    };
+
+    /// Appends `event:target` to the `log` of a `MovieClipLoader` listener.
+    fn log_event<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        event: &str,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Avm1Error<'gc>> {
+        let target = match args.get(0) {
+            Some(Value::Object(target)) => target
+                .as_display_object()
+                .map(|target| target.name().to_string())
+                .unwrap_or_default(),
+            _ => String::new(),
+        };
+        let log = this.get("log", activation)?.coerce_to_string(activation)?;
+        let log = format!("{}{}:{},", log, event, target);
+        this.set(
+            "log",
+            AvmString::new(activation.context.gc_context, log).into(),
+            activation,
+        )?;
+        Ok(Value::Undefined)
+    }
+
+    fn on_load_start<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Avm1Error<'gc>> {
+        log_event(activation, this, "onLoadStart", args)
+    }
+
+    fn on_load_progress<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Avm1Error<'gc>> {
+        log_event(activation, this, "onLoadProgress", args)
+    }
+
+    fn on_load_complete<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Avm1Error<'gc>> {
+        log_event(activation, this, "onLoadComplete", args)
+    }
+
+    fn on_load_init<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Avm1Error<'gc>> {
+        log_event(activation, this, "onLoadInit", args)
+    }
+
+    fn on_load_error<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        this: Object<'gc>,
+        args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, Avm1Error<'gc>> {
+        let error_code = args
+            .get(1)
+            .cloned()
+            .unwrap_or(Value::Undefined)
+            .coerce_to_string(activation)?;
+        let event = format!("onLoadError({})", error_code);
+        log_event(activation, this, &event, args)
+    }
+
+    /// Writes a one frame movie to `path`.
+    fn write_movie(path: &std::path::Path) {
+        let header = swf::Header {
+            stage_size: stage_size(100.0, 100.0),
+            ..swf_header(8, 10.0, 1)
+        };
+        let data = swf_data(header, vec![swf::Tag::ShowFrame]);
+        std::fs::write(path, data).unwrap();
+    }
+
+    /// Loads `url` into a new clip named `target` with a `MovieClipLoader`,
+    /// returning the log of events received by its listener.
+    fn load_clip_events(url: &'static str) -> String {
+        let base_path = TempDir::new(&format!("movie_clip_loader_{}", url));
+        write_movie(&base_path.path().join("child.swf"));
+
+        let (mut executor, channel) = NullExecutor::new();
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(NullNavigatorBackend::with_base_path(
+                base_path.path(),
+                channel,
+            )))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation =
+                Activation::from_stub(context.reborrow(), ActivationIdentifier::root("[Test]"));
+            let gc_context = activation.context.gc_context;
+            let globals = activation.context.avm1.global_object_cell();
+            let root = activation
+                .context
+                .stage
+                .root_clip()
+                .object()
+                .coerce_to_object(&mut activation);
+            let target = root
+                .call_method(
+                    "createEmptyMovieClip",
+                    &["target".into(), 1.into()],
+                    &mut activation,
+                )
+                .unwrap();
+
+            let mut listener = ScriptObject::object(
+                gc_context,
+                Some(activation.context.avm1.prototypes().object),
+            );
+            let fn_proto = Some(activation.context.avm1.prototypes().function);
+            listener.define_value(gc_context, "log", "".into(), Attribute::empty());
+            listener.force_set_function(
+                "onLoadStart",
+                on_load_start,
+                gc_context,
+                Attribute::empty(),
+                fn_proto,
+            );
+            listener.force_set_function(
+                "onLoadProgress",
+                on_load_progress,
+                gc_context,
+                Attribute::empty(),
+                fn_proto,
+            );
+            listener.force_set_function(
+                "onLoadComplete",
+                on_load_complete,
+                gc_context,
+                Attribute::empty(),
+                fn_proto,
+            );
+            listener.force_set_function(
+                "onLoadInit",
+                on_load_init,
+                gc_context,
+                Attribute::empty(),
+                fn_proto,
+            );
+            listener.force_set_function(
+                "onLoadError",
+                on_load_error,
+                gc_context,
+                Attribute::empty(),
+                fn_proto,
+            );
+            globals
+                .set("listener", listener.into(), &mut activation)
+                .unwrap();
+
+            let loader = globals
+                .get("MovieClipLoader", &mut activation)
+                .unwrap()
+                .coerce_to_object(&mut activation)
+                .construct(&mut activation, &[])
+                .unwrap()
+                .coerce_to_object(&mut activation);
+            loader
+                .call_method("addListener", &[listener.into()], &mut activation)
+                .unwrap();
+            loader
+                .call_method("loadClip", &[url.into(), target], &mut activation)
+                .unwrap();
+        });
+
+        executor.block_all().unwrap();
+
+        // `onLoadInit` fires once the loaded clip has run its first frame.
+        player.lock().unwrap().run_frame();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation =
+                Activation::from_stub(context.reborrow(), ActivationIdentifier::root("[Test]"));
+            let listener = activation
+                .context
+                .avm1
+                .global_object_cell()
+                .get("listener", &mut activation)
+                .unwrap()
+                .coerce_to_object(&mut activation);
+            listener
+                .get("log", &mut activation)
+                .unwrap()
+                .coerce_to_string(&mut activation)
+                .unwrap()
+                .to_string()
+        })
+    }
+
+    /// Calls `getBytesLoaded` and `getBytesTotal` on the clip named `target`.
+    fn target_bytes(activation: &mut Activation<'_, '_, '_>) -> (f64, f64) {
+        let root = activation
+            .context
+            .stage
+            .root_clip()
+            .object()
+            .coerce_to_object(activation);
+        let target = root
+            .get("target", activation)
+            .unwrap()
+            .coerce_to_object(activation);
+        let mut bytes = |name| {
+            target
+                .call_method(name, &[], activation)
+                .unwrap()
+                .coerce_to_f64(activation)
+                .unwrap()
+        };
+        (bytes("getBytesLoaded"), bytes("getBytesTotal"))
+    }
+
+    #[test]
+    fn movie_clip_reports_download_progress() {
+        let base_path = TempDir::new("movie_clip_download_progress");
+        let path = base_path.path().join("child.swf");
+        write_movie(&path);
+        let length = std::fs::read(&path).unwrap().len() as f64;
+
+        let (mut executor, channel) = NullExecutor::new();
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(NullNavigatorBackend::with_base_path(
+                base_path.path(),
+                channel,
+            )))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation =
+                Activation::from_stub(context.reborrow(), ActivationIdentifier::root("[Test]"));
+            let root = activation
+                .context
+                .stage
+                .root_clip()
+                .object()
+                .coerce_to_object(&mut activation);
+            let target = root
+                .call_method(
+                    "createEmptyMovieClip",
+                    &["target".into(), 1.into()],
+                    &mut activation,
+                )
+                .unwrap()
+                .coerce_to_object(&mut activation);
+            target
+                .call_method("loadMovie", &["child.swf".into()], &mut activation)
+                .unwrap();
+
+            // Nothing has been downloaded yet.
+            assert_eq!(target_bytes(&mut activation), (0.0, 0.0));
+        });
+
+        executor.block_all().unwrap();
+
+        player.lock().unwrap().update(|context| {
+            let mut activation =
+                Activation::from_stub(context.reborrow(), ActivationIdentifier::root("[Test]"));
+            assert_eq!(target_bytes(&mut activation), (length, length));
+        });
+    }
+
+    #[test]
+    fn movie_clip_loader_events() {
+        assert_eq!(
+            load_clip_events("child.swf"),
+            "onLoadStart:target,onLoadProgress:target,onLoadComplete:target,onLoadInit:target,"
+        );
+    }
+
+    #[test]
+    fn movie_clip_loader_missing_url() {
+        assert_eq!(
+            load_clip_events("missing.swf"),
+            "onLoadError(URLNotFound):target,"
+        );
+    }
+
+    #[test]
+    fn flv_audio_is_decoded_per_packet_and_starts_on_time() {
+        const TAG_AUDIO: u8 = 8;
+        // ADPCM at 44.1kHz in mono, with 2 bit samples. Each packet starts
+        // with a header, followed by four samples.
+        let packet = vec![0x1E, 0x00, 0xFA, 0x00, 0x00];
+        let data = flv(&[(TAG_AUDIO, 250, packet.clone()), (TAG_AUDIO, 350, packet)]);
+        let audio = flv::parse(&data).unwrap().audio.unwrap();
+
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let player = PlayerBuilder::new()
+            .with_audio(Box::new(RecordingAudioBackend::new(log.clone())))
+            .build()
+            .unwrap();
+
+        player.lock().unwrap().update(|context| {
+            *context.frame_rate = 10.0;
+            let start_time = audio.start_time;
+            let sound = register_flv_audio(context, audio, None).unwrap();
+            let video = Video::new(context.gc_context, 320, 240);
+            video.attach_flv_sound(context, sound, start_time);
+            assert_eq!(
+                *log.lock().unwrap(),
+                vec!["register Uncompressed 8 samples, 32 bytes"]
+            );
+
+            // The sound starts on the first frame at or after 250ms.
+            for _ in 0..2 {
+                video.run_frame(context);
+            }
+            assert_eq!(log.lock().unwrap().len(), 1);
+            video.run_frame(context);
+            assert_eq!(log.lock().unwrap().last().unwrap(), "start");
+
+            video.stop_net_stream(context);
+        });
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "register Uncompressed 8 samples, 32 bytes",
+                "start",
+                "stop",
+                "unregister"
+            ]
+        );
+    }
 }
@@ -6,36 +6,43 @@ use crate::avm1::property::Attribute;
 use crate::avm1::{Avm1, AvmString, ScriptObject, TObject, Timers, Value};
 use crate::avm2::{Avm2, Domain as Avm2Domain};
 use crate::backend::{
-    audio::{AudioBackend, AudioManager},
-    locale::LocaleBackend,
-    log::LogBackend,
-    navigator::{NavigatorBackend, RequestOptions},
-    render::RenderBackend,
-    storage::StorageBackend,
-    ui::{MouseCursor, UiBackend},
-    video::VideoBackend,
+    audio::{AudioBackend, AudioManager, NullAudioBackend},
+    locale::{LocaleBackend, NullLocaleBackend},
+    log::{LogBackend, NullLogBackend},
+    navigator::{NavigatorBackend, NullNavigatorBackend, RequestOptions, RetryPolicy},
+    render::{NullRenderer, RenderBackend},
+    storage::{MemoryStorageBackend, StorageBackend},
+    ui::{MouseCursor, NullUiBackend, UiBackend},
+    video::{NullVideoBackend, VideoBackend},
 };
-use crate::config::Letterbox;
+use crate::clock::{Clock, WallClock};
+use crate::config::{AxisScaling, Letterbox};
 use crate::context::{ActionQueue, ActionType, RenderContext, UpdateContext};
 use crate::context_menu::{ContextMenuCallback, ContextMenuItem, ContextMenuState};
-use crate::display_object::{EditText, MorphShape, MovieClip, Stage};
+use crate::diagnostics::DiagnosticsReport;
+use crate::display_object::{
+    EditText, MorphShape, MovieClip, Scene, Stage, StageQuality, StageScaleMode,
+};
 use crate::events::{ButtonKeyCode, ClipEvent, ClipEventResult, KeyCode, PlayerEvent};
 use crate::external::Value as ExternalValue;
 use crate::external::{ExternalInterface, ExternalInterfaceProvider};
 use crate::focus_tracker::FocusTracker;
+use crate::font::DeviceFontFiles;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::prelude::*;
+use crate::replay::{RecordingClock, Replay, ReplayClock, ReplayInput};
+use crate::security::{self, PolicyCache};
 use crate::tag_utils::SwfMovie;
 use crate::transform::TransformStack;
 use crate::vminterface::{AvmType, Instantiator};
 use gc_arena::{make_arena, ArenaParameters, Collect, GcCell};
 use instant::Instant;
 use log::info;
-use rand::{rngs::SmallRng, SeedableRng};
+use rand::{rngs::SmallRng, Rng, SeedableRng};
 use std::collections::{HashMap, VecDeque};
 use std::convert::TryFrom;
-use std::ops::DerefMut;
+use std::ops::{Deref, DerefMut};
 use std::sync::{Arc, Mutex, Weak};
 use std::time::Duration;
 
@@ -185,6 +192,15 @@ pub struct Player {
 
     frame_rate: f64,
 
+    /// The highest frame rate to run movies at, if limited by the user.
+    max_frame_rate_cap: Option<f64>,
+
+    /// Whether right-clicks open a context menu at all.
+    ///
+    /// When disabled, neither the built-in nor the movie's custom items are
+    /// offered, so the host can show its own menu instead.
+    show_context_menu: bool,
+
     /// A time budget for executing frames.
     /// Gained by passage of time between host frames, spent by executing SWF frames.
     /// This is how we support custom SWF framerates
@@ -195,6 +211,12 @@ pub struct Player {
     /// Faked time passage for fooling hand-written busy-loop FPS limiters.
     time_offset: u32,
 
+    /// The source of time for the movie.
+    clock: Box<dyn Clock>,
+
+    /// The time of `clock` when the player was last advanced to it.
+    clock_time: Duration,
+
     mouse_pos: (Twips, Twips),
     is_mouse_down: bool,
 
@@ -203,6 +225,15 @@ pub struct Player {
 
     system: SystemProperties,
 
+    /// Cross-domain policy files fetched so far.
+    policy_cache: PolicyCache,
+
+    /// Unsupported features encountered so far.
+    diagnostics: DiagnosticsReport,
+
+    /// The path of the display object to render in place of the whole stage.
+    render_root: Option<String>,
+
     /// The current instance ID. Used to generate default `instanceN` names.
     instance_counter: i32,
 
@@ -211,7 +242,9 @@ pub struct Player {
 
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
-    max_execution_duration: Duration,
+    ///
+    /// `None` disables the timeout, and scripts are never checked.
+    max_execution_duration: Option<Duration>,
 
     /// Self-reference to ourselves.
     ///
@@ -223,6 +256,9 @@ pub struct Player {
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     current_frame: Option<u16>,
+
+    /// The inputs recorded so far, if a recording is in progress.
+    recording: Option<Replay>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -243,7 +279,11 @@ impl Player {
         let movie_height = 400;
         let frame_rate = 12.0;
         // Disable script timeout in debug builds by default.
-        let max_execution_duration = if cfg!(debug_assertions) { u64::MAX } else { 15 };
+        let max_execution_duration = if cfg!(debug_assertions) {
+            None
+        } else {
+            Some(Duration::from_secs(15))
+        };
 
         let mut player = Player {
             player_version: NEWEST_PLAYER_VERSION,
@@ -283,9 +323,13 @@ impl Player {
             }),
 
             frame_rate,
+            max_frame_rate_cap: None,
+            show_context_menu: true,
             frame_accumulator: 0.0,
             recent_run_frame_timings: VecDeque::with_capacity(10),
             time_offset: 0,
+            clock: Box::new(WallClock::new()),
+            clock_time: Duration::default(),
 
             mouse_pos: (Twips::zero(), Twips::zero()),
             is_mouse_down: false,
@@ -300,11 +344,15 @@ impl Player {
             video,
             self_reference: None,
             system: SystemProperties::default(),
+            policy_cache: PolicyCache::new(),
+            diagnostics: DiagnosticsReport::new(),
+            render_root: None,
             instance_counter: 0,
             time_til_next_timer: None,
             storage,
-            max_execution_duration: Duration::from_secs(max_execution_duration),
+            max_execution_duration,
             current_frame: None,
+            recording: None,
         };
 
         player.mutate_with_update_context(|context| {
@@ -376,9 +424,10 @@ impl Player {
         );
 
         self.frame_rate = movie.header().frame_rate.into();
-        self.swf = movie;
         self.instance_counter = 0;
 
+        self.swf = movie;
+
         self.mutate_with_update_context(|context| {
             context.stage.set_movie_size(
                 context.gc_context,
@@ -446,6 +495,13 @@ impl Player {
         });
 
         self.preload();
+
+        // The sandbox depends on the `FileAttributes` read while preloading.
+        self.mutate_with_update_context(|context| {
+            let movie = context.swf.clone();
+            context.system.sandbox_type = security::movie_sandbox_type(context, &movie);
+        });
+
         self.audio.set_frame_rate(self.frame_rate);
     }
 
@@ -468,7 +524,7 @@ impl Player {
         if self.recent_run_frame_timings.is_empty() {
             5
         } else {
-            let frame_time = 1000.0 / self.frame_rate;
+            let frame_time = 1000.0 / self.effective_frame_rate();
             let average_run_frame_time = self.recent_run_frame_timings.iter().sum::<f64>()
                 / self.recent_run_frame_timings.len() as f64;
             ((frame_time / average_run_frame_time) as u32)
@@ -493,9 +549,24 @@ impl Player {
 
         if self.is_playing() {
             self.frame_accumulator += dt;
-            let frame_time = 1000.0 / self.frame_rate;
+            let frame_time = 1000.0 / self.effective_frame_rate();
+
+            // Stream sounds are the authority on timing. If a timeline has drifted
+            // behind its stream sound (e.g. after a slow frame), run enough frames
+            // to catch up with the audio, even past the usual per-tick frame limit.
+            // Small drifts are left alone to avoid jitter.
+            const STREAM_SYNC_THRESHOLD: f64 = 2.0;
+            let mut stream_catch_up_frames = 0;
+            if let Some(frames_behind) = self.stream_frames_behind() {
+                if frames_behind >= STREAM_SYNC_THRESHOLD {
+                    stream_catch_up_frames = frames_behind.floor() as u32;
+                    self.frame_accumulator = self
+                        .frame_accumulator
+                        .max(f64::from(stream_catch_up_frames) * frame_time);
+                }
+            }
 
-            let max_frames_per_tick = self.max_frames_per_tick();
+            let max_frames_per_tick = self.max_frames_per_tick().max(stream_catch_up_frames);
             let mut frame = 0;
 
             while frame < max_frames_per_tick && self.frame_accumulator >= frame_time {
@@ -536,10 +607,21 @@ impl Player {
         }
     }
 
+    /// Returns how many frames the timeline furthest behind its stream sound has fallen behind.
+    /// Returns `None` if no stream sound is playing.
+    fn stream_frames_behind(&mut self) -> Option<f64> {
+        let frame_rate = self.frame_rate;
+        self.mutate_with_update_context(|context| {
+            context
+                .audio_manager
+                .stream_frames_behind(context.audio, frame_rate)
+        })
+    }
+
     /// Returns the approximate duration of time until the next frame is due to run.
     /// This is only an approximation to be used for sleep durations.
     pub fn time_til_next_frame(&self) -> std::time::Duration {
-        let frame_time = 1000.0 / self.frame_rate;
+        let frame_time = 1000.0 / self.effective_frame_rate();
         let mut dt = if self.frame_accumulator <= 0.0 {
             frame_time
         } else if self.frame_accumulator >= frame_time {
@@ -561,7 +643,19 @@ impl Player {
         self.is_playing
     }
 
+    pub fn show_context_menu(&self) -> bool {
+        self.show_context_menu
+    }
+
+    pub fn set_show_context_menu(&mut self, show_context_menu: bool) {
+        self.show_context_menu = show_context_menu;
+    }
+
     pub fn prepare_context_menu(&mut self) -> Vec<ContextMenuItem> {
+        if !self.show_context_menu {
+            return vec![];
+        }
+
         self.mutate_with_update_context(|context| {
             if !context.stage.show_menu() {
                 return vec![];
@@ -717,6 +811,64 @@ impl Player {
         })
     }
 
+    pub fn scale_mode(&mut self) -> StageScaleMode {
+        self.mutate_with_update_context(|context| context.stage.scale_mode())
+    }
+
+    /// Override how the movie scales to fill the viewport.
+    pub fn set_scale_mode(&mut self, scale_mode: StageScaleMode) {
+        self.mutate_with_update_context(|context| context.stage.set_scale_mode(context, scale_mode))
+    }
+
+    pub fn axis_scaling(&mut self) -> AxisScaling {
+        self.mutate_with_update_context(|context| context.stage.axis_scaling())
+    }
+
+    /// Scale each axis of the movie independently, when it uses the default
+    /// `StageScaleMode::ShowAll`.
+    pub fn set_axis_scaling(&mut self, axis_scaling: AxisScaling) {
+        self.mutate_with_update_context(|context| {
+            context.stage.set_axis_scaling(context, axis_scaling)
+        })
+    }
+
+    pub fn letterbox_color(&mut self) -> Color {
+        self.mutate_with_update_context(|context| context.stage.letterbox_color())
+    }
+
+    pub fn set_letterbox_color(&mut self, color: Color) {
+        self.mutate_with_update_context(|context| {
+            context.stage.set_letterbox_color(context.gc_context, color)
+        })
+    }
+
+    pub fn quality(&mut self) -> StageQuality {
+        self.mutate_with_update_context(|context| context.stage.quality())
+    }
+
+    pub fn set_quality(&mut self, quality: StageQuality) {
+        self.mutate_with_update_context(|context| context.stage.set_quality(context, quality))
+    }
+
+    /// Registers a device font family from TrueType or OpenType font files.
+    ///
+    /// Text using device fonts will render with this family, instead of the
+    /// built-in fallback font, when it requests it by name.
+    pub fn register_device_font_family(
+        &mut self,
+        name: &str,
+        files: &DeviceFontFiles,
+    ) -> Result<(), Error> {
+        self.mutate_with_update_context(|context| {
+            context.library.register_device_font_family(
+                context.gc_context,
+                context.renderer,
+                name,
+                files,
+            )
+        })
+    }
+
     pub fn warn_on_unsupported_content(&self) -> bool {
         self.warn_on_unsupported_content
     }
@@ -745,6 +897,8 @@ impl Player {
     }
 
     pub fn handle_event(&mut self, event: PlayerEvent) {
+        self.record_input(|| ReplayInput::Event(event.clone()));
+
         let mut needs_render = self.needs_render;
         let inverse_view_matrix =
             self.mutate_with_update_context(|context| context.stage.inverse_view_matrix());
@@ -856,6 +1010,18 @@ impl Player {
             });
         }
 
+        // Tab moves keyboard focus, or Shift+Tab to go backwards.
+        if let PlayerEvent::KeyDown {
+            key_code: KeyCode::Tab,
+        } = event
+        {
+            let reverse = self.ui.is_key_down(KeyCode::Shift);
+            self.mutate_with_update_context(|context| {
+                let tracker = context.focus_tracker;
+                tracker.cycle(context, reverse);
+            });
+        }
+
         if let PlayerEvent::TextInput { codepoint } = event {
             self.mutate_with_update_context(|context| {
                 if let Some(text) = context.focus_tracker.get().and_then(|o| o.as_edit_text()) {
@@ -914,7 +1080,12 @@ impl Player {
             }
         });
 
-        let mut is_mouse_down = self.is_mouse_down;
+        // Scripts handling the press or release see the new button state.
+        match event {
+            PlayerEvent::MouseDown { .. } => self.is_mouse_down = true,
+            PlayerEvent::MouseUp { .. } => self.is_mouse_down = false,
+            _ => (),
+        }
         self.mutate_with_update_context(|context| {
             if let Some(node) = context.mouse_hovered_object {
                 if node.removed() {
@@ -924,7 +1095,6 @@ impl Player {
 
             match event {
                 PlayerEvent::MouseDown { .. } => {
-                    is_mouse_down = true;
                     needs_render = true;
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Press);
@@ -932,7 +1102,6 @@ impl Player {
                 }
 
                 PlayerEvent::MouseUp { .. } => {
-                    is_mouse_down = false;
                     needs_render = true;
                     if let Some(node) = context.mouse_hovered_object {
                         node.handle_clip_event(context, ClipEvent::Release);
@@ -944,7 +1113,6 @@ impl Player {
 
             Self::run_actions(context);
         });
-        self.is_mouse_down = is_mouse_down;
         if needs_render {
             self.needs_render = true;
         }
@@ -1065,6 +1233,8 @@ impl Player {
     }
 
     pub fn run_frame(&mut self) {
+        self.record_input(|| ReplayInput::RunFrame);
+
         self.update(|update_context| {
             // TODO: In what order are levels run?
             let stage = update_context.stage;
@@ -1082,8 +1252,12 @@ impl Player {
     }
 
     pub fn render(&mut self) {
-        let (renderer, ui, transform_stack) =
-            (&mut self.renderer, &mut self.ui, &mut self.transform_stack);
+        let (renderer, ui, transform_stack, render_root) = (
+            &mut self.renderer,
+            &mut self.ui,
+            &mut self.transform_stack,
+            &self.render_root,
+        );
 
         self.gc_arena.mutate(|_gc_context, gc_root| {
             let root_data = gc_root.0.read();
@@ -1097,18 +1271,81 @@ impl Player {
                 allow_mask: true,
             };
 
-            root_data.stage.render(&mut render_context);
+            let stage = root_data.stage;
+            match render_root
+                .as_deref()
+                .and_then(|path| stage.object_by_path(path))
+            {
+                Some(object) => stage.render_subtree(&mut render_context, object),
+                None => stage.render(&mut render_context),
+            }
         });
 
         self.needs_render = false;
     }
 
+    /// The path of the display object rendered in place of the stage, if any.
+    pub fn render_root(&self) -> Option<&str> {
+        self.render_root.as_deref()
+    }
+
+    /// Render only the display object at `path` and its children, scaled to
+    /// fit the viewport, instead of the whole stage.
+    ///
+    /// The path is made of instance names from the root movie, such as
+    /// `menu.icon`. It is resolved on every render, and the whole stage is
+    /// rendered while nothing exists at that path. The rest of the movie
+    /// still runs as usual. Passing `None` renders the whole stage again.
+    pub fn set_render_root(&mut self, path: Option<String>) {
+        self.render_root = path;
+        self.needs_render = true;
+    }
+
     /// The current frame of the main timeline, if available.
     /// The first frame is frame 1.
     pub fn current_frame(&self) -> Option<u16> {
         self.current_frame
     }
 
+    /// The scenes of the main timeline, in playback order.
+    ///
+    /// Movies without scene data have no scenes listed.
+    pub fn scenes(&mut self) -> Vec<Scene> {
+        self.mutate_with_update_context(|context| {
+            context
+                .stage
+                .root_clip()
+                .as_movie_clip()
+                .map(|root| root.scenes())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Jump the main timeline to the first frame of the named scene.
+    ///
+    /// Returns `false` if the main timeline has no such scene.
+    pub fn goto_scene(&mut self, scene_label: &str, stop: bool) -> bool {
+        let found = self.update(|context| {
+            let root = match context.stage.root_clip().as_movie_clip() {
+                Some(root) => root,
+                None => return false,
+            };
+
+            match root.scene(scene_label) {
+                Some(scene) => {
+                    root.goto_frame(context, scene.start, stop);
+                    true
+                }
+                None => false,
+            }
+        });
+
+        if found {
+            self.needs_render = true;
+        }
+        found
+    }
+
     pub fn audio(&self) -> &Audio {
         &self.audio
     }
@@ -1122,6 +1359,28 @@ impl Player {
         self.frame_rate
     }
 
+    /// The rate frames actually run at: the movie's frame rate, lowered to
+    /// the user's cap if one is set.
+    pub fn effective_frame_rate(&self) -> f64 {
+        match self.max_frame_rate_cap {
+            Some(cap) => self.frame_rate.min(cap),
+            None => self.frame_rate,
+        }
+    }
+
+    pub fn max_frame_rate_cap(&self) -> Option<f64> {
+        self.max_frame_rate_cap
+    }
+
+    /// Limit movies to run at no more than `cap` frames per second.
+    ///
+    /// Unlike setting the frame rate, this never speeds up slower movies, and
+    /// it keeps applying when a movie changes its frame rate. Timelines with
+    /// stream sounds still catch up to their audio.
+    pub fn set_max_frame_rate_cap(&mut self, cap: Option<f64>) {
+        self.max_frame_rate_cap = cap.filter(|cap| *cap > 0.0);
+    }
+
     pub fn renderer(&self) -> &Renderer {
         &self.renderer
     }
@@ -1130,6 +1389,10 @@ impl Player {
         &mut self.renderer
     }
 
+    pub fn navigator(&self) -> &Navigator {
+        &self.navigator
+    }
+
     pub fn storage(&self) -> &Storage {
         &self.storage
     }
@@ -1231,6 +1494,22 @@ impl Player {
                     );
                 }
 
+                // Message sent to a LocalConnection
+                ActionType::LocalConnectionMessage {
+                    receiver,
+                    method,
+                    args,
+                } => {
+                    Avm1::run_stack_frame_for_method(
+                        actions.clip,
+                        receiver,
+                        context.swf.header().version,
+                        context,
+                        &method,
+                        &args,
+                    );
+                }
+
                 // Event handler method call (e.g. onEnterFrame)
                 ActionType::NotifyListeners {
                     listener,
@@ -1260,6 +1539,14 @@ impl Player {
                         log::error!("Unhandled AVM2 exception in event handler: {}", e);
                     }
                 }
+
+                ActionType::Event2 { event, target } => {
+                    if let Err(e) = Avm2::dispatch_event(context, event, target) {
+                        log::error!("Unhandled AVM2 exception in event handler: {}", e);
+                    }
+                }
+
+                ActionType::ExitFrame => actions.clip.exit_frame(context),
             }
         }
     }
@@ -1270,6 +1557,13 @@ impl Player {
     where
         F: for<'a, 'gc> FnOnce(&mut UpdateContext<'a, 'gc, '_>) -> R,
     {
+        // While recording, every reading of the clock is logged for the replay.
+        let recording_clock = if self.recording.is_some() {
+            Some(RecordingClock::new(self.clock.deref()))
+        } else {
+            None
+        };
+
         // We have to do this piecewise borrowing of fields before the closure to avoid
         // completely borrowing `self`.
         let (
@@ -1281,8 +1575,11 @@ impl Player {
             ui,
             rng,
             mouse_position,
+            is_mouse_down,
             player,
             system_properties,
+            policy_cache,
+            diagnostics,
             instance_counter,
             storage,
             locale,
@@ -1292,6 +1589,7 @@ impl Player {
             max_execution_duration,
             current_frame,
             time_offset,
+            clock,
             frame_rate,
         ) = (
             self.player_version,
@@ -1302,8 +1600,11 @@ impl Player {
             self.ui.deref_mut(),
             &mut self.rng,
             &self.mouse_pos,
+            &self.is_mouse_down,
             self.self_reference.clone(),
             &mut self.system,
+            &mut self.policy_cache,
+            &mut self.diagnostics,
             &mut self.instance_counter,
             self.storage.deref_mut(),
             self.locale.deref_mut(),
@@ -1313,10 +1614,14 @@ impl Player {
             self.max_execution_duration,
             &mut self.current_frame,
             &mut self.time_offset,
+            match &recording_clock {
+                Some(recording_clock) => recording_clock as &dyn Clock,
+                None => self.clock.deref(),
+            },
             &mut self.frame_rate,
         );
 
-        self.gc_arena.mutate(|gc_context, gc_root| {
+        let ret = self.gc_arena.mutate(|gc_context, gc_root| {
             let mut root_data = gc_root.0.write(gc_context);
             let mouse_hovered_object = root_data.mouse_hovered_object;
             let focus_tracker = root_data.focus_tracker;
@@ -1350,6 +1655,7 @@ impl Player {
                 stage,
                 mouse_hovered_object,
                 mouse_position,
+                is_mouse_down,
                 drag_object,
                 player,
                 load_manager,
@@ -1360,6 +1666,8 @@ impl Player {
                 log: logging,
                 video,
                 shared_objects,
+                policy_cache,
+                diagnostics,
                 unbound_text_fields,
                 timers,
                 current_context_menu,
@@ -1372,6 +1680,7 @@ impl Player {
                 focus_tracker,
                 times_get_time_called: 0,
                 time_offset,
+                clock,
                 audio_manager,
                 frame_rate,
             };
@@ -1398,7 +1707,13 @@ impl Player {
             root_data.mouse_hovered_object = update_context.mouse_hovered_object;
 
             ret
-        })
+        });
+
+        if let (Some(recording), Some(recording_clock)) = (&mut self.recording, recording_clock) {
+            recording.extend_clock_readings(recording_clock.into_readings());
+        }
+
+        ret
     }
 
     /// Loads font data from the given buffer.
@@ -1461,13 +1776,123 @@ impl Player {
         });
     }
 
+    /// The names of all `SharedObject`s known to the player.
+    ///
+    /// This includes both the objects loaded by the movie and everything
+    /// saved in the storage backend.
+    pub fn shared_object_names(&mut self) -> Vec<String> {
+        let mut names = self.storage.keys();
+        self.mutate_with_update_context(|context| {
+            names.extend(context.shared_objects.keys().cloned())
+        });
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// The size in bytes of the saved data of a `SharedObject`.
+    ///
+    /// Returns `None` if the object was never flushed to storage.
+    pub fn shared_object_size(&self, name: &str) -> Option<usize> {
+        self.storage.get_size(name)
+    }
+
+    /// Delete the saved data of a `SharedObject`.
+    ///
+    /// If the movie loaded the object, the next `SharedObject.getLocal`
+    /// starts over with an empty object.
+    pub fn delete_shared_object(&mut self, name: &str) {
+        self.storage.remove_key(name);
+        self.mutate_with_update_context(|context| {
+            context.shared_objects.remove(name);
+        });
+    }
+
+    /// Replace the source of time for the movie.
+    ///
+    /// The next call to `advance_clock` starts counting from the current
+    /// time of the new clock.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock_time = clock.time_since_launch();
+        self.clock = clock;
+    }
+
+    /// Run all frames and timers that became due since the clock was last
+    /// advanced to.
+    ///
+    /// This is an alternative to `tick` for hosts that let the player's clock
+    /// keep time, rather than measuring it themselves.
+    pub fn advance_clock(&mut self) {
+        let now = self.clock.time_since_launch();
+        let dt = now.checked_sub(self.clock_time).unwrap_or_default();
+        self.clock_time = now;
+
+        self.tick(dt.as_micros() as f64 / 1000.0);
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
+        self.record_input(|| ReplayInput::UpdateTimers(dt));
+
         self.time_til_next_timer =
             self.mutate_with_update_context(|context| Timers::update_timers(context, dt));
     }
 
+    /// Start recording every input to the player, for later replay.
+    ///
+    /// The random number generator is reseeded, so that the recording can
+    /// reproduce any random numbers drawn by the movie.
+    pub fn start_recording(&mut self) {
+        let seed = self.rng.gen();
+        self.rng = SmallRng::seed_from_u64(seed);
+        self.recording = Some(Replay::new(seed));
+    }
+
+    /// Stop recording, and return the inputs recorded so far.
+    ///
+    /// If no recording was in progress, an empty replay is returned.
+    pub fn stop_recording(&mut self) -> Replay {
+        self.recording.take().unwrap_or_default()
+    }
+
+    fn record_input(&mut self, input: impl FnOnce() -> ReplayInput) {
+        if let Some(recording) = &mut self.recording {
+            recording.push(input());
+        }
+    }
+
+    /// Feed a recorded session back into the player.
+    ///
+    /// The player must be in the state it was in when recording started,
+    /// usually by loading the same movie into a new player.
+    pub fn replay(&mut self, replay: Replay) {
+        self.replay_with(replay, |_| {});
+    }
+
+    /// Feed a recorded session back into the player, calling `on_frame`
+    /// after every frame is run.
+    pub fn replay_with(&mut self, replay: Replay, mut on_frame: impl FnMut(&mut Self)) {
+        self.rng = SmallRng::seed_from_u64(replay.seed());
+        let clock = std::mem::replace(
+            &mut self.clock,
+            Box::new(ReplayClock::new(replay.clock_readings())),
+        );
+
+        for input in replay.inputs() {
+            match input {
+                ReplayInput::Event(event) => self.handle_event(event.clone()),
+                ReplayInput::RunFrame => {
+                    self.run_frame();
+                    on_frame(self);
+                }
+                ReplayInput::UpdateTimers(dt) => self.update_timers(*dt),
+            }
+        }
+
+        self.clock = clock;
+    }
+
     /// Returns whether this player consumes mouse wheel events.
     /// Used by web to prevent scrolling.
     pub fn should_prevent_scrolling(&mut self) -> bool {
@@ -1494,16 +1919,258 @@ impl Player {
         })
     }
 
+    /// Reads an AVM1 variable by its path, such as `_root.level.score` or
+    /// `/level:score`.
+    ///
+    /// The path is resolved from the root clip, as a `GetVariable` action on
+    /// the main timeline would. Returns `None` if the path doesn't lead to an
+    /// object.
+    pub fn get_variable(&mut self, path: &str) -> Option<ExternalValue> {
+        self.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip();
+            let globals = context.avm1.global_object_cell();
+            let version = context.swf.version();
+            let mut activation = Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Get Variable]"),
+                version,
+                globals,
+                root,
+            );
+
+            let (object, name) = activation.resolve_variable_path(root, path).ok()??;
+            let value = object.get(name, &mut activation).ok()?;
+            ExternalValue::from_avm1(&mut activation, value).ok()
+        })
+    }
+
+    /// Sets an AVM1 variable by its path, such as `_root.level.score` or
+    /// `/level:score`.
+    ///
+    /// The path is resolved from the root clip, as a `SetVariable` action on
+    /// the main timeline would. Fails if the path doesn't lead to an object.
+    pub fn set_variable(&mut self, path: &str, value: ExternalValue) -> Result<(), Error> {
+        self.mutate_with_update_context(|context| {
+            let root = context.stage.root_clip();
+            let globals = context.avm1.global_object_cell();
+            let version = context.swf.version();
+            let mut activation = Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Set Variable]"),
+                version,
+                globals,
+                root,
+            );
+
+            let (object, name) = match activation.resolve_variable_path(root, path) {
+                Ok(Some(target)) => target,
+                _ => return Err(format!("No object at variable path {}", path).into()),
+            };
+            let value = value.into_avm1(&mut activation);
+            object
+                .set(name, value, &mut activation)
+                .map_err(|e| format!("Couldn't set variable {}: {}", path, e).into())
+        })
+    }
+
     pub fn log_backend(&self) -> &Log {
         &self.log
     }
 
-    pub fn max_execution_duration(&self) -> Duration {
+    /// The maximum amount of time scripts may run in a single update, or
+    /// `None` if the script timeout is disabled.
+    pub fn max_execution_duration(&self) -> Option<Duration> {
         self.max_execution_duration
     }
 
     pub fn set_max_execution_duration(&mut self, max_execution_duration: Duration) {
-        self.max_execution_duration = max_execution_duration
+        self.max_execution_duration = Some(max_execution_duration)
+    }
+
+    /// Let scripts run for as long as they like, without ever being aborted
+    /// for taking too long.
+    ///
+    /// A runaway script will hang the player, so this is only meant for
+    /// trusted content, such as local files run by the desktop player. It is
+    /// deliberately not exposed to pages embedding the web player.
+    pub fn disable_script_timeout(&mut self) {
+        self.max_execution_duration = None
+    }
+
+    /// A summary of every unimplemented builtin, unknown tag and unsupported
+    /// codec this player has encountered so far.
+    pub fn diagnostics_report(&self) -> &DiagnosticsReport {
+        &self.diagnostics
+    }
+
+    /// The maximum number of nested AVM1 function calls before scripts are
+    /// aborted with a recursion error.
+    pub fn max_recursion_depth(&self) -> u16 {
+        self.gc_arena
+            .mutate(|_gc_context, gc_root| gc_root.0.read().avm1.max_recursion_depth())
+    }
+
+    /// Set the maximum AVM1 function call depth.
+    ///
+    /// Movies may still override this with a `ScriptLimits` tag.
+    pub fn set_max_recursion_depth(&mut self, max_recursion_depth: u16) {
+        self.mutate_with_update_context(|context| {
+            context.avm1.set_max_recursion_depth(max_recursion_depth)
+        })
+    }
+
+    /// How failed network loads are retried.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.gc_arena
+            .mutate(|_gc_context, gc_root| gc_root.0.read().load_manager.retry_policy())
+    }
+
+    /// Set how failed network loads made by movies are retried, and how long
+    /// each attempt may take.
+    ///
+    /// Once all retries fail, the last error is reported to the movie.
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.mutate_with_update_context(|context| {
+            context.load_manager.set_retry_policy(retry_policy)
+        })
+    }
+
+    /// The SWF version that AVM1 scripts are forced to behave as, if any.
+    pub fn force_swf_version(&self) -> Option<u8> {
+        self.gc_arena
+            .mutate(|_gc_context, gc_root| gc_root.0.read().avm1.force_swf_version())
+    }
+
+    /// Make AVM1 scripts behave as if they came from a movie of the given SWF
+    /// version, whatever version the movie itself declares, or follow the
+    /// movie's version again with `None`.
+    ///
+    /// This overrides `Activation::swf_version`, so it affects every
+    /// version-dependent behavior of AVM1, not only how values are coerced:
+    /// for example, forcing a version below 7 also makes identifiers case
+    /// insensitive and limits string case conversion to ASCII.
+    /// Fails if the version is outside of `1..=32`.
+    pub fn set_force_swf_version(&mut self, version: Option<u8>) -> Result<(), Error> {
+        if let Some(version) = version {
+            if !(1..=32).contains(&version) {
+                return Err(format!("Invalid SWF version {}", version).into());
+            }
+        }
+        self.mutate_with_update_context(|context| context.avm1.set_force_swf_version(version));
+        Ok(())
+    }
+
+    /// Whether movies run from local files are trusted.
+    pub fn trust_local_content(&self) -> bool {
+        self.system.trust_local_content
+    }
+
+    /// Choose whether movies run from local files are trusted, as in the
+    /// standalone player, or get only file or network access, as their
+    /// `FileAttributes` ask for. Local content is untrusted by default.
+    ///
+    /// The sandbox of the root movie is chosen when it is loaded, so this
+    /// should be called before `set_root_movie`.
+    pub fn set_trust_local_content(&mut self, trust_local_content: bool) {
+        self.system.trust_local_content = trust_local_content;
+    }
+
+    /// The version string reported to movies in place of the real one, if
+    /// any.
+    pub fn version_override(&self) -> Option<&str> {
+        self.system.version_override.as_deref()
+    }
+
+    /// Report a different version string, such as `WIN 9,0,115,0`, to movies
+    /// that check `$version`, `getVersion()` or `System.capabilities.version`.
+    ///
+    /// `$version` is set when a movie is loaded, so this should be called
+    /// before `set_root_movie`.
+    pub fn set_version_override(&mut self, version: Option<String>) {
+        self.system.version_override = version;
+    }
+}
+
+/// Builds a `Player` out of the backends an embedder provides.
+///
+/// Any backend that isn't provided is replaced by one that does nothing,
+/// such as `NullRenderer` for rendering or `MemoryStorageBackend` for
+/// storage.
+#[derive(Default)]
+pub struct PlayerBuilder {
+    renderer: Option<Renderer>,
+    audio: Option<Audio>,
+    navigator: Option<Navigator>,
+    storage: Option<Storage>,
+    locale: Option<Locale>,
+    video: Option<Video>,
+    log: Option<Log>,
+    ui: Option<Ui>,
+}
+
+impl PlayerBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_renderer(mut self, renderer: Renderer) -> Self {
+        self.renderer = Some(renderer);
+        self
+    }
+
+    pub fn with_audio(mut self, audio: Audio) -> Self {
+        self.audio = Some(audio);
+        self
+    }
+
+    pub fn with_navigator(mut self, navigator: Navigator) -> Self {
+        self.navigator = Some(navigator);
+        self
+    }
+
+    pub fn with_storage(mut self, storage: Storage) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    pub fn with_locale(mut self, locale: Locale) -> Self {
+        self.locale = Some(locale);
+        self
+    }
+
+    pub fn with_video(mut self, video: Video) -> Self {
+        self.video = Some(video);
+        self
+    }
+
+    pub fn with_log(mut self, log: Log) -> Self {
+        self.log = Some(log);
+        self
+    }
+
+    pub fn with_ui(mut self, ui: Ui) -> Self {
+        self.ui = Some(ui);
+        self
+    }
+
+    /// Construct the player.
+    pub fn build(self) -> Result<Arc<Mutex<Player>>, Error> {
+        Player::new(
+            self.renderer
+                .unwrap_or_else(|| Box::new(NullRenderer::new())),
+            self.audio
+                .unwrap_or_else(|| Box::new(NullAudioBackend::new())),
+            self.navigator
+                .unwrap_or_else(|| Box::new(NullNavigatorBackend::new())),
+            self.storage
+                .unwrap_or_else(|| Box::new(MemoryStorageBackend::default())),
+            self.locale
+                .unwrap_or_else(|| Box::new(NullLocaleBackend::new())),
+            self.video
+                .unwrap_or_else(|| Box::new(NullVideoBackend::new())),
+            self.log.unwrap_or_else(|| Box::new(NullLogBackend::new())),
+            self.ui.unwrap_or_else(|| Box::new(NullUiBackend::new())),
+        )
     }
 }
 
@@ -1521,3 +2188,989 @@ pub struct DragObject<'gc> {
     #[collect(require_static)]
     pub constraint: BoundingBox,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::avm1::function::{Executable, FunctionObject};
+    use crate::backend::render::{RecordingRenderer, RenderCommand};
+    use crate::clock::ManualClock;
+    use crate::test_utils::{
+        as3_file_attributes, empty_movie, movie_placing_squares, place_character, square_movie,
+        stage_size, swf_header, swf_movie, two_scene_movie, StreamClockAudioBackend,
+    };
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// The tags of a 10 FPS timeline with a stream sound block on every frame.
+    fn stream_tags(num_frames: u16) -> Vec<swf::Tag<'static>> {
+        let format = swf::SoundFormat {
+            compression: swf::AudioCompression::Uncompressed,
+            sample_rate: 44100,
+            is_stereo: true,
+            is_16_bit: true,
+        };
+        let mut tags = vec![swf::Tag::SoundStreamHead(Box::new(swf::SoundStreamHead {
+            stream_format: format.clone(),
+            playback_format: format,
+            num_samples_per_block: 4410,
+            latency_seek: 0,
+        }))];
+        for _ in 0..num_frames {
+            tags.push(swf::Tag::SoundStreamBlock(&[]));
+            tags.push(swf::Tag::ShowFrame);
+        }
+        tags
+    }
+
+    /// Builds a 10 FPS movie with a stream sound block on every frame.
+    fn stream_movie(num_frames: u16) -> SwfMovie {
+        swf_movie(
+            swf::Header {
+                stage_size: stage_size(100.0, 100.0),
+                ..swf_header(8, 10.0, num_frames)
+            },
+            stream_tags(num_frames),
+        )
+    }
+
+    /// Builds a 10 FPS movie whose stream sound plays in a child clip.
+    fn nested_stream_movie(num_frames: u16) -> SwfMovie {
+        let mut tags = vec![
+            swf::Tag::DefineSprite(swf::Sprite {
+                id: 1,
+                num_frames,
+                tags: stream_tags(num_frames),
+            }),
+            swf::Tag::PlaceObject(Box::new(place_character(1))),
+        ];
+        for _ in 0..num_frames {
+            tags.push(swf::Tag::ShowFrame);
+        }
+        swf_movie(
+            swf::Header {
+                stage_size: stage_size(100.0, 100.0),
+                ..swf_header(8, 10.0, num_frames)
+            },
+            tags,
+        )
+    }
+
+    fn root_frame(player: &mut Player) -> u16 {
+        player.update(|context| {
+            context
+                .stage
+                .root_clip()
+                .as_movie_clip()
+                .unwrap()
+                .current_frame()
+        })
+    }
+
+    #[test]
+    fn timeline_catches_up_to_stream_sound() {
+        let position = Arc::new(AtomicU32::new(0));
+        let player = PlayerBuilder::new()
+            .with_audio(Box::new(StreamClockAudioBackend::new(position.clone())))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(stream_movie(60)));
+        player.set_is_playing(true);
+
+        // The first frame starts the stream sound.
+        player.tick(100.0);
+        let start_frame = root_frame(&mut player);
+
+        // A slow frame: two seconds of audio play while the timeline only
+        // gets to run a single tick. The timeline catches up to the audio
+        // right away, regardless of the per-tick frame limit.
+        position.store(2000, Ordering::Relaxed);
+        player.tick(100.0);
+        assert_eq!(root_frame(&mut player), start_frame + 20);
+
+        // Once in sync, the timeline runs at its normal rate again.
+        player.tick(0.0);
+        assert_eq!(root_frame(&mut player), start_frame + 20);
+        player.tick(100.0);
+        assert_eq!(root_frame(&mut player), start_frame + 21);
+    }
+
+    #[test]
+    fn nested_timeline_catches_up_to_stream_sound() {
+        let position = Arc::new(AtomicU32::new(0));
+        let player = PlayerBuilder::new()
+            .with_audio(Box::new(StreamClockAudioBackend::new(position.clone())))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(nested_stream_movie(60)));
+        player.set_is_playing(true);
+
+        // The first frame places the child clip, which starts the stream sound.
+        player.tick(100.0);
+        let start_frame = root_frame(&mut player);
+
+        position.store(2000, Ordering::Relaxed);
+        player.tick(100.0);
+        assert!(root_frame(&mut player) >= start_frame + 19);
+    }
+
+    #[test]
+    fn frame_rate_cap_lowers_frame_rate() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(60.0, 120)));
+        player.set_is_playing(true);
+        player.set_max_frame_rate_cap(Some(30.0));
+        assert_eq!(player.effective_frame_rate(), 30.0);
+
+        // One second of ticks only runs 30 frames.
+        let start_frame = root_frame(&mut player);
+        for _ in 0..60 {
+            player.tick(1000.0 / 60.0);
+        }
+        assert_eq!(root_frame(&mut player), start_frame + 30);
+
+        // The cap still applies when the movie speeds itself up...
+        player.update(|context| *context.frame_rate = 120.0);
+        assert_eq!(player.effective_frame_rate(), 30.0);
+
+        // ...but doesn't speed up slower movies.
+        player.update(|context| *context.frame_rate = 24.0);
+        assert_eq!(player.effective_frame_rate(), 24.0);
+    }
+
+    #[test]
+    fn goto_scene_jumps_to_scene_start() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(two_scene_movie()));
+
+        assert_eq!(
+            player.scenes(),
+            vec![
+                Scene {
+                    name: "Intro".to_string(),
+                    start: 1,
+                    length: 2,
+                },
+                Scene {
+                    name: "Main".to_string(),
+                    start: 3,
+                    length: 3,
+                },
+            ]
+        );
+
+        assert!(player.goto_scene("Main", true));
+        assert_eq!(root_frame(&mut player), 3);
+
+        assert!(!player.goto_scene("Credits", true));
+        assert_eq!(root_frame(&mut player), 3);
+    }
+
+    /// Encodes AVM1 bytecode, terminated with an `End` action.
+    fn write_actions(actions: &[swf::avm1::types::Action]) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut writer = swf::avm1::write::Writer::new(&mut data, 8);
+        for action in actions {
+            writer.write_action(action).unwrap();
+        }
+        data.push(0);
+        data
+    }
+
+    /// Builds a movie whose root clip jumps to a random position whenever
+    /// the mouse is pressed.
+    fn random_position_movie() -> SwfMovie {
+        use swf::avm1::types::{Action, Value as ActionValue};
+
+        // `setProperty("", property, random(100))`
+        let set_random_property = |property| {
+            vec![
+                Action::Push(vec![
+                    ActionValue::Str("".into()),
+                    ActionValue::Int(property),
+                ]),
+                Action::Push(vec![ActionValue::Int(100)]),
+                Action::RandomNumber,
+                Action::SetProperty,
+            ]
+        };
+        let on_mouse_down =
+            write_actions(&[set_random_property(0), set_random_property(1)].concat());
+        let frame_actions = write_actions(&[
+            Action::Push(vec![ActionValue::Str("onMouseDown".into())]),
+            Action::DefineFunction {
+                name: "".into(),
+                params: vec![],
+                actions: &on_mouse_down,
+            },
+            Action::SetVariable,
+        ]);
+
+        swf_movie(
+            swf::Header {
+                stage_size: stage_size(100.0, 100.0),
+                ..swf_header(8, 10.0, 1)
+            },
+            vec![swf::Tag::DoAction(&frame_actions), swf::Tag::ShowFrame],
+        )
+    }
+
+    /// Describes the position of every object on the display list.
+    fn display_list_snapshot(player: &mut Player) -> Vec<String> {
+        fn describe(object: DisplayObject<'_>, snapshot: &mut Vec<String>) {
+            snapshot.push(format!(
+                "{} {} ({}, {})",
+                object.depth(),
+                object.name(),
+                object.x(),
+                object.y()
+            ));
+            if let Some(container) = object.as_container() {
+                for child in container.iter_render_list() {
+                    describe(child, snapshot);
+                }
+            }
+        }
+
+        player.update(|context| {
+            let mut snapshot = Vec::new();
+            describe(context.stage.root_clip(), &mut snapshot);
+            snapshot
+        })
+    }
+
+    #[test]
+    fn diagnostics_report_lists_stubs_and_unknown_tags() {
+        use swf::avm1::types::{Action, Value as ActionValue};
+
+        // `getProperty("", _droptarget)`, which is only stubbed out.
+        let frame_actions = write_actions(&[
+            Action::Push(vec![ActionValue::Str("".into()), ActionValue::Int(14)]),
+            Action::GetProperty,
+            Action::Pop,
+        ]);
+        let movie = swf_movie(
+            swf_header(8, 10.0, 1),
+            vec![
+                swf::Tag::Unknown {
+                    tag_code: 1000,
+                    data: &[],
+                },
+                swf::Tag::DoAction(&frame_actions),
+                swf::Tag::ShowFrame,
+            ],
+        );
+
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        assert!(player.diagnostics_report().is_empty());
+
+        player.set_root_movie(Arc::new(movie));
+        player.run_frame();
+
+        let report = player.diagnostics_report();
+        assert_eq!(
+            report.unimplemented_builtins().collect::<Vec<_>>(),
+            vec!["_droptarget"]
+        );
+        assert_eq!(report.unknown_tags().collect::<Vec<_>>(), vec![1000]);
+        assert_eq!(report.unsupported_codecs().count(), 0);
+    }
+
+    fn new_player() -> Arc<Mutex<Player>> {
+        let player = PlayerBuilder::new().build().unwrap();
+        player
+            .lock()
+            .unwrap()
+            .set_root_movie(Arc::new(random_position_movie()));
+        player
+    }
+
+    #[test]
+    fn replay_reproduces_recorded_session() {
+        let player = new_player();
+        let mut player = player.lock().unwrap();
+
+        player.start_recording();
+        let mut recorded = Vec::new();
+        for i in 0..12 {
+            if i % 3 == 0 {
+                let (x, y) = (f64::from(i), 50.0);
+                player.handle_event(PlayerEvent::MouseMove { x, y });
+                player.handle_event(PlayerEvent::MouseDown { x, y });
+                player.handle_event(PlayerEvent::MouseUp { x, y });
+            }
+            player.run_frame();
+            recorded.push(display_list_snapshot(&mut player));
+            player.update_timers(100.0);
+        }
+        let replay = player.stop_recording();
+
+        // The clicks moved the root clip around.
+        assert_ne!(recorded.first(), recorded.last());
+
+        let replay_player = new_player();
+        let mut replay_player = replay_player.lock().unwrap();
+        let mut replayed = Vec::new();
+        replay_player.replay_with(replay, |player| {
+            replayed.push(display_list_snapshot(player));
+        });
+
+        assert_eq!(replayed, recorded);
+    }
+
+    #[test]
+    fn replay_reproduces_clock_readings() {
+        let clock = ManualClock::new();
+        let player = new_player();
+        let mut player = player.lock().unwrap();
+        player.set_clock(Box::new(clock.clone()));
+
+        let read_clock = |player: &mut Player| {
+            player.update(|context| (context.clock.time_since_launch(), context.clock.date_time()))
+        };
+
+        player.start_recording();
+        let mut recorded = Vec::new();
+        for _ in 0..3 {
+            clock.advance(Duration::from_millis(100));
+            player.run_frame();
+            recorded.push(read_clock(&mut player));
+        }
+        let replay = player.stop_recording();
+
+        // The replaying player has its own clock, but sees the recorded time.
+        let replay_player = new_player();
+        let mut replay_player = replay_player.lock().unwrap();
+        let mut replayed = Vec::new();
+        replay_player.replay_with(replay, |player| {
+            replayed.push(read_clock(player));
+        });
+
+        assert_eq!(replayed, recorded);
+    }
+
+    /// Timer callback that counts how many times it ran in `_root.ticks`.
+    fn count_tick<'gc>(
+        activation: &mut Activation<'_, 'gc, '_>,
+        _this: Object<'gc>,
+        _args: &[Value<'gc>],
+    ) -> Result<Value<'gc>, crate::avm1::Error<'gc>> {
+        let root = activation.root_object()?.coerce_to_object(activation);
+        let ticks = root.get("ticks", activation)?.coerce_to_f64(activation)?;
+        root.set("ticks", (ticks + 1.0).into(), activation)?;
+        Ok(Value::Undefined)
+    }
+
+    /// Runs `f` in an AVM1 activation on the root clip.
+    fn with_root_activation<F, R>(player: &mut Player, f: F) -> R
+    where
+        F: for<'a, 'gc> FnOnce(&mut Activation<'_, 'gc, '_>, Object<'gc>) -> R,
+    {
+        player.update(|context| {
+            let root = context.stage.root_clip();
+            let globals = context.avm1.global_object_cell();
+            let version = context.swf.version();
+            let mut activation = Activation::from_nothing(
+                context.reborrow(),
+                ActivationIdentifier::root("[Test]"),
+                version,
+                globals,
+                root,
+            );
+            let root = root.object().coerce_to_object(&mut activation);
+            f(&mut activation, root)
+        })
+    }
+
+    #[test]
+    fn manual_clock_drives_timers() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        let clock = ManualClock::new();
+        player.set_clock(Box::new(clock.clone()));
+        player.set_root_movie(Arc::new(empty_movie(10.0, 20)));
+        player.set_is_playing(true);
+
+        // setInterval(countTick, 250);
+        with_root_activation(&mut player, |activation, root| {
+            root.set("ticks", 0.into(), activation).unwrap();
+            let callback = FunctionObject::function(
+                activation.context.gc_context,
+                Executable::Native(count_tick),
+                Some(activation.context.avm1.prototypes().function),
+                activation.context.avm1.prototypes().function,
+            );
+            let globals = activation.context.avm1.global_object_cell();
+            globals
+                .call_method("setInterval", &[callback.into(), 250.into()], activation)
+                .unwrap();
+        });
+
+        // Nothing happens until the clock moves.
+        let start_frame = root_frame(&mut player);
+        player.advance_clock();
+        assert_eq!(root_frame(&mut player), start_frame);
+
+        // Each 100ms step runs one frame, and the timer fires every 250ms.
+        let mut ticks = Vec::new();
+        for frame in 1..=10 {
+            clock.advance(Duration::from_millis(100));
+            player.advance_clock();
+            assert_eq!(root_frame(&mut player), start_frame + frame);
+            ticks.push(with_root_activation(&mut player, |activation, root| {
+                let ticks = root.get("ticks", activation).unwrap();
+                ticks.coerce_to_f64(activation).unwrap()
+            }));
+        }
+        assert_eq!(
+            ticks,
+            vec![0.0, 0.0, 1.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn parameters_are_readable_from_root() {
+        let mut movie = empty_movie(24.0, 1);
+        movie.append_parameters(vec![("greeting".to_string(), "hello world".to_string())]);
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie));
+
+        let greeting = with_root_activation(&mut player, |activation, root| {
+            root.get("greeting", activation)
+                .unwrap()
+                .coerce_to_string(activation)
+                .unwrap()
+                .to_string()
+        });
+        assert_eq!(greeting, "hello world");
+    }
+
+    #[test]
+    fn parameters_are_readable_from_loader_info() {
+        use crate::avm2::{
+            Activation as Avm2Activation, Namespace as Avm2Namespace, Object as Avm2Object,
+            QName as Avm2QName, TObject as _, Value as Avm2Value,
+        };
+
+        fn get<'gc>(
+            activation: &mut Avm2Activation<'_, 'gc, '_>,
+            object: Avm2Object<'gc>,
+            name: &'static str,
+        ) -> Avm2Value<'gc> {
+            object
+                .get_property(
+                    object,
+                    &Avm2QName::new(Avm2Namespace::public(), name),
+                    activation,
+                )
+                .unwrap()
+        }
+
+        let mut movie = swf_movie(
+            swf_header(10, 10.0, 1),
+            vec![as3_file_attributes(), swf::Tag::ShowFrame],
+        );
+        movie.append_parameters(vec![("greeting".to_string(), "hello world".to_string())]);
+
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie));
+
+        let greeting = player.update(|context| {
+            let root = context.stage.root_clip();
+            let mut activation = Avm2Activation::from_nothing(context.reborrow());
+            let root_object = root.object2().coerce_to_object(&mut activation).unwrap();
+            let loader_info = get(&mut activation, root_object, "loaderInfo")
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            let parameters = get(&mut activation, loader_info, "parameters")
+                .coerce_to_object(&mut activation)
+                .unwrap();
+            get(&mut activation, parameters, "greeting")
+                .coerce_to_string(&mut activation)
+                .unwrap()
+                .to_string()
+        });
+        assert_eq!(greeting, "hello world");
+    }
+
+    #[test]
+    fn frame_scripts_see_constructed_children() {
+        use crate::avm2::{
+            Activation as Avm2Activation, Error as Avm2Error, FunctionObject, Object as Avm2Object,
+            TObject as _, Value as Avm2Value,
+        };
+        use std::cell::Cell;
+
+        thread_local! {
+            static CHILD_CONSTRUCTED: Cell<Option<bool>> = Cell::new(None);
+        }
+
+        // Looks up the child placed on the root's first frame.
+        fn frame_script<'gc>(
+            _activation: &mut Avm2Activation<'_, 'gc, '_>,
+            this: Option<Avm2Object<'gc>>,
+            _args: &[Avm2Value<'gc>],
+        ) -> Result<Avm2Value<'gc>, Avm2Error> {
+            let child = this
+                .and_then(|this| this.as_display_object())
+                .and_then(|root| root.as_container())
+                .and_then(|root| root.child_by_depth(1));
+            let constructed = child.map_or(false, |child| {
+                matches!(child.object2(), Avm2Value::Object(_))
+            });
+            CHILD_CONSTRUCTED.with(|c| c.set(Some(constructed)));
+            Ok(Avm2Value::Undefined)
+        }
+
+        let movie = swf_movie(
+            swf_header(10, 10.0, 1),
+            vec![
+                as3_file_attributes(),
+                swf::Tag::DefineSprite(swf::Sprite {
+                    id: 2,
+                    num_frames: 1,
+                    tags: vec![swf::Tag::ShowFrame],
+                }),
+                swf::Tag::PlaceObject(Box::new(place_character(2))),
+                swf::Tag::ShowFrame,
+            ],
+        );
+
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie));
+        player.update(|context| {
+            let fn_proto = context.avm2.prototypes().function;
+            let callable = FunctionObject::from_builtin(context.gc_context, frame_script, fn_proto);
+            let root = context.stage.root_clip().as_movie_clip().unwrap();
+            root.register_frame_script(1, callable, context);
+        });
+        player.run_frame();
+
+        assert_eq!(CHILD_CONSTRUCTED.with(Cell::get), Some(true));
+    }
+
+    #[test]
+    fn variables_by_path() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+
+        player
+            .set_variable("_root.foo", ExternalValue::Number(5.0))
+            .unwrap();
+        assert_eq!(
+            player.get_variable("_root.foo"),
+            Some(ExternalValue::Number(5.0))
+        );
+        assert_eq!(
+            player.get_variable("/:foo"),
+            Some(ExternalValue::Number(5.0))
+        );
+
+        // The movie sees the variable, and its own variables can be read back.
+        let foo = with_root_activation(&mut player, |activation, root| {
+            root.set("bar", "baz".into(), activation).unwrap();
+            root.get("foo", activation)
+                .unwrap()
+                .coerce_to_f64(activation)
+                .unwrap()
+        });
+        assert_eq!(foo, 5.0);
+        assert_eq!(
+            player.get_variable("bar"),
+            Some(ExternalValue::String("baz".to_string()))
+        );
+
+        assert_eq!(player.get_variable("_root.missing.foo"), None);
+        assert!(player
+            .set_variable("_root.missing.foo", ExternalValue::Null)
+            .is_err());
+    }
+
+    #[test]
+    fn forced_swf_version_changes_coercions() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+
+        let hello_as_bool = |player: &mut Player| {
+            with_root_activation(player, |activation, _root| {
+                Value::from("Hello").as_bool(activation.swf_version())
+            })
+        };
+        assert!(hello_as_bool(&mut player));
+
+        // SWF 6 and below coerce non-numeric strings to `false`.
+        player.set_force_swf_version(Some(6)).unwrap();
+        assert_eq!(player.force_swf_version(), Some(6));
+        assert!(!hello_as_bool(&mut player));
+
+        player.set_force_swf_version(None).unwrap();
+        assert!(hello_as_bool(&mut player));
+
+        assert!(player.set_force_swf_version(Some(0)).is_err());
+        assert!(player.set_force_swf_version(Some(33)).is_err());
+        assert_eq!(player.force_swf_version(), None);
+    }
+
+    #[test]
+    fn forced_swf_version_changes_case_folding() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(swf_movie(
+            swf_header(8, 24.0, 1),
+            vec![swf::Tag::ShowFrame],
+        )));
+
+        let change_case = |player: &mut Player| {
+            with_root_activation(player, |activation, _root| {
+                let mut convert = |text: &'static str, method| {
+                    Value::from(text)
+                        .coerce_to_object(activation)
+                        .call_method(method, &[], activation)
+                        .unwrap()
+                        .coerce_to_string(activation)
+                        .unwrap()
+                        .to_string()
+                };
+                (convert("àé", "toUpperCase"), convert("ÀÉ", "toLowerCase"))
+            })
+        };
+        assert_eq!(
+            change_case(&mut player),
+            ("ÀÉ".to_string(), "àé".to_string())
+        );
+
+        // SWF 6 and below only change the case of ASCII letters.
+        player.set_force_swf_version(Some(6)).unwrap();
+        assert_eq!(
+            change_case(&mut player),
+            ("àé".to_string(), "ÀÉ".to_string())
+        );
+    }
+
+    #[test]
+    fn disabled_context_menu_is_never_built() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        // Multi-frame movies get the built-in playback items.
+        player.set_root_movie(Arc::new(empty_movie(24.0, 2)));
+        player.run_frame();
+
+        assert!(!player.prepare_context_menu().is_empty());
+        assert!(player.update(|context| context.current_context_menu.is_some()));
+        player.clear_custom_menu_items();
+
+        player.set_show_context_menu(false);
+        assert!(player.prepare_context_menu().is_empty());
+        assert!(player.update(|context| context.current_context_menu.is_none()));
+    }
+
+    #[test]
+    fn version_string_can_be_overridden() {
+        fn version_strings(player: &mut Player) -> (String, String) {
+            with_root_activation(player, |activation, root| {
+                let version = root
+                    .get("$version", activation)
+                    .unwrap()
+                    .coerce_to_string(activation)
+                    .unwrap()
+                    .to_string();
+                let get_version = activation
+                    .context
+                    .avm1
+                    .global_object_cell()
+                    .call_method("getVersion", &[], activation)
+                    .unwrap()
+                    .coerce_to_string(activation)
+                    .unwrap()
+                    .to_string();
+                (version, get_version)
+            })
+        }
+
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+        let expected = "LNX 32,0,0,0".to_string();
+        assert_eq!(version_strings(&mut player), (expected.clone(), expected));
+
+        player.set_version_override(Some("WIN 9,0,115,0".to_string()));
+        assert_eq!(player.version_override(), Some("WIN 9,0,115,0"));
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+        let expected = "WIN 9,0,115,0".to_string();
+        assert_eq!(version_strings(&mut player), (expected.clone(), expected));
+    }
+
+    #[test]
+    fn scale_mode_override_rebuilds_view_matrix() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.set_viewport_dimensions(40, 20, 1.0);
+        assert_eq!(player.scale_mode(), StageScaleMode::ShowAll);
+
+        player.set_scale_mode(StageScaleMode::ExactFit);
+        assert_eq!(player.scale_mode(), StageScaleMode::ExactFit);
+        assert_eq!(
+            player.update(|context| context.stage.inverse_view_matrix()),
+            Matrix::scale(0.25, 0.5)
+        );
+    }
+
+    #[test]
+    fn viewport_resize_queues_stage_resize_event() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.set_scale_mode(StageScaleMode::NoScale);
+        player.update(|context| while context.action_queue.pop_action().is_some() {});
+
+        player.set_viewport_dimensions(40, 30, 2.0);
+        player.update(|context| {
+            assert_eq!(context.stage.stage_size(), (20, 15));
+            let action = context.action_queue.pop_action().unwrap();
+            assert!(matches!(
+                action.action_type,
+                ActionType::NotifyListeners {
+                    listener: "Stage",
+                    method: "onResize",
+                    ..
+                }
+            ));
+            assert!(context.action_queue.pop_action().is_none());
+        });
+    }
+
+    #[test]
+    fn viewport_resize_queues_avm2_stage_resize_event() {
+        use crate::avm2::{Object as Avm2Object, Value as Avm2Value};
+
+        let movie = swf_movie(
+            swf_header(10, 24.0, 1),
+            vec![as3_file_attributes(), swf::Tag::ShowFrame],
+        );
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie));
+        player.set_scale_mode(StageScaleMode::NoScale);
+        player.update(|context| while context.action_queue.pop_action().is_some() {});
+
+        player.set_viewport_dimensions(40, 30, 2.0);
+        player.update(|context| {
+            let stage = match context.stage.object2() {
+                Avm2Value::Object(stage) => stage,
+                _ => panic!("Stage should have an AVM2 object"),
+            };
+            let action = context.action_queue.pop_action().unwrap();
+            match action.action_type {
+                ActionType::Event2 { event, target } => {
+                    assert_eq!(event.event_type(), "resize");
+                    assert!(!event.is_bubbling());
+                    assert!(Avm2Object::ptr_eq(target, stage));
+                }
+                _ => panic!("Expected a resize event, got {:?}", action.action_type),
+            }
+            assert!(context.action_queue.pop_action().is_none());
+        });
+    }
+
+    #[test]
+    fn shared_objects_are_enumerated() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(empty_movie(24.0, 1)));
+
+        with_root_activation(&mut player, |activation, _root| {
+            let constructor = activation
+                .context
+                .avm1
+                .prototypes()
+                .shared_object_constructor;
+            for (name, score) in &[("first", 1), ("second", 2)] {
+                let so = constructor
+                    .call_method("getLocal", &[(*name).into()], activation)
+                    .unwrap()
+                    .coerce_to_object(activation);
+                let data = so.get("data", activation).unwrap();
+                let data = data.coerce_to_object(activation);
+                data.set("score", (*score).into(), activation).unwrap();
+                so.call_method("flush", &[], activation).unwrap();
+            }
+        });
+
+        let names = player.shared_object_names();
+        assert_eq!(names, vec!["localhost//first", "localhost//second"]);
+        for name in &names {
+            assert!(player.shared_object_size(name).unwrap() > 0);
+        }
+
+        player.delete_shared_object("localhost//first");
+        assert_eq!(player.shared_object_names(), vec!["localhost//second"]);
+        assert_eq!(player.shared_object_size("localhost//first"), None);
+    }
+
+    #[test]
+    fn builder_uses_custom_renderer() {
+        let player = PlayerBuilder::new()
+            .with_renderer(Box::new(RecordingRenderer::new()))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.run_frame();
+        player.render();
+
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let shapes: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::RenderShape { shape, .. } => Some(renderer.shape_bounds(*shape)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(shapes.len(), 1);
+        assert_eq!(shapes[0].x_max, Twips::from_pixels(10.0));
+        assert_eq!(shapes[0].y_max, Twips::from_pixels(10.0));
+    }
+
+    #[test]
+    fn letterbox_bars_use_configured_color() {
+        let player = PlayerBuilder::new()
+            .with_renderer(Box::new(RecordingRenderer::new()))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.set_letterbox(Letterbox::On);
+        player.set_viewport_dimensions(20, 10, 1.0);
+
+        let color = Color::from_rgb(0x336699, 255);
+        player.set_letterbox_color(color.clone());
+        player.render();
+
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let bar_colors: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::DrawRect { color, .. } => Some(color.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(bar_colors, vec![color.clone(), color]);
+    }
+
+    #[test]
+    fn axis_scaling_stretches_width_and_letterboxes_height() {
+        let player = PlayerBuilder::new()
+            .with_renderer(Box::new(RecordingRenderer::new()))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.set_letterbox(Letterbox::On);
+        player.set_axis_scaling(crate::config::AxisScaling {
+            horizontal: crate::config::AxisScale::Stretch,
+            vertical: crate::config::AxisScale::Fit,
+        });
+
+        // The 10x10 movie fills the 40px width, while its height is scaled
+        // to fit, leaving bars above and below once the viewport is taller.
+        player.set_viewport_dimensions(40, 30, 1.0);
+        let view_matrix = player.update(|context| *context.stage.matrix());
+        assert_eq!((view_matrix.a, view_matrix.d), (4.0, 3.0));
+        assert_eq!(view_matrix.tx, Twips::zero());
+        assert_eq!(view_matrix.ty, Twips::zero());
+
+        player.set_viewport_dimensions(40, 60, 1.0);
+        let view_matrix = player.update(|context| *context.stage.matrix());
+        assert_eq!((view_matrix.a, view_matrix.d), (4.0, 4.0));
+        assert_eq!(view_matrix.ty, Twips::from_pixels(10.0));
+
+        player.render();
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let bars = renderer
+            .commands
+            .iter()
+            .filter(|command| matches!(command, RenderCommand::DrawRect { .. }))
+            .count();
+        assert_eq!(bars, 2);
+    }
+
+    #[test]
+    fn render_root_draws_only_that_subtree() {
+        let icon = swf::PlaceObject {
+            name: Some(swf::SwfStr::from_utf8_str("icon")),
+            ..place_character(1)
+        };
+        let sibling = swf::PlaceObject {
+            depth: 2,
+            matrix: Some(swf::Matrix::translate(
+                Twips::from_pixels(20.0),
+                Twips::zero(),
+            )),
+            name: Some(swf::SwfStr::from_utf8_str("sibling")),
+            ..place_character(1)
+        };
+        let stage_size = stage_size(30.0, 10.0);
+        let player = PlayerBuilder::new()
+            .with_renderer(Box::new(RecordingRenderer::new()))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie_placing_squares(
+            stage_size,
+            vec![icon, sibling],
+        )));
+        player.set_viewport_dimensions(20, 20, 1.0);
+        player.set_render_root(Some("_root.icon".to_string()));
+        player.run_frame();
+        player.render();
+
+        let renderer = player
+            .renderer()
+            .downcast_ref::<RecordingRenderer>()
+            .unwrap();
+        let shape_matrices: Vec<_> = renderer
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                RenderCommand::RenderShape { matrix, .. } => Some(*matrix),
+                _ => None,
+            })
+            .collect();
+        // The icon alone is scaled up to fill the viewport.
+        assert_eq!(shape_matrices, vec![Matrix::scale(2.0, 2.0)]);
+    }
+
+    #[test]
+    fn mouse_button_state_is_visible_to_scripts() {
+        let player = PlayerBuilder::new().build().unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(square_movie()));
+        player.set_viewport_dimensions(10, 10, 1.0);
+
+        player.handle_event(PlayerEvent::MouseDown { x: 5.0, y: 5.0 });
+        assert!(player.update(|context| context.is_mouse_down()));
+        player.handle_event(PlayerEvent::MouseUp { x: 5.0, y: 5.0 });
+        assert!(!player.update(|context| context.is_mouse_down()));
+    }
+}
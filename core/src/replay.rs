@@ -0,0 +1,148 @@
+//! Recording and replaying of player input
+
+use crate::clock::Clock;
+use crate::events::PlayerEvent;
+use chrono::{DateTime, TimeZone, Utc};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// A single input fed to the player while it was being recorded.
+#[derive(Debug, Clone)]
+pub enum ReplayInput {
+    /// An input event, such as a mouse click or key press.
+    Event(PlayerEvent),
+
+    /// A frame of the movie was run.
+    RunFrame,
+
+    /// AVM timers were advanced by a number of milliseconds.
+    UpdateTimers(f64),
+}
+
+/// A single reading of the player's clock, such as by `getTimer` or `new Date()`.
+#[derive(Debug, Clone, Copy)]
+pub enum ClockReading {
+    /// The time since the player was launched.
+    TimeSinceLaunch(Duration),
+
+    /// The current date and time.
+    DateTime(DateTime<Utc>),
+}
+
+/// A log of everything that influenced a recorded session of playback.
+///
+/// Frames are logged as they run, rather than the time that passed between
+/// them, so a replay runs the same frames regardless of how fast the host
+/// is. Every reading of the player's clock is logged as well, and handed back
+/// in the same order during the replay.
+#[derive(Debug, Clone, Default)]
+pub struct Replay {
+    /// The seed of the random number generator when recording started.
+    seed: u64,
+
+    inputs: Vec<ReplayInput>,
+
+    clock_readings: Vec<ClockReading>,
+}
+
+impl Replay {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            inputs: Vec::new(),
+            clock_readings: Vec::new(),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The recorded inputs, in the order they happened.
+    pub fn inputs(&self) -> &[ReplayInput] {
+        &self.inputs
+    }
+
+    /// The recorded readings of the player's clock, in the order they happened.
+    pub fn clock_readings(&self) -> &[ClockReading] {
+        &self.clock_readings
+    }
+
+    pub(crate) fn push(&mut self, input: ReplayInput) {
+        self.inputs.push(input);
+    }
+
+    pub(crate) fn extend_clock_readings(&mut self, readings: Vec<ClockReading>) {
+        self.clock_readings.extend(readings);
+    }
+}
+
+/// A clock that logs every reading of another clock.
+pub(crate) struct RecordingClock<'a> {
+    clock: &'a dyn Clock,
+    readings: RefCell<Vec<ClockReading>>,
+}
+
+impl<'a> RecordingClock<'a> {
+    pub(crate) fn new(clock: &'a dyn Clock) -> Self {
+        Self {
+            clock,
+            readings: RefCell::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn into_readings(self) -> Vec<ClockReading> {
+        self.readings.into_inner()
+    }
+}
+
+impl Clock for RecordingClock<'_> {
+    fn time_since_launch(&self) -> Duration {
+        let time = self.clock.time_since_launch();
+        self.readings
+            .borrow_mut()
+            .push(ClockReading::TimeSinceLaunch(time));
+        time
+    }
+
+    fn date_time(&self) -> DateTime<Utc> {
+        let date_time = self.clock.date_time();
+        self.readings
+            .borrow_mut()
+            .push(ClockReading::DateTime(date_time));
+        date_time
+    }
+}
+
+/// A clock that hands back the readings of a recording, in order.
+///
+/// If the replay reads the clock differently than the recording did, it has
+/// already diverged; the clock then reads as zero.
+pub(crate) struct ReplayClock {
+    readings: RefCell<VecDeque<ClockReading>>,
+}
+
+impl ReplayClock {
+    pub(crate) fn new(readings: &[ClockReading]) -> Self {
+        Self {
+            readings: RefCell::new(readings.iter().copied().collect()),
+        }
+    }
+}
+
+impl Clock for ReplayClock {
+    fn time_since_launch(&self) -> Duration {
+        match self.readings.borrow_mut().pop_front() {
+            Some(ClockReading::TimeSinceLaunch(time)) => time,
+            _ => Duration::default(),
+        }
+    }
+
+    fn date_time(&self) -> DateTime<Utc> {
+        match self.readings.borrow_mut().pop_front() {
+            Some(ClockReading::DateTime(date_time)) => date_time,
+            _ => Utc.timestamp(0, 0),
+        }
+    }
+}
@@ -0,0 +1,616 @@
+//! Security sandboxes and cross-domain policy files
+
+use crate::avm1::globals::system::SandboxType;
+use crate::backend::navigator::{NavigatorBackend, OwnedFuture, RequestOptions};
+use crate::context::UpdateContext;
+use crate::loader::Error;
+use crate::tag_utils::SwfMovie;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use url::Url;
+
+/// A parsed `crossdomain.xml` policy file.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PolicyFile {
+    /// The domain patterns listed in `allow-access-from` elements.
+    allowed_domains: Vec<String>,
+}
+
+impl PolicyFile {
+    /// Parse a policy file.
+    ///
+    /// Malformed policy files yield `None`, which grants no access.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(data);
+        let mut buf = Vec::new();
+        let mut allowed_domains = Vec::new();
+
+        loop {
+            match reader.read_event(&mut buf).ok()? {
+                Event::Start(element) | Event::Empty(element)
+                    if element.name() == b"allow-access-from" =>
+                {
+                    for attribute in element.attributes() {
+                        let attribute = attribute.ok()?;
+                        if attribute.key == b"domain" {
+                            allowed_domains
+                                .push(String::from_utf8_lossy(&attribute.value).to_lowercase());
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+            buf.clear();
+        }
+
+        Some(Self { allowed_domains })
+    }
+
+    /// Whether this policy grants access to movies served from `host`.
+    ///
+    /// A pattern of `*` matches any host, and `*.example.com` matches
+    /// `example.com` as well as all of its subdomains.
+    pub fn allows(&self, host: &str) -> bool {
+        let host = host.to_lowercase();
+
+        self.allowed_domains.iter().any(|pattern| {
+            if pattern == "*" {
+                true
+            } else if let Some(suffix) = pattern.strip_prefix("*.") {
+                host == suffix || host.ends_with(&format!(".{}", suffix))
+            } else {
+                *pattern == host
+            }
+        })
+    }
+}
+
+/// Policy files fetched so far, keyed by their URL.
+///
+/// Failed fetches are cached as `None`, so each policy file is only
+/// requested once per player.
+#[derive(Clone, Default)]
+pub struct PolicyCache(Arc<Mutex<HashMap<String, Option<PolicyFile>>>>);
+
+impl PolicyCache {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    fn get(&self, policy_url: &str) -> Option<Option<PolicyFile>> {
+        self.0.lock().unwrap().get(policy_url).cloned()
+    }
+
+    fn insert(&self, policy_url: String, policy: Option<PolicyFile>) {
+        self.0.lock().unwrap().insert(policy_url, policy);
+    }
+}
+
+/// Whether a movie may load a given URL.
+#[derive(Debug, PartialEq)]
+pub enum Access {
+    /// The load may proceed.
+    Allowed,
+
+    /// The load is forbidden by the movie's sandbox.
+    Denied,
+
+    /// The load is cross-domain, and may only proceed if the policy file at
+    /// `policy_url` grants access to `requester_host`.
+    NeedsPolicy {
+        policy_url: String,
+        requester_host: String,
+    },
+}
+
+/// Resolve the URL that a movie was loaded from, as seen by `navigator`.
+///
+/// Movie URLs may be relative, such as when an embedding page names the
+/// movie by path; these are resolved against the navigator's base URL.
+pub fn movie_url(navigator: &mut dyn NavigatorBackend, movie: &SwfMovie) -> Option<Url> {
+    let url = movie.url()?;
+    Url::parse(&navigator.resolve_relative_url(url)).ok()
+}
+
+/// Determine the sandbox that a movie runs in from where it was loaded.
+///
+/// `movie_url` is the movie's URL as resolved by `movie_url`. Movies served
+/// over the network are remote. Local movies are trusted if `trust_local` is
+/// set, as in the standalone player; otherwise, they get either file or
+/// network access, as their `FileAttributes` ask for with
+/// `uses_network_sandbox`.
+pub fn sandbox_type(
+    movie_url: Option<&Url>,
+    uses_network_sandbox: bool,
+    trust_local: bool,
+) -> SandboxType {
+    match movie_url {
+        Some(url) if url.scheme() == "http" || url.scheme() == "https" => SandboxType::Remote,
+        _ if trust_local => SandboxType::LocalTrusted,
+        _ if uses_network_sandbox => SandboxType::LocalWithNetwork,
+        _ => SandboxType::LocalWithFile,
+    }
+}
+
+/// Determine the sandbox of a movie that has been preloaded into the library.
+pub fn movie_sandbox_type(
+    context: &mut UpdateContext<'_, '_, '_>,
+    movie: &Arc<SwfMovie>,
+) -> SandboxType {
+    let movie_url = movie_url(context.navigator, movie);
+    let uses_network_sandbox = context
+        .library
+        .library_for_movie(movie.clone())
+        .map_or(false, |library| library.uses_network_sandbox());
+    sandbox_type(
+        movie_url.as_ref(),
+        uses_network_sandbox,
+        context.system.trust_local_content,
+    )
+}
+
+/// Determine whether a movie in `sandbox`, served from `movie_url`, may load
+/// `url`.
+pub fn check_access(sandbox: SandboxType, movie_url: Option<&Url>, url: &Url) -> Access {
+    let is_local = url.scheme() == "file";
+
+    match sandbox {
+        SandboxType::LocalTrusted => Access::Allowed,
+        SandboxType::LocalWithFile if is_local => Access::Allowed,
+        SandboxType::LocalWithNetwork if !is_local => Access::Allowed,
+        SandboxType::LocalWithFile | SandboxType::LocalWithNetwork => Access::Denied,
+        SandboxType::Remote if is_local => Access::Denied,
+        SandboxType::Remote => {
+            let movie_url = match movie_url {
+                Some(movie_url) => movie_url,
+                None => return Access::Denied,
+            };
+
+            if movie_url.origin() == url.origin() {
+                return Access::Allowed;
+            }
+
+            match (movie_url.host_str(), url.join("/crossdomain.xml")) {
+                (Some(host), Ok(policy_url)) => Access::NeedsPolicy {
+                    policy_url: policy_url.to_string(),
+                    requester_host: host.to_string(),
+                },
+                _ => Access::Denied,
+            }
+        }
+    }
+}
+
+/// Fetch a URL on behalf of `movie`, enforcing the movie's sandbox.
+///
+/// Cross-domain loads from remote movies first fetch the target domain's
+/// `crossdomain.xml`. Loads that are not permitted yield
+/// `Error::SecurityError` instead of any data. Failed loads are retried
+/// according to the load manager's `RetryPolicy`.
+pub fn fetch(
+    context: &mut UpdateContext<'_, '_, '_>,
+    movie: &Arc<SwfMovie>,
+    url: &str,
+    options: RequestOptions,
+) -> OwnedFuture<Vec<u8>, Error> {
+    let movie_url = movie_url(context.navigator, movie);
+    let resolved_url = match Url::parse(&context.navigator.resolve_relative_url(url)) {
+        Ok(resolved_url) => resolved_url,
+        // Let the navigator report URLs that it can't make sense of.
+        Err(_) => return fetch_with_retries(context, url, options),
+    };
+
+    let violation = Error::SecurityError(format!(
+        "{} cannot load data from {}",
+        movie_url
+            .as_ref()
+            .map(|movie_url| movie_url.as_str())
+            .unwrap_or("<unknown>"),
+        resolved_url
+    ));
+
+    let sandbox = movie_sandbox_type(context, movie);
+    match check_access(sandbox, movie_url.as_ref(), &resolved_url) {
+        Access::Allowed => fetch_with_retries(context, url, options),
+        Access::Denied => Box::pin(async move { Err(violation) }),
+        Access::NeedsPolicy {
+            policy_url,
+            requester_host,
+        } => {
+            let cache = context.policy_cache.clone();
+            let cached_policy = cache.get(&policy_url);
+            let policy_fetch = if cached_policy.is_none() {
+                Some(context.navigator.fetch(&policy_url, RequestOptions::get()))
+            } else {
+                None
+            };
+            let fetch = fetch_with_retries(context, url, options);
+
+            Box::pin(async move {
+                let policy = match (cached_policy, policy_fetch) {
+                    (Some(policy), _) => policy,
+                    (None, Some(policy_fetch)) => {
+                        let policy = policy_fetch
+                            .await
+                            .ok()
+                            .and_then(|data| PolicyFile::parse(&data));
+                        cache.insert(policy_url, policy.clone());
+                        policy
+                    }
+                    (None, None) => None,
+                };
+
+                if policy.map_or(false, |policy| policy.allows(&requester_host)) {
+                    fetch.await
+                } else {
+                    Err(violation)
+                }
+            })
+        }
+    }
+}
+
+/// Fetch a URL through the navigator, retrying failed attempts.
+///
+/// The first attempt is made right away. Retries go through the player's
+/// navigator once the backoff has passed, and stop early if the player is
+/// gone or the load was cancelled. The error of the last attempt is
+/// returned if none of them succeed.
+fn fetch_with_retries(
+    context: &mut UpdateContext<'_, '_, '_>,
+    url: &str,
+    options: RequestOptions,
+) -> OwnedFuture<Vec<u8>, Error> {
+    let policy = context.load_manager.retry_policy();
+    let options = match (options.timeout(), policy.timeout) {
+        (None, Some(timeout)) => options.with_timeout(timeout),
+        _ => options,
+    };
+
+    let first_attempt = context.navigator.fetch(url, options.clone());
+    let player = match (policy.max_retries, context.player.clone()) {
+        (0, _) | (_, None) => return first_attempt,
+        (_, Some(player)) => player,
+    };
+    let url = url.to_string();
+
+    Box::pin(async move {
+        let mut result = first_attempt.await;
+        for retry in 0..policy.max_retries {
+            if matches!(result, Ok(_) | Err(Error::Cancelled)) {
+                break;
+            }
+
+            let player = match player.upgrade() {
+                Some(player) => player,
+                None => break,
+            };
+
+            let sleep = player
+                .lock()
+                .expect("Could not lock player!!")
+                .navigator()
+                .sleep(policy.backoff(retry));
+            sleep.await?;
+
+            let attempt = player
+                .lock()
+                .expect("Could not lock player!!")
+                .navigator()
+                .fetch(&url, options.clone());
+            result = attempt.await;
+        }
+        result
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::navigator::{NullExecutor, RetryPolicy};
+    use crate::player::PlayerBuilder;
+    use crate::test_utils::{swf_data, swf_header, MapNavigatorBackend};
+    use std::time::Duration;
+
+    const MOVIE_URL: &str = "http://example.com/movie.swf";
+
+    /// Fetches `url` from a remote movie at `MOVIE_URL`, returning the result
+    /// and the URLs requested from the navigator.
+    fn fetch_from_remote_movie(
+        files: &[(&'static str, &'static str)],
+        url: &str,
+    ) -> (Result<Vec<u8>, Error>, Vec<String>) {
+        fetch_from_flaky_remote_movie(files, url, 0, RetryPolicy::default())
+    }
+
+    /// Like `fetch_from_remote_movie`, but the first `failures` fetches made
+    /// by the navigator fail, and failed loads are retried with the given
+    /// policy.
+    fn fetch_from_flaky_remote_movie(
+        files: &[(&'static str, &'static str)],
+        url: &str,
+        failures: u32,
+        retry_policy: RetryPolicy,
+    ) -> (Result<Vec<u8>, Error>, Vec<String>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(
+                MapNavigatorBackend::new(MOVIE_URL, files, requests.clone())
+                    .with_failures(failures),
+            ))
+            .build()
+            .unwrap();
+        player.lock().unwrap().set_retry_policy(retry_policy);
+        player
+            .lock()
+            .unwrap()
+            .set_root_movie(Arc::new(movie(Some(MOVIE_URL), vec![])));
+
+        let future = player.lock().unwrap().update(|context| {
+            assert_eq!(context.system.sandbox_type, SandboxType::Remote);
+            let movie = context.swf.clone();
+            fetch(context, &movie, url, RequestOptions::get())
+        });
+        let result = block_on(future);
+        let requests = requests.lock().unwrap().clone();
+        (result, requests)
+    }
+
+    /// Builds a one frame movie served from `url`.
+    fn movie(url: Option<&str>, mut tags: Vec<swf::Tag<'static>>) -> SwfMovie {
+        tags.push(swf::Tag::ShowFrame);
+        let data = swf_data(swf_header(10, 1.0, 1), tags);
+        SwfMovie::from_data(&data, url.map(str::to_string), None).unwrap()
+    }
+
+    /// Runs a fetch to completion.
+    fn block_on(future: OwnedFuture<Vec<u8>, Error>) -> Result<Vec<u8>, Error> {
+        let result = Arc::new(Mutex::new(None));
+        let result_slot = result.clone();
+        let (mut executor, channel) = NullExecutor::new();
+        channel
+            .send(Box::pin(async move {
+                *result_slot.lock().unwrap() = Some(future.await);
+                Ok(())
+            }))
+            .unwrap();
+        executor.block_all().unwrap();
+
+        let result = result.lock().unwrap().take().unwrap();
+        result
+    }
+
+    #[test]
+    fn policy_file_allows_listed_domains() {
+        let policy = PolicyFile::parse(
+            br#"<?xml version="1.0"?>
+            <cross-domain-policy>
+                <allow-access-from domain="example.com" />
+                <allow-access-from domain="*.ruffle.rs" />
+            </cross-domain-policy>"#,
+        )
+        .unwrap();
+
+        assert!(policy.allows("example.com"));
+        assert!(policy.allows("EXAMPLE.com"));
+        assert!(policy.allows("ruffle.rs"));
+        assert!(policy.allows("www.ruffle.rs"));
+        assert!(!policy.allows("www.example.com"));
+        assert!(!policy.allows("notruffle.rs"));
+    }
+
+    #[test]
+    fn remote_sandbox_requires_policy_for_other_domains() {
+        let movie_url = Url::parse(MOVIE_URL).unwrap();
+        let check = |url: &str| {
+            check_access(
+                SandboxType::Remote,
+                Some(&movie_url),
+                &Url::parse(url).unwrap(),
+            )
+        };
+
+        assert_eq!(check("http://example.com/data.txt"), Access::Allowed);
+        assert_eq!(check("file:///data.txt"), Access::Denied);
+        assert_eq!(
+            check("http://other.example/data.txt"),
+            Access::NeedsPolicy {
+                policy_url: "http://other.example/crossdomain.xml".to_string(),
+                requester_host: "example.com".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn sandbox_depends_on_movie_origin() {
+        let local = Url::parse("file:///movie.swf").unwrap();
+        let remote = Url::parse(MOVIE_URL).unwrap();
+
+        assert_eq!(
+            sandbox_type(Some(&remote), false, true),
+            SandboxType::Remote
+        );
+        assert_eq!(
+            sandbox_type(Some(&local), false, true),
+            SandboxType::LocalTrusted
+        );
+        assert_eq!(
+            sandbox_type(Some(&local), false, false),
+            SandboxType::LocalWithFile
+        );
+        assert_eq!(
+            sandbox_type(Some(&local), true, false),
+            SandboxType::LocalWithNetwork
+        );
+    }
+
+    #[test]
+    fn local_sandbox_follows_file_attributes() {
+        let sandbox = |use_network_sandbox| {
+            let player = PlayerBuilder::new()
+                .with_navigator(Box::new(MapNavigatorBackend::new(
+                    "file:///",
+                    &[],
+                    Arc::new(Mutex::new(Vec::new())),
+                )))
+                .build()
+                .unwrap();
+            let mut player = player.lock().unwrap();
+            let file_attributes = swf::Tag::FileAttributes(swf::FileAttributes {
+                use_direct_blit: false,
+                use_gpu: false,
+                has_metadata: false,
+                is_action_script_3: false,
+                use_network_sandbox,
+            });
+            player.set_root_movie(Arc::new(movie(
+                Some("file:///movie.swf"),
+                vec![file_attributes],
+            )));
+            player.update(|context| context.system.sandbox_type)
+        };
+
+        assert_eq!(sandbox(false), SandboxType::LocalWithFile);
+        assert_eq!(sandbox(true), SandboxType::LocalWithNetwork);
+    }
+
+    #[test]
+    fn relative_movie_url_is_resolved_by_navigator() {
+        // Web pages commonly embed movies by a path relative to the page.
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(MapNavigatorBackend::new(
+                "http://example.com/index.html",
+                &[],
+                Arc::new(Mutex::new(Vec::new())),
+            )))
+            .build()
+            .unwrap();
+        let mut player = player.lock().unwrap();
+        player.set_trust_local_content(true);
+        player.set_root_movie(Arc::new(movie(Some("movie.swf"), vec![])));
+
+        player.update(|context| {
+            assert_eq!(context.system.sandbox_type, SandboxType::Remote);
+        });
+    }
+
+    #[test]
+    fn loaded_movies_use_their_own_sandbox() {
+        let player = PlayerBuilder::new()
+            .with_navigator(Box::new(MapNavigatorBackend::new(
+                MOVIE_URL,
+                &[("http://other.example/data.txt", "a=1")],
+                Arc::new(Mutex::new(Vec::new())),
+            )))
+            .build()
+            .unwrap();
+        player
+            .lock()
+            .unwrap()
+            .set_root_movie(Arc::new(movie(Some("file:///movie.swf"), vec![])));
+
+        let child = movie(Some(MOVIE_URL), vec![]);
+        let (root_fetch, child_fetch) = player.lock().unwrap().update(|context| {
+            let root = context.swf.clone();
+            let url = "http://other.example/data.txt";
+            (
+                fetch(context, &root, url, RequestOptions::get()),
+                fetch(context, &child, url, RequestOptions::get()),
+            )
+        });
+
+        // The trusted local root may load anything, but the remote child
+        // needs a policy file from the other domain.
+        assert_eq!(block_on(root_fetch).unwrap(), b"a=1");
+        assert!(matches!(
+            block_on(child_fetch),
+            Err(Error::SecurityError(_))
+        ));
+    }
+
+    #[test]
+    fn same_origin_load_is_allowed() {
+        let (result, requests) =
+            fetch_from_remote_movie(&[("http://example.com/data.txt", "a=1")], "data.txt");
+
+        assert_eq!(result.unwrap(), b"a=1");
+        assert_eq!(requests, vec!["http://example.com/data.txt"]);
+    }
+
+    #[test]
+    fn cross_origin_load_without_policy_is_blocked() {
+        let (result, requests) = fetch_from_remote_movie(
+            &[("http://other.example/data.txt", "a=1")],
+            "http://other.example/data.txt",
+        );
+
+        assert!(matches!(result, Err(Error::SecurityError(_))));
+        assert!(requests.contains(&"http://other.example/crossdomain.xml".to_string()));
+    }
+
+    #[test]
+    fn cross_origin_load_with_policy_is_allowed() {
+        let (result, _) = fetch_from_remote_movie(
+            &[
+                ("http://other.example/data.txt", "a=1"),
+                (
+                    "http://other.example/crossdomain.xml",
+                    r#"<cross-domain-policy><allow-access-from domain="example.com"/></cross-domain-policy>"#,
+                ),
+            ],
+            "http://other.example/data.txt",
+        );
+
+        assert_eq!(result.unwrap(), b"a=1");
+    }
+
+    #[test]
+    fn failed_loads_are_retried() {
+        let retry_policy = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            timeout: Some(Duration::from_secs(5)),
+        };
+        let (result, requests) = fetch_from_flaky_remote_movie(
+            &[("http://example.com/data.txt", "a=1")],
+            "data.txt",
+            2,
+            retry_policy,
+        );
+
+        assert_eq!(result.unwrap(), b"a=1");
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[test]
+    fn failed_loads_give_up_after_retries() {
+        let retry_policy = RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            timeout: None,
+        };
+        let (result, requests) = fetch_from_flaky_remote_movie(
+            &[("http://example.com/data.txt", "a=1")],
+            "data.txt",
+            3,
+            retry_policy,
+        );
+
+        assert!(matches!(result, Err(Error::FetchError(_))));
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[test]
+    fn retry_backoff_doubles() {
+        let retry_policy = RetryPolicy {
+            initial_backoff: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        assert_eq!(retry_policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(retry_policy.backoff(2), Duration::from_millis(400));
+    }
+}
@@ -308,12 +308,27 @@ impl SwfSlice {
 }
 
 pub fn decode_tags<'a, F>(
+    reader: &mut SwfStream<'a>,
+    tag_callback: F,
+    stop_tag: TagCode,
+) -> Result<(), Box<dyn std::error::Error>>
+where
+    F: for<'b> FnMut(&'b mut SwfStream<'a>, TagCode, usize) -> DecodeResult,
+{
+    decode_tags_reporting_unknown(reader, tag_callback, |_| (), stop_tag)
+}
+
+/// Decode tags like `decode_tags`, additionally passing the code of every
+/// tag Ruffle doesn't know about to `unknown_tag_callback`.
+pub fn decode_tags_reporting_unknown<'a, F, U>(
     reader: &mut SwfStream<'a>,
     mut tag_callback: F,
+    mut unknown_tag_callback: U,
     stop_tag: TagCode,
 ) -> Result<(), Box<dyn std::error::Error>>
 where
     F: for<'b> FnMut(&'b mut SwfStream<'a>, TagCode, usize) -> DecodeResult,
+    U: FnMut(u16),
 {
     loop {
         let (tag_code, tag_len) = reader.read_tag_code_and_length()?;
@@ -340,6 +355,7 @@ where
             }
         } else {
             log::warn!("Unknown tag code: {:?}", tag_code);
+            unknown_tag_callback(tag_code);
         }
 
         *reader.get_mut() = end_slice;
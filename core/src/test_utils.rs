@@ -0,0 +1,633 @@
+//! Fixtures shared by tests throughout the crate.
+
+use crate::backend::audio::{
+    swf, AudioBackend, NullAudioBackend, SoundHandle, SoundInstanceHandle, SoundTransform,
+};
+use crate::backend::log::{LogBackend, LogCategory};
+use crate::backend::navigator::{NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions};
+use crate::backend::render::RecordingRenderer;
+use crate::loader::Error as LoaderError;
+use crate::player::{Player, PlayerBuilder};
+use crate::tag_utils::{SwfMovie, SwfSlice};
+use indexmap::IndexMap;
+use std::borrow::Cow;
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, Mutex};
+use swf::Twips;
+use url::Url;
+
+/// A directory under the system's temporary directory, which is deleted along
+/// with its contents when dropped.
+pub struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create an empty directory.
+    ///
+    /// Tests run in parallel, so `name` must be unique to the test.
+    pub fn new(name: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("ruffle_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// A log backend which collects the output of `trace` calls.
+pub struct TraceLogBackend(pub Arc<Mutex<Vec<String>>>);
+
+impl LogBackend for TraceLogBackend {
+    fn avm_trace(&self, message: &str) {
+        self.0.lock().unwrap().push(message.to_string());
+    }
+
+    fn log_event(&self, _category: LogCategory, _message: &str) {}
+}
+
+/// A log backend which collects every message, prefixed with its category.
+pub struct CategorizedLogBackend(pub Arc<Mutex<Vec<String>>>);
+
+impl LogBackend for CategorizedLogBackend {
+    fn avm_trace(&self, message: &str) {
+        self.log_event(LogCategory::AvmTrace, message);
+    }
+
+    fn log_event(&self, category: LogCategory, message: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("[{}] {}", category.name(), message));
+    }
+}
+
+/// A navigator serving a fixed set of absolute URLs, which records every URL
+/// it is asked to fetch. Relative URLs are resolved against a base URL.
+pub struct MapNavigatorBackend {
+    base_url: Url,
+    files: HashMap<String, Vec<u8>>,
+    requests: Arc<Mutex<Vec<String>>>,
+
+    /// The number of fetches that fail before any succeed.
+    failures_left: Cell<u32>,
+}
+
+impl MapNavigatorBackend {
+    pub fn new(base_url: &str, files: &[(&str, &str)], requests: Arc<Mutex<Vec<String>>>) -> Self {
+        Self {
+            base_url: Url::parse(base_url).unwrap(),
+            files: files
+                .iter()
+                .map(|(url, data)| (url.to_string(), data.as_bytes().to_vec()))
+                .collect(),
+            requests,
+            failures_left: Cell::new(0),
+        }
+    }
+
+    /// Fail the first `failures` fetches, whether or not the file exists.
+    pub fn with_failures(self, failures: u32) -> Self {
+        self.failures_left.set(failures);
+        self
+    }
+}
+
+impl NavigatorBackend for MapNavigatorBackend {
+    fn navigate_to_url(
+        &self,
+        _url: String,
+        _window: Option<String>,
+        _vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+    }
+
+    fn fetch(&self, url: &str, _options: RequestOptions) -> OwnedFuture<Vec<u8>, LoaderError> {
+        let url = self.base_url.join(url).unwrap();
+        self.requests.lock().unwrap().push(url.to_string());
+        if self.failures_left.get() > 0 {
+            self.failures_left.set(self.failures_left.get() - 1);
+            return Box::pin(async move { Err(LoaderError::FetchError(url.to_string())) });
+        }
+        let result = self
+            .files
+            .get(url.as_str())
+            .cloned()
+            .ok_or_else(|| LoaderError::FetchError(url.to_string()));
+
+        Box::pin(async move { result })
+    }
+
+    fn spawn_future(&mut self, _future: OwnedFuture<(), LoaderError>) {}
+
+    fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
+        match self.base_url.join(url) {
+            Ok(url) => url.into_string().into(),
+            Err(_) => url.into(),
+        }
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        url
+    }
+}
+
+/// A navigator that records the URLs it is asked to open, with any `GET`
+/// variables appended as a browser would. Every fetch fails.
+pub struct RecordingNavigator {
+    opened: Arc<Mutex<Vec<(String, Option<String>)>>>,
+}
+
+impl RecordingNavigator {
+    pub fn new(opened: Arc<Mutex<Vec<(String, Option<String>)>>>) -> Self {
+        Self { opened }
+    }
+}
+
+impl NavigatorBackend for RecordingNavigator {
+    fn navigate_to_url(
+        &self,
+        url: String,
+        window: Option<String>,
+        vars_method: Option<(NavigationMethod, IndexMap<String, String>)>,
+    ) {
+        let mut url = Url::parse(&url).unwrap();
+        if let Some((NavigationMethod::Get, vars)) = vars_method {
+            url.query_pairs_mut().extend_pairs(vars.iter());
+        }
+        self.opened
+            .lock()
+            .unwrap()
+            .push((url.into_string(), window));
+    }
+
+    fn fetch(&self, url: &str, _options: RequestOptions) -> OwnedFuture<Vec<u8>, LoaderError> {
+        let error = LoaderError::FetchError(url.to_string());
+        Box::pin(async move { Err(error) })
+    }
+
+    fn spawn_future(&mut self, _future: OwnedFuture<(), LoaderError>) {}
+
+    fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
+        url.into()
+    }
+
+    fn pre_process_url(&self, url: Url) -> Url {
+        url
+    }
+}
+
+type Error = Box<dyn std::error::Error>;
+
+/// An audio backend which records the sounds it is given and what is done
+/// with them. Sounds keep playing until they are stopped.
+pub struct RecordingAudioBackend {
+    inner: NullAudioBackend,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingAudioBackend {
+    pub fn new(log: Arc<Mutex<Vec<String>>>) -> Self {
+        Self {
+            inner: NullAudioBackend::new(),
+            log,
+        }
+    }
+
+    fn record(&self, event: String) {
+        self.log.lock().unwrap().push(event);
+    }
+}
+
+impl AudioBackend for RecordingAudioBackend {
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+    fn register_sound(&mut self, sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        self.record(format!(
+            "register {:?} {} samples, {} bytes",
+            sound.format.compression,
+            sound.num_samples,
+            sound.data.len()
+        ));
+        self.inner.register_sound(sound)
+    }
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.record("unregister".to_string());
+        self.inner.unregister_sound(sound)
+    }
+    fn start_sound(
+        &mut self,
+        sound: SoundHandle,
+        sound_info: &swf::SoundInfo,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.record("start".to_string());
+        self.inner.start_sound(sound, sound_info)
+    }
+    fn start_stream(
+        &mut self,
+        stream_handle: Option<SoundHandle>,
+        clip_frame: u16,
+        clip_data: SwfSlice,
+        handle: &swf::SoundStreamHead,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.record("start stream".to_string());
+        self.inner
+            .start_stream(stream_handle, clip_frame, clip_data, handle)
+    }
+    fn stop_sound(&mut self, _sound: SoundInstanceHandle) {
+        self.record("stop".to_string());
+    }
+    fn stop_all_sounds(&mut self) {
+        self.record("stop all".to_string());
+    }
+    fn get_sound_position(&self, _instance: SoundInstanceHandle) -> Option<u32> {
+        Some(0)
+    }
+    fn get_sound_duration(&self, _sound: SoundHandle) -> Option<u32> {
+        None
+    }
+    fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, _transform: SoundTransform) {}
+}
+
+/// An audio backend whose sounds report a playback position controlled by
+/// the test.
+pub struct StreamClockAudioBackend {
+    inner: NullAudioBackend,
+    position: Arc<AtomicU32>,
+}
+
+impl StreamClockAudioBackend {
+    pub fn new(position: Arc<AtomicU32>) -> Self {
+        Self {
+            inner: NullAudioBackend::new(),
+            position,
+        }
+    }
+}
+
+impl AudioBackend for StreamClockAudioBackend {
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+    fn muted(&self) -> bool {
+        self.inner.muted()
+    }
+    fn set_muted(&mut self, muted: bool) {
+        self.inner.set_muted(muted)
+    }
+    fn register_sound(&mut self, sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        self.inner.register_sound(sound)
+    }
+    fn start_sound(
+        &mut self,
+        sound: SoundHandle,
+        sound_info: &swf::SoundInfo,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.inner.start_sound(sound, sound_info)
+    }
+    fn start_stream(
+        &mut self,
+        stream_handle: Option<SoundHandle>,
+        clip_frame: u16,
+        clip_data: SwfSlice,
+        handle: &swf::SoundStreamHead,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.inner
+            .start_stream(stream_handle, clip_frame, clip_data, handle)
+    }
+    fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
+    fn stop_all_sounds(&mut self) {}
+    fn get_sound_position(&self, _instance: SoundInstanceHandle) -> Option<u32> {
+        Some(self.position.load(Ordering::Relaxed))
+    }
+    fn get_sound_duration(&self, _sound: SoundHandle) -> Option<u32> {
+        None
+    }
+    fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, _transform: SoundTransform) {}
+}
+
+/// An audio backend that keeps every sound playing, and records the last
+/// sound transform that was applied to a sound.
+pub struct SoundTransformAudioBackend {
+    inner: NullAudioBackend,
+    transform: Arc<Mutex<Option<SoundTransform>>>,
+}
+
+impl SoundTransformAudioBackend {
+    pub fn new(transform: Arc<Mutex<Option<SoundTransform>>>) -> Self {
+        Self {
+            inner: NullAudioBackend::new(),
+            transform,
+        }
+    }
+}
+
+impl AudioBackend for SoundTransformAudioBackend {
+    fn play(&mut self) {}
+    fn pause(&mut self) {}
+    fn muted(&self) -> bool {
+        self.inner.muted()
+    }
+    fn set_muted(&mut self, muted: bool) {
+        self.inner.set_muted(muted)
+    }
+    fn register_sound(&mut self, sound: &swf::Sound) -> Result<SoundHandle, Error> {
+        self.inner.register_sound(sound)
+    }
+    fn start_sound(
+        &mut self,
+        sound: SoundHandle,
+        sound_info: &swf::SoundInfo,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.inner.start_sound(sound, sound_info)
+    }
+    fn start_stream(
+        &mut self,
+        stream_handle: Option<SoundHandle>,
+        clip_frame: u16,
+        clip_data: SwfSlice,
+        handle: &swf::SoundStreamHead,
+    ) -> Result<SoundInstanceHandle, Error> {
+        self.inner
+            .start_stream(stream_handle, clip_frame, clip_data, handle)
+    }
+    fn stop_sound(&mut self, _sound: SoundInstanceHandle) {}
+    fn stop_all_sounds(&mut self) {}
+    fn get_sound_position(&self, _instance: SoundInstanceHandle) -> Option<u32> {
+        Some(0)
+    }
+    fn get_sound_duration(&self, _sound: SoundHandle) -> Option<u32> {
+        None
+    }
+    fn set_sound_transform(&mut self, _instance: SoundInstanceHandle, transform: SoundTransform) {
+        *self.transform.lock().unwrap() = Some(transform);
+    }
+}
+
+/// Build an FLV file from `(tag type, timestamp, body)` tags.
+pub fn flv(tags: &[(u8, u32, Vec<u8>)]) -> Vec<u8> {
+    let mut data = b"FLV\x01\x05\x00\x00\x00\x09".to_vec();
+    let mut previous_size = 0u32;
+
+    for (tag_type, timestamp, body) in tags {
+        data.extend_from_slice(&previous_size.to_be_bytes());
+        data.push(*tag_type);
+        data.extend_from_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        data.extend_from_slice(&timestamp.to_be_bytes()[1..]);
+        data.push((timestamp >> 24) as u8);
+        data.extend_from_slice(&[0, 0, 0]);
+        data.extend_from_slice(body);
+        previous_size = body.len() as u32 + 11;
+    }
+
+    data.extend_from_slice(&previous_size.to_be_bytes());
+    data
+}
+
+/// The header of an uncompressed movie with an empty stage.
+pub fn swf_header(version: u8, frame_rate: f32, num_frames: u16) -> swf::Header {
+    swf::Header {
+        compression: swf::Compression::None,
+        version,
+        uncompressed_length: 0,
+        stage_size: swf::Rectangle::default(),
+        frame_rate,
+        num_frames,
+    }
+}
+
+/// A stage of the given size, in pixels.
+pub fn stage_size(width: f64, height: f64) -> swf::Rectangle {
+    swf::Rectangle {
+        x_min: Twips::zero(),
+        x_max: Twips::from_pixels(width),
+        y_min: Twips::zero(),
+        y_max: Twips::from_pixels(height),
+    }
+}
+
+/// Write a movie out of its header and tags.
+pub fn swf_data(header: swf::Header, tags: Vec<swf::Tag>) -> Vec<u8> {
+    let mut data = Vec::new();
+    swf::write_swf(&swf::Swf { header, tags }, &mut data).unwrap();
+    data
+}
+
+/// Build a movie out of its header and tags.
+pub fn swf_movie(header: swf::Header, tags: Vec<swf::Tag>) -> SwfMovie {
+    SwfMovie::from_data(&swf_data(header, tags), None, None).unwrap()
+}
+
+/// The `FileAttributes` tag of a movie that runs ActionScript 3.
+pub fn as3_file_attributes() -> swf::Tag<'static> {
+    swf::Tag::FileAttributes(swf::FileAttributes {
+        use_direct_blit: false,
+        use_gpu: false,
+        has_metadata: false,
+        is_action_script_3: true,
+        use_network_sandbox: false,
+    })
+}
+
+/// Places the character `id` at depth 1.
+pub fn place_character(id: swf::CharacterId) -> swf::PlaceObject<'static> {
+    swf::PlaceObject {
+        version: 2,
+        action: swf::PlaceObjectAction::Place(id),
+        depth: 1,
+        matrix: Some(swf::Matrix::identity()),
+        color_transform: None,
+        ratio: None,
+        name: None,
+        clip_depth: None,
+        class_name: None,
+        filters: None,
+        background_color: None,
+        blend_mode: None,
+        clip_actions: None,
+        is_image: false,
+        is_bitmap_cached: None,
+        is_visible: None,
+        amf_data: None,
+    }
+}
+
+/// A movie of `num_frames` empty frames.
+pub fn empty_movie(frame_rate: f32, num_frames: u16) -> SwfMovie {
+    swf_movie(
+        swf_header(10, frame_rate, num_frames),
+        (0..num_frames).map(|_| swf::Tag::ShowFrame).collect(),
+    )
+}
+
+/// A movie with an "Intro" scene of two frames, followed by a "Main" scene of
+/// three frames.
+pub fn two_scene_movie() -> SwfMovie {
+    let mut tags = vec![swf::Tag::DefineSceneAndFrameLabelData(
+        swf::DefineSceneAndFrameLabelData {
+            scenes: vec![
+                swf::FrameLabelData {
+                    frame_num: 0,
+                    label: "Intro".into(),
+                },
+                swf::FrameLabelData {
+                    frame_num: 2,
+                    label: "Main".into(),
+                },
+            ],
+            frame_labels: vec![],
+        },
+    )];
+    tags.extend((0..5).map(|_| swf::Tag::ShowFrame));
+    swf_movie(swf_header(10, 10.0, 5), tags)
+}
+
+/// A 10x10 red square shape, defined as character 1.
+pub fn square_shape() -> swf::Shape {
+    let square = Twips::from_pixels(10.0);
+    let bounds = stage_size(10.0, 10.0);
+    let edge = |delta_x, delta_y| swf::ShapeRecord::StraightEdge { delta_x, delta_y };
+    swf::Shape {
+        version: 1,
+        id: 1,
+        shape_bounds: bounds.clone(),
+        edge_bounds: bounds,
+        has_fill_winding_rule: false,
+        has_non_scaling_strokes: false,
+        has_scaling_strokes: false,
+        styles: swf::ShapeStyles {
+            fill_styles: vec![swf::FillStyle::Color(swf::Color::from_rgb(0xFF0000, 255))],
+            line_styles: vec![],
+        },
+        shape: vec![
+            swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+                move_to: Some((Twips::zero(), Twips::zero())),
+                fill_style_0: None,
+                fill_style_1: Some(1),
+                line_style: None,
+                new_styles: None,
+            }),
+            edge(square, Twips::zero()),
+            edge(Twips::zero(), square),
+            edge(Twips::from_pixels(-10.0), Twips::zero()),
+            edge(Twips::zero(), Twips::from_pixels(-10.0)),
+        ],
+    }
+}
+
+/// A movie of the given size that defines a square shape and places it with
+/// each of the given `PlaceObject` tags on its only frame.
+pub fn movie_placing_squares(
+    stage_size: swf::Rectangle,
+    places: Vec<swf::PlaceObject>,
+) -> SwfMovie {
+    let mut tags = vec![swf::Tag::DefineShape(square_shape())];
+    tags.extend(
+        places
+            .into_iter()
+            .map(|place| swf::Tag::PlaceObject(Box::new(place))),
+    );
+    tags.push(swf::Tag::ShowFrame);
+    swf_movie(
+        swf::Header {
+            stage_size,
+            ..swf_header(10, 24.0, 1)
+        },
+        tags,
+    )
+}
+
+/// A movie that places a 10x10 red square on its only frame.
+pub fn square_movie() -> SwfMovie {
+    let bounds = square_shape().shape_bounds;
+    movie_placing_squares(bounds, vec![place_character(1)])
+}
+
+/// A movie that places a static text object, defined as character 2, that
+/// draws `record` using `font`.
+pub fn static_text_movie(font: swf::Tag, record: swf::TextRecord) -> SwfMovie {
+    let bounds = square_shape().shape_bounds;
+    let text = swf::Text {
+        id: 2,
+        bounds: bounds.clone(),
+        matrix: swf::Matrix::identity(),
+        records: vec![record],
+    };
+    swf_movie(
+        swf::Header {
+            stage_size: bounds,
+            ..swf_header(10, 24.0, 1)
+        },
+        vec![
+            font,
+            swf::Tag::DefineText(Box::new(text)),
+            swf::Tag::PlaceObject(Box::new(place_character(2))),
+            swf::Tag::ShowFrame,
+        ],
+    )
+}
+
+/// A movie with a line of static text whose glyphs are spaced 1.25px apart.
+pub fn text_movie() -> SwfMovie {
+    let size = Twips::from_pixels(1.0);
+    let glyph = vec![
+        swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+            move_to: Some((Twips::zero(), Twips::zero())),
+            fill_style_0: Some(1),
+            fill_style_1: None,
+            line_style: None,
+            new_styles: None,
+        }),
+        swf::ShapeRecord::StraightEdge {
+            delta_x: size,
+            delta_y: Twips::zero(),
+        },
+        swf::ShapeRecord::StraightEdge {
+            delta_x: Twips::zero(),
+            delta_y: size,
+        },
+    ];
+    let record = swf::TextRecord {
+        font_id: Some(1),
+        color: Some(swf::Color::from_rgb(0, 255)),
+        x_offset: Some(Twips::zero()),
+        y_offset: Some(Twips::zero()),
+        height: Some(Twips::from_pixels(12.0)),
+        glyphs: (0..3)
+            .map(|_| swf::GlyphEntry {
+                index: 0,
+                advance: 25,
+            })
+            .collect(),
+    };
+    let font = swf::Tag::DefineFont(Box::new(swf::FontV1 {
+        id: 1,
+        glyphs: vec![glyph],
+    }));
+    static_text_movie(font, record)
+}
+
+/// Runs the first frame of `movie` and renders it with a `RecordingRenderer`,
+/// returning the player.
+pub fn render_recorded(movie: SwfMovie) -> Arc<Mutex<Player>> {
+    let player = PlayerBuilder::new()
+        .with_renderer(Box::new(RecordingRenderer::new()))
+        .build()
+        .unwrap();
+    {
+        let mut player = player.lock().unwrap();
+        player.set_root_movie(Arc::new(movie));
+        player.run_frame();
+        player.render();
+    }
+    player
+}
@@ -4,10 +4,11 @@ use ruffle_core::backend::audio::decoders::{
     self, AdpcmDecoder, Mp3Decoder, NellymoserDecoder, PcmDecoder, SeekableDecoder,
 };
 use ruffle_core::backend::audio::{
-    swf, AudioBackend, SoundHandle, SoundInstanceHandle, SoundTransform,
+    swf, AudioBackend, SoundHandle, SoundInstanceDebugInfo, SoundInstanceHandle, SoundTransform,
 };
 use ruffle_core::tag_utils::SwfSlice;
 use std::io::Cursor;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use swf::AudioCompression;
 
@@ -18,6 +19,9 @@ pub struct CpalAudioBackend {
     stream: Stream,
     sounds: Arena<Sound>,
     sound_instances: Arc<Mutex<Arena<SoundInstance>>>,
+
+    /// Whether output is muted. Shared with the audio thread.
+    muted: Arc<AtomicBool>,
 }
 
 // Because of https://github.com/RustAudio/cpal/pull/348, we have to initialize cpal on a
@@ -67,6 +71,12 @@ struct SoundInstance {
     left_transform: [f32; 2],
 
     right_transform: [f32; 2],
+
+    /// The sample rate of the source audio.
+    sample_rate: u16,
+
+    /// The number of sample frames that have been mixed so far.
+    position: u32,
 }
 
 impl CpalAudioBackend {
@@ -98,10 +108,12 @@ impl CpalAudioBackend {
         let config = cpal::StreamConfig::from(config);
 
         let sound_instances: Arc<Mutex<Arena<SoundInstance>>> = Arc::new(Mutex::new(Arena::new()));
+        let muted = Arc::new(AtomicBool::new(false));
 
         // Start the audio stream.
         let stream = {
             let sound_instances = Arc::clone(&sound_instances);
+            let muted = Arc::clone(&muted);
             let error_handler = move |err| log::error!("Audio stream error: {}", err);
             let output_config = config.clone();
 
@@ -111,7 +123,8 @@ impl CpalAudioBackend {
                     &config,
                     move |buffer, _| {
                         let mut sound_instances = sound_instances.lock().unwrap();
-                        Self::mix_audio::<f32>(&mut sound_instances, &output_config, buffer)
+                        let muted = muted.load(Ordering::Relaxed);
+                        Self::mix_audio::<f32>(&mut sound_instances, &output_config, buffer, muted)
                     },
                     error_handler,
                 ),
@@ -119,7 +132,8 @@ impl CpalAudioBackend {
                     &config,
                     move |buffer, _| {
                         let mut sound_instances = sound_instances.lock().unwrap();
-                        Self::mix_audio::<i16>(&mut sound_instances, &output_config, buffer)
+                        let muted = muted.load(Ordering::Relaxed);
+                        Self::mix_audio::<i16>(&mut sound_instances, &output_config, buffer, muted)
                     },
                     error_handler,
                 ),
@@ -127,7 +141,8 @@ impl CpalAudioBackend {
                     &config,
                     move |buffer, _| {
                         let mut sound_instances = sound_instances.lock().unwrap();
-                        Self::mix_audio::<u16>(&mut sound_instances, &output_config, buffer)
+                        let muted = muted.load(Ordering::Relaxed);
+                        Self::mix_audio::<u16>(&mut sound_instances, &output_config, buffer, muted)
                     },
                     error_handler,
                 ),
@@ -142,6 +157,7 @@ impl CpalAudioBackend {
             stream: Stream(stream),
             sounds: Arena::new(),
             sound_instances,
+            muted,
         })
     }
 
@@ -270,10 +286,12 @@ impl CpalAudioBackend {
     /// Callback to the audio thread.
     /// Refill the output buffer by stepping through all active sounds
     /// and mixing in their output.
+    /// If `muted` is set, the sounds still advance, but silence is output.
     fn mix_audio<'a, T>(
         sound_instances: &mut Arena<SoundInstance>,
         output_format: &cpal::StreamConfig,
         mut output_buffer: &mut [T],
+        muted: bool,
     ) where
         T: 'a + cpal::Sample + Default + dasp::Sample,
         T::Signed: dasp::sample::conv::FromSample<i16>,
@@ -294,6 +312,7 @@ impl CpalAudioBackend {
             for (_, sound) in sound_instances.iter_mut() {
                 if sound.active && !sound.signal.is_exhausted() {
                     let sound_frame = sound.signal.next();
+                    sound.position += 1;
                     let [left_0, left_1] = sound_frame.mul_amp(sound.left_transform);
                     let [right_0, right_1] = sound_frame.mul_amp(sound.right_transform);
                     let sound_frame: Stereo<T::Signed> = [
@@ -306,6 +325,10 @@ impl CpalAudioBackend {
                 }
             }
 
+            if muted {
+                output_frame = Stereo::<T::Signed>::EQUILIBRIUM;
+            }
+
             for (buf_sample, output_sample) in buf_frame.iter_mut().zip(output_frame.iter()) {
                 *buf_sample = output_sample.to_sample();
             }
@@ -314,6 +337,26 @@ impl CpalAudioBackend {
         // Remove all dead sounds.
         sound_instances.retain(|_, sound| sound.active);
     }
+
+    /// Takes a snapshot of the state of all active sound instances.
+    fn debug_state_of(sound_instances: &Arena<SoundInstance>) -> Vec<SoundInstanceDebugInfo> {
+        sound_instances
+            .iter()
+            .filter(|(_, sound)| sound.active)
+            .map(|(instance, sound)| SoundInstanceDebugInfo {
+                instance,
+                sound: sound.handle,
+                sample_rate: sound.sample_rate,
+                position: sound.position,
+                transform: SoundTransform {
+                    left_to_left: sound.left_transform[0],
+                    right_to_left: sound.left_transform[1],
+                    left_to_right: sound.right_transform[0],
+                    right_to_right: sound.right_transform[1],
+                },
+            })
+            .collect()
+    }
 }
 
 impl AudioBackend for CpalAudioBackend {
@@ -336,6 +379,10 @@ impl AudioBackend for CpalAudioBackend {
         Ok(self.sounds.insert(sound))
     }
 
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.sounds.remove(sound);
+    }
+
     fn play(&mut self) {
         self.stream.0.play().expect("Error trying to resume CPAL audio stream. This feature may not be supported by your audio device.");
     }
@@ -344,6 +391,14 @@ impl AudioBackend for CpalAudioBackend {
         self.stream.0.pause().expect("Error trying to pause CPAL audio stream. This feature may not be supported by your audio device.");
     }
 
+    fn muted(&self) -> bool {
+        self.muted.load(Ordering::Relaxed)
+    }
+
+    fn set_muted(&mut self, muted: bool) {
+        self.muted.store(muted, Ordering::Relaxed);
+    }
+
     fn start_stream(
         &mut self,
         _stream_handle: Option<SoundHandle>,
@@ -365,6 +420,8 @@ impl AudioBackend for CpalAudioBackend {
             active: true,
             left_transform: [1.0, 0.0],
             right_transform: [0.0, 1.0],
+            sample_rate: format.sample_rate,
+            position: 0,
         });
         Ok(handle)
     }
@@ -398,6 +455,8 @@ impl AudioBackend for CpalAudioBackend {
             active: true,
             left_transform: [1.0, 0.0],
             right_transform: [0.0, 1.0],
+            sample_rate: sound.format.sample_rate,
+            position: 0,
         });
         Ok(handle)
     }
@@ -421,8 +480,16 @@ impl AudioBackend for CpalAudioBackend {
 
     fn get_sound_position(&self, instance: SoundInstanceHandle) -> Option<u32> {
         let sound_instances = self.sound_instances.lock().unwrap();
-        // TODO: Return actual position
-        sound_instances.get(instance).map(|_| 0)
+        let sound = sound_instances.get(instance)?;
+        if sound.handle.is_none() {
+            // Stream sounds report how much audio has been output, which is used
+            // to keep the timeline in sync with the stream.
+            let sample_rate = f64::from(self.output_config.sample_rate.0);
+            Some((f64::from(sound.position) * 1000.0 / sample_rate) as u32)
+        } else {
+            // TODO: Return actual position of event sounds
+            Some(0)
+        }
     }
 
     fn get_sound_duration(&self, sound: SoundHandle) -> Option<u32> {
@@ -446,6 +513,11 @@ impl AudioBackend for CpalAudioBackend {
     }
 
     fn tick(&mut self) {}
+
+    fn debug_state(&self) -> Vec<SoundInstanceDebugInfo> {
+        let sound_instances = self.sound_instances.lock().unwrap();
+        Self::debug_state_of(&sound_instances)
+    }
 }
 
 /// A dummy wrapper struct to implement `AsRef<[u8]>` for `Arc<Vec<u8>`.
@@ -641,3 +713,81 @@ impl dasp::signal::Signal for EnvelopeSignal {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mixes `num_frames` stereo frames into a new buffer.
+    fn mix(sound_instances: &mut Arena<SoundInstance>, num_frames: usize, muted: bool) -> Vec<i16> {
+        let output_config = cpal::StreamConfig {
+            channels: 2,
+            sample_rate: cpal::SampleRate(44100),
+            buffer_size: cpal::BufferSize::Default,
+        };
+        let mut buffer = vec![0; num_frames * 2];
+        CpalAudioBackend::mix_audio::<i16>(sound_instances, &output_config, &mut buffer, muted);
+        buffer
+    }
+
+    /// Creates a sound instance that outputs `num_frames` frames of silence.
+    fn silent_instance(
+        handle: Option<SoundHandle>,
+        sample_rate: u16,
+        num_frames: usize,
+    ) -> SoundInstance {
+        SoundInstance {
+            handle,
+            signal: Box::new(dasp::signal::from_iter(vec![[0i16, 0]; num_frames])),
+            active: true,
+            left_transform: [1.0, 0.0],
+            right_transform: [0.0, 1.0],
+            sample_rate,
+            position: 0,
+        }
+    }
+
+    #[test]
+    fn debug_state_lists_active_sounds() {
+        let mut sound_instances = Arena::new();
+        let event_sound = SoundHandle::from_raw_parts(3, 0);
+        let event_instance = sound_instances.insert(silent_instance(Some(event_sound), 22050, 8));
+        let stream_instance = sound_instances.insert(silent_instance(None, 44100, 8));
+        mix(&mut sound_instances, 2, false);
+
+        let state = CpalAudioBackend::debug_state_of(&sound_instances);
+        assert_eq!(state.len(), 2);
+        let event = state.iter().find(|s| s.instance == event_instance).unwrap();
+        assert_eq!(event.sound, Some(event_sound));
+        assert_eq!(event.sample_rate, 22050);
+        assert_eq!(event.position, 2);
+        assert_eq!(event.transform, SoundTransform::default());
+        let stream = state
+            .iter()
+            .find(|s| s.instance == stream_instance)
+            .unwrap();
+        assert_eq!(stream.sound, None);
+        assert_eq!(stream.sample_rate, 44100);
+    }
+
+    #[test]
+    fn muting_advances_playback() {
+        let mut sound_instances = Arena::new();
+        // A sound whose samples count upwards from 1.
+        let frames: Vec<[i16; 2]> = (1..=8).map(|i| [i, i]).collect();
+        sound_instances.insert(SoundInstance {
+            handle: None,
+            signal: Box::new(dasp::signal::from_iter(frames)),
+            active: true,
+            left_transform: [1.0, 0.0],
+            right_transform: [0.0, 1.0],
+            sample_rate: 44100,
+            position: 0,
+        });
+
+        assert_eq!(mix(&mut sound_instances, 4, true), vec![0; 8]);
+
+        // Playback resumes from where it would have been without muting.
+        assert_eq!(mix(&mut sound_instances, 2, false), vec![5, 5, 6, 6]);
+    }
+}
@@ -1,5 +1,5 @@
 use ruffle_core::backend::locale::LocaleBackend;
-use ruffle_core::chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use ruffle_core::chrono::{FixedOffset, Local, Offset};
 
 pub struct DesktopLocaleBackend();
 
@@ -10,10 +10,6 @@ impl DesktopLocaleBackend {
 }
 
 impl LocaleBackend for DesktopLocaleBackend {
-    fn get_current_date_time(&self) -> DateTime<Utc> {
-        Utc::now()
-    }
-
     fn get_timezone(&self) -> FixedOffset {
         Local::now().offset().fix()
     }
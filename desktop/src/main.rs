@@ -19,9 +19,11 @@ use crate::executor::GlutinAsyncExecutor;
 use clap::Clap;
 use isahc::{config::RedirectPolicy, prelude::*, HttpClient};
 use ruffle_core::{
-    backend::audio::AudioBackend, backend::video::NullVideoBackend, config::Letterbox, Player,
+    backend::audio::AudioBackend, backend::video::NullVideoBackend, config::Letterbox, Color,
+    DeviceFontFiles, Player, StageScaleMode,
 };
 use ruffle_render_wgpu::WgpuRenderBackend;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Instant;
@@ -95,6 +97,46 @@ struct Opt {
 
     #[clap(long, case_insensitive = true, takes_value = false)]
     timedemo: bool,
+
+    /// (Optional) Run movies at no more than this many frames per second, to save power.
+    /// Movies authored at a lower frame rate are unaffected.
+    #[clap(long)]
+    max_frame_rate_cap: Option<f64>,
+
+    /// Never abort scripts that run for too long.
+    /// Only use this for trusted movies, as a runaway script will hang the player.
+    #[clap(long, case_insensitive = true, takes_value = false)]
+    no_script_timeout: bool,
+
+    /// A font file to use for device text, given as FAMILY=PATH.
+    /// Style variants can be given as FAMILY:bold=PATH, FAMILY:italic=PATH or FAMILY:bold-italic=PATH.
+    /// This can be repeated multiple times.
+    #[clap(long = "font", number_of_values = 1)]
+    fonts: Vec<String>,
+
+    /// (Optional) Override the movie's background color, given as a hex color such as #FFFFFF
+    #[clap(long, parse(try_from_str = parse_color))]
+    background_color: Option<Color>,
+
+    /// (Optional) The color of the bars around the movie when the window's aspect ratio differs,
+    /// given as a hex color such as #000000
+    #[clap(long, parse(try_from_str = parse_color))]
+    letterbox_color: Option<Color>,
+
+    /// (Optional) Override how the movie scales to fill the window.
+    /// One of showAll, noBorder, exactFit or noScale.
+    #[clap(long, case_insensitive = true)]
+    scale_mode: Option<StageScaleMode>,
+}
+
+/// Parses a hex color such as `#FF8000`, with an optional leading `#`.
+fn parse_color(color: &str) -> Result<Color, String> {
+    let hex = color.strip_prefix('#').unwrap_or(color);
+    if hex.len() != 6 || !hex.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!("{} is not a hex color", color));
+    }
+    let rgb = u32::from_str_radix(hex, 16).map_err(|e| e.to_string())?;
+    Ok(Color::from_rgb(rgb, 255))
 }
 
 #[cfg(feature = "render_trace")]
@@ -177,6 +219,39 @@ fn set_movie_parameters(movie: &mut SwfMovie, parameters: &[String]) {
     movie.append_parameters(parameters)
 }
 
+fn register_device_fonts(
+    player: &mut Player,
+    fonts: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut families: HashMap<&str, DeviceFontFiles> = HashMap::new();
+    for font in fonts {
+        let mut split = font.splitn(2, '=');
+        let (name, path) = match (split.next(), split.next()) {
+            (Some(name), Some(path)) => (name, path),
+            _ => return Err(format!("Invalid font \"{}\", expected FAMILY=PATH", font).into()),
+        };
+        let mut split = name.splitn(2, ':');
+        let family = split.next().unwrap_or_default();
+        let files = families.entry(family).or_default();
+        let data = std::fs::read(path)?;
+        match split.next() {
+            None | Some("regular") => files.regular = data,
+            Some("bold") => files.bold = Some(data),
+            Some("italic") => files.italic = Some(data),
+            Some("bold-italic") => files.bold_italic = Some(data),
+            Some(style) => return Err(format!("Unknown font style \"{}\"", style).into()),
+        }
+    }
+
+    for (family, files) in families {
+        if files.regular.is_empty() {
+            return Err(format!("Font \"{}\" has no regular variant", family).into());
+        }
+        player.register_device_font_family(family, &files)?;
+    }
+    Ok(())
+}
+
 fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let movie_url = match &opt.input_path {
         Some(path) => {
@@ -270,9 +345,24 @@ fn run_player(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let player = Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
     {
         let mut player = player.lock().unwrap();
+        player.set_trust_local_content(true);
         player.set_root_movie(Arc::new(movie));
         player.set_is_playing(true); // Desktop player will auto-play.
         player.set_letterbox(Letterbox::On);
+        if let Some(color) = opt.background_color {
+            player.set_background_color(Some(color));
+        }
+        if let Some(color) = opt.letterbox_color {
+            player.set_letterbox_color(color);
+        }
+        if let Some(scale_mode) = opt.scale_mode {
+            player.set_scale_mode(scale_mode);
+        }
+        player.set_max_frame_rate_cap(opt.max_frame_rate_cap);
+        if opt.no_script_timeout {
+            player.disable_script_timeout();
+        }
+        register_device_fonts(&mut player, &opt.fonts)?;
         player.set_viewport_dimensions(
             viewport_size.width,
             viewport_size.height,
@@ -500,6 +590,7 @@ fn run_timedemo(opt: Opt) -> Result<(), Box<dyn std::error::Error>> {
     let log = Box::new(ruffle_core::backend::log::NullLogBackend::new());
     let ui = Box::new(ruffle_core::backend::ui::NullUiBackend::new());
     let player = Player::new(renderer, audio, navigator, storage, locale, video, log, ui)?;
+    player.lock().unwrap().set_trust_local_content(true);
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player.lock().unwrap().set_is_playing(true);
 
@@ -9,8 +9,12 @@ use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use std::borrow::Cow;
 use std::fs;
+use std::future::Future;
+use std::pin::Pin;
 use std::rc::Rc;
-use std::sync::mpsc::Sender;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::task::{Context, Poll, Waker};
+use std::thread;
 use std::time::{Duration, Instant};
 use url::Url;
 use winit::event_loop::EventLoopProxy;
@@ -27,12 +31,12 @@ pub struct ExternalNavigatorBackend {
     /// The url to use for all relative fetches.
     movie_url: Url,
 
-    /// The time that the SWF was launched.
-    start_time: Instant,
-
     // Client to use for network requests
     client: Option<Rc<HttpClient>>,
 
+    /// Wakes tasks that are waiting for `sleep`.
+    timer: Timer,
+
     upgrade_to_https: bool,
 }
 
@@ -57,8 +61,8 @@ impl ExternalNavigatorBackend {
             channel,
             event_loop,
             client,
+            timer: Timer::new(),
             movie_url,
-            start_time: Instant::now(),
             upgrade_to_https,
         }
     }
@@ -130,45 +134,10 @@ impl NavigatorBackend for ExternalNavigatorBackend {
                 fs::read(processed_url.to_file_path().unwrap_or_default())
                     .map_err(Error::NetworkError)
             }),
-            _ => Box::pin(async move {
-                let client = client.ok_or(Error::NetworkUnavailable)?;
-
-                let request = match options.method() {
-                    NavigationMethod::Get => Request::get(processed_url.to_string()),
-                    NavigationMethod::Post => Request::post(processed_url.to_string()),
-                };
-
-                let (body_data, _) = options.body().clone().unwrap_or_default();
-                let body = request
-                    .body(body_data)
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-
-                let mut response = client
-                    .send_async(body)
-                    .await
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-
-                if !response.status().is_success() {
-                    return Err(Error::FetchError(format!(
-                        "HTTP status is not ok, got {}",
-                        response.status()
-                    )));
-                }
-
-                let mut buffer = vec![];
-                response
-                    .copy_to(&mut buffer)
-                    .await
-                    .map_err(|e| Error::FetchError(e.to_string()))?;
-                Ok(buffer)
-            }),
+            _ => Box::pin(fetch_http(client, processed_url, options)),
         }
     }
 
-    fn time_since_launch(&mut self) -> Duration {
-        Instant::now().duration_since(self.start_time)
-    }
-
     fn spawn_future(&mut self, future: OwnedFuture<(), Error>) {
         self.channel.send(future).expect("working channel send");
 
@@ -179,6 +148,10 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         }
     }
 
+    fn sleep(&self, duration: Duration) -> OwnedFuture<(), Error> {
+        Box::pin(self.timer.sleep(duration))
+    }
+
     fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
         let relative = self.movie_url.join(url);
         if let Ok(relative) = relative {
@@ -195,3 +168,169 @@ impl NavigatorBackend for ExternalNavigatorBackend {
         url
     }
 }
+
+/// Make an HTTP request with the given options.
+async fn fetch_http(
+    client: Option<Rc<HttpClient>>,
+    url: Url,
+    options: RequestOptions,
+) -> Result<Vec<u8>, Error> {
+    let client = client.ok_or(Error::NetworkUnavailable)?;
+
+    let mut request = match options.method() {
+        NavigationMethod::Get => Request::get(url.to_string()),
+        NavigationMethod::Post => Request::post(url.to_string()),
+    };
+    if let Some(timeout) = options.timeout() {
+        request = request.timeout(timeout);
+    }
+
+    let (body_data, _) = options.body().clone().unwrap_or_default();
+    let body = request
+        .body(body_data)
+        .map_err(|e| Error::FetchError(e.to_string()))?;
+
+    let mut response = client
+        .send_async(body)
+        .await
+        .map_err(|e| Error::FetchError(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(Error::FetchError(format!(
+            "HTTP status is not ok, got {}",
+            response.status()
+        )));
+    }
+
+    let mut buffer = vec![];
+    response
+        .copy_to(&mut buffer)
+        .await
+        .map_err(|e| Error::FetchError(e.to_string()))?;
+    Ok(buffer)
+}
+
+/// Wakes sleeping tasks once their deadline has passed.
+///
+/// All sleeps share a single thread, which waits for the earliest deadline,
+/// as the event loop has no timers of its own that tasks can wait on.
+struct Timer {
+    sender: Sender<(Instant, Waker)>,
+}
+
+impl Timer {
+    fn new() -> Self {
+        let (sender, receiver) = channel::<(Instant, Waker)>();
+        thread::spawn(move || {
+            let mut pending: Vec<(Instant, Waker)> = Vec::new();
+            loop {
+                let now = Instant::now();
+                pending.retain(|(deadline, waker)| {
+                    if *deadline <= now {
+                        waker.wake_by_ref();
+                        false
+                    } else {
+                        true
+                    }
+                });
+
+                let received = match pending.iter().map(|(deadline, _)| *deadline).min() {
+                    Some(deadline) => receiver.recv_timeout(deadline - now),
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match received {
+                    Ok(sleep) => pending.push(sleep),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Self { sender }
+    }
+
+    fn sleep(&self, duration: Duration) -> Sleep {
+        Sleep {
+            deadline: Instant::now() + duration,
+            timer: Some(self.sender.clone()),
+        }
+    }
+}
+
+/// A future that resolves once its deadline has passed.
+struct Sleep {
+    deadline: Instant,
+
+    /// The timer to register with on the first poll.
+    timer: Option<Sender<(Instant, Waker)>>,
+}
+
+impl Future for Sleep {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if Instant::now() >= self.deadline {
+            return Poll::Ready(Ok(()));
+        }
+
+        if let Some(timer) = self.timer.take() {
+            let deadline = self.deadline;
+            if timer.send((deadline, cx.waker().clone())).is_err() {
+                // The timer is gone, so there is nothing left to wait for.
+                return Poll::Ready(Ok(()));
+            }
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::TcpListener;
+    use std::sync::Arc;
+    use std::task::Wake;
+
+    /// Wakes the thread that is blocked on a future.
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        let mut future = Box::pin(future);
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut context = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+
+    #[test]
+    fn sleep_waits_for_the_deadline() {
+        let timer = Timer::new();
+        let start = Instant::now();
+        block_on(timer.sleep(Duration::from_millis(50))).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn request_times_out() {
+        // A server that accepts connections, but never responds.
+        let server = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", server.local_addr().unwrap())).unwrap();
+        let client = HttpClient::new().ok().map(Rc::new);
+        let options = RequestOptions::get().with_timeout(Duration::from_millis(100));
+
+        let start = Instant::now();
+        let result = block_on(fetch_http(client, url, options));
+        assert!(matches!(result, Err(Error::FetchError(_))));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+}
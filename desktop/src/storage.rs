@@ -26,12 +26,28 @@ impl DiskStorageBackend {
 
 impl StorageBackend for DiskStorageBackend {
     fn get_string(&self, name: &str) -> Option<String> {
+        let bytes = self.get_bytes(name)?;
+        match String::from_utf8(bytes) {
+            Ok(string) => Some(string),
+            Err(r) => {
+                log::warn!("Unable to read file content {:?}", r);
+                None
+            }
+        }
+    }
+
+    fn put_string(&mut self, name: &str, value: String) -> bool {
+        self.put_bytes(name, value.as_bytes())
+    }
+
+    /// Binary data, such as `.sol` files, is stored as is.
+    fn get_bytes(&self, name: &str) -> Option<Vec<u8>> {
         let full_path = self.base_path.join(Path::new(name));
 
         match File::open(full_path) {
             Ok(mut file) => {
-                let mut buffer = String::new();
-                if let Err(r) = file.read_to_string(&mut buffer) {
+                let mut buffer = Vec::new();
+                if let Err(r) = file.read_to_end(&mut buffer) {
                     log::warn!("Unable to read file content {:?}", r);
                     None
                 } else {
@@ -45,7 +61,7 @@ impl StorageBackend for DiskStorageBackend {
         }
     }
 
-    fn put_string(&mut self, name: &str, value: String) -> bool {
+    fn put_bytes(&mut self, name: &str, value: &[u8]) -> bool {
         let full_path = self.base_path.join(Path::new(name));
         if let Some(parent_dir) = full_path.parent() {
             if !parent_dir.exists() {
@@ -58,7 +74,7 @@ impl StorageBackend for DiskStorageBackend {
 
         match File::create(full_path) {
             Ok(mut file) => {
-                if let Err(r) = file.write_all(value.as_bytes()) {
+                if let Err(r) = file.write_all(value) {
                     log::warn!("Unable to write file content {:?}", r);
                     false
                 } else {
@@ -76,4 +92,32 @@ impl StorageBackend for DiskStorageBackend {
         let full_path = self.base_path.join(Path::new(name));
         let _ = fs::remove_file(full_path);
     }
+
+    fn keys(&self) -> Vec<String> {
+        let mut keys = Vec::new();
+        let mut dirs = vec![self.base_path.clone()];
+        while let Some(dir) = dirs.pop() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(r) => {
+                    log::warn!("Unable to read storage dir {:?}", r);
+                    continue;
+                }
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if let Ok(relative) = path.strip_prefix(&self.base_path) {
+                    // Keys always use forward slashes, regardless of platform.
+                    let components: Vec<_> = relative
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy())
+                        .collect();
+                    keys.push(components.join("/"));
+                }
+            }
+        }
+        keys
+    }
 }
@@ -7,6 +7,7 @@ use ruffle_core::color_transform::ColorTransform;
 use ruffle_core::shape_utils::{DistilledShape, DrawCommand};
 use ruffle_core::swf::Matrix;
 use ruffle_web_common::JsResult;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use wasm_bindgen::{JsCast, JsValue};
 use web_sys::{
@@ -30,6 +31,15 @@ pub struct WebCanvasRenderBackend {
     use_color_transform_hack: bool,
     pixelated_property_value: &'static str,
     deactivating_mask: bool,
+
+    /// The canvases that `cacheAsBitmap` objects are rendered into, keyed by bitmap handle.
+    offscreen_bitmaps: HashMap<usize, (HtmlCanvasElement, CanvasRenderingContext2d)>,
+
+    /// Handles of freed offscreen bitmaps, which can be given out again.
+    free_offscreen_handles: Vec<usize>,
+
+    /// The render targets replaced by offscreen canvases that are being rendered to.
+    offscreen_stack: Vec<(HtmlCanvasElement, CanvasRenderingContext2d)>,
 }
 
 /// Canvas-drawable shape data extracted from an SWF file.
@@ -212,6 +222,9 @@ impl WebCanvasRenderBackend {
             viewport_height: 0,
             use_color_transform_hack: is_firefox,
             deactivating_mask: false,
+            offscreen_bitmaps: HashMap::new(),
+            free_offscreen_handles: vec![],
+            offscreen_stack: vec![],
 
             // For rendering non-smoothed bitmaps.
             // crisp-edges works in Firefox, pixelated works in Chrome (and others)?
@@ -542,7 +555,11 @@ impl RenderBackend for WebCanvasRenderBackend {
 
         self.set_transform(&transform.matrix);
         self.set_color_filter(transform);
-        if let Some(bitmap) = self.bitmaps.get(bitmap.0) {
+        if let Some((canvas, _)) = self.offscreen_bitmaps.get(&bitmap.0) {
+            let _ = self
+                .context
+                .draw_image_with_html_canvas_element(canvas, 0.0, 0.0);
+        } else if let Some(bitmap) = self.bitmaps.get(bitmap.0) {
             let _ = self
                 .context
                 .draw_image_with_html_image_element(&bitmap.image, 0.0, 0.0);
@@ -692,6 +709,70 @@ impl RenderBackend for WebCanvasRenderBackend {
             .unwrap();
     }
 
+    fn begin_offscreen(
+        &mut self,
+        width: u32,
+        height: u32,
+        reuse: Option<BitmapHandle>,
+    ) -> Option<BitmapHandle> {
+        let handle = match reuse.filter(|bitmap| self.offscreen_bitmaps.contains_key(&bitmap.0)) {
+            Some(bitmap) => bitmap.0,
+            None => {
+                let document = web_sys::window()?.document()?;
+                let canvas: HtmlCanvasElement =
+                    document.create_element("canvas").ok()?.dyn_into().ok()?;
+                let context: CanvasRenderingContext2d =
+                    canvas.get_context("2d").ok()??.dyn_into().ok()?;
+
+                // Offscreen bitmaps share the handle space of registered bitmaps.
+                let handle = self.free_offscreen_handles.pop().unwrap_or_else(|| {
+                    self.bitmaps.push(BitmapData {
+                        image: HtmlImageElement::new().unwrap(),
+                        width: 0,
+                        height: 0,
+                        data: String::new(),
+                    });
+                    self.bitmaps.len() - 1
+                });
+                self.offscreen_bitmaps.insert(handle, (canvas, context));
+                handle
+            }
+        };
+
+        let (canvas, context) = self.offscreen_bitmaps[&handle].clone();
+        // Resizing the canvas also clears it.
+        canvas.set_width(width);
+        canvas.set_height(height);
+
+        // Masks inside the offscreen bitmap are composited onto the current render target,
+        // so the offscreen canvas takes its place until `end_offscreen`.
+        let previous = std::mem::replace(
+            &mut self.render_targets[self.cur_render_target],
+            (canvas.clone(), context.clone()),
+        );
+        self.offscreen_stack.push(previous);
+        self.canvas = canvas;
+        self.context = context;
+
+        Some(BitmapHandle(handle))
+    }
+
+    fn end_offscreen(&mut self) {
+        if let Some(previous) = self.offscreen_stack.pop() {
+            self.canvas = previous.0.clone();
+            self.context = previous.1.clone();
+            self.render_targets[self.cur_render_target] = previous;
+        } else {
+            log::error!("Offscreen render target stack underflow");
+        }
+    }
+
+    fn unregister_bitmap(&mut self, bitmap: BitmapHandle) {
+        if self.offscreen_bitmaps.remove(&bitmap.0).is_some() {
+            self.free_offscreen_handles.push(bitmap.0);
+        }
+    }
+
     fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
         let window = web_sys::window().unwrap();
         let document = window.document().unwrap();
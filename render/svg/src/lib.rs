@@ -0,0 +1,896 @@
+//! SVG render backend
+//!
+//! Renders each frame into an SVG document instead of pixels, so that a frame
+//! can be exported with its vector art intact. Shapes and text become paths,
+//! gradients become SVG gradients, and bitmaps are embedded as PNG images.
+
+use ruffle_core::backend::render::{
+    swf::{self, GradientInterpolation, GradientSpread},
+    Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary, RenderBackend,
+    ShapeHandle, Transform,
+};
+use ruffle_core::color_transform::ColorTransform;
+use ruffle_core::shape_utils::{DistilledShape, DrawCommand, DrawPath};
+use ruffle_core::swf::{FillStyle, LineCapStyle, LineJoinStyle, Matrix, Twips};
+use std::convert::TryInto;
+use svg::node::element::{
+    path::Data, ClipPath, Definitions, Element, Filter, Group, Image, LinearGradient, Path,
+    Pattern, RadialGradient, Rectangle, Stop,
+};
+use svg::node::Node;
+use svg::Document;
+
+type Error = Box<dyn std::error::Error>;
+
+pub struct SvgRenderBackend {
+    viewport_width: u32,
+    viewport_height: u32,
+    shapes: Vec<ShapeData>,
+    bitmaps: Vec<BitmapData>,
+
+    /// The definitions (gradients, patterns, clip paths) of the frame being
+    /// rendered.
+    defs: Definitions,
+    num_defs: usize,
+
+    /// The groups that are currently being rendered into, innermost last.
+    layers: Vec<Layer>,
+
+    /// The last frame that was rendered.
+    frame: Option<String>,
+}
+
+/// A place that rendered elements are added to.
+enum Layer {
+    Group(Group),
+
+    /// The shapes of a mask, which clip everything that is rendered while
+    /// the mask is active.
+    Mask {
+        id: String,
+        clip_path: ClipPath,
+    },
+
+    /// Elements that don't show up in the frame, such as a mask that is
+    /// being deactivated.
+    Discard,
+}
+
+struct ShapeData(Vec<ShapePath>);
+
+/// A path of a shape, in the shape's own coordinate space.
+struct ShapePath {
+    paint: Paint,
+    stroke: Option<Stroke>,
+    data: Data,
+}
+
+struct Stroke {
+    width: Twips,
+    cap: LineCapStyle,
+    join: LineJoinStyle,
+}
+
+enum Paint {
+    Color(Color),
+    LinearGradient(swf::Gradient),
+    RadialGradient {
+        gradient: swf::Gradient,
+        focal_point: f32,
+    },
+    Bitmap {
+        handle: BitmapHandle,
+        matrix: Matrix,
+        is_smoothed: bool,
+    },
+}
+
+struct BitmapData {
+    bitmap: Bitmap,
+
+    /// The bitmap as a PNG `data:` URI.
+    data: String,
+}
+
+impl SvgRenderBackend {
+    pub fn new(viewport_width: u32, viewport_height: u32) -> Self {
+        Self {
+            viewport_width,
+            viewport_height,
+            shapes: vec![],
+            bitmaps: vec![],
+            defs: Definitions::new(),
+            num_defs: 0,
+            layers: vec![],
+            frame: None,
+        }
+    }
+
+    /// The SVG document of the last frame that was rendered, if any.
+    pub fn frame(&self) -> Option<&str> {
+        self.frame.as_deref()
+    }
+
+    fn bitmap_to_png_data_uri(bitmap: &Bitmap) -> Result<String, Error> {
+        use png::Encoder;
+        let mut png_data: Vec<u8> = vec![];
+        {
+            let mut encoder = Encoder::new(&mut png_data, bitmap.width, bitmap.height);
+            encoder.set_depth(png::BitDepth::Eight);
+            let data = match &bitmap.data {
+                BitmapFormat::Rgba(data) => {
+                    let mut data = data.clone();
+                    ruffle_core::backend::render::unmultiply_alpha_rgba(&mut data[..]);
+                    encoder.set_color(png::ColorType::RGBA);
+                    data
+                }
+                BitmapFormat::Rgb(data) => {
+                    encoder.set_color(png::ColorType::RGB);
+                    data.clone()
+                }
+            };
+            let mut writer = encoder.write_header()?;
+            writer.write_image_data(&data)?;
+        }
+
+        Ok(format!(
+            "data:image/png;base64,{}",
+            &base64::encode(&png_data[..])
+        ))
+    }
+
+    fn register_bitmap(&mut self, bitmap: Bitmap) -> Result<BitmapInfo, Error> {
+        let handle = BitmapHandle(self.bitmaps.len());
+        let info = BitmapInfo {
+            handle,
+            width: bitmap
+                .width
+                .try_into()
+                .expect("Bitmap dimensions too large"),
+            height: bitmap
+                .height
+                .try_into()
+                .expect("Bitmap dimensions too large"),
+        };
+        let data = Self::bitmap_to_png_data_uri(&bitmap)?;
+        self.bitmaps.push(BitmapData { bitmap, data });
+        Ok(info)
+    }
+
+    /// Adds an element to the group that is currently being rendered into.
+    fn add<T: Node>(&mut self, node: T) {
+        let layer = match self.layers.pop() {
+            Some(Layer::Group(group)) => Layer::Group(group.add(node)),
+            Some(Layer::Mask { id, clip_path }) => Layer::Mask {
+                id,
+                clip_path: clip_path.add(node),
+            },
+            Some(Layer::Discard) => Layer::Discard,
+            None => {
+                log::warn!("Rendering outside of a frame");
+                return;
+            }
+        };
+        self.layers.push(layer);
+    }
+
+    /// Adds a definition to the frame, returning its ID.
+    fn add_def<T: Node>(&mut self, def: T) -> String {
+        let defs = std::mem::replace(&mut self.defs, Definitions::new());
+        self.defs = defs.add(def);
+        let id = format!("d{}", self.num_defs);
+        self.num_defs += 1;
+        id
+    }
+
+    /// The ID that the next definition added to the frame will have.
+    fn next_def_id(&self) -> String {
+        format!("d{}", self.num_defs)
+    }
+
+    /// Adds a filter applying the given color transform, for content whose
+    /// colors can't be transformed directly, such as bitmaps.
+    fn color_filter(&mut self, color_transform: &ColorTransform) -> Option<String> {
+        if color_transform.is_identity() {
+            return None;
+        }
+
+        let mult = color_transform.mult_rgba_normalized();
+        let add = color_transform.add_rgba_normalized();
+        let mut matrix = Element::new("feColorMatrix");
+        matrix.assign("type", "matrix");
+        matrix.assign(
+            "values",
+            format!(
+                "{} 0 0 0 {} 0 {} 0 0 {} 0 0 {} 0 {} 0 0 0 {} {}",
+                mult[0], add[0], mult[1], add[1], mult[2], add[2], mult[3], add[3]
+            ),
+        );
+        let filter = Filter::new()
+            .set("id", self.next_def_id())
+            .set("color-interpolation-filters", "sRGB")
+            .add(matrix);
+        Some(format!("url(#{})", self.add_def(filter)))
+    }
+
+    /// Converts a paint into the value of a `fill` or `stroke` attribute,
+    /// adding any definitions it needs to the frame.
+    fn paint(&mut self, paint: &Paint, color_transform: &ColorTransform) -> String {
+        match paint {
+            Paint::Color(color) => transformed_color(color, color_transform),
+            Paint::LinearGradient(gradient) => {
+                let (attributes, stops) = self.gradient_parts(gradient, color_transform);
+                let mut svg_gradient = LinearGradient::new()
+                    .set("x1", -GRADIENT_RADIUS)
+                    .set("x2", GRADIENT_RADIUS)
+                    .set("y1", 0.0)
+                    .set("y2", 0.0);
+                for (name, value) in attributes {
+                    svg_gradient = svg_gradient.set(name, value);
+                }
+                for stop in stops {
+                    svg_gradient = svg_gradient.add(stop);
+                }
+                format!("url(#{})", self.add_def(svg_gradient))
+            }
+            Paint::RadialGradient {
+                gradient,
+                focal_point,
+            } => {
+                let (attributes, stops) = self.gradient_parts(gradient, color_transform);
+                let mut svg_gradient = RadialGradient::new()
+                    .set("cx", 0.0)
+                    .set("cy", 0.0)
+                    .set("r", GRADIENT_RADIUS)
+                    .set("fx", focal_point * GRADIENT_RADIUS)
+                    .set("fy", 0.0);
+                for (name, value) in attributes {
+                    svg_gradient = svg_gradient.set(name, value);
+                }
+                for stop in stops {
+                    svg_gradient = svg_gradient.add(stop);
+                }
+                format!("url(#{})", self.add_def(svg_gradient))
+            }
+            Paint::Bitmap {
+                handle,
+                matrix,
+                is_smoothed,
+            } => {
+                let filter = self.color_filter(color_transform);
+                let bitmap = match self.bitmaps.get(handle.0) {
+                    Some(bitmap) => bitmap,
+                    None => return "none".to_string(),
+                };
+
+                // Bitmap fills are embedded as a repeating pattern. Flash
+                // clamps the edges of non-repeating fills instead, which SVG
+                // can't express.
+                let mut image = Image::new()
+                    .set("width", bitmap.bitmap.width)
+                    .set("height", bitmap.bitmap.height)
+                    .set("xlink:href", bitmap.data.as_str());
+                if !is_smoothed {
+                    image = image.set("image-rendering", "optimizeSpeed");
+                }
+                if let Some(filter) = filter {
+                    image = image.set("filter", filter);
+                }
+                let pattern = Pattern::new()
+                    .set("id", self.next_def_id())
+                    .set("patternUnits", "userSpaceOnUse")
+                    .set("width", bitmap.bitmap.width)
+                    .set("height", bitmap.bitmap.height)
+                    .set(
+                        "patternTransform",
+                        svg_matrix(&Matrix {
+                            a: matrix.a / 20.0,
+                            b: matrix.b / 20.0,
+                            c: matrix.c / 20.0,
+                            d: matrix.d / 20.0,
+                            ..*matrix
+                        }),
+                    )
+                    .add(image);
+                format!("url(#{})", self.add_def(pattern))
+            }
+        }
+    }
+
+    /// The attributes and stops shared by linear and radial gradients.
+    fn gradient_parts(
+        &self,
+        gradient: &swf::Gradient,
+        color_transform: &ColorTransform,
+    ) -> (Vec<(&'static str, String)>, Vec<Stop>) {
+        let mut attributes = vec![
+            ("id", self.next_def_id()),
+            ("gradientUnits", "userSpaceOnUse".to_string()),
+            ("gradientTransform", svg_matrix(&gradient.matrix)),
+        ];
+        match gradient.spread {
+            GradientSpread::Pad => (), // default
+            GradientSpread::Reflect => attributes.push(("spreadMethod", "reflect".to_string())),
+            GradientSpread::Repeat => attributes.push(("spreadMethod", "repeat".to_string())),
+        }
+        if gradient.interpolation == GradientInterpolation::LinearRgb {
+            attributes.push(("color-interpolation", "linearRGB".to_string()));
+        }
+
+        let stops = gradient
+            .records
+            .iter()
+            .map(|record| {
+                Stop::new()
+                    .set("offset", format!("{}%", f32::from(record.ratio) / 2.55))
+                    .set(
+                        "stop-color",
+                        transformed_color(&record.color, color_transform),
+                    )
+            })
+            .collect();
+
+        (attributes, stops)
+    }
+}
+
+/// The distance from the center to the edge of a gradient in gradient space,
+/// in pixels.
+const GRADIENT_RADIUS: f32 = 16384.0 / 20.0;
+
+/// Formats a matrix as an SVG `transform` attribute, in pixels.
+fn svg_matrix(matrix: &Matrix) -> String {
+    format!(
+        "matrix({} {} {} {} {} {})",
+        matrix.a,
+        matrix.b,
+        matrix.c,
+        matrix.d,
+        matrix.tx.to_pixels(),
+        matrix.ty.to_pixels()
+    )
+}
+
+/// Formats a color as an SVG color, after applying a color transform.
+fn transformed_color(color: &Color, color_transform: &ColorTransform) -> String {
+    let mult = color_transform.mult_rgba_normalized();
+    let add = color_transform.add_rgba_normalized();
+    let channel = |value: u8, i: usize| {
+        (f32::from(value) / 255.0 * mult[i] + add[i])
+            .max(0.0)
+            .min(1.0)
+    };
+    format!(
+        "rgba({},{},{},{})",
+        (channel(color.r, 0) * 255.0).round(),
+        (channel(color.g, 1) * 255.0).round(),
+        (channel(color.b, 2) * 255.0).round(),
+        channel(color.a, 3)
+    )
+}
+
+fn paint_from_fill_style(style: &FillStyle, library: Option<&MovieLibrary<'_>>) -> Paint {
+    match style {
+        FillStyle::Color(color) => Paint::Color(color.clone()),
+        FillStyle::LinearGradient(gradient) => Paint::LinearGradient(gradient.clone()),
+        FillStyle::RadialGradient(gradient) => Paint::RadialGradient {
+            gradient: gradient.clone(),
+            focal_point: 0.0,
+        },
+        FillStyle::FocalGradient {
+            gradient,
+            focal_point,
+        } => Paint::RadialGradient {
+            gradient: gradient.clone(),
+            focal_point: *focal_point,
+        },
+        FillStyle::Bitmap {
+            id,
+            matrix,
+            is_smoothed,
+            ..
+        } => {
+            if let Some(bitmap) = library.and_then(|lib| lib.get_bitmap(*id)) {
+                Paint::Bitmap {
+                    handle: bitmap.bitmap_handle(),
+                    matrix: *matrix,
+                    is_smoothed: *is_smoothed,
+                }
+            } else {
+                log::error!("Couldn't fill shape with unknown bitmap {}", id);
+                Paint::Color(Color::from_rgb(0, 0))
+            }
+        }
+    }
+}
+
+fn pixels(twips: Twips) -> f32 {
+    twips.to_pixels() as f32
+}
+
+fn draw_commands_to_data(commands: &[DrawCommand], is_closed: bool) -> Data {
+    let mut data = Data::new();
+    for command in commands {
+        data = match command {
+            DrawCommand::MoveTo { x, y } => data.move_to((pixels(*x), pixels(*y))),
+            DrawCommand::LineTo { x, y } => data.line_to((pixels(*x), pixels(*y))),
+            DrawCommand::CurveTo { x1, y1, x2, y2 } => {
+                data.quadratic_curve_to((pixels(*x1), pixels(*y1), pixels(*x2), pixels(*y2)))
+            }
+        };
+    }
+    if is_closed {
+        data = data.close();
+    }
+    data
+}
+
+fn convert_shape(shape: DistilledShape, library: Option<&MovieLibrary<'_>>) -> ShapeData {
+    let paths = shape
+        .paths
+        .iter()
+        .map(|path| match path {
+            DrawPath::Fill { style, commands } => ShapePath {
+                paint: paint_from_fill_style(style, library),
+                stroke: None,
+                data: draw_commands_to_data(commands, false),
+            },
+            DrawPath::Stroke {
+                style,
+                is_closed,
+                commands,
+            } => ShapePath {
+                paint: match &style.fill_style {
+                    Some(fill_style) => paint_from_fill_style(fill_style, library),
+                    None => Paint::Color(style.color.clone()),
+                },
+                stroke: Some(Stroke {
+                    width: style.width,
+                    cap: style.start_cap,
+                    join: style.join_style,
+                }),
+                data: draw_commands_to_data(commands, *is_closed),
+            },
+        })
+        .collect();
+    ShapeData(paths)
+}
+
+impl RenderBackend for SvgRenderBackend {
+    fn set_viewport_dimensions(&mut self, width: u32, height: u32) {
+        self.viewport_width = width;
+        self.viewport_height = height;
+    }
+
+    fn register_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+    ) -> ShapeHandle {
+        let handle = ShapeHandle(self.shapes.len());
+        self.shapes.push(convert_shape(shape, library));
+        handle
+    }
+
+    fn replace_shape(
+        &mut self,
+        shape: DistilledShape,
+        library: Option<&MovieLibrary<'_>>,
+        handle: ShapeHandle,
+    ) {
+        self.shapes[handle.0] = convert_shape(shape, library);
+    }
+
+    fn register_glyph_shape(&mut self, glyph: &swf::Glyph) -> ShapeHandle {
+        let shape = ruffle_core::shape_utils::swf_glyph_to_shape(glyph);
+        self.register_shape((&shape).into(), None)
+    }
+
+    fn register_bitmap_jpeg(
+        &mut self,
+        data: &[u8],
+        jpeg_tables: Option<&[u8]>,
+    ) -> Result<BitmapInfo, Error> {
+        let data = ruffle_core::backend::render::glue_tables_to_jpeg(data, jpeg_tables);
+        self.register_bitmap_jpeg_2(&data[..])
+    }
+
+    fn register_bitmap_jpeg_2(&mut self, data: &[u8]) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_jpeg(data, None)?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn register_bitmap_jpeg_3(
+        &mut self,
+        jpeg_data: &[u8],
+        alpha_data: &[u8],
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap =
+            ruffle_core::backend::render::decode_define_bits_jpeg(jpeg_data, Some(alpha_data))?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn register_bitmap_png(
+        &mut self,
+        swf_tag: &swf::DefineBitsLossless,
+    ) -> Result<BitmapInfo, Error> {
+        let bitmap = ruffle_core::backend::render::decode_define_bits_lossless(swf_tag)?;
+        self.register_bitmap(bitmap)
+    }
+
+    fn begin_frame(&mut self, clear: Color) {
+        self.defs = Definitions::new();
+        self.num_defs = 0;
+        self.layers = vec![Layer::Group(Group::new())];
+
+        let background = Rectangle::new()
+            .set("width", self.viewport_width)
+            .set("height", self.viewport_height)
+            .set("fill", format!("rgb({},{},{})", clear.r, clear.g, clear.b));
+        self.add(background);
+    }
+
+    fn render_bitmap(&mut self, bitmap: BitmapHandle, transform: &Transform, smoothing: bool) {
+        let filter = self.color_filter(&transform.color_transform);
+        if let Some(bitmap) = self.bitmaps.get(bitmap.0) {
+            let mut image = Image::new()
+                .set("width", bitmap.bitmap.width)
+                .set("height", bitmap.bitmap.height)
+                .set("xlink:href", bitmap.data.as_str())
+                .set("transform", svg_matrix(&transform.matrix));
+            if !smoothing {
+                image = image.set("image-rendering", "optimizeSpeed");
+            }
+            if let Some(filter) = filter {
+                image = image.set("filter", filter);
+            }
+            self.add(image);
+        }
+    }
+
+    fn render_shape(&mut self, shape: ShapeHandle, transform: &Transform) {
+        let matrix = svg_matrix(&transform.matrix);
+        let index = shape.0;
+        let shape = match self.shapes.get_mut(index) {
+            Some(shape) => std::mem::replace(shape, ShapeData(vec![])),
+            None => return,
+        };
+        for path in &shape.0 {
+            let paint = self.paint(&path.paint, &transform.color_transform);
+            let mut svg_path = Path::new()
+                .set("d", path.data.clone())
+                .set("transform", matrix.as_str());
+            svg_path = match &path.stroke {
+                None => svg_path
+                    .set("fill", paint)
+                    // Flash fills shapes using the even-odd rule.
+                    .set("fill-rule", "evenodd")
+                    .set("clip-rule", "evenodd"),
+                Some(stroke) => {
+                    // Flash always renders strokes with a minimum width of 1 pixel.
+                    let width = std::cmp::max(stroke.width, Twips::from_pixels(1.0));
+                    let mut svg_path = svg_path
+                        .set("fill", "none")
+                        .set("stroke", paint)
+                        .set("stroke-width", pixels(width))
+                        .set(
+                            "stroke-linecap",
+                            match stroke.cap {
+                                LineCapStyle::Round => "round",
+                                LineCapStyle::Square => "square",
+                                LineCapStyle::None => "butt",
+                            },
+                        )
+                        .set(
+                            "stroke-linejoin",
+                            match stroke.join {
+                                LineJoinStyle::Round => "round",
+                                LineJoinStyle::Bevel => "bevel",
+                                LineJoinStyle::Miter(_) => "miter",
+                            },
+                        );
+                    if let LineJoinStyle::Miter(miter_limit) = stroke.join {
+                        svg_path = svg_path.set("stroke-miterlimit", miter_limit);
+                    }
+                    svg_path
+                }
+            };
+            self.add(svg_path);
+        }
+        self.shapes[index] = shape;
+    }
+
+    fn draw_rect(&mut self, color: Color, matrix: &Matrix) {
+        let rect = Rectangle::new()
+            .set("width", 1)
+            .set("height", 1)
+            .set(
+                "fill",
+                transformed_color(&color, &ColorTransform::default()),
+            )
+            .set("transform", svg_matrix(matrix));
+        self.add(rect);
+    }
+
+    fn end_frame(&mut self) {
+        // Close any masks that were left open.
+        let mut root = None;
+        while let Some(layer) = self.layers.pop() {
+            if let Layer::Group(group) = layer {
+                root = Some(match root {
+                    Some(inner) => group.add(inner),
+                    None => group,
+                });
+            }
+        }
+
+        let mut document = Document::new()
+            .set("width", self.viewport_width)
+            .set("height", self.viewport_height)
+            .set("viewBox", (0, 0, self.viewport_width, self.viewport_height))
+            .set("xmlns:xlink", "http://www.w3.org/1999/xlink");
+        if self.num_defs > 0 {
+            let defs = std::mem::replace(&mut self.defs, Definitions::new());
+            document = document.add(defs);
+        }
+        if let Some(root) = root {
+            document = document.add(root);
+        }
+        self.frame = Some(document.to_string());
+    }
+
+    fn push_mask(&mut self) {
+        // Masks are rendered into a clip path, which only uses the shape of
+        // the elements in it.
+        let id = self.next_def_id();
+        let clip_path = ClipPath::new().set("id", id.as_str());
+        self.num_defs += 1;
+        self.layers.push(Layer::Mask { id, clip_path });
+    }
+
+    fn activate_mask(&mut self) {
+        match self.layers.pop() {
+            Some(Layer::Mask { id, clip_path }) => {
+                let defs = std::mem::replace(&mut self.defs, Definitions::new());
+                self.defs = defs.add(clip_path);
+                self.layers.push(Layer::Group(
+                    Group::new().set("clip-path", format!("url(#{})", id)),
+                ));
+            }
+            layer => {
+                log::warn!("activate_mask called without push_mask");
+                self.layers.extend(layer);
+            }
+        }
+    }
+
+    fn deactivate_mask(&mut self) {
+        match self.layers.pop() {
+            Some(Layer::Group(group)) if !self.layers.is_empty() => {
+                self.add(group);
+                // The mask is rendered again to remove it, which has no
+                // equivalent here.
+                self.layers.push(Layer::Discard);
+            }
+            layer => {
+                log::warn!("deactivate_mask called without activate_mask");
+                self.layers.extend(layer);
+            }
+        }
+    }
+
+    fn pop_mask(&mut self) {
+        match self.layers.pop() {
+            Some(Layer::Discard) => (),
+            layer => {
+                log::warn!("pop_mask called without deactivate_mask");
+                self.layers.extend(layer);
+            }
+        }
+    }
+
+    fn get_bitmap_pixels(&mut self, bitmap: BitmapHandle) -> Option<Bitmap> {
+        self.bitmaps
+            .get(bitmap.0)
+            .map(|bitmap| bitmap.bitmap.clone())
+    }
+
+    fn register_bitmap_raw(
+        &mut self,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        Ok(self
+            .register_bitmap(Bitmap {
+                width,
+                height,
+                data: BitmapFormat::Rgba(rgba),
+            })?
+            .handle)
+    }
+
+    fn update_texture(
+        &mut self,
+        handle: BitmapHandle,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<BitmapHandle, Error> {
+        let bitmap = Bitmap {
+            width,
+            height,
+            data: BitmapFormat::Rgba(rgba),
+        };
+        let data = Self::bitmap_to_png_data_uri(&bitmap)?;
+        self.bitmaps[handle.0] = BitmapData { bitmap, data };
+        Ok(handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(fill_style: FillStyle) -> swf::Shape {
+        let size = Twips::from_pixels(10.0);
+        let bounds = swf::Rectangle {
+            x_min: Twips::zero(),
+            x_max: size,
+            y_min: Twips::zero(),
+            y_max: size,
+        };
+        let edge = |delta_x, delta_y| swf::ShapeRecord::StraightEdge { delta_x, delta_y };
+        swf::Shape {
+            version: 1,
+            id: 1,
+            shape_bounds: bounds.clone(),
+            edge_bounds: bounds,
+            has_fill_winding_rule: false,
+            has_non_scaling_strokes: false,
+            has_scaling_strokes: false,
+            styles: swf::ShapeStyles {
+                fill_styles: vec![fill_style],
+                line_styles: vec![],
+            },
+            shape: vec![
+                swf::ShapeRecord::StyleChange(swf::StyleChangeData {
+                    move_to: Some((Twips::zero(), Twips::zero())),
+                    fill_style_0: None,
+                    fill_style_1: Some(1),
+                    line_style: None,
+                    new_styles: None,
+                }),
+                edge(size, Twips::zero()),
+                edge(Twips::zero(), size),
+                edge(size * -1, Twips::zero()),
+                edge(Twips::zero(), size * -1),
+            ],
+        }
+    }
+
+    fn translated(x: f64, y: f64) -> Transform {
+        Transform {
+            matrix: Matrix {
+                tx: Twips::from_pixels(x),
+                ty: Twips::from_pixels(y),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn shape_renders_as_path() {
+        let mut renderer = SvgRenderBackend::new(100, 50);
+        let shape = square(FillStyle::Color(Color::from_rgb(0xFF0000, 255)));
+        let handle = renderer.register_shape((&shape).into(), None);
+
+        renderer.begin_frame(Color::from_rgb(0xFFFFFF, 255));
+        renderer.render_shape(handle, &translated(5.0, 20.0));
+        renderer.end_frame();
+
+        let frame = renderer.frame().unwrap();
+        assert!(frame.contains(r#"viewBox="0 0 100 50""#));
+        assert!(frame.contains(r#"d="M0,0 L10,0 L10,10 L0,10"#));
+        assert!(frame.contains(r#"fill="rgba(255,0,0,1)""#));
+        assert!(frame.contains(r#"transform="matrix(1 0 0 1 5 20)""#));
+    }
+
+    #[test]
+    fn gradient_and_color_transform() {
+        let mut renderer = SvgRenderBackend::new(100, 50);
+        let gradient = swf::Gradient {
+            matrix: Matrix::default(),
+            spread: GradientSpread::Pad,
+            interpolation: GradientInterpolation::Rgb,
+            records: vec![
+                swf::GradientRecord {
+                    ratio: 0,
+                    color: Color::from_rgb(0x000000, 255),
+                },
+                swf::GradientRecord {
+                    ratio: 255,
+                    color: Color::from_rgb(0xFFFFFF, 255),
+                },
+            ],
+        };
+        let shape = square(FillStyle::LinearGradient(gradient));
+        let handle = renderer.register_shape((&shape).into(), None);
+
+        let mut transform = translated(0.0, 0.0);
+        transform.color_transform.a_mult = swf::Fixed8::from_f32(0.5);
+        renderer.begin_frame(Color::from_rgb(0xFFFFFF, 255));
+        renderer.render_shape(handle, &transform);
+        renderer.end_frame();
+
+        let frame = renderer.frame().unwrap();
+        assert!(frame.contains("<linearGradient"));
+        assert!(frame.contains(r#"stop-color="rgba(255,255,255,0.5)""#));
+        assert!(frame.contains(r#"fill="url(#d0)""#));
+    }
+
+    #[test]
+    fn masks_become_clip_paths() {
+        let mut renderer = SvgRenderBackend::new(100, 50);
+        let shape = square(FillStyle::Color(Color::from_rgb(0x00FF00, 255)));
+        let handle = renderer.register_shape((&shape).into(), None);
+
+        renderer.begin_frame(Color::from_rgb(0xFFFFFF, 255));
+        renderer.push_mask();
+        renderer.render_shape(handle, &translated(0.0, 0.0));
+        renderer.activate_mask();
+        renderer.render_shape(handle, &translated(5.0, 0.0));
+        renderer.deactivate_mask();
+        renderer.render_shape(handle, &translated(0.0, 0.0));
+        renderer.pop_mask();
+        renderer.end_frame();
+
+        let frame = renderer.frame().unwrap();
+        assert!(frame.contains(r#"<clipPath id="d0">"#));
+        assert!(frame.contains(r#"clip-path="url(#d0)""#));
+        assert_eq!(frame.matches("<path").count(), 2);
+    }
+
+    #[test]
+    fn bitmap_is_embedded_as_png() {
+        let mut renderer = SvgRenderBackend::new(100, 50);
+        let handle = renderer
+            .register_bitmap_raw(1, 1, vec![255, 0, 0, 255])
+            .unwrap();
+
+        renderer.begin_frame(Color::from_rgb(0xFFFFFF, 255));
+        renderer.render_bitmap(handle, &translated(5.0, 20.0), false);
+        renderer.end_frame();
+
+        let frame = renderer.frame().unwrap();
+        assert!(frame.contains("<image"));
+        assert!(frame.contains(r#"xlink:href="data:image/png;base64,"#));
+        assert!(frame.contains(r#"image-rendering="optimizeSpeed""#));
+        assert!(frame.contains(r#"transform="matrix(1 0 0 1 5 20)""#));
+        assert!(!frame.contains("<filter"));
+    }
+
+    #[test]
+    fn bitmap_color_transform_becomes_filter() {
+        let mut renderer = SvgRenderBackend::new(100, 50);
+        let handle = renderer
+            .register_bitmap_raw(1, 1, vec![255, 0, 0, 255])
+            .unwrap();
+
+        let mut transform = translated(0.0, 0.0);
+        transform.color_transform.r_mult = swf::Fixed8::from_f32(0.5);
+        transform.color_transform.g_add = 51;
+        renderer.begin_frame(Color::from_rgb(0xFFFFFF, 255));
+        renderer.render_bitmap(handle, &transform, true);
+        renderer.end_frame();
+
+        let frame = renderer.frame().unwrap();
+        assert!(frame.contains("<filter"));
+        assert!(frame.contains(r#"color-interpolation-filters="sRGB""#));
+        assert!(frame.contains("<feColorMatrix"));
+        assert!(frame.contains(r#"type="matrix""#));
+        assert!(frame.contains(r#"values="0.5 0 0 0 0 0 1 0 0 0.2 0 0 1 0 0 0 0 0 1 0""#));
+        assert!(frame.contains(r#"filter="url(#d0)""#));
+    }
+}
@@ -1,7 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 use ruffle_core::backend::render::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary, RenderBackend,
-    ShapeHandle, Transform,
+    ShapeHandle, StageQuality, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf;
@@ -67,6 +67,9 @@ pub struct WebGlRenderBackend {
     msaa_buffers: Option<MsaaBuffers>,
     msaa_sample_count: u32,
 
+    /// The most MSAA samples that this device should render with.
+    max_msaa_sample_count: u32,
+
     color_program: ShaderProgram,
     bitmap_program: ShaderProgram,
     gradient_program: ShaderProgram,
@@ -215,6 +218,7 @@ impl WebGlRenderBackend {
 
             msaa_buffers: None,
             msaa_sample_count,
+            max_msaa_sample_count: msaa_sample_count,
 
             color_program,
             gradient_program,
@@ -355,16 +359,8 @@ impl WebGlRenderBackend {
     }
 
     fn build_msaa_buffers(&mut self, width: i32, height: i32) -> Result<(), Error> {
-        if self.gl2.is_none() || self.msaa_sample_count <= 1 {
-            self.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
-            self.gl.bind_renderbuffer(Gl::RENDERBUFFER, None);
-            return Ok(());
-        }
-
-        let gl = self.gl2.as_ref().unwrap();
-
         // Delete previous buffers, if they exist.
-        if let Some(msaa_buffers) = self.msaa_buffers.take() {
+        if let (Some(gl), Some(msaa_buffers)) = (&self.gl2, self.msaa_buffers.take()) {
             gl.delete_renderbuffer(Some(&msaa_buffers.color_renderbuffer));
             gl.delete_renderbuffer(Some(&msaa_buffers.stencil_renderbuffer));
             gl.delete_framebuffer(Some(&msaa_buffers.render_framebuffer));
@@ -372,6 +368,14 @@ impl WebGlRenderBackend {
             gl.delete_texture(Some(&msaa_buffers.framebuffer_texture));
         }
 
+        if self.gl2.is_none() || self.msaa_sample_count <= 1 {
+            self.gl.bind_framebuffer(Gl::FRAMEBUFFER, None);
+            self.gl.bind_renderbuffer(Gl::RENDERBUFFER, None);
+            return Ok(());
+        }
+
+        let gl = self.gl2.as_ref().unwrap();
+
         // Create frame and render buffers.
         let render_framebuffer = gl
             .create_framebuffer()
@@ -738,6 +742,16 @@ impl RenderBackend for WebGlRenderBackend {
             .viewport(0, 0, self.renderbuffer_width, self.renderbuffer_height);
     }
 
+    fn set_quality(&mut self, quality: StageQuality) {
+        let msaa_sample_count = quality.sample_count().min(self.max_msaa_sample_count);
+        if msaa_sample_count == self.msaa_sample_count {
+            return;
+        }
+
+        self.msaa_sample_count = msaa_sample_count;
+        let _ = self.build_msaa_buffers(self.renderbuffer_width, self.renderbuffer_height);
+    }
+
     fn register_shape(
         &mut self,
         shape: DistilledShape,
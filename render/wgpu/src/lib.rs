@@ -1,6 +1,6 @@
 use ruffle_core::backend::render::{
     Bitmap, BitmapFormat, BitmapHandle, BitmapInfo, Color, MovieLibrary, RenderBackend,
-    ShapeHandle, Transform,
+    ShapeHandle, StageQuality, Transform,
 };
 use ruffle_core::shape_utils::DistilledShape;
 use ruffle_core::swf;
@@ -51,8 +51,7 @@ pub struct Descriptors {
 
 impl Descriptors {
     pub fn new(device: wgpu::Device, queue: wgpu::Queue) -> Result<Self, Error> {
-        // TODO: Allow this to be set from command line/settings file.
-        let msaa_sample_count = 4;
+        let msaa_sample_count = msaa_sample_count(StageQuality::default());
 
         let bitmap_samplers = BitmapSamplers::new(&device);
         let globals = Globals::new(&device);
@@ -74,6 +73,16 @@ impl Descriptors {
     }
 }
 
+/// The MSAA sample count to render shapes with at the given quality.
+/// Only 1x and 4x are guaranteed to be supported by every device.
+fn msaa_sample_count(quality: StageQuality) -> u32 {
+    if quality.sample_count() > 1 {
+        4
+    } else {
+        1
+    }
+}
+
 pub struct WgpuRenderBackend<T: RenderTarget> {
     descriptors: Descriptors,
     target: T,
@@ -740,6 +749,31 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         self.descriptors.globals.set_resolution(width, height);
     }
 
+    fn set_quality(&mut self, quality: StageQuality) {
+        let msaa_sample_count = msaa_sample_count(quality);
+        if msaa_sample_count == self.descriptors.msaa_sample_count {
+            return;
+        }
+
+        let pipelines = match Pipelines::new(
+            &self.descriptors.device,
+            msaa_sample_count,
+            self.descriptors.bitmap_samplers.layout(),
+            self.descriptors.globals.layout(),
+        ) {
+            Ok(pipelines) => pipelines,
+            Err(e) => {
+                log::warn!("Couldn't change MSAA to {}x: {}", msaa_sample_count, e);
+                return;
+            }
+        };
+        self.descriptors.pipelines = pipelines;
+        self.descriptors.msaa_sample_count = msaa_sample_count;
+
+        // The frame buffers must be recreated with the new sample count.
+        self.set_viewport_dimensions(self.target.width(), self.target.height());
+    }
+
     fn register_shape(
         &mut self,
         shape: DistilledShape,
@@ -342,13 +342,12 @@ impl<W: Write> Writer<W> {
                         0
                     };
                 }
-                let len = 7
-                    + action_buf.len()
-                    + if let Some((CatchVar::Var(ref name), _)) = try_block.catch {
-                        name.len() + 1
-                    } else {
-                        1
-                    };
+                // The try, catch and finally bodies aren't included in the action length.
+                let len = 7 + if let Some((CatchVar::Var(ref name), _)) = try_block.catch {
+                    name.len() + 1
+                } else {
+                    1
+                };
                 self.write_action_header(OpCode::Try, len)?;
                 self.write_u8(
                     if let Some((CatchVar::Register(_), _)) = try_block.catch {
@@ -364,7 +363,8 @@ impl<W: Write> Writer<W> {
                 match try_block.catch {
                     Some((CatchVar::Var(name), _)) => self.write_string(name)?,
                     Some((CatchVar::Register(i), _)) => self.write_u8(i)?,
-                    _ => (),
+                    // Without a catch block, the catch variable is an empty string.
+                    None => self.write_u8(0)?,
                 }
                 self.output.write_all(&action_buf)?;
             }
@@ -384,7 +384,9 @@ impl<W: Write> Writer<W> {
                 self.write_u8(num_actions_to_skip)?;
             }
             Action::With { ref actions } => {
-                self.write_action_header(OpCode::With, actions.len())?;
+                // The with body isn't included in the action length.
+                self.write_action_header(OpCode::With, 2)?;
+                self.write_u16(actions.len() as u16)?;
                 self.output.write_all(&actions)?;
             }
             Action::Unknown { opcode, ref data } => {
@@ -2815,6 +2815,34 @@ pub fn avm1_tests() -> Vec<Avm1TestData> {
             },
             vec![0x8D, 1, 0, 34],
         ),
+        (
+            7,
+            Action::Try(TryBlock {
+                try_actions: &[0x07],
+                catch: Some((
+                    CatchVar::Var(SwfStr::from_str_with_encoding("e", WINDOWS_1252).unwrap()),
+                    &[0x06],
+                )),
+                finally: Some(&[0x04]),
+            }),
+            vec![
+                0x8F, 9, 0, 0b011, 1, 0, 1, 0, 1, 0, 101, 0, 0x07, 0x06, 0x04,
+            ],
+        ),
+        (
+            7,
+            Action::Try(TryBlock {
+                try_actions: &[0x07],
+                catch: None,
+                finally: Some(&[0x04]),
+            }),
+            vec![0x8F, 8, 0, 0b010, 1, 0, 0, 0, 1, 0, 0, 0x07, 0x04],
+        ),
+        (
+            5,
+            Action::With { actions: &[0x07] },
+            vec![0x94, 2, 0, 1, 0, 0x07],
+        ),
         (
             1,
             Action::Unknown {
@@ -4,20 +4,17 @@
 
 use approx::assert_relative_eq;
 use ruffle_core::backend::{
-    audio::NullAudioBackend,
-    locale::NullLocaleBackend,
     log::LogBackend,
     navigator::{NullExecutor, NullNavigatorBackend},
-    render::NullRenderer,
     storage::{MemoryStorageBackend, StorageBackend},
-    ui::NullUiBackend,
-    video::NullVideoBackend,
 };
+use ruffle_core::chrono::{FixedOffset, TimeZone};
+use ruffle_core::clock::ManualClock;
 use ruffle_core::context::UpdateContext;
 use ruffle_core::external::Value as ExternalValue;
 use ruffle_core::external::{ExternalInterfaceMethod, ExternalInterfaceProvider};
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::Player;
+use ruffle_core::{Player, PlayerBuilder};
 use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -861,21 +858,28 @@ fn run_swf(
     let frame_time = 1000.0 / movie.header().frame_rate as f64;
     let trace_output = Rc::new(RefCell::new(Vec::new()));
 
-    let player = Player::new(
-        Box::new(NullRenderer),
-        Box::new(NullAudioBackend::new()),
-        Box::new(NullNavigatorBackend::with_base_path(base_path, channel)),
-        Box::new(MemoryStorageBackend::default()),
-        Box::new(NullLocaleBackend::new()),
-        Box::new(NullVideoBackend::new()),
-        Box::new(TestLogBackend::new(trace_output.clone())),
-        Box::new(NullUiBackend::new()),
-    )?;
+    let player = PlayerBuilder::new()
+        .with_navigator(Box::new(NullNavigatorBackend::with_base_path(
+            base_path, channel,
+        )))
+        .with_log(Box::new(TestLogBackend::new(trace_output.clone())))
+        .build()?;
     player.lock().unwrap().set_root_movie(Arc::new(movie));
     player
         .lock()
         .unwrap()
         .set_max_execution_duration(Duration::from_secs(300));
+    // Keep `getTimer` and `Date` deterministic; the clock stays at 2001-02-03
+    // 04:05:06 in Nepal (+5:45) for the whole run.
+    player
+        .lock()
+        .unwrap()
+        .set_clock(Box::new(ManualClock::launched_at(
+            FixedOffset::east(20700)
+                .ymd(2001, 2, 3)
+                .and_hms(4, 5, 6)
+                .into(),
+        )));
 
     before_start(player.clone())?;
 
@@ -49,6 +49,32 @@ export enum Letterbox {
     On = "on",
 }
 
+/**
+ * Controls how the movie is scaled when the player's size does not match the
+ * movie's size.
+ */
+export enum ScaleMode {
+    /**
+     * The movie keeps its aspect ratio, with borders filling any extra space.
+     */
+    ShowAll = "showAll",
+
+    /**
+     * The movie keeps its aspect ratio, and is cropped to fill the player.
+     */
+    NoBorder = "noBorder",
+
+    /**
+     * The movie is stretched to fill the player.
+     */
+    ExactFit = "exactFit",
+
+    /**
+     * The movie is not scaled at all.
+     */
+    NoScale = "noScale",
+}
+
 /**
  * When the player is muted, this controls whether or not Ruffle will show a
  * "click to unmute" overlay on top of the movie.
@@ -124,6 +150,23 @@ export interface BaseLoadOptions {
      */
     letterbox?: Letterbox;
 
+    /**
+     * Controls the color of the letterbox bars.
+     * Must be an HTML color (e.g. "#000000"). CSS colors are not allowed.
+     * `null` uses black.
+     *
+     * @default null
+     */
+    letterboxColor?: string | null;
+
+    /**
+     * Controls how the movie is scaled to fit the player.
+     * `null` uses the movie's own scale mode.
+     *
+     * @default null
+     */
+    scaleMode?: ScaleMode | null;
+
     /**
      * Controls the visibility of the unmute overlay when the player
      * is started muted.
@@ -178,6 +221,15 @@ export interface BaseLoadOptions {
         secs: number;
         nanos: number;
     };
+
+    /**
+     * The highest frame rate to play movies at, to save power.
+     * Movies authored at a lower frame rate are unaffected.
+     * `null` plays movies at their own frame rate.
+     *
+     * @default null
+     */
+    maxFrameRateCap?: number | null;
 }
 
 /**
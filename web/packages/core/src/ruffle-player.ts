@@ -704,9 +704,14 @@ export class RufflePlayer extends HTMLElement {
     }
 
     private showContextMenu(e: MouseEvent): void {
+        // Let the page's own menu show when ours is disabled.
+        if (!this.hasContextMenu) {
+            return;
+        }
+
         e.preventDefault();
 
-        if (!this.hasContextMenu || this.isTouch) {
+        if (this.isTouch) {
             return;
         }
 
@@ -13,6 +13,11 @@ use web_sys::{AudioContext, GainNode};
 
 pub struct WebAudioBackend {
     context: AudioContext,
+
+    /// The node that all sounds are connected to. Muting sets its gain to 0.
+    output_node: GainNode,
+
+    muted: bool,
     sounds: Arena<Sound>,
     left_samples: Vec<f32>,
     right_samples: Vec<f32>,
@@ -133,7 +138,12 @@ struct AudioBufferInstance {
 
 impl AudioBufferInstance {
     #[allow(clippy::float_cmp)]
-    fn set_transform(&mut self, context: &AudioContext, transform: &SoundTransform) {
+    fn set_transform(
+        &mut self,
+        context: &AudioContext,
+        output_node: &GainNode,
+        transform: &SoundTransform,
+    ) {
         let is_full_transform = transform.left_to_right != 0.0
             || transform.right_to_left != 0.0
             || transform.left_to_left != transform.right_to_right;
@@ -142,14 +152,14 @@ impl AudioBufferInstance {
         match &self.sound_transform_nodes {
             SoundTransformNodes::None => {
                 if is_full_transform {
-                    let _ = self.create_full_transform(context);
+                    let _ = self.create_full_transform(context, output_node);
                 } else if transform.left_to_left != 1.0 || transform.right_to_right != 1.0 {
-                    let _ = self.create_volume_transform(context);
+                    let _ = self.create_volume_transform(context, output_node);
                 }
             }
             SoundTransformNodes::Volume { .. } => {
                 if is_full_transform {
-                    let _ = self.create_full_transform(context);
+                    let _ = self.create_full_transform(context, output_node);
                 }
             }
             SoundTransformNodes::Transform { .. } => (),
@@ -181,6 +191,7 @@ impl AudioBufferInstance {
     fn create_volume_transform(
         &mut self,
         context: &AudioContext,
+        output_node: &GainNode,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Create the gain node to control the volume.
         let gain = context.create_gain().into_js_result()?;
@@ -193,8 +204,7 @@ impl AudioBufferInstance {
             .connect_with_audio_node(&gain)
             .into_js_result()?;
 
-        gain.connect_with_audio_node(&context.destination())
-            .warn_on_error();
+        gain.connect_with_audio_node(output_node).warn_on_error();
 
         self.node = gain.clone().into();
         self.sound_transform_nodes = SoundTransformNodes::Volume { gain };
@@ -206,6 +216,7 @@ impl AudioBufferInstance {
     fn create_full_transform(
         &mut self,
         context: &AudioContext,
+        output_node: &GainNode,
     ) -> Result<(), Box<dyn std::error::Error>> {
         // Split the left and right channels.
         let splitter = context
@@ -262,9 +273,7 @@ impl AudioBufferInstance {
             .connect_with_audio_node_and_output_and_input(&merger, 0, 1)
             .into_js_result()?;
 
-        merger
-            .connect_with_audio_node(&context.destination())
-            .warn_on_error();
+        merger.connect_with_audio_node(output_node).warn_on_error();
 
         self.node = merger;
         self.envelope_is_stereo = true;
@@ -314,8 +323,17 @@ impl WebAudioBackend {
         }
         log::info!("Minimum audio buffer sample rate: {}", min_sample_rate);
 
+        let output_node = context
+            .create_gain()
+            .map_err(|_| "Unable to create output GainNode")?;
+        output_node
+            .connect_with_audio_node(&context.destination())
+            .map_err(|_| "Unable to connect output GainNode")?;
+
         Ok(Self {
             context,
+            output_node,
+            muted: false,
             sounds: Arena::new(),
             preload_stream_data: FnvHashMap::default(),
             next_stream_id: 0,
@@ -406,7 +424,7 @@ impl WebAudioBackend {
                     }
                 };
 
-                node.connect_with_audio_node(&self.context.destination())
+                node.connect_with_audio_node(&self.output_node)
                     .warn_on_error();
 
                 // Create the sound instance and add it to the active instances list.
@@ -816,6 +834,10 @@ impl AudioBackend for WebAudioBackend {
         Ok(self.sounds.insert(sound))
     }
 
+    fn unregister_sound(&mut self, sound: SoundHandle) {
+        self.sounds.remove(sound);
+    }
+
     fn preload_sound_stream_head(
         &mut self,
         stream_info: &swf::SoundStreamHead,
@@ -1000,6 +1022,17 @@ impl AudioBackend for WebAudioBackend {
         let _ = self.context.suspend();
     }
 
+    fn muted(&self) -> bool {
+        self.muted
+    }
+
+    fn set_muted(&mut self, muted: bool) {
+        self.muted = muted;
+        self.output_node
+            .gain()
+            .set_value(if muted { 0.0 } else { 1.0 });
+    }
+
     fn stop_all_sounds(&mut self) {
         SOUND_INSTANCES.with(|instances| {
             let mut instances = instances.borrow_mut();
@@ -1039,7 +1072,7 @@ impl AudioBackend for WebAudioBackend {
             let mut instances = instances.borrow_mut();
             if let Some(instance) = instances.get_mut(instance) {
                 if let SoundInstanceType::AudioBuffer(sound) = &mut instance.instance_type {
-                    sound.set_transform(&self.context, &transform);
+                    sound.set_transform(&self.context, &self.output_node, &transform);
                 }
             }
         })
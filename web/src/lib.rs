@@ -28,7 +28,7 @@ use ruffle_core::external::{
     ExternalInterfaceMethod, ExternalInterfaceProvider, Value as ExternalValue, Value,
 };
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::{Color, Player, PlayerEvent};
+use ruffle_core::{Color, Player, PlayerEvent, StageScaleMode};
 use ruffle_web_common::JsResult;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -132,6 +132,12 @@ pub struct Config {
 
     letterbox: Letterbox,
 
+    #[serde(rename = "letterboxColor")]
+    letterbox_color: Option<String>,
+
+    #[serde(rename = "scaleMode")]
+    scale_mode: Option<StageScaleMode>,
+
     #[serde(rename = "upgradeToHttps")]
     upgrade_to_https: bool,
 
@@ -141,8 +147,14 @@ pub struct Config {
     #[serde(rename = "logLevel")]
     log_level: log::Level,
 
+    #[serde(rename = "contextMenu")]
+    context_menu: bool,
+
     #[serde(rename = "maxExecutionDuration")]
     max_execution_duration: Duration,
+
+    #[serde(rename = "maxFrameRateCap")]
+    max_frame_rate_cap: Option<f64>,
 }
 
 impl Default for Config {
@@ -151,10 +163,14 @@ impl Default for Config {
             allow_script_access: false,
             background_color: Default::default(),
             letterbox: Default::default(),
+            letterbox_color: None,
+            scale_mode: None,
             upgrade_to_https: true,
             warn_on_unsupported_content: true,
             log_level: log::Level::Error,
+            context_menu: true,
             max_execution_duration: Duration::from_secs(15),
+            max_frame_rate_cap: None,
         }
     }
 }
@@ -474,8 +490,18 @@ impl Ruffle {
                 core.set_background_color(Some(color));
             }
             core.set_letterbox(config.letterbox);
+            if let Some(color) = config.letterbox_color.and_then(parse_html_color) {
+                core.set_letterbox_color(color);
+            }
+            if let Some(scale_mode) = config.scale_mode {
+                core.set_scale_mode(scale_mode);
+            }
             core.set_warn_on_unsupported_content(config.warn_on_unsupported_content);
             core.set_max_execution_duration(config.max_execution_duration);
+            core.set_max_frame_rate_cap(config.max_frame_rate_cap);
+            core.set_show_context_menu(config.context_menu);
+            // Movies on the web are never trusted, even when served from a local file.
+            core.set_trust_local_content(false);
 
             // Create the external interface.
             if allow_script_access {
@@ -1,4 +1,4 @@
-use chrono::{DateTime, FixedOffset, Local, Offset, Utc};
+use chrono::{FixedOffset, Local, Offset};
 use ruffle_core::backend::locale::LocaleBackend;
 
 pub struct WebLocaleBackend();
@@ -10,10 +10,6 @@ impl WebLocaleBackend {
 }
 
 impl LocaleBackend for WebLocaleBackend {
-    fn get_current_date_time(&self) -> DateTime<Utc> {
-        Utc::now()
-    }
-
     fn get_timezone(&self) -> FixedOffset {
         Local::now().offset().fix()
     }
@@ -1,5 +1,5 @@
 //! Navigator backend for web
-use js_sys::{Array, ArrayBuffer, Uint8Array};
+use js_sys::{Array, ArrayBuffer, Promise, Uint8Array};
 use ruffle_core::backend::navigator::{
     url_from_relative_url, NavigationMethod, NavigatorBackend, OwnedFuture, RequestOptions,
 };
@@ -8,13 +8,11 @@ use ruffle_core::loader::Error;
 use std::borrow::Cow;
 use std::time::Duration;
 use url::Url;
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
 use wasm_bindgen_futures::{spawn_local, JsFuture};
-use web_sys::{window, Blob, BlobPropertyBag, Performance, Request, RequestInit, Response};
+use web_sys::{window, AbortController, Blob, BlobPropertyBag, Request, RequestInit, Response};
 
 pub struct WebNavigatorBackend {
-    performance: Performance,
-    start_time: f64,
     allow_script_access: bool,
     upgrade_to_https: bool,
 }
@@ -22,15 +20,12 @@ pub struct WebNavigatorBackend {
 impl WebNavigatorBackend {
     pub fn new(allow_script_access: bool, upgrade_to_https: bool) -> Self {
         let window = web_sys::window().expect("window()");
-        let performance = window.performance().expect("window.performance()");
 
         // Upgarde to HTTPS takes effect if the current page is hosted on HTTPS.
         let upgrade_to_https =
             upgrade_to_https && window.location().protocol().unwrap_or_default() == "https:";
 
         WebNavigatorBackend {
-            start_time: performance.now(),
-            performance,
             allow_script_access,
             upgrade_to_https,
         }
@@ -120,11 +115,6 @@ impl NavigatorBackend for WebNavigatorBackend {
         }
     }
 
-    fn time_since_launch(&mut self) -> Duration {
-        let dt = self.performance.now() - self.start_time;
-        Duration::from_millis(dt as u64)
-    }
-
     fn fetch(&self, url: &str, options: RequestOptions) -> OwnedFuture<Vec<u8>, Error> {
         let url = if let Ok(parsed_url) = Url::parse(url) {
             self.pre_process_url(parsed_url).to_string()
@@ -134,6 +124,7 @@ impl NavigatorBackend for WebNavigatorBackend {
 
         Box::pin(async move {
             let mut init = RequestInit::new();
+            let window = web_sys::window().unwrap();
 
             init.method(match options.method() {
                 NavigationMethod::Get => "GET",
@@ -163,37 +154,34 @@ impl NavigatorBackend for WebNavigatorBackend {
                 init.body(Some(&datablob));
             }
 
-            let request = Request::new_with_str_and_init(&url, &init)
-                .map_err(|_| Error::FetchError(format!("Unable to create request for {}", url)))?;
-
-            let window = web_sys::window().unwrap();
-            let fetchval = JsFuture::from(window.fetch_with_request(&request)).await;
-            if fetchval.is_err() {
-                return Err(Error::NetworkError(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "Could not fetch, got JS Error",
-                )));
-            }
+            // Abort the request, including the download of its body, once
+            // the timeout passes.
+            let timeout = options.timeout().and_then(|timeout| {
+                let controller = AbortController::new().ok()?;
+                let signal = controller.signal();
+                init.signal(Some(&signal));
+                let abort = Closure::once_into_js(move || controller.abort());
+                let handle = window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        abort.unchecked_ref(),
+                        timeout.as_millis() as i32,
+                    )
+                    .ok()?;
+                Some((signal, handle))
+            });
 
-            let resp: Response = fetchval.unwrap().dyn_into().unwrap();
+            let result = fetch_body(&window, &url, &init).await;
 
-            if !resp.ok() {
-                return Err(Error::FetchError(format!(
-                    "HTTP status is not ok, got {}",
-                    resp.status_text()
-                )));
+            match timeout {
+                Some((signal, _)) if signal.aborted() => {
+                    Err(Error::FetchError(format!("Request to {} timed out", url)))
+                }
+                Some((_, handle)) => {
+                    window.clear_timeout_with_handle(handle);
+                    result
+                }
+                None => result,
             }
-
-            let data: ArrayBuffer = JsFuture::from(resp.array_buffer().unwrap())
-                .await
-                .unwrap()
-                .dyn_into()
-                .unwrap();
-            let jsarray = Uint8Array::new(&data);
-            let mut rust_array = vec![0; jsarray.length() as usize];
-            jsarray.copy_to(&mut rust_array);
-
-            Ok(rust_array)
         })
     }
 
@@ -205,6 +193,27 @@ impl NavigatorBackend for WebNavigatorBackend {
         })
     }
 
+    fn sleep(&self, duration: Duration) -> OwnedFuture<(), Error> {
+        let promise = Promise::new(&mut |resolve, _reject| {
+            let timer = window().and_then(|window| {
+                window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(
+                        &resolve,
+                        duration.as_millis() as i32,
+                    )
+                    .ok()
+            });
+            if timer.is_none() {
+                let _ = resolve.call0(&JsValue::UNDEFINED);
+            }
+        });
+
+        Box::pin(async move {
+            let _ = JsFuture::from(promise).await;
+            Ok(())
+        })
+    }
+
     fn resolve_relative_url<'a>(&mut self, url: &'a str) -> Cow<'a, str> {
         let window = web_sys::window().expect("window()");
         let document = window.document().expect("document()");
@@ -225,3 +234,41 @@ impl NavigatorBackend for WebNavigatorBackend {
         url
     }
 }
+
+/// Make a request, and read the whole body of the response.
+async fn fetch_body(
+    window: &web_sys::Window,
+    url: &str,
+    init: &RequestInit,
+) -> Result<Vec<u8>, Error> {
+    let request = Request::new_with_str_and_init(url, init)
+        .map_err(|_| Error::FetchError(format!("Unable to create request for {}", url)))?;
+
+    let fetchval = JsFuture::from(window.fetch_with_request(&request)).await;
+    if fetchval.is_err() {
+        return Err(Error::NetworkError(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "Could not fetch, got JS Error",
+        )));
+    }
+
+    let resp: Response = fetchval.unwrap().dyn_into().unwrap();
+
+    if !resp.ok() {
+        return Err(Error::FetchError(format!(
+            "HTTP status is not ok, got {}",
+            resp.status_text()
+        )));
+    }
+
+    let data: ArrayBuffer = JsFuture::from(resp.array_buffer().unwrap())
+        .await
+        .map_err(|_| Error::FetchError(format!("Could not read the response from {}", url)))?
+        .dyn_into()
+        .unwrap();
+    let jsarray = Uint8Array::new(&data);
+    let mut rust_array = vec![0; jsarray.length() as usize];
+    jsarray.copy_to(&mut rust_array);
+
+    Ok(rust_array)
+}
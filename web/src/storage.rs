@@ -23,4 +23,11 @@ impl StorageBackend for LocalStorageBackend {
     fn remove_key(&mut self, name: &str) {
         let _ = self.storage.delete(name);
     }
+
+    fn keys(&self) -> Vec<String> {
+        let length = self.storage.length().unwrap_or_default();
+        (0..length)
+            .filter_map(|i| self.storage.key(i).ok().flatten())
+            .collect()
+    }
 }